@@ -0,0 +1,52 @@
+//! Stops the build with a clear message on compilers older than the minimum supported Rust
+//! version, also where the `rust-version` of the manifest is ignored.
+
+use std::env;
+use std::process::Command;
+
+fn parse_version(text: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = text
+        .split(|character: char| !character.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok());
+    Some((
+        parts.next()??,
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-env-changed=RUSTC");
+
+    let minimum = match env::var("CARGO_PKG_RUST_VERSION")
+        .ok()
+        .and_then(|version| parse_version(&version))
+    {
+        Some(minimum) => minimum,
+        None => return,
+    };
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let current = match Command::new(&rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|version| version.split_whitespace().nth(1).and_then(parse_version))
+    {
+        Some(current) => current,
+        None => {
+            println!("cargo:warning=Could not determine the version of {}", rustc);
+            return;
+        }
+    };
+
+    if current < minimum {
+        panic!(
+            "gymnarium_base requires Rust {}.{}.{} or newer on the stable channel, but {} is {}.{}.{}",
+            minimum.0, minimum.1, minimum.2, rustc, current.0, current.1, current.2
+        );
+    }
+}
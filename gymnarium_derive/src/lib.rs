@@ -0,0 +1,180 @@
+//! # Gymnarium Derive
+//!
+//! Derive macros for `gymnarium_base`, re-exported by its `derive` feature.
+//!
+//! `ToPosition` and `FromPosition` map the fields of a struct in order of declaration onto a
+//! one-dimensional `Position`. Every field type has to implement `PositionField`, which is done
+//! for `f32`, `i32` and arrays of them.
+//!
+//! The boundaries of a field default to the whole range of its type and can be set with
+//! `#[position(boundaries = <expr>)]`, where the expression converts into `DimensionBoundaries`.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Index};
+
+#[proc_macro_derive(ToPosition, attributes(position))]
+pub fn derive_to_position(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match named_fields(&input) {
+        Ok(fields) => {
+            let name = &input.ident;
+            let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+            let boundaries = fields.iter().map(|field| {
+                let ty = &field.ty;
+                let given = match &field.boundaries {
+                    Some(expr) => quote! {
+                        ::std::option::Option::Some(
+                            ::gymnarium_base::space::DimensionBoundaries::from(#expr)
+                        )
+                    },
+                    None => quote! { ::std::option::Option::None },
+                };
+                quote! {
+                    boundaries.extend(
+                        <#ty as ::gymnarium_base::space::PositionField>::boundaries(#given)
+                    );
+                }
+            });
+            let values = fields.iter().map(|field| {
+                let ty = &field.ty;
+                let member = &field.member;
+                quote! {
+                    <#ty as ::gymnarium_base::space::PositionField>::write_values(
+                        &self.#member,
+                        &mut values,
+                    );
+                }
+            });
+            TokenStream::from(quote! {
+                impl #impl_generics ::gymnarium_base::space::ToPosition
+                    for #name #type_generics #where_clause
+                {
+                    fn space() -> ::gymnarium_base::space::Space {
+                        let mut boundaries = ::std::vec::Vec::new();
+                        #(#boundaries)*
+                        ::gymnarium_base::space::Space::simple(boundaries)
+                    }
+
+                    fn to_position(&self) -> ::gymnarium_base::space::Position {
+                        let mut values = ::std::vec::Vec::new();
+                        #(#values)*
+                        ::gymnarium_base::space::Position::simple(values)
+                    }
+                }
+            })
+        }
+        Err(error) => TokenStream::from(error.to_compile_error()),
+    }
+}
+
+#[proc_macro_derive(FromPosition, attributes(position))]
+pub fn derive_from_position(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match named_fields(&input) {
+        Ok(fields) => {
+            let name = &input.ident;
+            let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+            let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+            let reads = fields.iter().map(|field| {
+                let ty = &field.ty;
+                let member = &field.member;
+                quote! {
+                    #member: {
+                        let value = <#ty as ::gymnarium_base::space::PositionField>::read_values(
+                            &values[offset..],
+                        )
+                        .map_err(|index| {
+                            <#ty as ::gymnarium_base::space::PositionField>::type_mismatch(
+                                values,
+                                offset,
+                                index,
+                            )
+                        })?;
+                        offset += <#ty as ::gymnarium_base::space::PositionField>::LENGTH;
+                        value
+                    },
+                }
+            });
+            TokenStream::from(quote! {
+                impl #impl_generics ::gymnarium_base::space::FromPosition
+                    for #name #type_generics #where_clause
+                {
+                    fn from_position(
+                        position: &::gymnarium_base::space::Position,
+                    ) -> ::std::result::Result<Self, ::gymnarium_base::space::SpaceMismatch> {
+                        let length = 0 #(+ <#types as ::gymnarium_base::space::PositionField>::LENGTH)*;
+                        if position.dimensions() != &[length] {
+                            return ::std::result::Result::Err(
+                                ::gymnarium_base::space::SpaceMismatch::ShapeMismatch {
+                                    expected: ::std::vec![length],
+                                    actual: position.dimensions().clone(),
+                                },
+                            );
+                        }
+                        let values = &*position.values();
+                        let mut offset = 0;
+                        ::std::result::Result::Ok(Self { #(#reads)* })
+                    }
+                }
+            })
+        }
+        Err(error) => TokenStream::from(error.to_compile_error()),
+    }
+}
+
+/// A field of the derived struct together with its `#[position(...)]` settings.
+struct PositionFieldInput {
+    member: TokenStream2,
+    ty: syn::Type,
+    boundaries: Option<Expr>,
+}
+
+fn named_fields(input: &DeriveInput) -> syn::Result<Vec<PositionFieldInput>> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "Positions can only be derived for structs",
+            ))
+        }
+    };
+    let fields: Vec<&syn::Field> = match fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => quote! { #ident },
+                None => {
+                    let index = Index::from(index);
+                    quote! { #index }
+                }
+            };
+            let mut boundaries = None;
+            for attribute in field.attrs.iter().filter(|a| a.path().is_ident("position")) {
+                attribute.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("boundaries") {
+                        boundaries = Some(meta.value()?.parse::<Expr>()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("Expected `boundaries = <expr>`"))
+                    }
+                })?;
+            }
+            Ok(PositionFieldInput {
+                member,
+                ty: field.ty.clone(),
+                boundaries,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,16 @@
+//! Contains ready-made agents, which are useful as baselines, for tests or for humans playing
+//! environments themselves.
+
+mod closure;
+mod ensemble;
+mod human;
+mod random;
+
+pub use closure::ClosureAgent;
+pub use ensemble::{
+    CombinationStrategy, EnsembleAgent, EnsembleError, MajorityVote, Mean, Weighting,
+};
+pub use human::{
+    ActionCallback, ActionSource, HumanAgent, HumanAgentError, InputMode, MissingActionPolicy,
+};
+pub use random::RandomAgent;
@@ -0,0 +1,70 @@
+use std::convert::Infallible;
+
+use crate::{Agent, AgentAction, EnvironmentState, Reward, Seed};
+
+/// Agent choosing its actions with a function of the current state, e.g. a scripted policy.
+///
+/// The agent ignores rewards and never fails.
+///
+/// ```
+/// use gymnarium_base::{position, Agent};
+/// use gymnarium_base::agents::ClosureAgent;
+///
+/// let mut agent = ClosureAgent::new(|state: &gymnarium_base::EnvironmentState| {
+///     position![-state.value(&[0]).expect_integer()]
+/// });
+/// let action = Agent::<_, f64, ()>::choose_action(&mut agent, &position![3]).unwrap();
+/// assert_eq!(position![-3], action);
+/// ```
+pub struct ClosureAgent<F> {
+    policy: F,
+}
+
+impl<F: FnMut(&EnvironmentState) -> AgentAction> ClosureAgent<F> {
+    pub fn new(policy: F) -> Self {
+        Self { policy }
+    }
+
+    pub fn into_inner(self) -> F {
+        self.policy
+    }
+}
+
+impl<F, R> Agent<Infallible, R, ()> for ClosureAgent<F>
+where
+    F: FnMut(&EnvironmentState) -> AgentAction,
+    R: Reward,
+{
+    fn reseed(&mut self, _random_seed: Option<Seed>) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn choose_action(&mut self, state: &EnvironmentState) -> Result<AgentAction, Infallible> {
+        Ok((self.policy)(state))
+    }
+
+    fn process_reward(
+        &mut self,
+        _old_state: &EnvironmentState,
+        _last_action: &AgentAction,
+        _new_state: &EnvironmentState,
+        _reward: R,
+        _is_done: bool,
+    ) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn load(&mut self, _data: ()) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn store(&self) {}
+
+    fn close(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
@@ -0,0 +1,436 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::curriculum::TrainingProgress;
+use crate::space::DimensionValue;
+use crate::{Agent, AgentAction, EnvironmentState, Reward, Seed};
+
+/* --- --- --- COMBINATION STRATEGY --- --- --- */
+
+/// Combines the actions proposed by the members of an `EnsembleAgent` into one action.
+///
+/// Implemented for closures taking the proposals and the recent returns of their members.
+pub trait CombinationStrategy {
+    /// Returns the action to execute. `recent_returns` holds the mean recent return of the member
+    /// proposing the action at the same index, if it has finished an episode yet.
+    fn combine(&mut self, proposals: &[AgentAction], recent_returns: &[Option<f64>])
+        -> AgentAction;
+}
+
+impl<F> CombinationStrategy for F
+where
+    F: FnMut(&[AgentAction], &[Option<f64>]) -> AgentAction,
+{
+    fn combine(
+        &mut self,
+        proposals: &[AgentAction],
+        recent_returns: &[Option<f64>],
+    ) -> AgentAction {
+        self(proposals, recent_returns)
+    }
+}
+
+/// How much say every member of an ensemble has.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Weighting {
+    Uniform,
+    /// Weights the members by the softmax of their recent returns with the given temperature.
+    ///
+    /// Falls back to uniform weights until every member finished an episode.
+    RecentReturn {
+        temperature: f64,
+    },
+}
+
+impl Weighting {
+    fn weights(&self, recent_returns: &[Option<f64>]) -> Vec<f64> {
+        let returns = match self {
+            Self::Uniform => None,
+            Self::RecentReturn { .. } => recent_returns.iter().copied().collect::<Option<Vec<_>>>(),
+        };
+        match (self, returns) {
+            (Self::RecentReturn { temperature }, Some(returns)) => {
+                let max = returns.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                returns
+                    .iter()
+                    .map(|value| ((value - max) / temperature).exp())
+                    .collect()
+            }
+            _ => vec![1f64; recent_returns.len()],
+        }
+    }
+}
+
+/// Chooses the action proposed by the most (weighted) members, preferring earlier members on ties.
+///
+/// Meant for discrete actions.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MajorityVote {
+    weighting: Weighting,
+}
+
+impl MajorityVote {
+    pub fn new() -> Self {
+        Self {
+            weighting: Weighting::Uniform,
+        }
+    }
+
+    pub fn with_weighting(weighting: Weighting) -> Self {
+        Self { weighting }
+    }
+}
+
+impl Default for MajorityVote {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CombinationStrategy for MajorityVote {
+    fn combine(
+        &mut self,
+        proposals: &[AgentAction],
+        recent_returns: &[Option<f64>],
+    ) -> AgentAction {
+        let weights = self.weighting.weights(recent_returns);
+        let mut best: Option<(usize, f64)> = None;
+        for (index, proposal) in proposals.iter().enumerate() {
+            if proposals[..index].contains(proposal) {
+                continue;
+            }
+            let votes = proposals
+                .iter()
+                .zip(&weights)
+                .filter(|(other, _)| *other == proposal)
+                .map(|(_, weight)| weight)
+                .sum::<f64>();
+            if best.is_none_or(|(_, best_votes)| votes > best_votes) {
+                best = Some((index, votes));
+            }
+        }
+        proposals[best.map_or(0, |(index, _)| index)].clone()
+    }
+}
+
+/// Averages the proposed actions element-wise, rounding integer values.
+///
+/// Meant for continuous actions. The result has the dimensions of the first proposal.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Mean {
+    weighting: Weighting,
+}
+
+impl Mean {
+    pub fn new() -> Self {
+        Self {
+            weighting: Weighting::Uniform,
+        }
+    }
+
+    pub fn with_weighting(weighting: Weighting) -> Self {
+        Self { weighting }
+    }
+}
+
+impl Default for Mean {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CombinationStrategy for Mean {
+    fn combine(
+        &mut self,
+        proposals: &[AgentAction],
+        recent_returns: &[Option<f64>],
+    ) -> AgentAction {
+        let weights = self.weighting.weights(recent_returns);
+        let total_weight = weights.iter().sum::<f64>();
+        let first = &proposals[0];
+        let values = first
+            .values()
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let mean = proposals
+                    .iter()
+                    .zip(&weights)
+                    .map(|(proposal, weight)| {
+                        weight
+                            * match proposal.values()[index] {
+                                DimensionValue::Integer(value) => value as f64,
+                                DimensionValue::Float(value) => value as f64,
+                            }
+                    })
+                    .sum::<f64>()
+                    / total_weight;
+                match value {
+                    DimensionValue::Integer(_) => DimensionValue::Integer(mean.round() as i32),
+                    DimensionValue::Float(_) => DimensionValue::Float(mean as f32),
+                }
+            })
+            .collect();
+        AgentAction::new(values, first.dimensions().clone())
+            .expect("values of the mean fit the dimensions of the first proposal")
+    }
+}
+
+/* --- --- --- ENSEMBLE ERROR --- --- --- */
+
+/// Errors of an `EnsembleAgent`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum EnsembleError<E> {
+    /// The member at the given index returned an error.
+    Member { index: usize, error: E },
+    /// The loaded data does not contain one entry per member.
+    MemberCountMismatch { expected: usize, actual: usize },
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for EnsembleError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Member { index, error } => {
+                write!(f, "Ensemble member {} failed with \"{}\"", index, error)
+            }
+            Self::MemberCountMismatch { expected, actual } => write!(
+                f,
+                "Loaded data for {} members into an ensemble of {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for EnsembleError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Member { error, .. } => Some(error),
+            Self::MemberCountMismatch { .. } => None,
+        }
+    }
+}
+
+/* --- --- --- ENSEMBLE AGENT --- --- --- */
+
+/// Agent letting several agents propose an action and executing their combination.
+///
+/// Every member processes every reward. The recent return of a member sums only the rewards of
+/// steps in which the executed action was its own proposal, averaged over the last episodes.
+///
+/// ```
+/// use gymnarium_base::{position, Agent, EnvironmentState};
+/// use gymnarium_base::agents::{ClosureAgent, EnsembleAgent, MajorityVote};
+///
+/// let members = [1, 0, 1]
+///     .iter()
+///     .map(|value| {
+///         let value = *value;
+///         ClosureAgent::new(move |_: &EnvironmentState| position![value])
+///     })
+///     .collect();
+/// let mut ensemble = EnsembleAgent::new(members, MajorityVote::new());
+/// let action = Agent::<_, f64, _>::choose_action(&mut ensemble, &position![0]).unwrap();
+/// assert_eq!(position![1], action);
+/// ```
+pub struct EnsembleAgent<A, S> {
+    members: Vec<A>,
+    strategy: S,
+    proposals: Vec<AgentAction>,
+    episode_credits: Vec<f64>,
+    progress: Vec<TrainingProgress>,
+}
+
+impl<A, S: CombinationStrategy> EnsembleAgent<A, S> {
+    /// Creates the ensemble averaging the recent returns over the last 10 episodes.
+    ///
+    /// Panics if no member is given.
+    pub fn new(members: Vec<A>, strategy: S) -> Self {
+        assert!(!members.is_empty(), "An ensemble needs at least one member");
+        Self {
+            proposals: Vec::with_capacity(members.len()),
+            episode_credits: vec![0f64; members.len()],
+            progress: vec![TrainingProgress::new(10); members.len()],
+            members,
+            strategy,
+        }
+    }
+
+    /// Averages the recent returns over the given number of episodes.
+    pub fn with_return_window(mut self, window: usize) -> Self {
+        self.progress = vec![TrainingProgress::new(window); self.members.len()];
+        self
+    }
+
+    /// Returns the mean recent return of every member.
+    pub fn recent_returns(&self) -> Vec<Option<f64>> {
+        self.progress
+            .iter()
+            .map(TrainingProgress::mean_return)
+            .collect()
+    }
+
+    pub fn members(&self) -> &[A] {
+        &self.members
+    }
+
+    pub fn members_mut(&mut self) -> &mut [A] {
+        &mut self.members
+    }
+
+    pub fn into_members(self) -> Vec<A> {
+        self.members
+    }
+}
+
+impl<A, S, E, R, D> Agent<EnsembleError<E>, R, Vec<D>> for EnsembleAgent<A, S>
+where
+    A: Agent<E, R, D>,
+    S: CombinationStrategy,
+    E: std::error::Error + 'static,
+    R: Reward + Clone,
+    D: Serialize + DeserializeOwned,
+{
+    /// Reseeds every member with its own seed derived from the given one.
+    fn reseed(&mut self, random_seed: Option<Seed>) -> Result<(), EnsembleError<E>> {
+        for (index, member) in self.members.iter_mut().enumerate() {
+            let member_seed = random_seed.as_ref().map(|seed| {
+                let mut seed_value = seed.seed_value.clone();
+                seed_value.extend_from_slice(format!("/{}", index).as_bytes());
+                Seed::from(seed_value)
+            });
+            member
+                .reseed(member_seed)
+                .map_err(|error| EnsembleError::Member { index, error })?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), EnsembleError<E>> {
+        self.proposals.clear();
+        self.episode_credits
+            .iter_mut()
+            .for_each(|credit| *credit = 0f64);
+        for (index, member) in self.members.iter_mut().enumerate() {
+            member
+                .reset()
+                .map_err(|error| EnsembleError::Member { index, error })?;
+        }
+        Ok(())
+    }
+
+    fn choose_action(&mut self, state: &EnvironmentState) -> Result<AgentAction, EnsembleError<E>> {
+        self.proposals.clear();
+        for (index, member) in self.members.iter_mut().enumerate() {
+            self.proposals.push(
+                member
+                    .choose_action(state)
+                    .map_err(|error| EnsembleError::Member { index, error })?,
+            );
+        }
+        let recent_returns = self.recent_returns();
+        Ok(self.strategy.combine(&self.proposals, &recent_returns))
+    }
+
+    fn process_reward(
+        &mut self,
+        old_state: &EnvironmentState,
+        last_action: &AgentAction,
+        new_state: &EnvironmentState,
+        reward: R,
+        is_done: bool,
+    ) -> Result<(), EnsembleError<E>> {
+        let value = reward.value();
+        for (index, member) in self.members.iter_mut().enumerate() {
+            member
+                .process_reward(old_state, last_action, new_state, reward.clone(), is_done)
+                .map_err(|error| EnsembleError::Member { index, error })?;
+            if self.proposals.get(index) == Some(last_action) {
+                self.episode_credits[index] += value;
+            }
+            if is_done {
+                self.progress[index].record_episode(self.episode_credits[index]);
+                self.episode_credits[index] = 0f64;
+            }
+        }
+        Ok(())
+    }
+
+    fn load(&mut self, data: Vec<D>) -> Result<(), EnsembleError<E>> {
+        if data.len() != self.members.len() {
+            return Err(EnsembleError::MemberCountMismatch {
+                expected: self.members.len(),
+                actual: data.len(),
+            });
+        }
+        for (index, (member, data)) in self.members.iter_mut().zip(data).enumerate() {
+            member
+                .load(data)
+                .map_err(|error| EnsembleError::Member { index, error })?;
+        }
+        Ok(())
+    }
+
+    fn store(&self) -> Vec<D> {
+        self.members.iter().map(|member| member.store()).collect()
+    }
+
+    fn close(&mut self) -> Result<(), EnsembleError<E>> {
+        for (index, member) in self.members.iter_mut().enumerate() {
+            member
+                .close()
+                .map_err(|error| EnsembleError::Member { index, error })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position;
+    use crate::runner::test_agent::ConstantAgent;
+    use crate::runner::EpisodeRunner;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn strategies_combine_proposals() {
+        let proposals = vec![
+            position![1.0f32, 0],
+            position![3.0f32, 2],
+            position![3.0f32, 2],
+        ];
+        assert_eq!(
+            position![3.0f32, 2],
+            MajorityVote::new().combine(&proposals, &[None, None, None])
+        );
+        assert_eq!(
+            position![1.0f32, 0],
+            MajorityVote::with_weighting(Weighting::RecentReturn { temperature: 1f64 })
+                .combine(&proposals, &[Some(10f64), Some(0f64), Some(0f64)])
+        );
+        assert_eq!(
+            position![7.0f32 / 3.0f32, 1],
+            Mean::new().combine(&proposals, &[None, None, None])
+        );
+    }
+
+    #[test]
+    fn credits_rewards_to_agreeing_members() {
+        let members = vec![
+            ConstantAgent::new(1),
+            ConstantAgent::new(-1),
+            ConstantAgent::new(1),
+        ];
+        let mut ensemble = EnsembleAgent::new(members, MajorityVote::new());
+        let result = EpisodeRunner::new()
+            .run_episode(&mut CountingEnvironment::default(), &mut ensemble, None)
+            .unwrap();
+        assert_eq!(5, result.steps);
+        assert_eq!(
+            vec![Some(15f64), Some(0f64), Some(15f64)],
+            ensemble.recent_returns()
+        );
+        assert_eq!(5, ensemble.members()[1].rewards.len());
+    }
+}
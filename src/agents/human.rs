@@ -0,0 +1,251 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::Receiver;
+
+use crate::{Agent, AgentAction, EnvironmentState, Reward, Seed};
+
+/* --- --- --- ACTION SOURCE --- --- --- */
+
+/// Delivers actions chosen outside of the program, e.g. by a human using a keyboard or gamepad.
+pub trait ActionSource {
+    /// Returns an action if one is available right now.
+    fn poll_action(&mut self, state: &EnvironmentState) -> Option<AgentAction>;
+
+    /// Waits for the next action, returning `None` only if no action will ever come.
+    ///
+    /// Sources unable to wait return the result of `poll_action`.
+    fn wait_action(&mut self, state: &EnvironmentState) -> Option<AgentAction> {
+        self.poll_action(state)
+    }
+}
+
+impl ActionSource for Receiver<AgentAction> {
+    fn poll_action(&mut self, _state: &EnvironmentState) -> Option<AgentAction> {
+        self.try_recv().ok()
+    }
+
+    fn wait_action(&mut self, _state: &EnvironmentState) -> Option<AgentAction> {
+        self.recv().ok()
+    }
+}
+
+impl ActionSource for VecDeque<AgentAction> {
+    fn poll_action(&mut self, _state: &EnvironmentState) -> Option<AgentAction> {
+        self.pop_front()
+    }
+}
+
+/// Uses a callback as `ActionSource`, which is asked for an action whenever one is needed.
+pub struct ActionCallback<F> {
+    callback: F,
+}
+
+impl<F: FnMut(&EnvironmentState) -> Option<AgentAction>> ActionCallback<F> {
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+impl<F: FnMut(&EnvironmentState) -> Option<AgentAction>> ActionSource for ActionCallback<F> {
+    fn poll_action(&mut self, state: &EnvironmentState) -> Option<AgentAction> {
+        (self.callback)(state)
+    }
+}
+
+/* --- --- --- HUMAN AGENT ERROR --- --- --- */
+
+/// Errors of a `HumanAgent`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum HumanAgentError {
+    /// The action source was waited for in blocking mode, but will never deliver an action.
+    SourceExhausted,
+}
+
+impl std::fmt::Display for HumanAgentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SourceExhausted => write!(f, "The action source will not deliver any action"),
+        }
+    }
+}
+
+impl std::error::Error for HumanAgentError {}
+
+/* --- --- --- HUMAN AGENT --- --- --- */
+
+/// Whether a `HumanAgent` waits for input or keeps the environment going without it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InputMode {
+    /// Waits for the source to deliver every single action.
+    Blocking,
+    /// Uses the `MissingActionPolicy` whenever the source has no action ready.
+    NonBlocking,
+}
+
+/// Action chosen by a `HumanAgent` in non-blocking mode if no input is available.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MissingActionPolicy {
+    /// Repeats the last action, or the default action before the first input.
+    RepeatLast,
+    /// Always chooses the default action, e.g. a "do nothing" action.
+    Default,
+}
+
+/// Agent acting on the actions delivered by an `ActionSource`.
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use gymnarium_base::{position, Agent};
+/// use gymnarium_base::agents::{HumanAgent, InputMode};
+///
+/// let inputs: VecDeque<_> = vec![position![1]].into_iter().collect();
+/// let mut agent = HumanAgent::new(inputs, position![0]).with_mode(InputMode::NonBlocking);
+/// let state = position![0];
+///
+/// assert_eq!(position![1], Agent::<_, f64, ()>::choose_action(&mut agent, &state).unwrap());
+/// assert_eq!(position![1], Agent::<_, f64, ()>::choose_action(&mut agent, &state).unwrap());
+/// ```
+pub struct HumanAgent<S> {
+    source: S,
+    mode: InputMode,
+    policy: MissingActionPolicy,
+    default_action: AgentAction,
+    last_action: Option<AgentAction>,
+}
+
+impl<S: ActionSource> HumanAgent<S> {
+    /// Creates the agent in blocking mode, using the default action if input is missing.
+    pub fn new(source: S, default_action: AgentAction) -> Self {
+        Self {
+            source,
+            mode: InputMode::Blocking,
+            policy: MissingActionPolicy::RepeatLast,
+            default_action,
+            last_action: None,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: InputMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_policy(mut self, policy: MissingActionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: InputMode) {
+        self.mode = mode;
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    pub fn into_source(self) -> S {
+        self.source
+    }
+}
+
+impl<S: ActionSource, R: Reward> Agent<HumanAgentError, R, ()> for HumanAgent<S> {
+    fn reseed(&mut self, _random_seed: Option<Seed>) -> Result<(), HumanAgentError> {
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), HumanAgentError> {
+        self.last_action = None;
+        Ok(())
+    }
+
+    fn choose_action(&mut self, state: &EnvironmentState) -> Result<AgentAction, HumanAgentError> {
+        let action = match self.mode {
+            InputMode::Blocking => Some(
+                self.source
+                    .wait_action(state)
+                    .ok_or(HumanAgentError::SourceExhausted)?,
+            ),
+            InputMode::NonBlocking => self.source.poll_action(state),
+        };
+        let action = match (action, &self.policy, &self.last_action) {
+            (Some(action), _, _) => action,
+            (None, MissingActionPolicy::RepeatLast, Some(last_action)) => last_action.clone(),
+            (None, _, _) => self.default_action.clone(),
+        };
+        self.last_action = Some(action.clone());
+        Ok(action)
+    }
+
+    fn process_reward(
+        &mut self,
+        _old_state: &EnvironmentState,
+        _last_action: &AgentAction,
+        _new_state: &EnvironmentState,
+        _reward: R,
+        _is_done: bool,
+    ) -> Result<(), HumanAgentError> {
+        Ok(())
+    }
+
+    fn load(&mut self, _data: ()) -> Result<(), HumanAgentError> {
+        Ok(())
+    }
+
+    fn store(&self) {}
+
+    fn close(&mut self) -> Result<(), HumanAgentError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use super::*;
+    use crate::position;
+
+    fn choose<S: ActionSource>(agent: &mut HumanAgent<S>) -> Result<AgentAction, HumanAgentError> {
+        Agent::<_, f64, ()>::choose_action(agent, &position![0])
+    }
+
+    #[test]
+    fn blocking_mode_waits_for_channel() {
+        let (sender, receiver) = channel();
+        let mut agent = HumanAgent::new(receiver, position![0]);
+        let input = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            sender.send(position![1]).unwrap();
+        });
+        assert_eq!(Ok(position![1]), choose(&mut agent));
+        input.join().unwrap();
+        assert_eq!(Err(HumanAgentError::SourceExhausted), choose(&mut agent));
+    }
+
+    #[test]
+    fn non_blocking_mode_applies_policy() {
+        let mut calls = 0;
+        let source = ActionCallback::new(|_: &EnvironmentState| {
+            calls += 1;
+            if calls == 2 {
+                Some(position![-1])
+            } else {
+                None
+            }
+        });
+        let mut agent = HumanAgent::new(source, position![0]).with_mode(InputMode::NonBlocking);
+        assert_eq!(Ok(position![0]), choose(&mut agent));
+        assert_eq!(Ok(position![-1]), choose(&mut agent));
+        assert_eq!(Ok(position![-1]), choose(&mut agent));
+
+        let mut agent = agent.with_policy(MissingActionPolicy::Default);
+        assert_eq!(Ok(position![0]), choose(&mut agent));
+    }
+}
@@ -0,0 +1,119 @@
+use std::convert::Infallible;
+
+use crate::rng::{RngProvider, RngState};
+use crate::{ActionSpace, Agent, AgentAction, EnvironmentState, Reward, Seed};
+
+/// Agent sampling its actions uniformly from the action space.
+///
+/// The stored data is the state of its random number generator, so a loaded agent continues with
+/// the exact same actions.
+///
+/// ```
+/// use gymnarium_base::{position, Agent, Seed};
+/// use gymnarium_base::agents::RandomAgent;
+/// use gymnarium_base::space::{DimensionBoundaries, Space};
+///
+/// let space = Space::simple(vec![DimensionBoundaries::from(0..=3)]);
+/// let mut agent = RandomAgent::new(space.clone(), Some(Seed::from(7)));
+/// let action = Agent::<_, f64, _>::choose_action(&mut agent, &position![0]).unwrap();
+/// assert!(space.contains(&action).is_ok());
+/// ```
+pub struct RandomAgent {
+    action_space: ActionSpace,
+    rng: RngProvider,
+}
+
+impl RandomAgent {
+    /// Creates the agent seeded with the given seed or by entropy.
+    pub fn new(action_space: ActionSpace, seed: Option<Seed>) -> Self {
+        Self {
+            action_space,
+            rng: match seed {
+                Some(seed) => RngProvider::new(seed),
+                None => RngProvider::from_entropy(),
+            },
+        }
+    }
+
+    pub fn action_space(&self) -> &ActionSpace {
+        &self.action_space
+    }
+}
+
+impl<R: Reward> Agent<Infallible, R, RngState> for RandomAgent {
+    fn reseed(&mut self, random_seed: Option<Seed>) -> Result<(), Infallible> {
+        self.rng.reseed(random_seed);
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn choose_action(&mut self, _state: &EnvironmentState) -> Result<AgentAction, Infallible> {
+        Ok(self.action_space.sample_with(&mut self.rng))
+    }
+
+    fn process_reward(
+        &mut self,
+        _old_state: &EnvironmentState,
+        _last_action: &AgentAction,
+        _new_state: &EnvironmentState,
+        _reward: R,
+        _is_done: bool,
+    ) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn load(&mut self, data: RngState) -> Result<(), Infallible> {
+        self.rng = RngProvider::from_state(data);
+        Ok(())
+    }
+
+    fn store(&self) -> RngState {
+        self.rng.state()
+    }
+
+    fn close(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position;
+    use crate::runner::EpisodeRunner;
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::Environment;
+
+    #[test]
+    fn seeded_agents_repeat_their_actions() {
+        let space = CountingEnvironment::default().action_space();
+        let mut first = RandomAgent::new(space.clone(), Some(Seed::from(3)));
+        let mut second = RandomAgent::new(space, Some(Seed::from(3)));
+
+        let choose = |agent: &mut RandomAgent| {
+            Agent::<_, f64, _>::choose_action(agent, &position![0]).unwrap()
+        };
+        choose(&mut first);
+        let stored = Agent::<_, f64, _>::store(&first);
+        let expected = (0..10).map(|_| choose(&mut first)).collect::<Vec<_>>();
+        Agent::<_, f64, _>::load(&mut second, stored).unwrap();
+        assert_eq!(
+            expected,
+            (0..10).map(|_| choose(&mut second)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn plays_an_episode() {
+        let mut environment = CountingEnvironment::default();
+        let mut agent = RandomAgent::new(environment.action_space(), Some(Seed::from(1)));
+        let result = EpisodeRunner::new()
+            .with_max_steps(20)
+            .run_episode(&mut environment, &mut agent, None)
+            .unwrap();
+        assert!(result.steps > 0);
+    }
+}
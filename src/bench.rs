@@ -0,0 +1,234 @@
+//! Measures the performance of environments to compare implementations and catch regressions.
+//!
+//! `benchmark_environment` steps an environment with random actions and times its resets and
+//! the serialization of its stored data. The `BenchmarkReport` can be written as JSON and
+//! compared against earlier runs.
+//!
+//! ```
+//! use gymnarium_base::bench::benchmark_environment;
+//! # use gymnarium_base::{AgentAction, Environment, EnvironmentState, ResetOptions, Seed};
+//! # use gymnarium_base::space::{DimensionBoundaries, Space};
+//! # struct Walk(i32);
+//! # impl Environment<std::fmt::Error, f64, (), i32> for Walk {
+//! #     fn action_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(-1..=1)]) }
+//! #     fn observation_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(-3..=3)]) }
+//! #     fn suggested_episode_steps_count(&self) -> Option<u128> { None }
+//! #     fn reset(&mut self, _: Option<Seed>, _: Option<ResetOptions>) -> Result<EnvironmentState, std::fmt::Error> { self.0 = 0; Ok(self.state()) }
+//! #     fn state(&self) -> EnvironmentState { gymnarium_base::position![self.0] }
+//! #     fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, f64, bool, ()), std::fmt::Error> {
+//! #         self.0 += action.value(&[0]).expect_integer();
+//! #         Ok((self.state(), 0.0, self.0.abs() == 3, ()))
+//! #     }
+//! #     fn load(&mut self, data: i32) -> Result<(), std::fmt::Error> { self.0 = data; Ok(()) }
+//! #     fn store(&self) -> i32 { self.0 }
+//! #     fn close(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+//! # }
+//! let report = benchmark_environment(&mut Walk(0), 1_000).unwrap();
+//!
+//! assert_eq!(1_000, report.steps);
+//! assert!(report.steps_per_second > 0.0);
+//! assert_eq!(report.episodes + 1, report.reset.samples);
+//!
+//! let json = serde_json::to_value(&report).unwrap();
+//! assert!(json["store"]["mean_seconds"].is_number());
+//! ```
+
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::{Environment, Reward};
+
+/// How often storing and loading the data of the environment is timed.
+pub const SERIALIZATION_SAMPLES: u64 = 16;
+
+/* --- --- --- BENCHMARK ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum BenchmarkError<E> {
+    Environment(E),
+    /// The stored data could not be turned into JSON and back.
+    Serialization(String),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for BenchmarkError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::Serialization(error) => write!(f, "Serialization Error \"{}\" occurred", error),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for BenchmarkError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            Self::Serialization(_) => None,
+        }
+    }
+}
+
+/* --- --- --- BENCHMARK REPORT --- --- --- */
+
+/// Timings of repeated calls in seconds.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Latency {
+    pub samples: u64,
+    pub mean_seconds: f64,
+    pub min_seconds: f64,
+    pub max_seconds: f64,
+}
+
+impl Latency {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let seconds = samples.iter().map(Duration::as_secs_f64);
+        Self {
+            samples: samples.len() as u64,
+            mean_seconds: seconds.clone().sum::<f64>() / samples.len() as f64,
+            min_seconds: seconds.clone().fold(f64::INFINITY, f64::min),
+            max_seconds: seconds.fold(0f64, f64::max),
+        }
+    }
+}
+
+/// Results of `benchmark_environment`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub environment_id: String,
+    pub steps: u64,
+    /// Episodes finished while stepping.
+    pub episodes: u64,
+    /// Steps per second spent inside `step`, not counting resets or choosing actions.
+    pub steps_per_second: f64,
+    /// Every reset, the initial one and those after finished episodes.
+    pub reset: Latency,
+    /// Calling `store` and serializing the data as JSON.
+    pub store: Latency,
+    /// Deserializing the JSON data and calling `load`.
+    pub load: Latency,
+    /// Length of the JSON data of the environment.
+    pub stored_bytes: usize,
+}
+
+/* --- --- --- BENCHMARK --- --- --- */
+
+/// Steps the environment `steps` times with uniformly random actions, resetting it whenever an
+/// episode is done.
+///
+/// Actions are drawn from a fixed seed, so repeated benchmarks of deterministic environments
+/// take the same steps. Afterwards storing and loading the data of the environment is timed
+/// `SERIALIZATION_SAMPLES` times, leaving the environment in the state it was in.
+pub fn benchmark_environment<Env, E, R, I, D>(
+    environment: &mut Env,
+    steps: u64,
+) -> Result<BenchmarkReport, BenchmarkError<E>>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut resets = Vec::new();
+    let mut reset = |environment: &mut Env| {
+        let started = Instant::now();
+        environment
+            .reset(None, None)
+            .map_err(BenchmarkError::Environment)?;
+        resets.push(started.elapsed());
+        Ok(())
+    };
+
+    reset(environment)?;
+    let action_space = environment.action_space();
+    let mut episodes = 0;
+    let mut stepping = Duration::ZERO;
+    for _ in 0..steps {
+        let action = action_space.sample_with(&mut rng);
+        let started = Instant::now();
+        let (_, _, done, _) = environment
+            .step(&action)
+            .map_err(BenchmarkError::Environment)?;
+        stepping += started.elapsed();
+        if done {
+            episodes += 1;
+            reset(environment)?;
+        }
+    }
+
+    let mut stores = Vec::new();
+    let mut loads = Vec::new();
+    let mut stored_bytes = 0;
+    for _ in 0..SERIALIZATION_SAMPLES {
+        let started = Instant::now();
+        let json = serde_json::to_vec(&environment.store())
+            .map_err(|error| BenchmarkError::Serialization(error.to_string()))?;
+        stores.push(started.elapsed());
+        stored_bytes = json.len();
+
+        let started = Instant::now();
+        let data = serde_json::from_slice(&json)
+            .map_err(|error| BenchmarkError::Serialization(error.to_string()))?;
+        environment
+            .load(data)
+            .map_err(BenchmarkError::Environment)?;
+        loads.push(started.elapsed());
+    }
+
+    Ok(BenchmarkReport {
+        environment_id: Env::environment_id(),
+        steps,
+        episodes,
+        steps_per_second: match stepping.as_secs_f64() {
+            seconds if seconds > 0.0 => steps as f64 / seconds,
+            _ => 0.0,
+        },
+        reset: Latency::from_samples(&resets),
+        store: Latency::from_samples(&stores),
+        load: Latency::from_samples(&loads),
+        stored_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn latencies_summarize_samples() {
+        assert_eq!(Latency::default(), Latency::from_samples(&[]));
+        let latency = Latency::from_samples(&[
+            Duration::from_millis(1),
+            Duration::from_millis(4),
+            Duration::from_millis(1),
+        ]);
+        assert_eq!(3, latency.samples);
+        assert!((latency.mean_seconds - 0.002).abs() < 1e-12);
+        assert_eq!(0.001, latency.min_seconds);
+        assert_eq!(0.004, latency.max_seconds);
+    }
+
+    #[test]
+    fn benchmarks_keep_the_state_of_the_environment() {
+        let mut environment = CountingEnvironment::default();
+        let report = benchmark_environment(&mut environment, 200).unwrap();
+        assert_eq!(200, report.steps);
+        assert_eq!(report.episodes + 1, report.reset.samples);
+        assert_eq!(SERIALIZATION_SAMPLES, report.load.samples);
+        assert_eq!(environment.count.to_string().len(), report.stored_bytes);
+        assert_eq!(
+            report,
+            serde_json::from_value(serde_json::to_value(&report).unwrap()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Limits on the resources a training run may use.
+//!
+//! A `TrainingBudget` keeps track of what was used so far. Stored inside a checkpoint, a resumed
+//! run continues with what is left of the original budget instead of starting anew.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Steps, episodes and wall-clock time used by a run.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub steps: u128,
+    pub episodes: u64,
+    pub elapsed: Duration,
+}
+
+/// What is left of a budget; `None` where no limit is set.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct RemainingBudget {
+    pub steps: Option<u128>,
+    pub episodes: Option<u64>,
+    pub duration: Option<Duration>,
+}
+
+impl RemainingBudget {
+    /// Returns whether any of the limits is used up.
+    pub fn is_exhausted(&self) -> bool {
+        self.steps == Some(0) || self.episodes == Some(0) || self.duration == Some(Duration::ZERO)
+    }
+}
+
+/// Maximum number of environment steps, episodes and wall-clock time of a run.
+///
+/// ```
+/// use std::time::Duration;
+/// use gymnarium_base::budget::TrainingBudget;
+///
+/// let mut budget = TrainingBudget::new()
+///     .with_max_steps(100)
+///     .with_max_duration(Duration::from_secs(60));
+/// budget.consume_steps(40);
+/// budget.consume_time(Duration::from_secs(15));
+///
+/// let remaining = budget.remaining();
+/// assert_eq!(Some(60), remaining.steps);
+/// assert_eq!(None, remaining.episodes);
+/// assert_eq!(Some(Duration::from_secs(45)), remaining.duration);
+/// assert!(!budget.is_exhausted());
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TrainingBudget {
+    pub max_steps: Option<u128>,
+    pub max_episodes: Option<u64>,
+    pub max_duration: Option<Duration>,
+    used: BudgetUsage,
+}
+
+impl TrainingBudget {
+    /// Creates a budget without any limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_steps(mut self, steps: u128) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    pub fn with_max_episodes(mut self, episodes: u64) -> Self {
+        self.max_episodes = Some(episodes);
+        self
+    }
+
+    pub fn with_max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Continues from the given usage, e.g. of an earlier run.
+    pub fn with_usage(mut self, used: BudgetUsage) -> Self {
+        self.used = used;
+        self
+    }
+
+    pub fn used(&self) -> BudgetUsage {
+        self.used
+    }
+
+    pub fn consume_steps(&mut self, steps: u128) {
+        self.used.steps = self.used.steps.saturating_add(steps);
+    }
+
+    pub fn consume_episodes(&mut self, episodes: u64) {
+        self.used.episodes = self.used.episodes.saturating_add(episodes);
+    }
+
+    pub fn consume_time(&mut self, duration: Duration) {
+        self.used.elapsed = self.used.elapsed.saturating_add(duration);
+    }
+
+    pub fn remaining(&self) -> RemainingBudget {
+        RemainingBudget {
+            steps: self
+                .max_steps
+                .map(|max_steps| max_steps.saturating_sub(self.used.steps)),
+            episodes: self
+                .max_episodes
+                .map(|max_episodes| max_episodes.saturating_sub(self.used.episodes)),
+            duration: self
+                .max_duration
+                .map(|max_duration| max_duration.saturating_sub(self.used.elapsed)),
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining().is_exhausted()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_used_up_limit_exhausts_the_budget() {
+        let mut budget = TrainingBudget::new().with_max_episodes(2);
+        assert!(!TrainingBudget::new().is_exhausted());
+        budget.consume_episodes(1);
+        assert!(!budget.is_exhausted());
+        budget.consume_episodes(3);
+        assert_eq!(Some(0), budget.remaining().episodes);
+        assert!(budget.is_exhausted());
+    }
+
+    #[test]
+    fn usage_survives_serialization() {
+        let mut budget = TrainingBudget::new().with_max_steps(10);
+        budget.consume_steps(4);
+        budget.consume_time(Duration::from_millis(1500));
+        let restored: TrainingBudget =
+            serde_json::from_value(serde_json::to_value(budget).unwrap()).unwrap();
+        assert_eq!(budget, restored);
+        assert_eq!(Some(6), restored.remaining().steps);
+    }
+}
@@ -0,0 +1,158 @@
+//! Hooks into the training loop of an `EpisodeRunner`.
+//!
+//! Logging, early stopping or anything else reacting to the progress of a run implements
+//! `Callback` and is handed to the runner, instead of the loop being written anew.
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use crate::budget::RemainingBudget;
+use crate::runner::EpisodeResult;
+use crate::{AgentAction, EnvironmentState};
+
+/// Tells the runner whether to go on after a callback was called.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Flow {
+    Continue,
+    /// Ends the current episode after this call and does not start another one.
+    Stop,
+}
+
+impl Flow {
+    /// Returns `Stop` if any of both flows is `Stop`.
+    pub fn and(self, other: Flow) -> Flow {
+        if self == Flow::Stop || other == Flow::Stop {
+            Flow::Stop
+        } else {
+            Flow::Continue
+        }
+    }
+}
+
+/// Everything that happened within a single step.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StepInfo<'a> {
+    /// Index of the episode within the lifetime of the runner.
+    pub episode: u64,
+    /// Index of the step within the episode.
+    pub step: u128,
+    /// Number of steps taken by the runner including this one.
+    pub total_steps: u128,
+    pub old_state: &'a EnvironmentState,
+    pub action: &'a AgentAction,
+    pub new_state: &'a EnvironmentState,
+    pub reward: f64,
+    pub done: bool,
+    /// What is left of the budget of the runner after this step, if it has one.
+    pub budget: Option<RemainingBudget>,
+}
+
+/// Receives the events of an `EpisodeRunner`.
+///
+/// Every method does nothing by default, so only the interesting events need to be handled.
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use gymnarium_base::callbacks::{Callback, Flow};
+/// use gymnarium_base::runner::EpisodeResult;
+///
+/// #[derive(Default)]
+/// struct ReturnLog {
+///     returns: Vec<f64>,
+/// }
+///
+/// impl Callback for ReturnLog {
+///     fn on_episode_end(&mut self, _episode: u64, result: &EpisodeResult) -> Flow {
+///         self.returns.push(result.total_reward);
+///         Flow::Continue
+///     }
+/// }
+///
+/// // Sharing the callback keeps it readable while the runner owns a handle.
+/// let log = Rc::new(RefCell::new(ReturnLog::default()));
+/// let mut handle: Box<dyn Callback> = Box::new(log.clone());
+/// handle.on_episode_end(0, &EpisodeResult { steps: 3, total_reward: 2.5, done: true });
+/// assert_eq!(vec![2.5], log.borrow().returns);
+/// ```
+pub trait Callback {
+    /// Called after the environment and the agent were reset.
+    fn on_episode_start(&mut self, _episode: u64, _state: &EnvironmentState) -> Flow {
+        Flow::Continue
+    }
+
+    /// Called after the agent processed the reward of a step.
+    fn on_step(&mut self, _step: &StepInfo<'_>) -> Flow {
+        Flow::Continue
+    }
+
+    /// Called after an episode ended, either by the environment or by a limit.
+    fn on_episode_end(&mut self, _episode: u64, _result: &EpisodeResult) -> Flow {
+        Flow::Continue
+    }
+
+    /// Called after the runner saved a checkpoint to the given path.
+    fn on_checkpoint(&mut self, _episode: u64, _path: &Path) {}
+}
+
+impl<C: Callback + ?Sized> Callback for Box<C> {
+    fn on_episode_start(&mut self, episode: u64, state: &EnvironmentState) -> Flow {
+        (**self).on_episode_start(episode, state)
+    }
+
+    fn on_step(&mut self, step: &StepInfo<'_>) -> Flow {
+        (**self).on_step(step)
+    }
+
+    fn on_episode_end(&mut self, episode: u64, result: &EpisodeResult) -> Flow {
+        (**self).on_episode_end(episode, result)
+    }
+
+    fn on_checkpoint(&mut self, episode: u64, path: &Path) {
+        (**self).on_checkpoint(episode, path)
+    }
+}
+
+impl<C: Callback + ?Sized> Callback for Rc<RefCell<C>> {
+    fn on_episode_start(&mut self, episode: u64, state: &EnvironmentState) -> Flow {
+        self.borrow_mut().on_episode_start(episode, state)
+    }
+
+    fn on_step(&mut self, step: &StepInfo<'_>) -> Flow {
+        self.borrow_mut().on_step(step)
+    }
+
+    fn on_episode_end(&mut self, episode: u64, result: &EpisodeResult) -> Flow {
+        self.borrow_mut().on_episode_end(episode, result)
+    }
+
+    fn on_checkpoint(&mut self, episode: u64, path: &Path) {
+        self.borrow_mut().on_checkpoint(episode, path)
+    }
+}
+
+impl<C: Callback + ?Sized> Callback for Arc<Mutex<C>> {
+    fn on_episode_start(&mut self, episode: u64, state: &EnvironmentState) -> Flow {
+        lock(self).on_episode_start(episode, state)
+    }
+
+    fn on_step(&mut self, step: &StepInfo<'_>) -> Flow {
+        lock(self).on_step(step)
+    }
+
+    fn on_episode_end(&mut self, episode: u64, result: &EpisodeResult) -> Flow {
+        lock(self).on_episode_end(episode, result)
+    }
+
+    fn on_checkpoint(&mut self, episode: u64, path: &Path) {
+        lock(self).on_checkpoint(episode, path)
+    }
+}
+
+fn lock<C: ?Sized>(mutex: &Mutex<C>) -> std::sync::MutexGuard<'_, C> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
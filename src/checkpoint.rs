@@ -0,0 +1,302 @@
+//! Saving and loading the state of an environment and an agent in a single file.
+//!
+//! A checkpoint is stored as JSON together with a format version. Files of older format versions
+//! can be upgraded while loading with `Checkpoint::load_migrating`.
+
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::budget::TrainingBudget;
+use crate::{Agent, Environment, Reward, Seed};
+
+/// The format version written by `Checkpoint::save`.
+pub const CHECKPOINT_FORMAT_VERSION: u32 = 1;
+
+/* --- --- --- CHECKPOINT ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CheckpointError {
+    Io(String),
+    Serialization(String),
+    MissingFormatVersion,
+    UnsupportedFormatVersion { found: u32, supported: u32 },
+    Migration { from_version: u32, message: String },
+}
+
+impl std::fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(message) => write!(f, "Checkpoint file could not be accessed ({})", message),
+            Self::Serialization(message) => {
+                write!(f, "Checkpoint could not be (de)serialized ({})", message)
+            }
+            Self::MissingFormatVersion => write!(f, "Checkpoint has no format version"),
+            Self::UnsupportedFormatVersion { found, supported } => write!(
+                f,
+                "Checkpoint format version {} is newer than the supported version {}",
+                found, supported
+            ),
+            Self::Migration {
+                from_version,
+                message,
+            } => write!(
+                f,
+                "Checkpoint could not be migrated from format version {} ({})",
+                from_version, message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<std::io::Error> for CheckpointError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CheckpointError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error.to_string())
+    }
+}
+
+/* --- --- --- CHECKPOINT --- --- --- */
+
+/// The stored data of an environment and an agent together with training progress.
+///
+/// ```
+/// use gymnarium_base::checkpoint::Checkpoint;
+///
+/// let path = std::env::temp_dir().join("gymnarium_checkpoint_doctest.json");
+/// let checkpoint = Checkpoint::new(vec![1, 2, 3], "agent weights".to_string()).with_steps(7, 120);
+/// checkpoint.save(&path).unwrap();
+///
+/// let loaded: Checkpoint<Vec<i32>, String> = Checkpoint::load(&path).unwrap();
+/// assert_eq!(checkpoint, loaded);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Checkpoint<ED, AD> {
+    pub environment: ED,
+    pub agent: AD,
+    pub seed: Option<Seed>,
+    pub episodes: u64,
+    pub steps: u64,
+    /// Free form statistics, e.g. of episode monitors.
+    pub statistics: serde_json::Value,
+    /// Budget of the run including what was used of it, so a resumed run keeps to it.
+    #[serde(default)]
+    pub budget: Option<TrainingBudget>,
+}
+
+impl<ED, AD> Checkpoint<ED, AD> {
+    pub fn new(environment: ED, agent: AD) -> Self {
+        Self {
+            environment,
+            agent,
+            seed: None,
+            episodes: 0,
+            steps: 0,
+            statistics: serde_json::Value::Null,
+            budget: None,
+        }
+    }
+
+    /// Stores the data returned by `store` of the environment and the agent.
+    pub fn capture<Env, Ag, EE, AE, R, I>(environment: &Env, agent: &Ag) -> Self
+    where
+        Env: Environment<EE, R, I, ED>,
+        Ag: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        Self::new(environment.store(), agent.store())
+    }
+
+    pub fn with_seed(mut self, seed: Seed) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_steps(mut self, episodes: u64, steps: u64) -> Self {
+        self.episodes = episodes;
+        self.steps = steps;
+        self
+    }
+
+    pub fn with_budget(mut self, budget: TrainingBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    pub fn with_statistics(mut self, statistics: serde_json::Value) -> Self {
+        self.statistics = statistics;
+        self
+    }
+}
+
+impl<ED: Serialize, AD: Serialize> Checkpoint<ED, AD> {
+    /// Writes this checkpoint with the current format version into the file at `path`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), CheckpointError> {
+        let envelope = VersionedCheckpoint {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            checkpoint: serde_json::to_value(self)?,
+        };
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, &envelope)?;
+        Ok(())
+    }
+}
+
+impl<ED: DeserializeOwned, AD: DeserializeOwned> Checkpoint<ED, AD> {
+    /// Reads a checkpoint of the current format version from the file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, CheckpointError> {
+        Self::load_migrating(path, |from_version, _| {
+            Err(CheckpointError::Migration {
+                from_version,
+                message: "No migration available".to_string(),
+            })
+        })
+    }
+
+    /// Reads a checkpoint from the file at `path`, upgrading older format versions.
+    ///
+    /// `migrate` is called with the format version and the stored checkpoint and has to return
+    /// the checkpoint in the next format version. It is called until the current format version
+    /// is reached.
+    pub fn load_migrating<P, M>(path: P, mut migrate: M) -> Result<Self, CheckpointError>
+    where
+        P: AsRef<Path>,
+        M: FnMut(u32, serde_json::Value) -> Result<serde_json::Value, CheckpointError>,
+    {
+        let reader = BufReader::new(File::open(path)?);
+        let envelope: serde_json::Value = serde_json::from_reader(reader)?;
+        let mut format_version = envelope
+            .get("format_version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or(CheckpointError::MissingFormatVersion)? as u32;
+        let mut checkpoint = envelope
+            .get("checkpoint")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+        if format_version > CHECKPOINT_FORMAT_VERSION {
+            return Err(CheckpointError::UnsupportedFormatVersion {
+                found: format_version,
+                supported: CHECKPOINT_FORMAT_VERSION,
+            });
+        }
+        while format_version < CHECKPOINT_FORMAT_VERSION {
+            checkpoint = migrate(format_version, checkpoint)?;
+            format_version += 1;
+        }
+        Ok(serde_json::from_value(checkpoint)?)
+    }
+}
+
+#[derive(Serialize)]
+struct VersionedCheckpoint {
+    format_version: u32,
+    checkpoint: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temporary_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("gymnarium_checkpoint_{}.json", name))
+    }
+
+    #[test]
+    fn older_format_versions_are_migrated() {
+        let path = temporary_path("migration");
+        std::fs::write(
+            &path,
+            r#"{"format_version": 0, "checkpoint": {"env": 5, "agent": null}}"#,
+        )
+        .unwrap();
+
+        assert!(Checkpoint::<i32, ()>::load(&path).is_err());
+        let loaded = Checkpoint::<i32, ()>::load_migrating(&path, |from_version, old| {
+            assert_eq!(0, from_version);
+            Ok(
+                serde_json::to_value(Checkpoint::new(old["env"].as_i64().unwrap() as i32, ()))
+                    .unwrap(),
+            )
+        });
+        assert_eq!(Ok(Checkpoint::new(5, ())), loaded);
+
+        std::fs::write(&path, r#"{"format_version": 99, "checkpoint": null}"#).unwrap();
+        assert_eq!(
+            Err(CheckpointError::UnsupportedFormatVersion {
+                found: 99,
+                supported: CHECKPOINT_FORMAT_VERSION
+            }),
+            Checkpoint::<i32, ()>::load(&path)
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn capture_stores_environment_data() {
+        use crate::wrappers::test_environment::CountingEnvironment;
+
+        let environment = CountingEnvironment { count: 3 };
+        let checkpoint: Checkpoint<i32, i32> = Checkpoint::capture(&environment, &StoringAgent(8));
+        assert_eq!(Checkpoint::new(3, 8), checkpoint);
+    }
+
+    struct StoringAgent(i32);
+
+    impl Agent<std::fmt::Error, f64, i32> for StoringAgent {
+        fn reseed(&mut self, _random_seed: Option<Seed>) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn reset(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn choose_action(
+            &mut self,
+            state: &crate::EnvironmentState,
+        ) -> Result<crate::AgentAction, std::fmt::Error> {
+            Ok(state.clone())
+        }
+
+        fn process_reward(
+            &mut self,
+            _old_state: &crate::EnvironmentState,
+            _last_action: &crate::AgentAction,
+            _new_state: &crate::EnvironmentState,
+            _reward: f64,
+            _is_done: bool,
+        ) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn load(&mut self, data: i32) -> Result<(), std::fmt::Error> {
+            self.0 = data;
+            Ok(())
+        }
+
+        fn store(&self) -> i32 {
+            self.0
+        }
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! Builds environments from configuration files, so experiments are defined without code edits.
+//!
+//! An environment implements `FromConfig`, declaring a serde-based configuration struct and the
+//! `Format` every configuration has to follow. `FromConfig::from_config_str` parses JSON, or TOML
+//! with the feature `toml`, checks the keys and value counts against the format and builds the
+//! environment. Registered with `registry::Registry::register_configurable`, the environment is
+//! built by its versioned identifier through `registry::Registry::make_from_config` instead, so
+//! that the configuration file chooses the environment as well.
+//!
+//! ```
+//! use gymnarium_base::config::FromConfig;
+//! use gymnarium_base::space::Format;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct CorridorConfig {
+//!     length: u32,
+//!     slip: [f64; 2],
+//! }
+//!
+//! struct Corridor {
+//!     length: u32,
+//! }
+//!
+//! impl FromConfig for Corridor {
+//!     type Config = CorridorConfig;
+//!     type Error = std::fmt::Error;
+//!
+//!     fn config_format() -> Format {
+//!         let mut format = Format::default();
+//!         format.add("length".to_string(), vec![1]).unwrap();
+//!         format.add("slip".to_string(), vec![2]).unwrap();
+//!         format
+//!     }
+//!
+//!     fn from_config(config: CorridorConfig) -> Result<Self, std::fmt::Error> {
+//!         Ok(Self { length: config.length })
+//!     }
+//! }
+//!
+//! let corridor = Corridor::from_config_str(r#"{"length": 12, "slip": [0.1, 0.05]}"#).unwrap();
+//! assert_eq!(12, corridor.length);
+//! ```
+
+use serde::de::DeserializeOwned;
+
+use crate::space::Format;
+
+/* --- --- --- CONFIG ERROR --- --- --- */
+
+/// Errors occurring while building an environment from a configuration.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConfigError<E> {
+    /// The configuration is neither valid JSON nor, with the feature `toml`, valid TOML.
+    Syntax(String),
+    /// A key of the format is missing in the configuration.
+    MissingKey(String),
+    /// The configuration contains a key the format does not know.
+    UnknownKey(String),
+    /// The value of a key does not have as many elements as its shape.
+    ShapeMismatch {
+        key: String,
+        needed: usize,
+        given: usize,
+    },
+    /// The configuration follows the format but does not fit the configuration struct.
+    Deserialization(String),
+    /// The environment returned an error itself while being built.
+    Environment(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ConfigError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(error) => write!(f, "Syntax Error \"{}\" occurred", error),
+            Self::MissingKey(key) => write!(f, "Key \"{}\" is missing", key),
+            Self::UnknownKey(key) => write!(f, "Key \"{}\" is unknown", key),
+            Self::ShapeMismatch { key, needed, given } => write!(
+                f,
+                "Key \"{}\" needs {} values but {} are given",
+                key, needed, given
+            ),
+            Self::Deserialization(error) => {
+                write!(f, "Deserialization Error \"{}\" occurred", error)
+            }
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ConfigError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/* --- --- --- FROM CONFIG --- --- --- */
+
+/// Environments which can be built from a configuration.
+pub trait FromConfig: Sized {
+    type Config: DeserializeOwned;
+    type Error: std::error::Error;
+
+    /// Returns the format every configuration has to follow.
+    ///
+    /// Keys of nested formats are written as nested tables, values with more than one element as
+    /// arrays, which may be nested as well.
+    fn config_format() -> Format;
+
+    fn from_config(config: Self::Config) -> Result<Self, Self::Error>;
+
+    /// Parses the configuration, checks it against `config_format` and builds the environment.
+    ///
+    /// Configurations starting with `{` are parsed as JSON, all others as TOML.
+    fn from_config_str(source: &str) -> Result<Self, ConfigError<Self::Error>> {
+        let value = parse(source)?;
+        validate(&value, &Self::config_format())?;
+        let config = serde_json::from_value(value)
+            .map_err(|error| ConfigError::Deserialization(error.to_string()))?;
+        Self::from_config(config).map_err(ConfigError::Environment)
+    }
+}
+
+fn parse<E>(source: &str) -> Result<serde_json::Value, ConfigError<E>> {
+    if source.trim_start().starts_with('{') {
+        return serde_json::from_str(source)
+            .map_err(|error| ConfigError::Syntax(error.to_string()));
+    }
+    #[cfg(feature = "toml")]
+    {
+        toml::from_str(source).map_err(|error| ConfigError::Syntax(error.to_string()))
+    }
+    #[cfg(not(feature = "toml"))]
+    {
+        Err(ConfigError::Syntax(
+            "Only JSON objects are supported without the feature toml".to_string(),
+        ))
+    }
+}
+
+fn validate<E>(value: &serde_json::Value, format: &Format) -> Result<(), ConfigError<E>> {
+    let mut given = Vec::new();
+    collect_leaves("", value, &mut given);
+    let entries = format.iter().collect::<Vec<_>>();
+    if let Some((key, _)) = given
+        .iter()
+        .find(|(key, _)| entries.iter().all(|(entry, _, _)| entry != key))
+    {
+        return Err(ConfigError::UnknownKey(key.clone()));
+    }
+    for (key, shape, _) in entries {
+        let needed = shape.iter().product::<usize>();
+        match given.iter().find(|(given_key, _)| *given_key == key) {
+            None => return Err(ConfigError::MissingKey(key)),
+            Some((_, count)) if *count != needed => {
+                return Err(ConfigError::ShapeMismatch {
+                    key,
+                    needed,
+                    given: *count,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Collects the dotted keys of all values which are no tables, with their element counts.
+fn collect_leaves(prefix: &str, value: &serde_json::Value, leaves: &mut Vec<(String, usize)>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                collect_leaves(&key, value, leaves);
+            }
+        }
+        value => leaves.push((prefix.to_string(), count_elements(value))),
+    }
+}
+
+fn count_elements(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::Array(values) => values.iter().map(count_elements).sum(),
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct CountingConfig {
+        start: i32,
+        weights: Vec<Vec<f64>>,
+    }
+
+    impl FromConfig for CountingEnvironment {
+        type Config = CountingConfig;
+        type Error = std::fmt::Error;
+
+        fn config_format() -> Format {
+            let mut format = Format::default();
+            format.add("start".to_string(), vec![1]).unwrap();
+            format.add("weights".to_string(), vec![2, 2]).unwrap();
+            format
+        }
+
+        fn from_config(config: CountingConfig) -> Result<Self, std::fmt::Error> {
+            if config.start > 5 || config.weights.len() != 2 {
+                return Err(std::fmt::Error);
+            }
+            Ok(Self {
+                count: config.start,
+            })
+        }
+    }
+
+    fn make(source: &str) -> Result<CountingEnvironment, ConfigError<std::fmt::Error>> {
+        CountingEnvironment::from_config_str(source)
+    }
+
+    #[test]
+    fn json_configurations_build_environments() {
+        let environment = make(r#"{"start": 3, "weights": [[1.0, 0.0], [0.0, 1.0]]}"#).unwrap();
+        assert_eq!(3, environment.count);
+        assert_eq!(
+            Err(ConfigError::Environment(std::fmt::Error)),
+            make(r#"{"start": 6, "weights": [[1.0, 0.0], [0.0, 1.0]]}"#).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn configurations_have_to_follow_the_format() {
+        assert_eq!(
+            Err(ConfigError::MissingKey("weights".to_string())),
+            make(r#"{"start": 3}"#).map(|_| ())
+        );
+        assert_eq!(
+            Err(ConfigError::UnknownKey("speed".to_string())),
+            make(r#"{"start": 3, "speed": 1, "weights": [[1.0, 0.0], [0.0, 1.0]]}"#).map(|_| ())
+        );
+        assert_eq!(
+            Err(ConfigError::ShapeMismatch {
+                key: "weights".to_string(),
+                needed: 4,
+                given: 3,
+            }),
+            make(r#"{"start": 3, "weights": [[1.0, 0.0], [0.0]]}"#).map(|_| ())
+        );
+        assert!(matches!(
+            make(r#"{"start": "three", "weights": [[1.0, 0.0], [0.0, 1.0]]}"#),
+            Err(ConfigError::Deserialization(_))
+        ));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_configurations_build_environments() {
+        let environment = make("start = 2\nweights = [[1.0, 0.0], [0.0, 1.0]]\n").unwrap();
+        assert_eq!(2, environment.count);
+        assert!(matches!(make("start = "), Err(ConfigError::Syntax(_))));
+    }
+}
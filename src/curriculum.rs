@@ -0,0 +1,217 @@
+//! Adapting environment parameters to the training progress.
+//!
+//! A `Curriculum` chooses the parameters of a `ConfigurableEnvironment` from the number of
+//! finished episodes and the mean return, e.g. to increase the difficulty step by step.
+
+use std::collections::VecDeque;
+
+use crate::Parameters;
+
+/* --- --- --- CONFIGURABLE ENVIRONMENT --- --- --- */
+
+/// Environments whose behaviour can be changed with parameters between episodes.
+pub trait ConfigurableEnvironment<E: std::error::Error> {
+    /// Replaces the parameters used from the next reset on.
+    fn set_parameters(&mut self, parameters: Parameters) -> Result<(), E>;
+}
+
+/* --- --- --- TRAINING PROGRESS --- --- --- */
+
+/// Counts finished episodes and averages the returns of the most recent ones.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TrainingProgress {
+    episodes: u64,
+    window: usize,
+    returns: VecDeque<f64>,
+}
+
+impl TrainingProgress {
+    /// Creates the progress averaging over the last `window` episodes.
+    pub fn new(window: usize) -> Self {
+        Self {
+            episodes: 0,
+            window: window.max(1),
+            returns: VecDeque::with_capacity(window.max(1)),
+        }
+    }
+
+    pub fn record_episode(&mut self, episode_return: f64) {
+        self.episodes += 1;
+        if self.returns.len() == self.window {
+            self.returns.pop_front();
+        }
+        self.returns.push_back(episode_return);
+    }
+
+    pub fn episodes(&self) -> u64 {
+        self.episodes
+    }
+
+    /// Returns the mean return of the last episodes or `None` before the first episode.
+    pub fn mean_return(&self) -> Option<f64> {
+        if self.returns.is_empty() {
+            None
+        } else {
+            Some(self.returns.iter().sum::<f64>() / self.returns.len() as f64)
+        }
+    }
+}
+
+/* --- --- --- CURRICULUM --- --- --- */
+
+/// Base trait for mapping training progress to environment parameters.
+pub trait Curriculum {
+    /// Returns the parameters to use for the given progress.
+    fn parameters(&mut self, progress: &TrainingProgress) -> Parameters;
+}
+
+/// A stage of a `StagedCurriculum`, entered once all its requirements are met.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Stage {
+    pub min_episodes: u64,
+    pub min_mean_return: Option<f64>,
+    pub parameters: Parameters,
+}
+
+impl Stage {
+    pub fn new(parameters: Parameters) -> Self {
+        Self {
+            min_episodes: 0,
+            min_mean_return: None,
+            parameters,
+        }
+    }
+
+    pub fn after_episodes(mut self, episodes: u64) -> Self {
+        self.min_episodes = episodes;
+        self
+    }
+
+    pub fn after_mean_return(mut self, mean_return: f64) -> Self {
+        self.min_mean_return = Some(mean_return);
+        self
+    }
+
+    fn is_reached(&self, progress: &TrainingProgress) -> bool {
+        progress.episodes() >= self.min_episodes
+            && self
+                .min_mean_return
+                .is_none_or(|min| progress.mean_return().is_some_and(|mean| mean >= min))
+    }
+}
+
+/// Advances through a list of stages, never going back to an earlier one.
+///
+/// ```
+/// use gymnarium_base::curriculum::{Curriculum, Stage, StagedCurriculum, TrainingProgress};
+/// use gymnarium_base::Parameters;
+///
+/// let level = |level: u32| Parameters::from([("level".to_string(), level.into())]);
+/// let mut curriculum = StagedCurriculum::new(vec![
+///     Stage::new(level(1)),
+///     Stage::new(level(2)).after_episodes(2).after_mean_return(10.0),
+/// ]);
+///
+/// let mut progress = TrainingProgress::new(2);
+/// progress.record_episode(12.0);
+/// progress.record_episode(4.0);
+/// assert_eq!(level(1), curriculum.parameters(&progress));
+/// progress.record_episode(20.0);
+/// assert_eq!(level(2), curriculum.parameters(&progress));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct StagedCurriculum {
+    stages: Vec<Stage>,
+    current: usize,
+    applied: Option<usize>,
+}
+
+impl StagedCurriculum {
+    /// Creates the curriculum starting in the first stage.
+    ///
+    /// # Panics
+    /// If no stages are given.
+    pub fn new(stages: Vec<Stage>) -> Self {
+        assert!(!stages.is_empty(), "A curriculum needs at least one stage");
+        Self {
+            stages,
+            current: 0,
+            applied: None,
+        }
+    }
+
+    /// Returns the index of the current stage.
+    pub fn stage(&self) -> usize {
+        self.current
+    }
+
+    /// Sets the parameters for the given progress if the stage changed since the last call and
+    /// returns whether the environment was updated.
+    pub fn update<E, Env>(
+        &mut self,
+        environment: &mut Env,
+        progress: &TrainingProgress,
+    ) -> Result<bool, E>
+    where
+        E: std::error::Error,
+        Env: ConfigurableEnvironment<E>,
+    {
+        self.advance(progress);
+        if self.applied == Some(self.current) {
+            return Ok(false);
+        }
+        environment.set_parameters(self.stages[self.current].parameters.clone())?;
+        self.applied = Some(self.current);
+        Ok(true)
+    }
+
+    fn advance(&mut self, progress: &TrainingProgress) {
+        while self.current + 1 < self.stages.len()
+            && self.stages[self.current + 1].is_reached(progress)
+        {
+            self.current += 1;
+        }
+    }
+}
+
+impl Curriculum for StagedCurriculum {
+    fn parameters(&mut self, progress: &TrainingProgress) -> Parameters {
+        self.advance(progress);
+        self.stages[self.current].parameters.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder(Vec<Parameters>);
+
+    impl ConfigurableEnvironment<std::fmt::Error> for Recorder {
+        fn set_parameters(&mut self, parameters: Parameters) -> Result<(), std::fmt::Error> {
+            self.0.push(parameters);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn update_sets_parameters_only_on_stage_changes() {
+        let speed = |speed: f64| Parameters::from([("speed".to_string(), speed.into())]);
+        let mut curriculum = StagedCurriculum::new(vec![
+            Stage::new(speed(1.0)),
+            Stage::new(speed(2.0)).after_episodes(1),
+            Stage::new(speed(3.0)).after_episodes(2),
+        ]);
+        let mut environment = Recorder::default();
+        let mut progress = TrainingProgress::new(1);
+
+        assert_eq!(Ok(true), curriculum.update(&mut environment, &progress));
+        assert_eq!(Ok(false), curriculum.update(&mut environment, &progress));
+        progress.record_episode(0.0);
+        progress.record_episode(0.0);
+        assert_eq!(Ok(true), curriculum.update(&mut environment, &progress));
+        assert_eq!(vec![speed(1.0), speed(3.0)], environment.0);
+        assert_eq!(2, curriculum.stage());
+    }
+}
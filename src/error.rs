@@ -0,0 +1,121 @@
+//! Ready-made error type for environments and agents.
+
+use crate::space::{FormatError, SpaceError};
+
+/// Error wrapping everything that can go wrong inside this crate, so environments and agents
+/// can use it instead of defining their own error type.
+///
+/// ```
+/// use gymnarium_base::GymnariumError;
+/// use gymnarium_base::space::{DimensionBoundaries, Space};
+///
+/// fn observation_space(shape: Vec<usize>) -> Result<Space, GymnariumError> {
+///     Ok(Space::new(vec![DimensionBoundaries::from(0..=1); 4], shape)?)
+/// }
+///
+/// assert!(observation_space(vec![2, 2]).is_ok());
+/// assert!(matches!(observation_space(vec![3]), Err(GymnariumError::Space(_))));
+/// ```
+#[derive(Debug)]
+pub enum GymnariumError {
+    Space(SpaceError),
+    Format(FormatError),
+    /// Error of an environment or agent not fitting into any other variant.
+    Environment(Box<dyn std::error::Error + Send + Sync + 'static>),
+    Io(std::io::Error),
+    Serialization(serde_json::Error),
+}
+
+impl GymnariumError {
+    pub fn environment<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+        Self::Environment(Box::new(error))
+    }
+}
+
+impl std::fmt::Display for GymnariumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Space(error) => write!(f, "Space Error \"{}\" occurred", error),
+            Self::Format(error) => write!(f, "Format Error \"{}\" occurred", error),
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::Io(error) => write!(f, "IO Error \"{}\" occurred", error),
+            Self::Serialization(error) => {
+                write!(f, "Serialization Error \"{}\" occurred", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GymnariumError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Space(error) => Some(error),
+            Self::Format(error) => Some(error),
+            Self::Environment(error) => Some(error.as_ref()),
+            Self::Io(error) => Some(error),
+            Self::Serialization(error) => Some(error),
+        }
+    }
+}
+
+impl From<SpaceError> for GymnariumError {
+    fn from(error: SpaceError) -> Self {
+        Self::Space(error)
+    }
+}
+
+impl From<FormatError> for GymnariumError {
+    fn from(error: FormatError) -> Self {
+        Self::Format(error)
+    }
+}
+
+impl From<std::io::Error> for GymnariumError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for GymnariumError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn sources_are_chained() {
+        let error = GymnariumError::from(FormatError::SpaceCreationError {
+            key: "position".to_string(),
+            error: SpaceError::InvalidBoundaries { index: 1 },
+        });
+        let source = error.source().unwrap();
+        assert!(source.is::<FormatError>());
+        assert_eq!(
+            Some(SpaceError::InvalidBoundaries { index: 1 }.to_string()),
+            source.source().map(|error| error.to_string())
+        );
+
+        let error = GymnariumError::environment(std::fmt::Error);
+        assert!(error.source().unwrap().is::<std::fmt::Error>());
+    }
+
+    #[test]
+    fn foreign_errors_are_converted() {
+        fn parse(text: &str) -> Result<u8, GymnariumError> {
+            Ok(serde_json::from_str(text)?)
+        }
+        assert!(matches!(
+            parse("no json"),
+            Err(GymnariumError::Serialization(_))
+        ));
+        assert!(matches!(
+            GymnariumError::from(std::io::Error::from(std::io::ErrorKind::NotFound)),
+            GymnariumError::Io(_)
+        ));
+    }
+}
@@ -0,0 +1,283 @@
+//! Standardized evaluation of agents and early stopping of training runs.
+//!
+//! `evaluate` resets every episode with its own seed derived from one evaluation seed, so two
+//! agents evaluated with the same seed face exactly the same episodes.
+
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::callbacks::{Callback, Flow};
+use crate::curriculum::TrainingProgress;
+use crate::runner::{episode_seed, EpisodeResult, RunnerError};
+use crate::{Agent, Environment, Reward, Seed};
+
+/// Info key whose value in the info of the last step tells whether an episode was a success.
+pub const SUCCESS_INFO_KEY: &str = "is_success";
+
+/* --- --- --- EVALUATION --- --- --- */
+
+/// Results of evaluating an agent over several episodes.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct EvalReport {
+    /// Total reward of every episode in order.
+    pub returns: Vec<f64>,
+    pub mean_return: f64,
+    /// Population standard deviation of the returns.
+    pub std_return: f64,
+    pub mean_length: f64,
+    /// Share of successful episodes, or `None` if no episode reported success or failure.
+    pub success_rate: Option<f64>,
+}
+
+impl EvalReport {
+    pub fn episodes(&self) -> usize {
+        self.returns.len()
+    }
+}
+
+fn is_success(info: &serde_json::Value) -> Option<bool> {
+    match info.get(SUCCESS_INFO_KEY)? {
+        serde_json::Value::Bool(success) => Some(*success),
+        serde_json::Value::Number(number) => number.as_f64().map(|number| number != 0f64),
+        _ => None,
+    }
+}
+
+/// Plays the given number of episodes without handing rewards to the agent and reports the
+/// returns.
+///
+/// Episodes are limited to the suggested episode steps count of the environment. Successes are
+/// read from the `SUCCESS_INFO_KEY` entry of the serialized info of the last step.
+///
+/// ```
+/// use gymnarium_base::evaluation::evaluate;
+/// # use gymnarium_base::{position, AgentAction, Environment, EnvironmentState, Info, ResetOptions, Seed};
+/// # use gymnarium_base::agents::ClosureAgent;
+/// # use gymnarium_base::space::{DimensionBoundaries, Space};
+/// # struct Coin(u32);
+/// # impl Environment<std::fmt::Error, f64, Info, ()> for Coin {
+/// #     fn action_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(0..=1)]) }
+/// #     fn observation_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(0..=0)]) }
+/// #     fn suggested_episode_steps_count(&self) -> Option<u128> { Some(1) }
+/// #     fn reset(&mut self, _: Option<Seed>, _: Option<ResetOptions>) -> Result<EnvironmentState, std::fmt::Error> { self.0 += 1; Ok(self.state()) }
+/// #     fn state(&self) -> EnvironmentState { position![0] }
+/// #     fn step(&mut self, _: &AgentAction) -> Result<(EnvironmentState, f64, bool, Info), std::fmt::Error> {
+/// #         let mut info = Info::new();
+/// #         info.insert("is_success".to_string(), (self.0 % 2 == 0).into());
+/// #         Ok((self.state(), self.0 as f64, true, info))
+/// #     }
+/// #     fn load(&mut self, _: ()) -> Result<(), std::fmt::Error> { Ok(()) }
+/// #     fn store(&self) {}
+/// #     fn close(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+/// # }
+///
+/// let mut agent = ClosureAgent::new(|_: &EnvironmentState| position![0]);
+/// let report = evaluate(&mut Coin(0), &mut agent, 4, Seed::from(7)).unwrap();
+/// assert_eq!(vec![1.0, 2.0, 3.0, 4.0], report.returns);
+/// assert_eq!(2.5, report.mean_return);
+/// assert_eq!(Some(0.5), report.success_rate);
+/// ```
+pub fn evaluate<Env, A, EE, AE, R, I, ED, AD>(
+    environment: &mut Env,
+    agent: &mut A,
+    episodes: u64,
+    seed: Seed,
+) -> Result<EvalReport, RunnerError<EE, AE>>
+where
+    Env: Environment<EE, R, I, ED>,
+    A: Agent<AE, R, AD>,
+    EE: std::error::Error,
+    AE: std::error::Error,
+    R: Reward,
+    I: Debug + Serialize,
+    ED: Serialize + DeserializeOwned,
+    AD: Serialize + DeserializeOwned,
+{
+    let max_steps = environment.suggested_episode_steps_count();
+    let mut report = EvalReport::default();
+    let mut lengths = 0u128;
+    let mut successes = Vec::new();
+
+    for episode in 0..episodes {
+        let mut state = environment
+            .reset(Some(episode_seed(&seed, episode)), None)
+            .map_err(RunnerError::Environment)?;
+        agent.reset().map_err(RunnerError::Agent)?;
+
+        let mut result = EpisodeResult::default();
+        let mut last_info = None;
+        while !result.done && max_steps.is_none_or(|max_steps| result.steps < max_steps) {
+            let action = agent.choose_action(&state).map_err(RunnerError::Agent)?;
+            let (new_state, reward, done, info) = environment
+                .step(&action)
+                .map_err(RunnerError::Environment)?;
+            result.steps += 1;
+            result.total_reward += reward.value();
+            result.done = done;
+            state = new_state;
+            last_info = Some(info);
+        }
+
+        report.returns.push(result.total_reward);
+        lengths += result.steps;
+        if let Some(success) = last_info
+            .and_then(|info| serde_json::to_value(info).ok())
+            .and_then(|info| is_success(&info))
+        {
+            successes.push(success);
+        }
+    }
+
+    if !report.returns.is_empty() {
+        let count = report.returns.len() as f64;
+        report.mean_return = report.returns.iter().sum::<f64>() / count;
+        report.std_return = (report
+            .returns
+            .iter()
+            .map(|value| (value - report.mean_return).powi(2))
+            .sum::<f64>()
+            / count)
+            .sqrt();
+        report.mean_length = lengths as f64 / count;
+    }
+    if !successes.is_empty() {
+        report.success_rate = Some(
+            successes.iter().filter(|success| **success).count() as f64 / successes.len() as f64,
+        );
+    }
+    Ok(report)
+}
+
+/* --- --- --- EARLY STOPPER --- --- --- */
+
+/// Callback stopping a run once the rolling mean return stops improving or reaches a target.
+///
+/// Besides the returns of the training episodes seen as callback, scores like the mean return of
+/// an `EvalReport` can be fed with `observe`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EarlyStopper {
+    progress: TrainingProgress,
+    patience: u64,
+    min_delta: f64,
+    target: Option<f64>,
+    best: Option<f64>,
+    without_improvement: u64,
+}
+
+impl EarlyStopper {
+    /// Stops after `patience` observations without the mean of the last `window` returns
+    /// improving on its best value.
+    pub fn new(window: usize, patience: u64) -> Self {
+        Self {
+            progress: TrainingProgress::new(window),
+            patience,
+            min_delta: 0f64,
+            target: None,
+            best: None,
+            without_improvement: 0,
+        }
+    }
+
+    /// Counts only improvements by more than `min_delta`.
+    pub fn with_min_delta(mut self, min_delta: f64) -> Self {
+        self.min_delta = min_delta;
+        self
+    }
+
+    /// Stops as soon as the rolling mean return reaches the target.
+    pub fn with_target(mut self, target: f64) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Returns the best rolling mean return seen so far.
+    pub fn best_mean_return(&self) -> Option<f64> {
+        self.best
+    }
+
+    pub fn should_stop(&self) -> bool {
+        let reached_target = match (self.target, self.progress.mean_return()) {
+            (Some(target), Some(mean)) => mean >= target,
+            _ => false,
+        };
+        reached_target || self.without_improvement >= self.patience
+    }
+
+    /// Adds a return to the rolling window and tells whether to stop.
+    pub fn observe(&mut self, episode_return: f64) -> Flow {
+        self.progress.record_episode(episode_return);
+        let mean = self
+            .progress
+            .mean_return()
+            .expect("a return was just recorded");
+        if self.best.is_none_or(|best| mean > best + self.min_delta) {
+            self.best = Some(mean);
+            self.without_improvement = 0;
+        } else {
+            self.without_improvement += 1;
+        }
+        if self.should_stop() {
+            Flow::Stop
+        } else {
+            Flow::Continue
+        }
+    }
+}
+
+impl Callback for EarlyStopper {
+    fn on_episode_end(&mut self, _episode: u64, result: &EpisodeResult) -> Flow {
+        self.observe(result.total_reward)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::test_agent::ConstantAgent;
+    use crate::runner::EpisodeRunner;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn evaluation_is_deterministic_and_untrained() {
+        let mut agent = ConstantAgent::new(1);
+        let report = evaluate(
+            &mut CountingEnvironment::default(),
+            &mut agent,
+            3,
+            Seed::from(1),
+        )
+        .unwrap();
+        assert_eq!(vec![15f64; 3], report.returns);
+        assert_eq!(
+            (15f64, 0f64, 5f64),
+            (report.mean_return, report.std_return, report.mean_length)
+        );
+        assert_eq!(None, report.success_rate);
+        assert!(agent.rewards.is_empty());
+    }
+
+    #[test]
+    fn early_stopper_stops_without_improvement_or_at_target() {
+        let mut stopper = EarlyStopper::new(1, 2);
+        assert_eq!(Flow::Continue, stopper.observe(1f64));
+        assert_eq!(Flow::Continue, stopper.observe(2f64));
+        assert_eq!(Flow::Continue, stopper.observe(2f64));
+        assert_eq!(Flow::Stop, stopper.observe(1f64));
+        assert_eq!(Some(2f64), stopper.best_mean_return());
+
+        let mut runner = EpisodeRunner::new()
+            .with_max_steps(3)
+            .with_callback(EarlyStopper::new(2, 100).with_target(6f64));
+        let results = runner
+            .run(
+                &mut CountingEnvironment::default(),
+                &mut ConstantAgent::new(1),
+                10,
+                None,
+            )
+            .unwrap();
+        assert_eq!(1, results.len());
+    }
+}
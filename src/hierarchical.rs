@@ -0,0 +1,403 @@
+//! Hierarchical agents acting with temporally extended options.
+//!
+//! A `HighLevelAgent` chooses an option, e.g. a sub-goal or skill, and a `LowLevelAgent` acts
+//! under it step by step until the option terminates. The `HierarchicalRunner` alternates both
+//! levels within an episode and hands every level the results it needs to learn.
+
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::runner::EpisodeResult;
+use crate::{AgentAction, Environment, EnvironmentState, Reward, Seed};
+
+/* --- --- --- AGENT TRAITS --- --- --- */
+
+/// Agent choosing options of type `O` instead of actions.
+pub trait HighLevelAgent<E, O, D>
+where
+    E: std::error::Error,
+    D: Serialize + DeserializeOwned,
+{
+    /// Resets a possible internal random number generator with the given seed or by entropy.
+    fn reseed(&mut self, random_seed: Option<Seed>) -> Result<(), E>;
+
+    /// Resets the state and initial resources of the agent.
+    fn reset(&mut self) -> Result<(), E>;
+
+    /// Returns the option to follow from the given environment state on.
+    fn choose_option(&mut self, state: &EnvironmentState) -> Result<O, E>;
+
+    /// Lets this agent process the result of a terminated option.
+    ///
+    /// `reward` is the discounted sum of the reward values collected during the `steps` steps the
+    /// option lasted.
+    fn process_option_result(
+        &mut self,
+        start_state: &EnvironmentState,
+        option: &O,
+        end_state: &EnvironmentState,
+        reward: f64,
+        steps: u128,
+        is_done: bool,
+    ) -> Result<(), E>;
+
+    /// Overrides the agents state with the provided data structure containing a previous state.
+    fn load(&mut self, data: D) -> Result<(), E>;
+
+    /// Returns a serializable structure containing everything to reconstruct the agent.
+    fn store(&self) -> D;
+
+    /// Cleans up resources of this agent.
+    fn close(&mut self) -> Result<(), E>;
+}
+
+/// Agent acting under an option of type `O` chosen by a `HighLevelAgent`.
+pub trait LowLevelAgent<E, R, O, D>
+where
+    E: std::error::Error,
+    R: Reward,
+    D: Serialize + DeserializeOwned,
+{
+    /// Resets a possible internal random number generator with the given seed or by entropy.
+    fn reseed(&mut self, random_seed: Option<Seed>) -> Result<(), E>;
+
+    /// Resets the state and initial resources of the agent.
+    fn reset(&mut self) -> Result<(), E>;
+
+    /// Returns an action following the given option.
+    fn choose_action(&mut self, state: &EnvironmentState, option: &O) -> Result<AgentAction, E>;
+
+    /// Lets this agent process the result of the last step taken under the option.
+    fn process_reward(
+        &mut self,
+        option: &O,
+        old_state: &EnvironmentState,
+        last_action: &AgentAction,
+        new_state: &EnvironmentState,
+        reward: R,
+        is_done: bool,
+    ) -> Result<(), E>;
+
+    /// Termination condition of the option, checked after every step with the number of steps
+    /// taken under the option so far.
+    fn is_option_terminated(&mut self, state: &EnvironmentState, option: &O, steps: u128) -> bool;
+
+    /// Overrides the agents state with the provided data structure containing a previous state.
+    fn load(&mut self, data: D) -> Result<(), E>;
+
+    /// Returns a serializable structure containing everything to reconstruct the agent.
+    fn store(&self) -> D;
+
+    /// Cleans up resources of this agent.
+    fn close(&mut self) -> Result<(), E>;
+}
+
+/* --- --- --- HIERARCHICAL ERROR --- --- --- */
+
+/// Errors while running a hierarchical episode.
+#[derive(Debug, PartialEq, Clone)]
+pub enum HierarchicalError<EE, HE, LE> {
+    Environment(EE),
+    HighLevel(HE),
+    LowLevel(LE),
+}
+
+impl<EE, HE, LE> std::fmt::Display for HierarchicalError<EE, HE, LE>
+where
+    EE: std::fmt::Display,
+    HE: std::fmt::Display,
+    LE: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::HighLevel(error) => write!(f, "High level agent Error \"{}\" occurred", error),
+            Self::LowLevel(error) => write!(f, "Low level agent Error \"{}\" occurred", error),
+        }
+    }
+}
+
+impl<EE, HE, LE> std::error::Error for HierarchicalError<EE, HE, LE>
+where
+    EE: std::error::Error + 'static,
+    HE: std::error::Error + 'static,
+    LE: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            Self::HighLevel(error) => Some(error),
+            Self::LowLevel(error) => Some(error),
+        }
+    }
+}
+
+/* --- --- --- HIERARCHICAL RUNNER --- --- --- */
+
+/// Summary of a finished hierarchical episode.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct HierarchicalEpisodeResult {
+    pub episode: EpisodeResult,
+    /// Number of options chosen within the episode.
+    pub options: u64,
+}
+
+/// Plays episodes with a high level agent choosing options and a low level agent executing them.
+///
+/// An option ends when the low level agent reports it as terminated, when it lasted the maximum
+/// option length or when the episode ends.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HierarchicalRunner {
+    max_steps: Option<u128>,
+    max_option_steps: Option<u128>,
+    option_discount: f64,
+}
+
+impl HierarchicalRunner {
+    pub fn new() -> Self {
+        Self {
+            max_steps: None,
+            max_option_steps: None,
+            option_discount: 1f64,
+        }
+    }
+
+    /// Ends every episode after the given number of steps at the latest.
+    pub fn with_max_steps(mut self, max_steps: u128) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Terminates every option after the given number of steps at the latest.
+    pub fn with_max_option_steps(mut self, max_option_steps: u128) -> Self {
+        self.max_option_steps = Some(max_option_steps);
+        self
+    }
+
+    /// Discounts the rewards summed up for the high level agent within an option.
+    pub fn with_option_discount(mut self, option_discount: f64) -> Self {
+        self.option_discount = option_discount;
+        self
+    }
+
+    /// Plays a single episode, resetting the environment with the given seed.
+    pub fn run_episode<Env, H, L, EE, HE, LE, R, I, O, ED, HD, LD>(
+        &mut self,
+        environment: &mut Env,
+        high_level: &mut H,
+        low_level: &mut L,
+        seed: Option<Seed>,
+    ) -> Result<HierarchicalEpisodeResult, HierarchicalError<EE, HE, LE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        H: HighLevelAgent<HE, O, HD>,
+        L: LowLevelAgent<LE, R, O, LD>,
+        EE: std::error::Error,
+        HE: std::error::Error,
+        LE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        HD: Serialize + DeserializeOwned,
+        LD: Serialize + DeserializeOwned,
+    {
+        let mut state = environment
+            .reset(seed, None)
+            .map_err(HierarchicalError::Environment)?;
+        high_level.reset().map_err(HierarchicalError::HighLevel)?;
+        low_level.reset().map_err(HierarchicalError::LowLevel)?;
+
+        let mut result = HierarchicalEpisodeResult::default();
+        let within_limit = |steps: u128| self.max_steps.is_none_or(|max_steps| steps < max_steps);
+        while !result.episode.done && within_limit(result.episode.steps) {
+            let option = high_level
+                .choose_option(&state)
+                .map_err(HierarchicalError::HighLevel)?;
+            result.options += 1;
+            let start_state = state.clone();
+            let mut option_reward = 0f64;
+            let mut option_steps = 0u128;
+            let mut discount = 1f64;
+
+            loop {
+                let action = low_level
+                    .choose_action(&state, &option)
+                    .map_err(HierarchicalError::LowLevel)?;
+                let (new_state, reward, done, _) = environment
+                    .step(&action)
+                    .map_err(HierarchicalError::Environment)?;
+                let reward_value = reward.value();
+                low_level
+                    .process_reward(&option, &state, &action, &new_state, reward, done)
+                    .map_err(HierarchicalError::LowLevel)?;
+                state = new_state;
+
+                option_reward += discount * reward_value;
+                discount *= self.option_discount;
+                option_steps += 1;
+                result.episode.steps += 1;
+                result.episode.total_reward += reward_value;
+                result.episode.done = done;
+
+                let option_exhausted = self
+                    .max_option_steps
+                    .is_some_and(|max_option_steps| option_steps >= max_option_steps);
+                if done
+                    || option_exhausted
+                    || !within_limit(result.episode.steps)
+                    || low_level.is_option_terminated(&state, &option, option_steps)
+                {
+                    break;
+                }
+            }
+
+            high_level
+                .process_option_result(
+                    &start_state,
+                    &option,
+                    &state,
+                    option_reward,
+                    option_steps,
+                    result.episode.done,
+                )
+                .map_err(HierarchicalError::HighLevel)?;
+        }
+        Ok(result)
+    }
+}
+
+impl Default for HierarchicalRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    /// Chooses to move by `+1` for three steps, then by `-1` for a single step.
+    #[derive(Default)]
+    struct Planner {
+        results: Vec<(i32, f64, u128)>,
+    }
+
+    impl HighLevelAgent<std::fmt::Error, i32, ()> for Planner {
+        fn reseed(&mut self, _random_seed: Option<Seed>) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn reset(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn choose_option(&mut self, _state: &EnvironmentState) -> Result<i32, std::fmt::Error> {
+            Ok(if self.results.len().is_multiple_of(2) {
+                1
+            } else {
+                -1
+            })
+        }
+
+        fn process_option_result(
+            &mut self,
+            _start_state: &EnvironmentState,
+            option: &i32,
+            _end_state: &EnvironmentState,
+            reward: f64,
+            steps: u128,
+            _is_done: bool,
+        ) -> Result<(), std::fmt::Error> {
+            self.results.push((*option, reward, steps));
+            Ok(())
+        }
+
+        fn load(&mut self, _data: ()) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn store(&self) {}
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+
+    struct Mover;
+
+    impl LowLevelAgent<std::fmt::Error, f64, i32, ()> for Mover {
+        fn reseed(&mut self, _random_seed: Option<Seed>) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn reset(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn choose_action(
+            &mut self,
+            _state: &EnvironmentState,
+            option: &i32,
+        ) -> Result<AgentAction, std::fmt::Error> {
+            Ok(position![*option])
+        }
+
+        fn process_reward(
+            &mut self,
+            _option: &i32,
+            _old_state: &EnvironmentState,
+            _last_action: &AgentAction,
+            _new_state: &EnvironmentState,
+            _reward: f64,
+            _is_done: bool,
+        ) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn is_option_terminated(
+            &mut self,
+            _state: &EnvironmentState,
+            option: &i32,
+            _: u128,
+        ) -> bool {
+            *option < 0
+        }
+
+        fn load(&mut self, _data: ()) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn store(&self) {}
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn options_terminate_by_condition_length_or_episode_end() {
+        let mut planner = Planner::default();
+        let result = HierarchicalRunner::new()
+            .with_max_option_steps(3)
+            .run_episode(
+                &mut CountingEnvironment::default(),
+                &mut planner,
+                &mut Mover,
+                None,
+            )
+            .unwrap();
+
+        // Counts 1, 2, 3 then 2, then 3, 4, 5 where the episode ends.
+        assert_eq!(
+            vec![(1, 6f64, 3), (-1, 2f64, 1), (1, 12f64, 3)],
+            planner.results
+        );
+        assert_eq!(3, result.options);
+        assert_eq!(7, result.episode.steps);
+        assert!(result.episode.done);
+    }
+}
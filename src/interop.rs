@@ -0,0 +1,10 @@
+//! Exposes environments to other languages and frameworks.
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod numpy;
+#[cfg(feature = "arrow")]
+pub mod parquet;
+pub mod python;
+#[cfg(feature = "shm")]
+pub mod shm;
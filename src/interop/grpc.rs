@@ -0,0 +1,587 @@
+//! Serves environments over gRPC and drives them remotely as if they were local.
+//!
+//! The messages and the `gymnarium.EnvironmentService` are defined in `proto/gymnarium.proto`,
+//! so clients in other languages can be generated from it. `EnvironmentServer` serves any
+//! environment, `RemoteEnvironment` connects to such a server and implements `Environment` itself.
+//! Infos and the data of `load` and `store` are exchanged as JSON.
+//!
+//! ```no_run
+//! use gymnarium_base::interop::grpc::{EnvironmentServer, RemoteEnvironment};
+//! # use gymnarium_base::Environment;
+//! # fn serve<Env: Environment<std::fmt::Error, f64, (), ()> + Send + 'static>(environment: Env) {
+//!
+//! let listener = std::net::TcpListener::bind("127.0.0.1:50051").unwrap();
+//! std::thread::spawn(move || EnvironmentServer::new(environment).serve(listener));
+//!
+//! let mut remote = RemoteEnvironment::connect("http://127.0.0.1:50051").unwrap();
+//! let state = remote.reset(None, None).unwrap();
+//! # }
+//! ```
+
+pub mod proto;
+
+use std::convert::{Infallible, TryFrom};
+use std::fmt::Debug;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::codegen::{http, Service};
+use tonic::server::NamedService;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Status};
+
+use crate::space::Position;
+use crate::{
+    ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace, ResetOptions,
+    Reward, Seed,
+};
+
+const SERVICE_NAME: &str = "gymnarium.EnvironmentService";
+
+/* --- --- --- GRPC ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum GrpcError {
+    /// The connection could not be established or broke down.
+    Transport(String),
+    /// The server answered with an error status.
+    Status { code: Code, message: String },
+    /// A message could not be converted into the own types.
+    Conversion(String),
+}
+
+impl std::fmt::Display for GrpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transport(message) => write!(f, "Transport Error \"{}\" occurred", message),
+            Self::Status { code, message } => {
+                write!(f, "Status \"{:?}\" with \"{}\" received", code, message)
+            }
+            Self::Conversion(message) => write!(f, "Conversion Error \"{}\" occurred", message),
+        }
+    }
+}
+
+impl std::error::Error for GrpcError {}
+
+impl From<Status> for GrpcError {
+    fn from(status: Status) -> Self {
+        Self::Status {
+            code: status.code(),
+            message: status.message().to_string(),
+        }
+    }
+}
+
+impl From<tonic::transport::Error> for GrpcError {
+    fn from(error: tonic::transport::Error) -> Self {
+        Self::Transport(error.to_string())
+    }
+}
+
+impl From<std::io::Error> for GrpcError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Transport(error.to_string())
+    }
+}
+
+/* --- --- --- SERVED ENVIRONMENT --- --- --- */
+
+/// Reason a request could not be answered, turned into a `Status` for the client.
+enum Failure {
+    InvalidArgument(String),
+    Internal(String),
+}
+
+impl From<Failure> for Status {
+    fn from(failure: Failure) -> Self {
+        match failure {
+            Failure::InvalidArgument(message) => Status::invalid_argument(message),
+            Failure::Internal(message) => Status::internal(message),
+        }
+    }
+}
+
+fn invalid_argument<T: std::fmt::Display>(error: T) -> Failure {
+    Failure::InvalidArgument(error.to_string())
+}
+
+fn internal<T: std::fmt::Display>(error: T) -> Failure {
+    Failure::Internal(error.to_string())
+}
+
+/// The environment of a server with all type parameters erased.
+trait Served: Send {
+    fn spaces(&self) -> proto::SpacesResponse;
+    fn reset(&mut self, request: proto::ResetRequest) -> Result<proto::Position, Failure>;
+    fn state(&self) -> proto::Position;
+    fn step(&mut self, action: proto::Position) -> Result<proto::StepResult, Failure>;
+    fn load(&mut self, data: proto::EnvironmentData) -> Result<(), Failure>;
+    fn store(&self) -> Result<proto::EnvironmentData, Failure>;
+    fn close(&mut self) -> Result<(), Failure>;
+}
+
+type TypeParameters<E, R, I, D> = fn() -> (E, R, I, D);
+
+struct Serving<Env, E, R, I, D> {
+    environment: Env,
+    phantom: PhantomData<TypeParameters<E, R, I, D>>,
+}
+
+impl<Env, E, R, I, D> Served for Serving<Env, E, R, I, D>
+where
+    Env: Environment<E, R, I, D> + Send,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug + Serialize,
+    D: Serialize + DeserializeOwned,
+{
+    fn spaces(&self) -> proto::SpacesResponse {
+        proto::SpacesResponse {
+            action_space: Some(proto::Space::from(&self.environment.action_space())),
+            observation_space: Some(proto::Space::from(&self.environment.observation_space())),
+            suggested_episode_steps_count: self
+                .environment
+                .suggested_episode_steps_count()
+                .map(|count| count.min(u64::MAX as u128) as u64),
+        }
+    }
+
+    fn reset(&mut self, request: proto::ResetRequest) -> Result<proto::Position, Failure> {
+        let options = if request.options_json.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::from_str::<ResetOptions>(&request.options_json)
+                    .map_err(invalid_argument)?,
+            )
+        };
+        self.environment
+            .reset(request.seed.map(Seed::from), options)
+            .map(|state| proto::Position::from(&state))
+            .map_err(internal)
+    }
+
+    fn state(&self) -> proto::Position {
+        proto::Position::from(&self.environment.state())
+    }
+
+    fn step(&mut self, action: proto::Position) -> Result<proto::StepResult, Failure> {
+        let action = Position::try_from(action).map_err(Failure::InvalidArgument)?;
+        self.environment
+            .action_space()
+            .contains(&action)
+            .map_err(invalid_argument)?;
+        let (state, reward, done, info) = self.environment.step(&action).map_err(internal)?;
+        Ok(proto::StepResult {
+            state: Some(proto::Position::from(&state)),
+            reward: reward.value(),
+            done,
+            info_json: serde_json::to_string(&info).map_err(internal)?,
+        })
+    }
+
+    fn load(&mut self, data: proto::EnvironmentData) -> Result<(), Failure> {
+        let data = serde_json::from_str(&data.json).map_err(invalid_argument)?;
+        self.environment.load(data).map_err(internal)
+    }
+
+    fn store(&self) -> Result<proto::EnvironmentData, Failure> {
+        Ok(proto::EnvironmentData {
+            json: serde_json::to_string(&self.environment.store()).map_err(internal)?,
+        })
+    }
+
+    fn close(&mut self) -> Result<(), Failure> {
+        self.environment.close().map_err(internal)
+    }
+}
+
+type Shared = Arc<Mutex<dyn Served>>;
+
+type Handler<M, T> = fn(&mut dyn Served, M) -> Result<T, Failure>;
+
+/// A single rpc of the service, handled by locking the environment.
+struct Method<M, T> {
+    served: Shared,
+    handler: Handler<M, T>,
+}
+
+impl<M, T> Service<tonic::Request<M>> for Method<M, T> {
+    type Response = tonic::Response<T>;
+    type Error = Status;
+    type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: tonic::Request<M>) -> Self::Future {
+        std::future::ready(match self.served.lock() {
+            Ok(mut served) => (self.handler)(&mut *served, request.into_inner())
+                .map(tonic::Response::new)
+                .map_err(Status::from),
+            Err(_) => Err(Status::internal("Environment is poisoned")),
+        })
+    }
+}
+
+type ResponseFuture =
+    Pin<Box<dyn Future<Output = Result<http::Response<BoxBody>, Infallible>> + Send>>;
+
+fn unary<M, T>(
+    served: Shared,
+    request: http::Request<BoxBody>,
+    handler: Handler<M, T>,
+) -> ResponseFuture
+where
+    M: prost::Message + Default + Send + 'static,
+    T: prost::Message + Send + 'static,
+{
+    Box::pin(async move {
+        let mut grpc = tonic::server::Grpc::new(ProstCodec::<T, M>::default());
+        Ok(grpc.unary(Method { served, handler }, request).await)
+    })
+}
+
+/* --- --- --- ENVIRONMENT SERVER --- --- --- */
+
+/// Serves an environment as `gymnarium.EnvironmentService`.
+///
+/// Requests are handled one after another, as they all work on the same environment.
+/// Errors of the environment are answered with the status `INTERNAL`, invalid requests with
+/// `INVALID_ARGUMENT`.
+#[derive(Clone)]
+pub struct EnvironmentServer {
+    served: Shared,
+}
+
+impl EnvironmentServer {
+    pub fn new<Env, E, R, I, D>(environment: Env) -> Self
+    where
+        Env: Environment<E, R, I, D> + Send + 'static,
+        E: std::error::Error + 'static,
+        R: Reward + 'static,
+        I: Debug + Serialize + 'static,
+        D: Serialize + DeserializeOwned + 'static,
+    {
+        Self {
+            served: Arc::new(Mutex::new(Serving {
+                environment,
+                phantom: PhantomData,
+            })),
+        }
+    }
+
+    /// Serves on the given listener until an error occurs, blocking the current thread.
+    pub fn serve(self, listener: std::net::TcpListener) -> Result<(), GrpcError> {
+        listener.set_nonblocking(true)?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(async move {
+            let incoming = tonic::transport::server::TcpIncoming::from_listener(
+                tokio::net::TcpListener::from_std(listener)?,
+                true,
+                None,
+            )
+            .map_err(|e| GrpcError::Transport(e.to_string()))?;
+            tonic::transport::Server::builder()
+                .add_service(self)
+                .serve_with_incoming(incoming)
+                .await?;
+            Ok(())
+        })
+    }
+}
+
+impl NamedService for EnvironmentServer {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+impl Service<http::Request<BoxBody>> for EnvironmentServer {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        let served = Arc::clone(&self.served);
+        let method = request
+            .uri()
+            .path()
+            .strip_prefix('/')
+            .and_then(|path| path.strip_prefix(SERVICE_NAME))
+            .and_then(|path| path.strip_prefix('/'))
+            .map(str::to_string);
+        match method.as_deref() {
+            Some("Spaces") => unary(served, request, |served, _: proto::Empty| {
+                Ok(served.spaces())
+            }),
+            Some("Reset") => unary(served, request, |served, request| served.reset(request)),
+            Some("State") => unary(
+                served,
+                request,
+                |served, _: proto::Empty| Ok(served.state()),
+            ),
+            Some("Step") => unary(served, request, |served, action| served.step(action)),
+            Some("Load") => unary(served, request, |served, data| {
+                served.load(data).map(|_| proto::Empty {})
+            }),
+            Some("Store") => unary(served, request, |served, _: proto::Empty| served.store()),
+            Some("Close") => unary(served, request, |served, _: proto::Empty| {
+                served.close().map(|_| proto::Empty {})
+            }),
+            _ => Box::pin(async { Ok(Status::unimplemented("Unknown method").into_http()) }),
+        }
+    }
+}
+
+/* --- --- --- REMOTE ENVIRONMENT --- --- --- */
+
+/// Environment running behind an `EnvironmentServer`.
+///
+/// Calls block until the server answered. The spaces are fetched once while connecting and the
+/// state is remembered from the last answer, so neither of them needs a round trip.
+pub struct RemoteEnvironment {
+    runtime: tokio::runtime::Runtime,
+    client: tonic::client::Grpc<Channel>,
+    action_space: ActionSpace,
+    observation_space: ObservationSpace,
+    suggested_episode_steps_count: Option<u128>,
+    state: EnvironmentState,
+}
+
+impl RemoteEnvironment {
+    /// Connects to a server at an address like `http://127.0.0.1:50051`.
+    pub fn connect<A: Into<String>>(address: A) -> Result<Self, GrpcError> {
+        let endpoint = Endpoint::from_shared(address.into())
+            .map_err(|e| GrpcError::Transport(e.to_string()))?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let channel = runtime.block_on(endpoint.connect())?;
+        let mut client = tonic::client::Grpc::new(channel);
+
+        let spaces: proto::SpacesResponse =
+            Self::call_with(&runtime, &mut client, "Spaces", proto::Empty {})?;
+        let state = Self::call_with(&runtime, &mut client, "State", proto::Empty {})?;
+        Ok(Self {
+            action_space: Self::convert_space(spaces.action_space)?,
+            observation_space: Self::convert_space(spaces.observation_space)?,
+            suggested_episode_steps_count: spaces.suggested_episode_steps_count.map(u128::from),
+            state: Self::convert_position(Some(state))?,
+            runtime,
+            client,
+        })
+    }
+
+    fn call_with<M, T>(
+        runtime: &tokio::runtime::Runtime,
+        client: &mut tonic::client::Grpc<Channel>,
+        method: &str,
+        message: M,
+    ) -> Result<T, GrpcError>
+    where
+        M: prost::Message + Send + Sync + 'static,
+        T: prost::Message + Default + Send + Sync + 'static,
+    {
+        let path = PathAndQuery::try_from(format!("/{}/{}", SERVICE_NAME, method))
+            .map_err(|e| GrpcError::Transport(e.to_string()))?;
+        runtime.block_on(async {
+            client
+                .ready()
+                .await
+                .map_err(|e| GrpcError::Transport(e.to_string()))?;
+            let response = client
+                .unary(
+                    tonic::Request::new(message),
+                    path,
+                    ProstCodec::<M, T>::default(),
+                )
+                .await?;
+            Ok(response.into_inner())
+        })
+    }
+
+    fn call<M, T>(&self, method: &str, message: M) -> Result<T, GrpcError>
+    where
+        M: prost::Message + Send + Sync + 'static,
+        T: prost::Message + Default + Send + Sync + 'static,
+    {
+        Self::call_with(&self.runtime, &mut self.client.clone(), method, message)
+    }
+
+    fn convert_space(space: Option<proto::Space>) -> Result<crate::space::Space, GrpcError> {
+        space
+            .ok_or_else(|| GrpcError::Conversion("Space is missing".to_string()))
+            .and_then(|space| crate::space::Space::try_from(space).map_err(GrpcError::Conversion))
+    }
+
+    fn convert_position(position: Option<proto::Position>) -> Result<Position, GrpcError> {
+        position
+            .ok_or_else(|| GrpcError::Conversion("Position is missing".to_string()))
+            .and_then(|position| Position::try_from(position).map_err(GrpcError::Conversion))
+    }
+
+    fn update_state(&mut self) -> Result<(), GrpcError> {
+        let state = self.call("State", proto::Empty {})?;
+        self.state = Self::convert_position(Some(state))?;
+        Ok(())
+    }
+}
+
+impl Environment<GrpcError, f64, serde_json::Value, serde_json::Value> for RemoteEnvironment {
+    fn action_space(&self) -> ActionSpace {
+        self.action_space.clone()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.observation_space.clone()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.suggested_episode_steps_count
+    }
+
+    fn reset(
+        &mut self,
+        random_seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, GrpcError> {
+        let request = proto::ResetRequest {
+            seed: random_seed.map(|seed| seed.seed_value),
+            options_json: match options {
+                Some(options) => serde_json::to_string(&options)
+                    .map_err(|e| GrpcError::Conversion(e.to_string()))?,
+                None => String::new(),
+            },
+        };
+        let state = self.call("Reset", request)?;
+        self.state = Self::convert_position(Some(state))?;
+        Ok(self.state.clone())
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.state.clone()
+    }
+
+    fn step(
+        &mut self,
+        action: &AgentAction,
+    ) -> Result<(EnvironmentState, f64, bool, serde_json::Value), GrpcError> {
+        let result: proto::StepResult = self.call("Step", proto::Position::from(action))?;
+        self.state = Self::convert_position(result.state)?;
+        let info = serde_json::from_str(&result.info_json)
+            .map_err(|e| GrpcError::Conversion(e.to_string()))?;
+        Ok((self.state.clone(), result.reward, result.done, info))
+    }
+
+    fn load(&mut self, data: serde_json::Value) -> Result<(), GrpcError> {
+        let _: proto::Empty = self.call(
+            "Load",
+            proto::EnvironmentData {
+                json: data.to_string(),
+            },
+        )?;
+        self.update_state()
+    }
+
+    /// Returns `Value::Null` if the server could not be reached.
+    fn store(&self) -> serde_json::Value {
+        self.call("Store", proto::Empty {})
+            .ok()
+            .and_then(|data: proto::EnvironmentData| serde_json::from_str(&data.json).ok())
+            .unwrap_or(serde_json::Value::Null)
+    }
+
+    fn close(&mut self) -> Result<(), GrpcError> {
+        let _: proto::Empty = self.call("Close", proto::Empty {})?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::DimensionValue;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    fn serve_counting_environment() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            EnvironmentServer::new(CountingEnvironment::default()).serve(listener)
+        });
+        address
+    }
+
+    #[test]
+    fn positions_and_spaces_survive_conversion() {
+        let space = CountingEnvironment::default().action_space();
+        assert_eq!(
+            space,
+            crate::space::Space::try_from(proto::Space::from(&space)).unwrap()
+        );
+        let categorical = crate::space::Space::builder()
+            .categorical(vec!["even", "odd"])
+            .integer(0..=3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            categorical,
+            crate::space::Space::try_from(proto::Space::from(&categorical)).unwrap()
+        );
+        let position = Position::simple(vec![DimensionValue::Integer(3)]);
+        assert_eq!(
+            position,
+            Position::try_from(proto::Position::from(&position)).unwrap()
+        );
+        assert!(Position::try_from(proto::Position {
+            dimensions: vec![1],
+            values: vec![proto::DimensionValue { kind: None }],
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn remote_environment_steps_through_an_episode() {
+        let mut remote = RemoteEnvironment::connect(serve_counting_environment()).unwrap();
+        assert_eq!(
+            CountingEnvironment::default().action_space(),
+            remote.action_space()
+        );
+        assert_eq!(
+            Position::simple(vec![DimensionValue::Integer(0)]),
+            remote.reset(None, None).unwrap()
+        );
+
+        let action = Position::simple(vec![DimensionValue::Integer(1)]);
+        let (state, reward, done, _) = remote.step(&action).unwrap();
+        assert_eq!(Position::simple(vec![DimensionValue::Integer(1)]), state);
+        assert_eq!(1.0, reward);
+        assert!(!done);
+        assert_eq!(state, remote.state());
+
+        let invalid = Position::simple(vec![DimensionValue::Integer(5)]);
+        assert!(matches!(
+            remote.step(&invalid),
+            Err(GrpcError::Status {
+                code: Code::InvalidArgument,
+                ..
+            })
+        ));
+        remote.close().unwrap();
+    }
+}
@@ -0,0 +1,244 @@
+//! Protobuf messages of `proto/gymnarium.proto` and conversions from and into the own types.
+
+use std::convert::TryFrom;
+
+use crate::space;
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct IntegerRange {
+    #[prost(int32, tag = "1")]
+    pub min: i32,
+    #[prost(int32, tag = "2")]
+    pub max: i32,
+}
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct FloatRange {
+    #[prost(float, tag = "1")]
+    pub min: f32,
+    #[prost(float, tag = "2")]
+    pub max: f32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Categories {
+    #[prost(uint32, tag = "1")]
+    pub count: u32,
+    #[prost(string, repeated, tag = "2")]
+    pub labels: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum BoundariesKind {
+    #[prost(message, tag = "1")]
+    Integer(IntegerRange),
+    #[prost(message, tag = "2")]
+    Float(FloatRange),
+    #[prost(message, tag = "3")]
+    Categorical(Categories),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DimensionBoundaries {
+    #[prost(oneof = "BoundariesKind", tags = "1, 2, 3")]
+    pub kind: Option<BoundariesKind>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Space {
+    #[prost(uint64, repeated, tag = "1")]
+    pub dimensions: Vec<u64>,
+    #[prost(message, repeated, tag = "2")]
+    pub boundaries: Vec<DimensionBoundaries>,
+}
+
+#[derive(Clone, Copy, PartialEq, prost::Oneof)]
+pub enum ValueKind {
+    #[prost(int32, tag = "1")]
+    Integer(i32),
+    #[prost(float, tag = "2")]
+    Float(f32),
+}
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct DimensionValue {
+    #[prost(oneof = "ValueKind", tags = "1, 2")]
+    pub kind: Option<ValueKind>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Position {
+    #[prost(uint64, repeated, tag = "1")]
+    pub dimensions: Vec<u64>,
+    #[prost(message, repeated, tag = "2")]
+    pub values: Vec<DimensionValue>,
+}
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct Empty {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SpacesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub action_space: Option<Space>,
+    #[prost(message, optional, tag = "2")]
+    pub observation_space: Option<Space>,
+    #[prost(uint64, optional, tag = "3")]
+    pub suggested_episode_steps_count: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ResetRequest {
+    #[prost(bytes = "vec", optional, tag = "1")]
+    pub seed: Option<Vec<u8>>,
+    #[prost(string, tag = "2")]
+    pub options_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StepResult {
+    #[prost(message, optional, tag = "1")]
+    pub state: Option<Position>,
+    #[prost(double, tag = "2")]
+    pub reward: f64,
+    #[prost(bool, tag = "3")]
+    pub done: bool,
+    #[prost(string, tag = "4")]
+    pub info_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EnvironmentData {
+    #[prost(string, tag = "1")]
+    pub json: String,
+}
+
+/* --- --- --- CONVERSIONS --- --- --- */
+
+fn to_dimensions(dimensions: &[usize]) -> Vec<u64> {
+    dimensions
+        .iter()
+        .map(|dimension| *dimension as u64)
+        .collect()
+}
+
+fn from_dimensions(dimensions: &[u64]) -> Result<Vec<usize>, String> {
+    dimensions
+        .iter()
+        .map(|dimension| {
+            usize::try_from(*dimension).map_err(|_| format!("Dimension {} is too big", dimension))
+        })
+        .collect()
+}
+
+impl From<&space::Space> for Space {
+    fn from(space: &space::Space) -> Self {
+        Self {
+            dimensions: to_dimensions(space.dimensions()),
+            boundaries: space
+                .get_boundaries()
+                .iter()
+                .enumerate()
+                .map(|(index, boundaries)| DimensionBoundaries {
+                    kind: Some(match boundaries {
+                        space::DimensionBoundaries::Integer(min, max) => {
+                            BoundariesKind::Integer(IntegerRange {
+                                min: *min,
+                                max: *max,
+                            })
+                        }
+                        space::DimensionBoundaries::Float(min, max) => {
+                            BoundariesKind::Float(FloatRange {
+                                min: *min,
+                                max: *max,
+                            })
+                        }
+                        space::DimensionBoundaries::Categorical(count) => {
+                            BoundariesKind::Categorical(Categories {
+                                count: *count,
+                                labels: space
+                                    .categories()
+                                    .and_then(|categories| categories.get(index))
+                                    .cloned()
+                                    .unwrap_or_default(),
+                            })
+                        }
+                    }),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<Space> for space::Space {
+    type Error = String;
+
+    fn try_from(space: Space) -> Result<Self, Self::Error> {
+        let (boundaries, categories) = space
+            .boundaries
+            .into_iter()
+            .map(|boundaries| match boundaries.kind {
+                Some(BoundariesKind::Integer(range)) => Ok((
+                    space::DimensionBoundaries::Integer(range.min, range.max),
+                    Vec::new(),
+                )),
+                Some(BoundariesKind::Float(range)) => Ok((
+                    space::DimensionBoundaries::Float(range.min, range.max),
+                    Vec::new(),
+                )),
+                Some(BoundariesKind::Categorical(categories)) => Ok((
+                    space::DimensionBoundaries::Categorical(categories.count),
+                    categories.labels,
+                )),
+                None => Err("Boundaries without kind".to_string()),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip::<_, _, Vec<_>, Vec<Vec<String>>>();
+        let space = space::Space::new(boundaries, from_dimensions(&space.dimensions)?)
+            .map_err(|error| error.to_string())?;
+        if categories.iter().all(Vec::is_empty) {
+            Ok(space)
+        } else {
+            space
+                .with_categories(categories)
+                .map_err(|error| error.to_string())
+        }
+    }
+}
+
+impl From<&space::Position> for Position {
+    fn from(position: &space::Position) -> Self {
+        Self {
+            dimensions: to_dimensions(position.dimensions()),
+            values: position
+                .values()
+                .iter()
+                .map(|value| DimensionValue {
+                    kind: Some(match value {
+                        space::DimensionValue::Integer(value) => ValueKind::Integer(*value),
+                        space::DimensionValue::Float(value) => ValueKind::Float(*value),
+                    }),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<Position> for space::Position {
+    type Error = String;
+
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        let values = position
+            .values
+            .iter()
+            .map(|value| match value.kind {
+                Some(ValueKind::Integer(value)) => Ok(space::DimensionValue::Integer(value)),
+                Some(ValueKind::Float(value)) => Ok(space::DimensionValue::Float(value)),
+                None => Err("Value without kind".to_string()),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        space::Position::new(values, from_dimensions(&position.dimensions)?)
+            .map_err(|error| error.to_string())
+    }
+}
@@ -0,0 +1,732 @@
+//! Reads and writes numpy's `.npy` and `.npz` files, to analyse batches and recorded episodes in
+//! Python with `np.load`.
+//!
+//! Arrays are stored as little-endian `float32` or `int32`. Reading also accepts `float64` and
+//! `int64`, which are narrowed, as numpy creates those by default. Archives are written
+//! uncompressed like `np.savez` does, archives of `np.savez_compressed` can not be read.
+//!
+//! ```
+//! use gymnarium_base::interop::numpy::NpyArray;
+//! use gymnarium_base::space::{DimensionValue, Position, PositionBatch};
+//!
+//! let batch = PositionBatch::from_positions(&[
+//!     Position::simple(vec![DimensionValue::from(1), DimensionValue::from(2)]),
+//!     Position::simple(vec![DimensionValue::from(3), DimensionValue::from(4)]),
+//! ])
+//! .unwrap();
+//!
+//! let mut file = Vec::new();
+//! NpyArray::from_batch(&batch).write_npy(&mut file).unwrap();
+//! let array = NpyArray::read_npy(file.as_slice()).unwrap();
+//! assert_eq!(vec![2, 2], array.shape);
+//! assert_eq!(batch, array.to_batch().unwrap());
+//! ```
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::replay::{RecordedStep, Recording};
+use crate::space::{DimensionValue, Position, PositionBatch};
+
+/* --- --- --- NPY ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum NpyError {
+    Io(String),
+    /// The file is no valid `.npy` or `.npz` file.
+    InvalidFormat(String),
+    /// The file is valid, but uses something not supported here.
+    Unsupported(String),
+    MissingArray(String),
+    /// The shape of an array does not fit to where it is used.
+    ShapeMismatch {
+        name: String,
+        shape: Vec<usize>,
+    },
+}
+
+impl std::fmt::Display for NpyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(message) => write!(f, "File could not be accessed ({})", message),
+            Self::InvalidFormat(message) => write!(f, "File is invalid ({})", message),
+            Self::Unsupported(message) => write!(f, "File is not supported ({})", message),
+            Self::MissingArray(name) => write!(f, "Array \"{}\" is missing", name),
+            Self::ShapeMismatch { name, shape } => {
+                write!(f, "Array \"{}\" has the unfitting shape {:?}", name, shape)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NpyError {}
+
+impl From<std::io::Error> for NpyError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+fn invalid<T: Into<String>>(message: T) -> NpyError {
+    NpyError::InvalidFormat(message.into())
+}
+
+/* --- --- --- NPY ARRAY --- --- --- */
+
+/// Values of an array in row-major order.
+#[derive(Debug, PartialEq, Clone)]
+pub enum NpyData {
+    Float32(Vec<f32>),
+    Int32(Vec<i32>),
+}
+
+impl NpyData {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Float32(values) => values.len(),
+            Self::Int32(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn descr(&self) -> &'static str {
+        match self {
+            Self::Float32(_) => "<f4",
+            Self::Int32(_) => "<i4",
+        }
+    }
+
+    fn value(&self, index: usize) -> DimensionValue {
+        match self {
+            Self::Float32(values) => DimensionValue::Float(values[index]),
+            Self::Int32(values) => DimensionValue::Integer(values[index]),
+        }
+    }
+}
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// A n-dimensional array as stored inside a `.npy` file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NpyArray {
+    pub shape: Vec<usize>,
+    pub data: NpyData,
+}
+
+impl NpyArray {
+    /// Creates an array, whose data needs as many values as the shape describes.
+    pub fn new(shape: Vec<usize>, data: NpyData) -> Result<Self, NpyError> {
+        if shape.iter().product::<usize>() != data.len() {
+            return Err(invalid(format!(
+                "{} values do not fit the shape {:?}",
+                data.len(),
+                shape
+            )));
+        }
+        Ok(Self { shape, data })
+    }
+
+    /// Creates an `int32` array if all values are integers and a `float32` array otherwise,
+    /// with the shape `[len, ...position dimensions]`.
+    pub fn from_batch(batch: &PositionBatch) -> Self {
+        let integers = match batch.as_i32_slice() {
+            Some(integers) => Some(integers.to_vec()),
+            None => batch
+                .get_values()
+                .iter()
+                .map(|value| match value {
+                    DimensionValue::Integer(value) => Some(*value),
+                    DimensionValue::Float(_) => None,
+                })
+                .collect::<Option<Vec<i32>>>(),
+        };
+        Self {
+            shape: batch.dimensions(),
+            data: match integers {
+                Some(integers) => NpyData::Int32(integers),
+                None => NpyData::Float32(batch.to_f32_vec()),
+            },
+        }
+    }
+
+    /// Creates a one-dimensional `float32` array.
+    pub fn from_f32_values(values: Vec<f32>) -> Self {
+        Self {
+            shape: vec![values.len()],
+            data: NpyData::Float32(values),
+        }
+    }
+
+    /// Creates a one-dimensional `int32` array.
+    pub fn from_i32_values(values: Vec<i32>) -> Self {
+        Self {
+            shape: vec![values.len()],
+            data: NpyData::Int32(values),
+        }
+    }
+
+    /// Splits the array along its first axis into the positions of a batch.
+    pub fn to_batch(&self) -> Result<PositionBatch, NpyError> {
+        let (len, position_dimensions) = match self.shape.split_first() {
+            Some((len, position_dimensions)) => (*len, position_dimensions.to_vec()),
+            None => {
+                return Err(NpyError::ShapeMismatch {
+                    name: "batch".to_string(),
+                    shape: self.shape.clone(),
+                })
+            }
+        };
+        let position_length = position_dimensions.iter().product::<usize>();
+        let mut batch = PositionBatch::with_capacity(position_dimensions.clone(), len);
+        for index in 0..len {
+            let values = (index * position_length..(index + 1) * position_length)
+                .map(|value_index| self.data.value(value_index))
+                .collect();
+            let position = Position::new(values, position_dimensions.clone())
+                .map_err(|e| invalid(e.to_string()))?;
+            batch.push(&position).map_err(|e| invalid(e.to_string()))?;
+        }
+        Ok(batch)
+    }
+
+    /// Writes the array in the `.npy` format version 1.0.
+    pub fn write_npy<W: Write>(&self, mut writer: W) -> Result<(), NpyError> {
+        let shape = match self.shape.as_slice() {
+            [length] => format!("({},)", length),
+            shape => format!(
+                "({})",
+                shape
+                    .iter()
+                    .map(|length| length.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let mut header = format!(
+            "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+            self.data.descr(),
+            shape
+        );
+        // Magic, version and header length take 10 bytes, the header ends with a newline and
+        // the data has to start at a multiple of 64 bytes.
+        let padding = 63 - (10 + header.len()) % 64;
+        header.extend(std::iter::repeat_n(' ', padding));
+        header.push('\n');
+        let header_length =
+            u16::try_from(header.len()).map_err(|_| NpyError::Unsupported("shape".to_string()))?;
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&header_length.to_le_bytes())?;
+        writer.write_all(header.as_bytes())?;
+        match &self.data {
+            NpyData::Float32(values) => values
+                .iter()
+                .try_for_each(|value| writer.write_all(&value.to_le_bytes()))?,
+            NpyData::Int32(values) => values
+                .iter()
+                .try_for_each(|value| writer.write_all(&value.to_le_bytes()))?,
+        }
+        Ok(())
+    }
+
+    /// Reads an array of the `.npy` format versions 1.0 to 3.0.
+    pub fn read_npy<R: Read>(mut reader: R) -> Result<Self, NpyError> {
+        let mut preamble = [0u8; 8];
+        reader.read_exact(&mut preamble)?;
+        if &preamble[..6] != MAGIC {
+            return Err(invalid("Magic string is missing"));
+        }
+        let header_length = match preamble[6] {
+            1 => {
+                let mut length = [0u8; 2];
+                reader.read_exact(&mut length)?;
+                u16::from_le_bytes(length) as usize
+            }
+            2 | 3 => {
+                let mut length = [0u8; 4];
+                reader.read_exact(&mut length)?;
+                u32::from_le_bytes(length) as usize
+            }
+            version => return Err(NpyError::Unsupported(format!("Format version {}", version))),
+        };
+        let mut header = vec![0u8; header_length];
+        reader.read_exact(&mut header)?;
+        let header = String::from_utf8(header).map_err(|e| invalid(e.to_string()))?;
+
+        let descr = header_value(&header, "descr")?;
+        if header_value(&header, "fortran_order")? != "False" {
+            return Err(NpyError::Unsupported("Fortran order".to_string()));
+        }
+        let shape = parse_shape(header_value(&header, "shape")?)?;
+        let count = shape.iter().product::<usize>();
+
+        let data = match descr.trim_matches('\'') {
+            "<f4" => NpyData::Float32(read_values(&mut reader, count, f32::from_le_bytes)?),
+            "<i4" => NpyData::Int32(read_values(&mut reader, count, i32::from_le_bytes)?),
+            "<f8" => NpyData::Float32(
+                read_values(&mut reader, count, f64::from_le_bytes)?
+                    .into_iter()
+                    .map(|value| value as f32)
+                    .collect(),
+            ),
+            "<i8" => NpyData::Int32(
+                read_values(&mut reader, count, i64::from_le_bytes)?
+                    .into_iter()
+                    .map(|value| {
+                        i32::try_from(value)
+                            .map_err(|_| NpyError::Unsupported(format!("Value {}", value)))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            other => return Err(NpyError::Unsupported(format!("Type {}", other))),
+        };
+        Ok(Self { shape, data })
+    }
+
+    pub fn save_npy<P: AsRef<Path>>(&self, path: P) -> Result<(), NpyError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_npy(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn load_npy<P: AsRef<Path>>(path: P) -> Result<Self, NpyError> {
+        Self::read_npy(BufReader::new(File::open(path)?))
+    }
+}
+
+/// Returns the text of the value of `key` inside the header dictionary.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let pattern = format!("'{}':", key);
+    let start = header
+        .find(&pattern)
+        .ok_or_else(|| invalid(format!("Header has no {}", key)))?;
+    let value = header[start + pattern.len()..].trim_start();
+    let end = match value.chars().next() {
+        Some('\'') => value[1..].find('\'').map(|end| end + 2),
+        Some('(') => value.find(')').map(|end| end + 1),
+        _ => value.find([',', '}']),
+    }
+    .ok_or_else(|| invalid(format!("Header value of {} is not terminated", key)))?;
+    Ok(value[..end].trim())
+}
+
+fn parse_shape(shape: &str) -> Result<Vec<usize>, NpyError> {
+    shape
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|length| !length.is_empty())
+        .map(|length| {
+            length
+                .parse()
+                .map_err(|_| invalid(format!("Shape {} is invalid", shape)))
+        })
+        .collect()
+}
+
+fn read_values<R: Read, T, const N: usize>(
+    reader: &mut R,
+    count: usize,
+    from_le_bytes: fn([u8; N]) -> T,
+) -> Result<Vec<T>, NpyError> {
+    let mut bytes = [0u8; N];
+    (0..count)
+        .map(|_| {
+            reader.read_exact(&mut bytes)?;
+            Ok(from_le_bytes(bytes))
+        })
+        .collect()
+}
+
+/* --- --- --- NPZ ARCHIVE --- --- --- */
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+/// 1980-01-01, the earliest date a zip archive can store.
+const DOS_DATE: u16 = 0x0021;
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+/// Writes the arrays as an uncompressed `.npz` archive, naming the entries `<name>.npy`.
+pub fn write_npz<W: Write>(
+    arrays: &BTreeMap<String, NpyArray>,
+    mut writer: W,
+) -> Result<(), NpyError> {
+    let too_big = || NpyError::Unsupported("Archives over 4 GiB".to_string());
+    let mut central_directory = Vec::new();
+    let mut offset = 0u32;
+    for (name, array) in arrays {
+        let name = format!("{}.npy", name);
+        let mut content = Vec::new();
+        array.write_npy(&mut content)?;
+        let size = u32::try_from(content.len()).map_err(|_| too_big())?;
+        let crc = crc32(&content);
+
+        let mut entry = Vec::new();
+        entry.extend_from_slice(&20u16.to_le_bytes()); // version needed to extract
+        entry.extend_from_slice(&0u16.to_le_bytes()); // flags
+        entry.extend_from_slice(&0u16.to_le_bytes()); // stored without compression
+        entry.extend_from_slice(&0u16.to_le_bytes()); // time
+        entry.extend_from_slice(&DOS_DATE.to_le_bytes());
+        entry.extend_from_slice(&crc.to_le_bytes());
+        entry.extend_from_slice(&size.to_le_bytes()); // compressed size
+        entry.extend_from_slice(&size.to_le_bytes()); // uncompressed size
+        entry.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        entry.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+
+        writer.write_all(&LOCAL_HEADER_SIGNATURE.to_le_bytes())?;
+        writer.write_all(&entry)?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&content)?;
+
+        central_directory.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        central_directory.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central_directory.extend_from_slice(&entry);
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        central_directory.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        central_directory.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+
+        offset = u32::try_from(30 + name.len() + content.len())
+            .ok()
+            .and_then(|length| offset.checked_add(length))
+            .ok_or_else(too_big)?;
+    }
+    let entries = u16::try_from(arrays.len())
+        .map_err(|_| NpyError::Unsupported("More than 65535 arrays".to_string()))?;
+    writer.write_all(&central_directory)?;
+    writer.write_all(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?; // number of this disk
+    writer.write_all(&0u16.to_le_bytes())?; // disk of the central directory
+    writer.write_all(&entries.to_le_bytes())?;
+    writer.write_all(&entries.to_le_bytes())?;
+    writer.write_all(&(central_directory.len() as u32).to_le_bytes())?;
+    writer.write_all(&offset.to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?; // comment length
+    Ok(())
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, NpyError> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| invalid("Archive is truncated"))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, NpyError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| invalid("Archive is truncated"))
+}
+
+/// Reads all arrays of an uncompressed `.npz` archive, named without the `.npy` extension.
+pub fn read_npz<R: Read>(mut reader: R) -> Result<BTreeMap<String, NpyArray>, NpyError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let end = (0..bytes.len().saturating_sub(21))
+        .rev()
+        .find(|offset| u32_at(&bytes, *offset).ok() == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+        .ok_or_else(|| invalid("End of central directory is missing"))?;
+    let entries = u16_at(&bytes, end + 10)?;
+    let mut offset = u32_at(&bytes, end + 16)? as usize;
+
+    let mut arrays = BTreeMap::new();
+    for _ in 0..entries {
+        if u32_at(&bytes, offset)? != CENTRAL_HEADER_SIGNATURE {
+            return Err(invalid("Central directory entry is invalid"));
+        }
+        let compression = u16_at(&bytes, offset + 10)?;
+        let size = u32_at(&bytes, offset + 20)? as usize;
+        let name_length = u16_at(&bytes, offset + 28)? as usize;
+        let extra_length = u16_at(&bytes, offset + 30)? as usize;
+        let comment_length = u16_at(&bytes, offset + 32)? as usize;
+        let local_offset = u32_at(&bytes, offset + 42)? as usize;
+        let name = bytes
+            .get(offset + 46..offset + 46 + name_length)
+            .ok_or_else(|| invalid("Archive is truncated"))?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        offset += 46 + name_length + extra_length + comment_length;
+
+        if compression != 0 {
+            return Err(NpyError::Unsupported(format!("Compressed entry {}", name)));
+        }
+        if u32_at(&bytes, local_offset)? != LOCAL_HEADER_SIGNATURE {
+            return Err(invalid("Local file header is invalid"));
+        }
+        let data_offset = local_offset
+            + 30
+            + u16_at(&bytes, local_offset + 26)? as usize
+            + u16_at(&bytes, local_offset + 28)? as usize;
+        let content = bytes
+            .get(data_offset..data_offset + size)
+            .ok_or_else(|| invalid("Archive is truncated"))?;
+        let name = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+        arrays.insert(name, NpyArray::read_npy(content)?);
+    }
+    Ok(arrays)
+}
+
+pub fn save_npz<P: AsRef<Path>>(
+    arrays: &BTreeMap<String, NpyArray>,
+    path: P,
+) -> Result<(), NpyError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npz(arrays, &mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn load_npz<P: AsRef<Path>>(path: P) -> Result<BTreeMap<String, NpyArray>, NpyError> {
+    read_npz(BufReader::new(File::open(path)?))
+}
+
+/* --- --- --- RECORDINGS --- --- --- */
+
+/// Converts a recorded episode into the arrays `observations` with the initial state and the
+/// state after every step, `actions`, `rewards` and `dones` as `0` or `1`.
+///
+/// ```
+/// use gymnarium_base::interop::numpy::{recording_from_arrays, recording_to_arrays};
+/// use gymnarium_base::position;
+/// use gymnarium_base::replay::{RecordedStep, Recording};
+///
+/// let mut recording = Recording::new(None, position![0]);
+/// recording.steps.push(RecordedStep {
+///     action: position![1],
+///     state: position![1],
+///     reward: 0.5,
+///     done: true,
+///     rng: None,
+/// });
+///
+/// let arrays = recording_to_arrays(&recording).unwrap();
+/// assert_eq!(vec![2, 1], arrays["observations"].shape);
+/// assert_eq!(vec![1], arrays["rewards"].shape);
+/// assert_eq!(recording, recording_from_arrays(&arrays).unwrap());
+/// ```
+pub fn recording_to_arrays(recording: &Recording) -> Result<BTreeMap<String, NpyArray>, NpyError> {
+    let mismatch = |e: crate::space::SpaceMismatch| invalid(e.to_string());
+    let observations = PositionBatch::from_positions(
+        std::iter::once(&recording.initial_state)
+            .chain(recording.steps.iter().map(|step| &step.state)),
+    )
+    .map_err(mismatch)?;
+    let actions = if recording.steps.is_empty() {
+        NpyArray::from_i32_values(Vec::new())
+    } else {
+        NpyArray::from_batch(
+            &PositionBatch::from_positions(recording.steps.iter().map(|step| &step.action))
+                .map_err(mismatch)?,
+        )
+    };
+
+    let mut arrays = BTreeMap::new();
+    arrays.insert(
+        "observations".to_string(),
+        NpyArray::from_batch(&observations),
+    );
+    arrays.insert("actions".to_string(), actions);
+    arrays.insert(
+        "rewards".to_string(),
+        NpyArray::from_f32_values(
+            recording
+                .steps
+                .iter()
+                .map(|step| step.reward as f32)
+                .collect(),
+        ),
+    );
+    arrays.insert(
+        "dones".to_string(),
+        NpyArray::from_i32_values(
+            recording
+                .steps
+                .iter()
+                .map(|step| step.done as i32)
+                .collect(),
+        ),
+    );
+    Ok(arrays)
+}
+
+/// Converts the arrays of `recording_to_arrays` back into a recording without seed and states of
+/// the random number generator.
+pub fn recording_from_arrays(arrays: &BTreeMap<String, NpyArray>) -> Result<Recording, NpyError> {
+    let array = |name: &str| {
+        arrays
+            .get(name)
+            .ok_or_else(|| NpyError::MissingArray(name.to_string()))
+    };
+    let observations_array = array("observations")?;
+    let actions_array = array("actions")?;
+    let observations = observations_array.to_batch()?;
+    let actions = actions_array.to_batch()?;
+    let rewards = array("rewards")?;
+    let dones = array("dones")?;
+
+    let steps = actions.len();
+    let mismatch = |name: &str, array: &NpyArray| NpyError::ShapeMismatch {
+        name: name.to_string(),
+        shape: array.shape.clone(),
+    };
+    if observations_array.shape.first() != Some(&(steps + 1)) {
+        return Err(mismatch("observations", observations_array));
+    }
+    for (name, array) in [("rewards", rewards), ("dones", dones)] {
+        if array.shape != [steps] {
+            return Err(mismatch(name, array));
+        }
+    }
+
+    let initial_state = observations
+        .get(0)
+        .map(|state| state.to_position())
+        .ok_or_else(|| NpyError::MissingArray("observations".to_string()))?;
+    let mut recording = Recording::new(None, initial_state);
+    for index in 0..steps {
+        recording.steps.push(RecordedStep {
+            action: actions
+                .get(index)
+                .ok_or_else(|| mismatch("actions", actions_array))?
+                .to_position(),
+            state: observations
+                .get(index + 1)
+                .ok_or_else(|| mismatch("observations", observations_array))?
+                .to_position(),
+            reward: match rewards.data.value(index) {
+                DimensionValue::Float(reward) => reward as f64,
+                DimensionValue::Integer(reward) => reward as f64,
+            },
+            done: dones.data.value(index) != DimensionValue::Integer(0),
+            rng: None,
+        });
+    }
+    Ok(recording)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_are_aligned_and_parsed() {
+        let array = NpyArray::new(
+            vec![2, 1, 3],
+            NpyData::Float32(vec![0.5, 1.0, 1.5, 2.0, 2.5, 3.0]),
+        )
+        .unwrap();
+        let mut file = Vec::new();
+        array.write_npy(&mut file).unwrap();
+        assert_eq!(0, (file.len() - 6 * 4) % 64);
+        assert_eq!(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (2, 1, 3), }",
+            String::from_utf8_lossy(&file[10..])
+                .split('\n')
+                .next()
+                .unwrap()
+                .trim_end()
+        );
+        assert_eq!(array, NpyArray::read_npy(file.as_slice()).unwrap());
+    }
+
+    fn npy_file(descr: &str, values: &[u8]) -> Vec<u8> {
+        let header = format!(
+            "{{'descr': '{}', 'fortran_order': False, 'shape': (2,), }}\n",
+            descr
+        );
+        let mut file = MAGIC.to_vec();
+        file.extend_from_slice(&[1, 0, header.len() as u8, 0]);
+        file.extend_from_slice(header.as_bytes());
+        file.extend_from_slice(values);
+        file
+    }
+
+    #[test]
+    fn float64_and_int64_files_are_narrowed() {
+        let integers = [7i64.to_le_bytes(), (-2i64).to_le_bytes()].concat();
+        assert_eq!(
+            NpyArray::from_i32_values(vec![7, -2]),
+            NpyArray::read_npy(npy_file("<i8", &integers).as_slice()).unwrap()
+        );
+        let floats = [0.5f64.to_le_bytes(), 4f64.to_le_bytes()].concat();
+        assert_eq!(
+            NpyArray::from_f32_values(vec![0.5, 4.0]),
+            NpyArray::read_npy(npy_file("<f8", &floats).as_slice()).unwrap()
+        );
+        assert!(matches!(
+            NpyArray::read_npy(npy_file("<u2", &[0; 4]).as_slice()),
+            Err(NpyError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn archives_contain_named_arrays() {
+        let mut arrays = BTreeMap::new();
+        arrays.insert("a".to_string(), NpyArray::from_i32_values(vec![1, 2, 3]));
+        arrays.insert("b".to_string(), NpyArray::from_f32_values(vec![0.25]));
+        let mut archive = Vec::new();
+        write_npz(&arrays, &mut archive).unwrap();
+        assert_eq!(&LOCAL_HEADER_SIGNATURE.to_le_bytes(), &archive[..4]);
+        assert_eq!(arrays, read_npz(archive.as_slice()).unwrap());
+        assert!(matches!(
+            read_npz(&archive[..archive.len() - 30]),
+            Err(NpyError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn recordings_need_one_reward_and_done_per_step() {
+        let mut recording = Recording::new(None, crate::position![0]);
+        recording.steps.push(RecordedStep {
+            action: crate::position![1],
+            state: crate::position![1],
+            reward: 1.0,
+            done: true,
+            rng: None,
+        });
+        let mut arrays = recording_to_arrays(&recording).unwrap();
+        assert_eq!(1, recording_from_arrays(&arrays).unwrap().steps.len());
+
+        for shape in [vec![1, 0], vec![1, 2]] {
+            let length = shape.iter().product();
+            let rewards = NpyArray::new(shape.clone(), NpyData::Float32(vec![1.0; length]));
+            arrays.insert("rewards".to_string(), rewards.unwrap());
+            assert_eq!(
+                Some(NpyError::ShapeMismatch {
+                    name: "rewards".to_string(),
+                    shape,
+                }),
+                recording_from_arrays(&arrays).err()
+            );
+        }
+    }
+
+    #[test]
+    fn crc32_matches_the_reference() {
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+    }
+}
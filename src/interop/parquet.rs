@@ -0,0 +1,549 @@
+//! Writes trajectories into columnar Parquet files for offline reinforcement learning.
+//!
+//! Every row is one step with the columns `episode`, `step`, one column per dimension of the
+//! observation and of the action, `reward` and `done`. Observations are the states the actions
+//! were chosen in. Integer dimensions become `Int32` columns, float dimensions `Float32` columns,
+//! each annotated with its boundaries as `min` and `max` field metadata. The spaces and formats
+//! are stored as JSON inside the schema metadata.
+//!
+//! ```
+//! use gymnarium_base::interop::parquet::{TrajectorySchema, TrajectoryWriter};
+//! use gymnarium_base::position;
+//! use gymnarium_base::space::{DimensionBoundaries, Format, Space};
+//!
+//! let observation_space = Space::simple(vec![
+//!     DimensionBoundaries::from(-1f32..=1f32),
+//!     DimensionBoundaries::from(-1f32..=1f32),
+//!     DimensionBoundaries::from(0..=9),
+//! ]);
+//! let mut format = Format::default();
+//! format.add("position".to_string(), vec![2]).unwrap();
+//! format.add("fuel".to_string(), vec![1]).unwrap();
+//!
+//! let schema = TrajectorySchema::new(
+//!     observation_space,
+//!     Space::simple(vec![DimensionBoundaries::from(0..=3)]),
+//! )
+//! .with_observation_format(&format)
+//! .unwrap();
+//! assert_eq!(
+//!     vec!["observation.position_0", "observation.position_1", "observation.fuel"],
+//!     schema.observation_columns()
+//! );
+//!
+//! let mut writer = TrajectoryWriter::new(Vec::new(), schema).unwrap();
+//! writer
+//!     .write_step(0, 0, &position![0.5f32, -0.5f32, 9], &position![2], 1.0, false)
+//!     .unwrap();
+//! let file = writer.finish().unwrap();
+//! assert_eq!(b"PAR1", &file[..4]);
+//! ```
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Float32Array, Float64Array, Int32Array, RecordBatch, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+
+use crate::replay::Recording;
+use crate::space::{DimensionBoundaries, DimensionValue, Format, Position, Space, SpaceMismatch};
+
+/* --- --- --- TRAJECTORY ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TrajectoryError {
+    Io(String),
+    Parquet(String),
+    Serialization(String),
+    /// A format describes more dimensions than its space has.
+    FormatDoesNotFit {
+        needed: usize,
+        given: usize,
+    },
+    /// A position does not lie inside the space of its columns.
+    PositionMismatch {
+        column: String,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for TrajectoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(message) => write!(f, "File could not be accessed ({})", message),
+            Self::Parquet(message) => write!(f, "Parquet Error \"{}\" occurred", message),
+            Self::Serialization(message) => {
+                write!(f, "Metadata could not be serialized ({})", message)
+            }
+            Self::FormatDoesNotFit { needed, given } => write!(
+                f,
+                "Format needs {} dimensions, but the space has {}",
+                needed, given
+            ),
+            Self::PositionMismatch { column, message } => write!(
+                f,
+                "Position does not fit the {} columns ({})",
+                column, message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TrajectoryError {}
+
+impl From<std::io::Error> for TrajectoryError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+impl From<parquet::errors::ParquetError> for TrajectoryError {
+    fn from(error: parquet::errors::ParquetError) -> Self {
+        Self::Parquet(error.to_string())
+    }
+}
+
+impl From<arrow_schema::ArrowError> for TrajectoryError {
+    fn from(error: arrow_schema::ArrowError) -> Self {
+        Self::Parquet(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for TrajectoryError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error.to_string())
+    }
+}
+
+/* --- --- --- TRAJECTORY SCHEMA --- --- --- */
+
+/// Column names and types of a trajectory file, derived from the spaces.
+///
+/// Without a format, the columns of the dimensions are named `observation_<index>` and
+/// `action_<index>` in row-major order. A format names them `observation.<key>`, or
+/// `observation.<key>_<index>` for keys with more than one dimension.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TrajectorySchema {
+    observation_space: Space,
+    action_space: Space,
+    observation_columns: Vec<String>,
+    action_columns: Vec<String>,
+    metadata: HashMap<String, String>,
+}
+
+fn indexed_columns(prefix: &str, space: &Space) -> Vec<String> {
+    (0..space.get_boundaries().len())
+        .map(|index| format!("{}_{}", prefix, index))
+        .collect()
+}
+
+fn formatted_columns(
+    prefix: &str,
+    space: &Space,
+    format: &Format,
+) -> Result<Vec<String>, TrajectoryError> {
+    let mut columns = indexed_columns(prefix, space);
+    for (key, shape, offset) in format.iter() {
+        let length = shape.iter().product::<usize>();
+        if offset + length > columns.len() {
+            return Err(TrajectoryError::FormatDoesNotFit {
+                needed: offset + length,
+                given: columns.len(),
+            });
+        }
+        for index in 0..length {
+            columns[offset + index] = if length == 1 {
+                format!("{}.{}", prefix, key)
+            } else {
+                format!("{}.{}_{}", prefix, key, index)
+            };
+        }
+    }
+    Ok(columns)
+}
+
+fn dimension_fields(columns: &[String], space: &Space) -> Vec<Field> {
+    columns
+        .iter()
+        .zip(space.get_boundaries())
+        .map(|(column, boundaries)| {
+            let (data_type, min, max) = match boundaries {
+                DimensionBoundaries::Integer(min, max) => {
+                    (DataType::Int32, min.to_string(), max.to_string())
+                }
+                DimensionBoundaries::Float(min, max) => {
+                    (DataType::Float32, min.to_string(), max.to_string())
+                }
+                DimensionBoundaries::Categorical(count) => (
+                    DataType::Int32,
+                    0.to_string(),
+                    (i64::from(*count) - 1).to_string(),
+                ),
+            };
+            Field::new(column.clone(), data_type, false).with_metadata(
+                vec![("min".to_string(), min), ("max".to_string(), max)]
+                    .into_iter()
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+impl TrajectorySchema {
+    pub fn new(observation_space: Space, action_space: Space) -> Self {
+        let mut metadata = HashMap::new();
+        if let Ok(json) = serde_json::to_string(&observation_space) {
+            metadata.insert("gymnarium.observation_space".to_string(), json);
+        }
+        if let Ok(json) = serde_json::to_string(&action_space) {
+            metadata.insert("gymnarium.action_space".to_string(), json);
+        }
+        Self {
+            observation_columns: indexed_columns("observation", &observation_space),
+            action_columns: indexed_columns("action", &action_space),
+            observation_space,
+            action_space,
+            metadata,
+        }
+    }
+
+    /// Names the observation columns after the keys of the format.
+    pub fn with_observation_format(mut self, format: &Format) -> Result<Self, TrajectoryError> {
+        self.observation_columns =
+            formatted_columns("observation", &self.observation_space, format)?;
+        self.metadata.insert(
+            "gymnarium.observation_format".to_string(),
+            serde_json::to_string(format)?,
+        );
+        Ok(self)
+    }
+
+    /// Names the action columns after the keys of the format.
+    pub fn with_action_format(mut self, format: &Format) -> Result<Self, TrajectoryError> {
+        self.action_columns = formatted_columns("action", &self.action_space, format)?;
+        self.metadata.insert(
+            "gymnarium.action_format".to_string(),
+            serde_json::to_string(format)?,
+        );
+        Ok(self)
+    }
+
+    pub fn observation_columns(&self) -> &[String] {
+        &self.observation_columns
+    }
+
+    pub fn action_columns(&self) -> &[String] {
+        &self.action_columns
+    }
+
+    pub fn to_arrow_schema(&self) -> Schema {
+        let mut fields = vec![
+            Field::new("episode", DataType::UInt64, false),
+            Field::new("step", DataType::UInt64, false),
+        ];
+        fields.extend(dimension_fields(
+            &self.observation_columns,
+            &self.observation_space,
+        ));
+        fields.extend(dimension_fields(&self.action_columns, &self.action_space));
+        fields.push(Field::new("reward", DataType::Float64, false));
+        fields.push(Field::new("done", DataType::Boolean, false));
+        Schema::new_with_metadata(fields, self.metadata.clone())
+    }
+}
+
+/* --- --- --- TRAJECTORY WRITER --- --- --- */
+
+enum ColumnValues {
+    Int32(Vec<i32>),
+    Float32(Vec<f32>),
+}
+
+impl ColumnValues {
+    fn for_space(space: &Space) -> Vec<Self> {
+        space
+            .get_boundaries()
+            .iter()
+            .map(|boundaries| match boundaries {
+                DimensionBoundaries::Integer(_, _) | DimensionBoundaries::Categorical(_) => {
+                    Self::Int32(Vec::new())
+                }
+                DimensionBoundaries::Float(_, _) => Self::Float32(Vec::new()),
+            })
+            .collect()
+    }
+
+    fn push(&mut self, value: &DimensionValue) {
+        match (self, value) {
+            (Self::Int32(values), DimensionValue::Integer(value)) => values.push(*value),
+            (Self::Float32(values), DimensionValue::Float(value)) => values.push(*value),
+            (Self::Int32(values), DimensionValue::Float(value)) => values.push(*value as i32),
+            (Self::Float32(values), DimensionValue::Integer(value)) => values.push(*value as f32),
+        }
+    }
+
+    fn take(&mut self) -> ArrayRef {
+        match self {
+            Self::Int32(values) => Arc::new(Int32Array::from(std::mem::take(values))),
+            Self::Float32(values) => Arc::new(Float32Array::from(std::mem::take(values))),
+        }
+    }
+}
+
+/// Collects steps into record batches of `rows_per_batch` rows and writes them as Parquet.
+pub struct TrajectoryWriter<W: Write + Send> {
+    schema: TrajectorySchema,
+    arrow_schema: SchemaRef,
+    writer: ArrowWriter<W>,
+    rows_per_batch: usize,
+    episodes: Vec<u64>,
+    steps: Vec<u64>,
+    observations: Vec<ColumnValues>,
+    actions: Vec<ColumnValues>,
+    rewards: Vec<f64>,
+    dones: Vec<bool>,
+}
+
+impl<W: Write + Send> TrajectoryWriter<W> {
+    pub fn new(writer: W, schema: TrajectorySchema) -> Result<Self, TrajectoryError> {
+        let arrow_schema = Arc::new(schema.to_arrow_schema());
+        Ok(Self {
+            writer: ArrowWriter::try_new(writer, Arc::clone(&arrow_schema), None)?,
+            arrow_schema,
+            rows_per_batch: 8192,
+            episodes: Vec::new(),
+            steps: Vec::new(),
+            observations: ColumnValues::for_space(&schema.observation_space),
+            actions: ColumnValues::for_space(&schema.action_space),
+            rewards: Vec::new(),
+            dones: Vec::new(),
+            schema,
+        })
+    }
+
+    pub fn with_rows_per_batch(mut self, rows_per_batch: usize) -> Self {
+        self.rows_per_batch = rows_per_batch.max(1);
+        self
+    }
+
+    pub fn schema(&self) -> &TrajectorySchema {
+        &self.schema
+    }
+
+    /// Appends one step, in which `action` was chosen while observing `observation`.
+    pub fn write_step(
+        &mut self,
+        episode: u64,
+        step: u64,
+        observation: &Position,
+        action: &Position,
+        reward: f64,
+        done: bool,
+    ) -> Result<(), TrajectoryError> {
+        fn mismatch(column: &'static str) -> impl Fn(SpaceMismatch) -> TrajectoryError {
+            move |error| TrajectoryError::PositionMismatch {
+                column: column.to_string(),
+                message: error.to_string(),
+            }
+        }
+        self.schema
+            .observation_space
+            .contains(observation)
+            .map_err(mismatch("observation"))?;
+        self.schema
+            .action_space
+            .contains(action)
+            .map_err(mismatch("action"))?;
+
+        self.episodes.push(episode);
+        self.steps.push(step);
+        for (column, value) in self
+            .observations
+            .iter_mut()
+            .zip(observation.values().iter())
+        {
+            column.push(value);
+        }
+        for (column, value) in self.actions.iter_mut().zip(action.values().iter()) {
+            column.push(value);
+        }
+        self.rewards.push(reward);
+        self.dones.push(done);
+
+        if self.rewards.len() >= self.rows_per_batch {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    /// Appends every step of a recorded episode.
+    pub fn write_recording(
+        &mut self,
+        episode: u64,
+        recording: &Recording,
+    ) -> Result<(), TrajectoryError> {
+        let mut observation = &recording.initial_state;
+        for (step, recorded) in recording.steps.iter().enumerate() {
+            self.write_step(
+                episode,
+                step as u64,
+                observation,
+                &recorded.action,
+                recorded.reward,
+                recorded.done,
+            )?;
+            observation = &recorded.state;
+        }
+        Ok(())
+    }
+
+    fn write_batch(&mut self) -> Result<(), TrajectoryError> {
+        if self.rewards.is_empty() {
+            return Ok(());
+        }
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(UInt64Array::from(std::mem::take(&mut self.episodes))),
+            Arc::new(UInt64Array::from(std::mem::take(&mut self.steps))),
+        ];
+        columns.extend(self.observations.iter_mut().map(ColumnValues::take));
+        columns.extend(self.actions.iter_mut().map(ColumnValues::take));
+        columns.push(Arc::new(Float64Array::from(std::mem::take(
+            &mut self.rewards,
+        ))));
+        columns.push(Arc::new(BooleanArray::from(std::mem::take(
+            &mut self.dones,
+        ))));
+        let batch = RecordBatch::try_new(Arc::clone(&self.arrow_schema), columns)?;
+        self.writer.write(&batch)?;
+        Ok(())
+    }
+
+    /// Writes the remaining steps and the file footer and returns the inner writer.
+    pub fn finish(mut self) -> Result<W, TrajectoryError> {
+        self.write_batch()?;
+        Ok(self.writer.into_inner()?)
+    }
+}
+
+impl TrajectoryWriter<BufWriter<File>> {
+    /// Creates the file at `path` to write into.
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        schema: TrajectorySchema,
+    ) -> Result<Self, TrajectoryError> {
+        Self::new(BufWriter::new(File::create(path)?), schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position;
+    use crate::replay::RecordedStep;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn schema() -> TrajectorySchema {
+        TrajectorySchema::new(
+            Space::simple(vec![
+                DimensionBoundaries::from(0..=5),
+                DimensionBoundaries::from(-1f32..=1f32),
+            ]),
+            Space::simple(vec![DimensionBoundaries::from(-1..=1)]),
+        )
+    }
+
+    #[test]
+    fn schema_follows_the_spaces() {
+        let arrow_schema = schema().to_arrow_schema();
+        let names = arrow_schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "episode",
+                "step",
+                "observation_0",
+                "observation_1",
+                "action_0",
+                "reward",
+                "done"
+            ],
+            names
+        );
+        assert_eq!(&DataType::Int32, arrow_schema.field(2).data_type());
+        assert_eq!(&DataType::Float32, arrow_schema.field(3).data_type());
+        assert_eq!(
+            Some(&"5".to_string()),
+            arrow_schema.field(2).metadata().get("max")
+        );
+        assert!(arrow_schema
+            .metadata()
+            .contains_key("gymnarium.observation_space"));
+
+        let mut format = Format::default();
+        format.add("too_big".to_string(), vec![3]).unwrap();
+        assert_eq!(
+            Err(TrajectoryError::FormatDoesNotFit {
+                needed: 3,
+                given: 2
+            }),
+            schema().with_observation_format(&format)
+        );
+    }
+
+    #[test]
+    fn recordings_are_written_in_batches() {
+        let mut recording = Recording::new(None, position![0, 0.5f32]);
+        for step in 1..=5 {
+            recording.steps.push(RecordedStep {
+                action: position![1],
+                state: position![step, 0.5f32],
+                reward: step as f64,
+                done: step == 5,
+                rng: None,
+            });
+        }
+        let path = std::env::temp_dir().join("gymnarium_trajectory.parquet");
+        let mut writer = TrajectoryWriter::create(&path, schema())
+            .unwrap()
+            .with_rows_per_batch(2);
+        writer.write_recording(7, &recording).unwrap();
+        assert!(matches!(
+            writer.write_step(8, 0, &position![9, 0.5f32], &position![1], 0.0, false),
+            Err(TrajectoryError::PositionMismatch { .. })
+        ));
+        writer.finish().unwrap().flush().unwrap();
+
+        let batches = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(5, batches.iter().map(RecordBatch::num_rows).sum::<usize>());
+        let last = batches.last().unwrap();
+        let observations = last
+            .column_by_name("observation_0")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(4, observations.value(observations.len() - 1));
+        let dones = last
+            .column_by_name("done")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert!(dones.value(dones.len() - 1));
+    }
+}
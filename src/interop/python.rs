@@ -0,0 +1,505 @@
+//! A line based JSON protocol to drive an environment from Python's gym.
+//!
+//! Every request is a single JSON object on its own line with a `command` of `spaces`, `reset`,
+//! `step` or `close`, and is answered by a single line with a `status` of `ok` or `error`.
+//! Positions are exchanged as flat lists of numbers in row-major order, which the shim reshapes
+//! with the shape of the space:
+//!
+//! ```text
+//! > {"command": "spaces"}
+//! < {"status":"ok","action_space":{"shape":[1],"low":[0.0],"high":[1.0],"dtype":"int32"},...}
+//! > {"command": "reset", "seed": 42}
+//! < {"status":"ok","observation":[0.0]}
+//! > {"command": "step", "action": [1]}
+//! < {"status":"ok","observation":[1.0],"reward":1.0,"done":false,"info":{}}
+//! > {"command": "close"}
+//! < {"status":"ok"}
+//! ```
+//!
+//! With the feature `shm`, a `share` request names a region of `interop::shm` and a slot inside
+//! it. Afterwards observations are written into that slot and answered as empty lists, which
+//! `vector::SubprocVecEnvironment::with_shared_observations` relies on.
+//!
+//! A thin shim on the Python side only has to start the process and translate:
+//!
+//! ```python
+//! class GymnariumEnv(gym.Env):
+//!     def __init__(self, command):
+//!         self.process = subprocess.Popen(command, stdin=PIPE, stdout=PIPE, text=True)
+//!         spaces = self.call(command="spaces")
+//!         self.action_space = to_box(spaces["action_space"])
+//!         self.observation_space = to_box(spaces["observation_space"])
+//!
+//!     def call(self, **request):
+//!         self.process.stdin.write(json.dumps(request) + "\n")
+//!         self.process.stdin.flush()
+//!         response = json.loads(self.process.stdout.readline())
+//!         if response["status"] != "ok":
+//!             raise RuntimeError(response["message"])
+//!         return response
+//! ```
+
+use std::fmt::Debug;
+use std::io::{BufRead, Write};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::space::{DimensionBoundaries, Position, Space, SpaceError};
+use crate::{Environment, ResetOptions, Reward, Seed};
+
+/* --- --- --- SPACE SCHEMA --- --- --- */
+
+/// Element type of a space as a numpy dtype name, or `mixed` for integers and floats together.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dtype {
+    Int32,
+    Float32,
+    Mixed,
+}
+
+/// Description of a space in the terms of gym's `Box`, with bounds in row-major order.
+///
+/// ```
+/// use gymnarium_base::interop::python::{Dtype, SpaceSchema};
+/// use gymnarium_base::space::{DimensionBoundaries, Space};
+///
+/// let space = Space::simple(vec![DimensionBoundaries::from(0..=3), DimensionBoundaries::from(2..=5)]);
+/// let schema = SpaceSchema::of(&space);
+/// assert_eq!(vec![2], schema.shape);
+/// assert_eq!(vec![0.0, 2.0], schema.low);
+/// assert_eq!(vec![3.0, 5.0], schema.high);
+/// assert_eq!(Dtype::Int32, schema.dtype);
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SpaceSchema {
+    pub shape: Vec<usize>,
+    pub low: Vec<f64>,
+    pub high: Vec<f64>,
+    pub dtype: Dtype,
+}
+
+impl SpaceSchema {
+    pub fn of(space: &Space) -> Self {
+        let boundaries = space.get_boundaries();
+        let integers = boundaries
+            .iter()
+            .filter(|boundaries| boundaries.integer_range().is_some())
+            .count();
+        let (low, high) = boundaries
+            .iter()
+            .map(|boundaries| match boundaries {
+                DimensionBoundaries::Integer(min, max) => (*min as f64, *max as f64),
+                DimensionBoundaries::Float(min, max) => (*min as f64, *max as f64),
+                DimensionBoundaries::Categorical(count) => (0f64, *count as f64 - 1f64),
+            })
+            .unzip();
+        Self {
+            shape: space.dimensions().clone(),
+            low,
+            high,
+            dtype: if integers == boundaries.len() {
+                Dtype::Int32
+            } else if integers == 0 {
+                Dtype::Float32
+            } else {
+                Dtype::Mixed
+            },
+        }
+    }
+
+    /// Turns the description back into a space, with float boundaries for `mixed` spaces.
+    ///
+    /// ```
+    /// use gymnarium_base::interop::python::SpaceSchema;
+    /// use gymnarium_base::space::{DimensionBoundaries, Space};
+    ///
+    /// let space = Space::simple(vec![DimensionBoundaries::from(0..=3)]);
+    /// assert_eq!(Ok(space.clone()), SpaceSchema::of(&space).to_space());
+    /// ```
+    pub fn to_space(&self) -> Result<Space, SpaceError> {
+        let boundaries = self
+            .low
+            .iter()
+            .zip(&self.high)
+            .map(|(low, high)| match self.dtype {
+                Dtype::Int32 => DimensionBoundaries::Integer(*low as i32, *high as i32),
+                Dtype::Float32 | Dtype::Mixed => {
+                    DimensionBoundaries::Float(*low as f32, *high as f32)
+                }
+            })
+            .collect();
+        Space::new(boundaries, self.shape.clone())
+    }
+}
+
+/* --- --- --- MESSAGES --- --- --- */
+
+/// Seed of a reset request, either a number or a text.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SeedValue {
+    Number(u64),
+    Text(String),
+}
+
+impl From<SeedValue> for Seed {
+    fn from(value: SeedValue) -> Self {
+        match value {
+            SeedValue::Number(number) => Seed::from(number),
+            SeedValue::Text(text) => Seed::from(text),
+        }
+    }
+}
+
+/// A request sent by the Python side.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Request {
+    Spaces,
+    Reset {
+        #[serde(default)]
+        seed: Option<SeedValue>,
+        #[serde(default)]
+        options: Option<Box<ResetOptions>>,
+    },
+    Step {
+        action: Vec<f64>,
+    },
+    /// Writes observations into `slot` of the shared region at `path` instead of answering them.
+    Share {
+        path: String,
+        slot: usize,
+        slots: usize,
+        shape: Vec<usize>,
+        dtype: Dtype,
+    },
+    Close,
+}
+
+/// The answer to a request.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Response {
+    Ok(ResponseBody),
+    Error { message: String },
+}
+
+/// Content of a successful response, depending on the request.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ResponseBody {
+    Spaces {
+        action_space: SpaceSchema,
+        observation_space: SpaceSchema,
+        suggested_episode_steps_count: Option<u128>,
+    },
+    Step {
+        observation: Vec<f64>,
+        reward: f64,
+        done: bool,
+        info: serde_json::Value,
+    },
+    Reset {
+        observation: Vec<f64>,
+    },
+    Shared {
+        slot: usize,
+    },
+    Closed {},
+}
+
+fn error<T: std::fmt::Display>(error: T) -> Response {
+    Response::Error {
+        message: error.to_string(),
+    }
+}
+
+/* --- --- --- SERVER --- --- --- */
+
+/// Answers a single request.
+///
+/// Errors of the environment and invalid actions are answered with an error response. `share`
+/// requests are only answered by `serve`, which keeps the shared region between requests.
+pub fn handle<Env, E, R, I, D>(environment: &mut Env, request: Request) -> Response
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug + Serialize,
+    D: Serialize + DeserializeOwned,
+{
+    if let Request::Share { .. } = request {
+        return error("Observations can only be shared while serving");
+    }
+    respond(environment, request, &mut ObservationSlot::default())
+}
+
+fn respond<Env, E, R, I, D>(
+    environment: &mut Env,
+    request: Request,
+    slot: &mut ObservationSlot,
+) -> Response
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug + Serialize,
+    D: Serialize + DeserializeOwned,
+{
+    match request {
+        Request::Spaces => Response::Ok(ResponseBody::Spaces {
+            action_space: SpaceSchema::of(&environment.action_space()),
+            observation_space: SpaceSchema::of(&environment.observation_space()),
+            suggested_episode_steps_count: environment.suggested_episode_steps_count(),
+        }),
+        Request::Reset { seed, options } => {
+            match environment.reset(seed.map(Seed::from), options.map(|options| *options)) {
+                Ok(state) => match slot.observe(&state) {
+                    Ok(observation) => Response::Ok(ResponseBody::Reset { observation }),
+                    Err(e) => error(e),
+                },
+                Err(e) => error(e),
+            }
+        }
+        Request::Step { action } => {
+            let action = match Position::from_f64_values_in(&action, &environment.action_space()) {
+                Ok(action) => action,
+                Err(e) => return error(e),
+            };
+            match environment.step(&action) {
+                Ok((state, reward, done, info)) => match slot.observe(&state) {
+                    Ok(observation) => Response::Ok(ResponseBody::Step {
+                        observation,
+                        reward: reward.value(),
+                        done,
+                        info: serde_json::to_value(info).unwrap_or(serde_json::Value::Null),
+                    }),
+                    Err(e) => error(e),
+                },
+                Err(e) => error(e),
+            }
+        }
+        Request::Share {
+            path,
+            slot: index,
+            slots,
+            shape,
+            dtype,
+        } => {
+            let schema = SpaceSchema::of(&environment.observation_space());
+            if schema.shape != shape || schema.dtype != dtype {
+                return error("Shared region does not fit the observation space");
+            }
+            match slot.share(&path, index, slots, shape, dtype) {
+                Ok(()) => Response::Ok(ResponseBody::Shared { slot: index }),
+                Err(e) => error(e),
+            }
+        }
+        Request::Close => match environment.close() {
+            Ok(()) => Response::Ok(ResponseBody::Closed {}),
+            Err(e) => error(e),
+        },
+    }
+}
+
+/// Where the observations of a served environment go, into the answers or a shared region.
+#[derive(Default)]
+struct ObservationSlot {
+    #[cfg(feature = "shm")]
+    shared: Option<(crate::interop::shm::SharedObservations, usize)>,
+}
+
+impl ObservationSlot {
+    #[cfg(feature = "shm")]
+    fn share(
+        &mut self,
+        path: &str,
+        slot: usize,
+        slots: usize,
+        shape: Vec<usize>,
+        dtype: Dtype,
+    ) -> Result<(), String> {
+        if slot >= slots {
+            return Err(format!("Slot {} is outside of the {} slots", slot, slots));
+        }
+        let region = crate::interop::shm::SharedObservations::open(path, dtype, shape, slots)
+            .map_err(|e| e.to_string())?;
+        self.shared = Some((region, slot));
+        Ok(())
+    }
+
+    #[cfg(not(feature = "shm"))]
+    fn share(
+        &mut self,
+        _path: &str,
+        _slot: usize,
+        _slots: usize,
+        _shape: Vec<usize>,
+        _dtype: Dtype,
+    ) -> Result<(), String> {
+        Err("Sharing observations needs the feature `shm`".to_string())
+    }
+
+    /// Returns the values to answer, which are empty if the state went into the shared region.
+    fn observe(&mut self, state: &Position) -> Result<Vec<f64>, String> {
+        #[cfg(feature = "shm")]
+        if let Some((region, slot)) = &mut self.shared {
+            region.write(*slot, state).map_err(|e| e.to_string())?;
+            return Ok(Vec::new());
+        }
+        Ok(state.to_f64_values())
+    }
+}
+
+/// Answers requests read line by line until a `close` request or the end of the input.
+///
+/// Lines which are no valid requests are answered with an error response.
+pub fn serve<Env, E, R, I, D, In, Out>(
+    environment: &mut Env,
+    input: In,
+    mut output: Out,
+) -> std::io::Result<()>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug + Serialize,
+    D: Serialize + DeserializeOwned,
+    In: BufRead,
+    Out: Write,
+{
+    let mut slot = ObservationSlot::default();
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (response, closing) = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                let closing = request == Request::Close;
+                (respond(environment, request, &mut slot), closing)
+            }
+            Err(e) => (error(e), false),
+        };
+        serde_json::to_writer(&mut output, &response)?;
+        writeln!(output)?;
+        output.flush()?;
+        if closing {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Serves the environment over the standard input and output of this process.
+///
+/// Nothing else may be printed to the standard output while serving.
+pub fn serve_stdio<Env, E, R, I, D>(environment: &mut Env) -> std::io::Result<()>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug + Serialize,
+    D: Serialize + DeserializeOwned,
+{
+    let stdin = std::io::stdin();
+    serve(environment, stdin.lock(), std::io::stdout().lock())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    fn exchange(requests: &str) -> Vec<serde_json::Value> {
+        let mut output = Vec::new();
+        serve(
+            &mut CountingEnvironment::default(),
+            requests.as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn empty_categorical_dimensions_have_an_empty_range() {
+        let schema = SpaceSchema::of(&Space::simple(vec![DimensionBoundaries::Categorical(0)]));
+        assert_eq!(vec![0.0], schema.low);
+        assert_eq!(vec![-1.0], schema.high);
+    }
+
+    #[test]
+    fn serves_an_episode_until_close() {
+        let responses = exchange(concat!(
+            "{\"command\": \"spaces\"}\n",
+            "{\"command\": \"reset\", \"seed\": 3}\n",
+            "{\"command\": \"step\", \"action\": [1]}\n",
+            "{\"command\": \"close\"}\n",
+            "{\"command\": \"spaces\"}\n",
+        ));
+        assert_eq!(4, responses.len());
+        assert_eq!(
+            serde_json::json!([-1.0]),
+            responses[0]["action_space"]["low"]
+        );
+        assert_eq!(
+            serde_json::json!("int32"),
+            responses[0]["observation_space"]["dtype"]
+        );
+        assert_eq!(serde_json::json!([0.0]), responses[1]["observation"]);
+        assert_eq!(
+            serde_json::json!({"status": "ok", "observation": [1.0], "reward": 1.0, "done": false, "info": {}}),
+            responses[2]
+        );
+        assert_eq!(serde_json::json!({"status": "ok"}), responses[3]);
+    }
+
+    #[cfg(feature = "shm")]
+    #[test]
+    fn shared_observations_are_written_instead_of_answered() {
+        let space = CountingEnvironment::default().observation_space();
+        let region = crate::interop::shm::SharedObservations::create(&space, 2).unwrap();
+        let share = serde_json::to_string(&Request::Share {
+            path: region.path().to_string_lossy().into_owned(),
+            slot: 1,
+            slots: 2,
+            shape: vec![1],
+            dtype: Dtype::Int32,
+        })
+        .unwrap();
+        let responses = exchange(&format!(
+            "{}\n{}\n",
+            share, "{\"command\": \"step\", \"action\": [1]}"
+        ));
+        assert_eq!(serde_json::json!({"status": "ok", "slot": 1}), responses[0]);
+        assert_eq!(serde_json::json!([]), responses[1]["observation"]);
+        assert_eq!(Some(crate::position![1]), region.read(1));
+
+        let mut environment = CountingEnvironment::default();
+        let request = serde_json::from_str(&share).unwrap();
+        assert!(matches!(
+            handle(&mut environment, request),
+            Response::Error { .. }
+        ));
+    }
+
+    #[test]
+    fn invalid_requests_are_answered_with_errors() {
+        let responses = exchange(concat!(
+            "not json\n",
+            "{\"command\": \"step\", \"action\": [5]}\n",
+            "{\"command\": \"step\", \"action\": [1, 1]}\n",
+        ));
+        assert_eq!(3, responses.len());
+        assert!(responses
+            .iter()
+            .all(|response| response["status"] == "error" && response["message"].is_string()));
+    }
+}
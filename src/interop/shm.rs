@@ -0,0 +1,418 @@
+//! Observations exchanged through memory shared between processes instead of being serialized.
+//!
+//! A `SharedObservations` region holds one slot per worker, each as large as a single
+//! observation of the observation space. Workers write their observations directly into their
+//! slot and the owner of the region reads all of them as one `PositionBatchView` without any
+//! copy. The region is a file mapped into every process, inside `/dev/shm` where it exists, so
+//! it lives in memory only.
+//!
+//! Regions need spaces of only floats or only integers, whose observations are stored as plain
+//! `f32` or `i32` values in the byte order of the machine.
+//!
+//! Processes never lock a region. Borrowing it is only sound while no other process writes into
+//! it, so workers have to write only while they handle a request and before they answer it, like
+//! `interop::python::serve` does. `vector::SubprocVecEnvironment` relies on this to borrow the
+//! observations between its requests. Otherwise slots are copied with `SharedObservations::read`,
+//! which may return a partially written observation but never borrows the shared memory.
+
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use memmap2::{MmapOptions, MmapRaw};
+
+use crate::interop::python::{Dtype, SpaceSchema};
+use crate::space::{DimensionValue, Position, PositionBatchView, Space};
+
+/// Counts the regions created by this process to give them unique names.
+static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+/* --- --- --- SHARED MEMORY ERROR --- --- --- */
+
+#[derive(Debug)]
+pub enum SharedMemoryError {
+    /// The file of the region could not be created, opened or mapped.
+    Io(std::io::Error),
+    /// Spaces mixing floats and integers or without any dimension cannot be shared.
+    UnsupportedSpace(Dtype),
+    /// The file opened as region does not have the size of the given slots in bytes.
+    RegionMismatch {
+        needed: u64,
+        given: u64,
+    },
+    SlotOutOfRange {
+        slot: usize,
+        slots: usize,
+    },
+    /// The position does not fit into a slot of the region.
+    PositionMismatch {
+        slot: usize,
+    },
+}
+
+impl std::fmt::Display for SharedMemoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Input/Output Error \"{}\" occurred", error),
+            Self::UnsupportedSpace(dtype) => {
+                write!(f, "Spaces of dtype {:?} cannot be shared", dtype)
+            }
+            Self::RegionMismatch { needed, given } => write!(
+                f,
+                "Region needs {} bytes but the file has {} bytes",
+                needed, given
+            ),
+            Self::SlotOutOfRange { slot, slots } => {
+                write!(f, "Slot {} is outside of the {} slots", slot, slots)
+            }
+            Self::PositionMismatch { slot } => {
+                write!(f, "Position does not fit into slot {}", slot)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SharedMemoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SharedMemoryError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/* --- --- --- SHARED OBSERVATIONS --- --- --- */
+
+/// Memory shared between processes holding one observation per slot.
+///
+/// The process creating the region owns its file and removes it when dropped. Other processes
+/// open the region with the path, dtype, shape and number of slots of the owner.
+///
+/// ```
+/// use gymnarium_base::interop::shm::SharedObservations;
+/// use gymnarium_base::space::{DimensionBoundaries, Position, Space};
+///
+/// let space = Space::all(DimensionBoundaries::from(0f32..=1f32), vec![2, 2]);
+/// let owner = SharedObservations::create(&space, 2).unwrap();
+///
+/// let mut worker = SharedObservations::open(
+///     owner.path(),
+///     owner.dtype(),
+///     owner.position_dimensions().to_vec(),
+///     owner.slots(),
+/// )
+/// .unwrap();
+/// let frame = Position::from_f32_values(vec![0.0, 0.25, 0.5, 1.0], vec![2, 2]).unwrap();
+/// worker.write(1, &frame).unwrap();
+///
+/// assert_eq!(Some(frame), owner.read(1));
+/// // The worker answers a request only after writing, so nothing writes while this view lives.
+/// let view = unsafe { owner.view() };
+/// assert_eq!(vec![2, 2, 2], view.dimensions());
+/// ```
+pub struct SharedObservations {
+    path: PathBuf,
+    map: MmapRaw,
+    dtype: Dtype,
+    position_dimensions: Vec<usize>,
+    position_length: usize,
+    slots: usize,
+    owner: bool,
+}
+
+impl SharedObservations {
+    /// Creates a region of `slots` observations of the space, negotiating the dtype and the
+    /// size of a slot from the space.
+    pub fn create(space: &Space, slots: usize) -> Result<Self, SharedMemoryError> {
+        let dtype = SpaceSchema::of(space).dtype;
+        let position_length = space.get_boundaries().len();
+        if dtype == Dtype::Mixed || position_length == 0 || slots == 0 {
+            return Err(SharedMemoryError::UnsupportedSpace(dtype));
+        }
+        let directory = Path::new("/dev/shm");
+        let directory = if directory.is_dir() {
+            directory.to_path_buf()
+        } else {
+            std::env::temp_dir()
+        };
+        let path = directory.join(format!(
+            "gymnarium-{}-{}",
+            std::process::id(),
+            CREATED.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        let created = file
+            .set_len(region_size(slots, position_length))
+            .and_then(|_| MmapOptions::new().map_raw(&file));
+        match created {
+            Ok(map) => Ok(Self {
+                path,
+                map,
+                dtype,
+                position_dimensions: space.dimensions().clone(),
+                position_length,
+                slots,
+                owner: true,
+            }),
+            Err(error) => {
+                let _ = std::fs::remove_file(&path);
+                Err(error.into())
+            }
+        }
+    }
+
+    /// Opens a region created by another process.
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        dtype: Dtype,
+        position_dimensions: Vec<usize>,
+        slots: usize,
+    ) -> Result<Self, SharedMemoryError> {
+        let position_length = position_dimensions.iter().product::<usize>();
+        if dtype == Dtype::Mixed || position_length == 0 || slots == 0 {
+            return Err(SharedMemoryError::UnsupportedSpace(dtype));
+        }
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+        let needed = region_size(slots, position_length);
+        let given = file.metadata()?.len();
+        if given != needed {
+            return Err(SharedMemoryError::RegionMismatch { needed, given });
+        }
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            map: MmapOptions::new().map_raw(&file)?,
+            dtype,
+            position_dimensions,
+            position_length,
+            slots,
+            owner: false,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn dtype(&self) -> Dtype {
+        self.dtype
+    }
+
+    /// Returns the dimensions of the observation of a single slot.
+    pub fn position_dimensions(&self) -> &[usize] {
+        &self.position_dimensions
+    }
+
+    pub fn slots(&self) -> usize {
+        self.slots
+    }
+
+    /// Borrows the observations of all slots.
+    ///
+    /// # Safety
+    /// No process may write into the region while the view lives, see the module documentation.
+    pub unsafe fn view(&self) -> PositionBatchView<'_> {
+        let length = self.slots * self.position_length;
+        match self.dtype {
+            Dtype::Int32 => PositionBatchView::whole_i32_positions(
+                std::slice::from_raw_parts(self.slot_ptr(0), length),
+                &self.position_dimensions,
+            ),
+            _ => PositionBatchView::whole_f32_positions(
+                std::slice::from_raw_parts(self.slot_ptr(0), length),
+                &self.position_dimensions,
+            ),
+        }
+    }
+
+    /// Copies the observation of the slot without borrowing the shared memory.
+    pub fn read(&self, slot: usize) -> Option<Position> {
+        if slot >= self.slots {
+            return None;
+        }
+        let dimensions = self.position_dimensions.clone();
+        match self.dtype {
+            Dtype::Int32 => Position::from_i32_values(self.read_slot(slot), dimensions).ok(),
+            _ => Position::from_f32_values(self.read_slot(slot), dimensions).ok(),
+        }
+    }
+
+    /// Writes the position into the slot, where it becomes visible to every process sharing
+    /// this region.
+    ///
+    /// Nothing is written if the position does not fit the slot.
+    pub fn write(&mut self, slot: usize, position: &Position) -> Result<(), SharedMemoryError> {
+        if slot >= self.slots {
+            return Err(SharedMemoryError::SlotOutOfRange {
+                slot,
+                slots: self.slots,
+            });
+        }
+        if position.dimensions() != &self.position_dimensions {
+            return Err(SharedMemoryError::PositionMismatch { slot });
+        }
+        match self.dtype {
+            Dtype::Int32 => match position.as_i32_slice() {
+                Some(values) => self.write_slot(slot, values),
+                None => {
+                    let values = convert_values(position, slot, |value| match value {
+                        DimensionValue::Integer(value) => Some(value),
+                        DimensionValue::Float(_) => None,
+                    })?;
+                    self.write_slot(slot, &values)
+                }
+            },
+            _ => match position.as_f32_slice() {
+                Some(values) => self.write_slot(slot, values),
+                None => {
+                    let values = convert_values(position, slot, |value| match value {
+                        DimensionValue::Float(value) => Some(value),
+                        DimensionValue::Integer(_) => None,
+                    })?;
+                    self.write_slot(slot, &values)
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Returns the start of the slot, which has to be at most `slots`.
+    ///
+    /// Mappings start at a page boundary, so that slots are aligned for `f32` and `i32`.
+    fn slot_ptr<T>(&self, slot: usize) -> *mut T {
+        debug_assert!(slot <= self.slots);
+        // SAFETY: The offset lies inside of the mapping or directly after it.
+        unsafe { self.map.as_mut_ptr().add(slot * self.position_length * 4) }.cast()
+    }
+
+    fn read_slot<T: Copy>(&self, slot: usize) -> Vec<T> {
+        let start = self.slot_ptr::<T>(slot);
+        (0..self.position_length)
+            // SAFETY: The slot lies inside of the mapping. Volatile reads never assume that other
+            // processes leave the values unchanged.
+            .map(|index| unsafe { start.add(index).read_volatile() })
+            .collect()
+    }
+
+    fn write_slot<T: Copy>(&mut self, slot: usize, values: &[T]) {
+        let start = self.slot_ptr::<T>(slot);
+        for (index, value) in values.iter().take(self.position_length).enumerate() {
+            // SAFETY: The slot lies inside of the mapping.
+            unsafe { start.add(index).write_volatile(*value) };
+        }
+    }
+}
+
+impl Drop for SharedObservations {
+    fn drop(&mut self) {
+        if self.owner {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+impl std::fmt::Debug for SharedObservations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedObservations")
+            .field("path", &self.path)
+            .field("dtype", &self.dtype)
+            .field("position_dimensions", &self.position_dimensions)
+            .field("slots", &self.slots)
+            .finish()
+    }
+}
+
+fn region_size(slots: usize, position_length: usize) -> u64 {
+    (slots * position_length * 4) as u64
+}
+
+/// Converts all values before any of them is written, so that a mismatch leaves the slot as is.
+fn convert_values<T>(
+    position: &Position,
+    slot: usize,
+    convert: impl Fn(DimensionValue) -> Option<T>,
+) -> Result<Vec<T>, SharedMemoryError> {
+    position
+        .values()
+        .iter()
+        .map(|value| convert(*value))
+        .collect::<Option<_>>()
+        .ok_or(SharedMemoryError::PositionMismatch { slot })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::DimensionBoundaries;
+
+    #[test]
+    fn writes_of_other_mappings_are_visible_without_copies() {
+        let space = Space::simple_all(DimensionBoundaries::from(0..=9), 3);
+        let owner = SharedObservations::create(&space, 2).unwrap();
+        assert_eq!(Dtype::Int32, owner.dtype());
+        let mut worker = SharedObservations::open(owner.path(), Dtype::Int32, vec![3], 2).unwrap();
+        worker.write(0, &crate::position![1, 2, 3]).unwrap();
+        worker
+            .write(
+                1,
+                &Position::from_i32_values(vec![4, 5, 6], vec![3]).unwrap(),
+            )
+            .unwrap();
+
+        let view = unsafe { owner.view() };
+        assert_eq!(Some(&[1, 2, 3, 4, 5, 6][..]), view.as_i32_slice());
+        assert_eq!(Some(crate::position![4, 5, 6]), owner.read(1));
+        assert_eq!(None, owner.read(2));
+        assert!(matches!(
+            worker.write(2, &crate::position![1, 2, 3]),
+            Err(SharedMemoryError::SlotOutOfRange { slot: 2, slots: 2 })
+        ));
+        assert!(matches!(
+            SharedObservations::open(owner.path(), Dtype::Int32, vec![4], 2),
+            Err(SharedMemoryError::RegionMismatch {
+                needed: 32,
+                given: 24
+            })
+        ));
+
+        let path = owner.path().to_path_buf();
+        drop(worker);
+        drop(owner);
+        assert!(!path.exists());
+        let error = SharedObservations::open(&path, Dtype::Int32, vec![3], 2).unwrap_err();
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn rejected_positions_leave_the_slot_as_is() {
+        let space = Space::simple_all(DimensionBoundaries::from(0..=9), 3);
+        let mut owner = SharedObservations::create(&space, 1).unwrap();
+        owner.write(0, &crate::position![1, 2, 3]).unwrap();
+        assert!(matches!(
+            owner.write(0, &crate::position![4, 5f32, 6]),
+            Err(SharedMemoryError::PositionMismatch { slot: 0 })
+        ));
+        assert_eq!(Some(crate::position![1, 2, 3]), owner.read(0));
+    }
+
+    #[test]
+    fn mixed_spaces_cannot_be_shared() {
+        let space = Space::simple(vec![
+            DimensionBoundaries::from(1),
+            DimensionBoundaries::from(1f32),
+        ]);
+        assert!(matches!(
+            SharedObservations::create(&space, 1),
+            Err(SharedMemoryError::UnsupportedSpace(Dtype::Mixed))
+        ));
+    }
+}
@@ -2,21 +2,142 @@
 //!
 //! `gymnarium_base` is a collection of structs, traits and enums to support creating
 //! reinforcement environments like the python package `gym`.
+//!
+//! The default `std` feature enables everything. Without it, the crate is `no_std` and only needs
+//! `alloc`, leaving `space`, `math` and `Seed` to encode states and actions on targets like
+//! embedded or wasm simulations.
+//!
+//! The `wasm` feature lets `thread_rng` draw its entropy from the browser on
+//! `wasm32-unknown-unknown` and exchanges positions with JavaScript through
+//! `Position::to_js_array` and `Position::from_js_array`.
+//!
+//! The `grpc` feature serves environments over gRPC with `interop::grpc`, following the
+//! definitions in `proto/gymnarium.proto`.
+//!
+//! The `arrow` feature writes trajectories into Parquet files with `interop::parquet`.
+//!
+//! The `image` feature converts pixel observations into `image::RgbImage` with
+//! `Position::to_rgb_image` and back with `Position::from_rgb_image`, and writes rendered
+//! episodes as animated GIFs or PNG sequences with `render::save_gif` and
+//! `render::save_png_sequence`.
+//!
+//! The `arbitrary` feature implements `arbitrary::Arbitrary` for `Seed`, `space::Space`,
+//! `space::Position`, `space::DimensionBoundaries` and `math::Transformation2D` to fuzz
+//! environments and wrappers. Generated spaces always have valid boundaries.
+//!
+//! The `toml` feature lets `config::FromConfig::from_config_str` and
+//! `registry::Registry::make_from_config` build environments from TOML files besides JSON.
+//!
+//! The `shm` feature lets the workers of `vector::SubprocVecEnvironment` write observations into
+//! memory shared with the main process through `interop::shm`, instead of serializing them.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Lets the code generated by the derive macros refer to `::gymnarium_base` inside this crate.
+extern crate self as gymnarium_base;
+
+extern crate alloc;
 
+#[cfg(feature = "nalgebra")]
+pub extern crate nalgebra;
 pub extern crate rand;
 pub extern crate serde;
-
+pub extern crate serde_json;
+
+#[cfg(feature = "std")]
+pub mod agents;
+#[cfg(feature = "std")]
+pub mod bench;
+#[cfg(feature = "std")]
+pub mod budget;
+#[cfg(feature = "std")]
+pub mod callbacks;
+#[cfg(feature = "std")]
+pub mod checkpoint;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
+pub mod curriculum;
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "std")]
+pub mod evaluation;
+#[cfg(all(not(feature = "std"), not(test)))]
+mod float;
+#[cfg(feature = "std")]
+pub mod hierarchical;
+#[cfg(feature = "std")]
+pub mod interop;
+mod macros;
+#[cfg(feature = "std")]
+pub mod mappers;
 pub mod math;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "std")]
+pub mod noise;
+#[cfg(feature = "std")]
+pub mod offline;
+#[cfg(feature = "std")]
+pub mod progress;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub mod reward;
+#[cfg(feature = "std")]
+pub mod rng;
+#[cfg(feature = "std")]
+pub mod runner;
+mod seed_encoding;
+#[cfg(feature = "std")]
+pub mod self_play;
+#[cfg(feature = "std")]
+pub mod shaping;
+#[cfg(feature = "std")]
+pub mod snapshot;
 pub mod space;
-
-use std::fmt::Debug;
+#[cfg(feature = "std")]
+pub mod spec;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod timing;
+#[cfg(feature = "std")]
+pub mod typed_environment;
+#[cfg(feature = "std")]
+pub mod vector;
+#[cfg(feature = "std")]
+pub mod wrappers;
+
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::Debug;
 
 use rand::Rng;
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "std")]
+pub use error::GymnariumError;
+pub use seed_encoding::SeedParseError;
+#[cfg(feature = "std")]
 use serde::de::DeserializeOwned;
+#[cfg(feature = "std")]
+use snapshot::{Snapshot, SnapshotError};
 use space::{Position, Space};
+#[cfg(feature = "std")]
+use spec::EnvironmentSpec;
+
+/// Lets the exported macros allocate without `std` in the calling crate.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec;
+}
 
 /// Space for the observable environment state.
 pub type ObservationSpace = Space;
@@ -30,14 +151,24 @@ pub type ActionSpace = Space;
 /// Position in Space as the agent action.
 pub type AgentAction = Position;
 
+/// Standard map of additional information returned by environments with each step.
+///
+/// Wrappers adding information, like `RecordEpisodeStatistics`, insert their entries here.
+pub type Info = BTreeMap<String, serde_json::Value>;
+
+/// Environment specific parameters by name, e.g. set by reset options or a curriculum.
+pub type Parameters = BTreeMap<String, serde_json::Value>;
+
 /// Provides conversion from various values into acceptable seed values.
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Seed {
     pub seed_value: Vec<u8>,
 }
 
 impl Seed {
     /// Creates a new seed generated with 32 randomly selected u8 values from the thread_rng().
+    #[cfg(feature = "std")]
     pub fn new_random() -> Self {
         let mut seed_value = Vec::with_capacity(32);
         for _ in 0..32 {
@@ -45,6 +176,60 @@ impl Seed {
         }
         Self { seed_value }
     }
+
+    /// Mixes all bytes of the seed value into `N` bytes with SplitMix64.
+    ///
+    /// Every input byte influences every output byte, so reordered or short seed values still
+    /// lead to distinct and fully populated arrays. Prefer this over the `Into` conversions.
+    ///
+    /// ```
+    /// # use gymnarium_base::Seed;
+    /// let abc: [u8; 32] = Seed::from("abc").mixed();
+    /// let cba: [u8; 32] = Seed::from("cba").mixed();
+    /// assert_ne!(abc, cba);
+    /// assert_eq!(abc, Seed::from("abc").mixed::<32>());
+    ///
+    /// let short = Seed::from(vec![1]).mixed::<32>();
+    /// assert!(short[16..].iter().any(|byte| *byte != 0));
+    /// ```
+    pub fn mixed<const N: usize>(&self) -> [u8; N] {
+        let mut state = self.seed_value.len() as u64;
+        for chunk in self.seed_value.chunks(8) {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            state ^= u64::from_le_bytes(word);
+            state = split_mix_64(&mut state);
+        }
+        let mut output = [0u8; N];
+        for chunk in output.chunks_mut(8) {
+            chunk.copy_from_slice(&split_mix_64(&mut state).to_le_bytes()[..chunk.len()]);
+        }
+        output
+    }
+
+    /// Folds the seed value into `N` bytes by adding up every `N`th byte with wrapping.
+    ///
+    /// This is what the `Into` conversions do. Permutations of the same bytes collide and short
+    /// seed values leave the remaining bytes zero.
+    #[deprecated(note = "collides on similar seed values, use `Seed::mixed` instead")]
+    pub fn folded<const N: usize>(&self) -> [u8; N] {
+        let mut output = [0u8; N];
+        if N > 0 {
+            for (index, value) in self.seed_value.iter().enumerate() {
+                output[index % N] = output[index % N].wrapping_add(*value);
+            }
+        }
+        output
+    }
+}
+
+/// Advances the state and returns the next SplitMix64 output.
+fn split_mix_64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
 impl From<String> for Seed {
@@ -135,6 +320,8 @@ impl From<u64> for Seed {
 // Allowing into because I am not sure implementing it the other way around is the correct way
 #[allow(clippy::from_over_into)]
 impl Into<[u8; 32]> for Seed {
+    /// Folds the seed value like the deprecated `Seed::folded`, prefer `Seed::mixed`.
+    ///
     /// # Examples
     /// ```
     /// # use gymnarium_base::Seed;
@@ -147,19 +334,16 @@ impl Into<[u8; 32]> for Seed {
     /// assert_eq!(expected_array, seed_array);
     /// ```
     fn into(self) -> [u8; 32] {
-        self.seed_value
-            .into_iter()
-            .fold(([0u8; 32], 0usize), |(mut output, index), v| {
-                output[index] = output[index].overflowing_add(v).0;
-                (output, (index + 1) % 32)
-            })
-            .0
+        #[allow(deprecated)]
+        self.folded()
     }
 }
 
 // Allowing into because I am not sure implementing it the other way around is the correct way
 #[allow(clippy::from_over_into)]
 impl Into<[u8; 16]> for Seed {
+    /// Folds the seed value like the deprecated `Seed::folded`, prefer `Seed::mixed`.
+    ///
     /// # Examples
     /// ```
     /// # use gymnarium_base::Seed;
@@ -171,19 +355,16 @@ impl Into<[u8; 16]> for Seed {
     /// assert_eq!(expected_array, seed_array);
     /// ```
     fn into(self) -> [u8; 16] {
-        self.seed_value
-            .into_iter()
-            .fold(([0u8; 16], 0usize), |(mut output, index), v| {
-                output[index] = output[index].overflowing_add(v).0;
-                (output, (index + 1) % 16)
-            })
-            .0
+        #[allow(deprecated)]
+        self.folded()
     }
 }
 
 // Allowing into because I am not sure implementing it the other way around is the correct way
 #[allow(clippy::from_over_into)]
 impl Into<[u8; 8]> for Seed {
+    /// Folds the seed value like the deprecated `Seed::folded`, prefer `Seed::mixed`.
+    ///
     /// # Examples
     /// ```
     /// # use gymnarium_base::Seed;
@@ -194,13 +375,8 @@ impl Into<[u8; 8]> for Seed {
     /// assert_eq!(expected_array, seed_array);
     /// ```
     fn into(self) -> [u8; 8] {
-        self.seed_value
-            .into_iter()
-            .fold(([0u8; 8], 0usize), |(mut output, index), v| {
-                output[index] = output[index].overflowing_add(v).0;
-                (output, (index + 1) % 8)
-            })
-            .0
+        #[allow(deprecated)]
+        self.folded()
     }
 }
 
@@ -226,11 +402,91 @@ impl Into<u64> for Seed {
     }
 }
 
+/// Distribution the initial state of an environment is chosen from on reset.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum InitialStateDistribution {
+    /// Always starts in the given state, e.g. for evaluation protocols.
+    Fixed(EnvironmentState),
+    /// Starts in a state sampled uniformly from the given sub space of the observation space.
+    Uniform(ObservationSpace),
+}
+
+impl InitialStateDistribution {
+    /// Returns the fixed state or samples one from the space.
+    pub fn initial_state<G: Rng + ?Sized>(&self, rng: &mut G) -> EnvironmentState {
+        match self {
+            Self::Fixed(state) => state.clone(),
+            Self::Uniform(space) => space.sample_with(rng),
+        }
+    }
+}
+
+/// Options for `Environment::reset`.
+///
+/// ```
+/// # use gymnarium_base::{EnvironmentState, InitialStateDistribution, ResetOptions};
+/// # use gymnarium_base::space::DimensionValue;
+/// let start = EnvironmentState::simple(vec![DimensionValue::from(3)]);
+/// let options = ResetOptions::new()
+///     .with_initial_state(start.clone())
+///     .with_parameter("difficulty", 2);
+///
+/// assert_eq!(
+///     Some(&InitialStateDistribution::Fixed(start)),
+///     options.initial_state.as_ref()
+/// );
+/// assert_eq!(Some(&2.into()), options.parameter("difficulty"));
+/// ```
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct ResetOptions {
+    pub initial_state: Option<InitialStateDistribution>,
+    /// Environment specific parameters passed through unchanged.
+    pub parameters: Parameters,
+}
+
+impl ResetOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_initial_state(mut self, state: EnvironmentState) -> Self {
+        self.initial_state = Some(InitialStateDistribution::Fixed(state));
+        self
+    }
+
+    pub fn with_initial_space(mut self, space: ObservationSpace) -> Self {
+        self.initial_state = Some(InitialStateDistribution::Uniform(space));
+        self
+    }
+
+    pub fn with_parameter<K: Into<String>, V: Into<serde_json::Value>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Self {
+        self.parameters.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn parameter(&self, key: &str) -> Option<&serde_json::Value> {
+        self.parameters.get(key)
+    }
+}
+
 /// Base trait for any structure mapping something to [`AgentAction`]s.
+#[cfg(feature = "std")]
 pub trait ToActionMapper<I: Clone, E: std::error::Error> {
     fn map(&mut self, input: &I) -> Result<AgentAction, E>;
 }
 
+/// Base trait for any structure mapping [`AgentAction`]s back to something.
+///
+/// This is the inverse of [`ToActionMapper`], e.g. to decode recorded actions.
+#[cfg(feature = "std")]
+pub trait FromActionMapper<O, E: std::error::Error> {
+    fn map_from(&mut self, action: &AgentAction) -> Result<O, E>;
+}
+
 /// Base trait for rewards returned by environments with the step method.
 pub trait Reward: Debug {
     fn value(&self) -> f64;
@@ -242,7 +498,30 @@ impl Reward for f64 {
     }
 }
 
+impl Reward for f32 {
+    fn value(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl Reward for i32 {
+    fn value(&self) -> f64 {
+        *self as f64
+    }
+}
+
 /// Base trait for an environment.
+///
+/// # Migrating from associated space functions
+///
+/// `action_space`, `observation_space` and `suggested_episode_steps_count` take `&self`, so that
+/// wrappers like `TransformObservation` can return spaces chosen at runtime. Implementations
+/// written against the former associated functions only need the receiver added, e.g.
+/// `fn action_space() -> ActionSpace` becomes `fn action_space(&self) -> ActionSpace`. Callers
+/// which asked the type, like `Env::action_space()`, ask an instance instead, e.g.
+/// `environment.action_space()`. Environments whose spaces do not depend on the instance can
+/// implement `TypedEnvironment`, which keeps them associated.
+#[cfg(feature = "std")]
 pub trait Environment<E, R, I, D>
 where
     E: std::error::Error,
@@ -251,24 +530,61 @@ where
     D: Serialize + DeserializeOwned,
 {
     /// Returns the available boundaries for the actions for this environment.
-    fn action_space() -> ActionSpace;
+    fn action_space(&self) -> ActionSpace;
 
     /// Returns the boundaries for the observable states for this environment.
-    fn observation_space() -> ObservationSpace;
+    fn observation_space(&self) -> ObservationSpace;
 
     /// Returns the suggested episode step count if the environment provides one.
-    fn suggested_episode_steps_count() -> Option<u128>;
+    fn suggested_episode_steps_count(&self) -> Option<u128>;
+
+    /// Returns the identifier written into snapshots of this environment.
+    ///
+    /// Defaults to the type name, wrappers should return the identifier of the wrapped environment.
+    fn environment_id() -> String {
+        std::any::type_name::<Self>().to_string()
+    }
+
+    /// Returns the metadata describing this environment.
+    ///
+    /// Defaults to the `environment_id` and the suggested episode steps count, wrappers return
+    /// the spec of the wrapped environment. `registry::Registry::register` registers the
+    /// environment under this spec.
+    fn spec(&self) -> EnvironmentSpec {
+        EnvironmentSpec {
+            max_episode_steps: self.suggested_episode_steps_count(),
+            ..EnvironmentSpec::new(Self::environment_id())
+        }
+    }
 
     /// Resets a possible internal random number generator with the given seed or by entropy.
-    fn reseed(&mut self, random_seed: Option<Seed>) -> Result<(), E>;
+    ///
+    /// Unlike before the seed moved to `reset`, the default implementation is not free of side
+    /// effects: it resets the environment with the given seed, discarding the current episode. A
+    /// following `reset(None, None)` therefore starts from a generator which has already produced
+    /// one episode, so migrate `reseed(seed)` plus `reset()` to a single `reset(seed, None)` to
+    /// keep the former episodes.
+    #[deprecated(
+        note = "resets the environment now; pass the seed to `reset` instead of calling `reseed` \
+                before it"
+    )]
+    fn reseed(&mut self, random_seed: Option<Seed>) -> Result<(), E> {
+        self.reset(random_seed, None).map(|_| ())
+    }
 
     /// Resets the state and initial resources of the environment and returns the initial state.
     ///
     /// Should be called even before the first step is done.
     /// Otherwise there might be no or an invalid state.
     ///
-    /// If predictable behaviour is wished, it's recommended to call `seed` in front of `reset`.
-    fn reset(&mut self) -> Result<EnvironmentState, E>;
+    /// A given seed resets a possible internal random number generator, without a seed the
+    /// generator keeps its state. Environments not supporting initial state distributions or
+    /// custom parameters may ignore the options.
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E>;
 
     /// Returns the current state of the environment.
     fn state(&self) -> EnvironmentState;
@@ -283,13 +599,71 @@ where
     /// the given state.
     fn store(&self) -> D;
 
+    /// Returns the stored data wrapped in a versioned `Snapshot`.
+    fn snapshot(&self) -> Snapshot<D> {
+        Snapshot::new(Self::environment_id(), self.store())
+    }
+
+    /// Loads the data of the snapshot after checking it was created by a compatible crate version
+    /// and by this environment.
+    fn load_snapshot(&mut self, snapshot: Snapshot<D>) -> Result<(), SnapshotError<E>> {
+        snapshot.check_compatibility(&Self::environment_id())?;
+        self.load(snapshot.into_data())
+            .map_err(SnapshotError::Environment)
+    }
+
     /// Cleans up resources of this environment.
     ///
     /// Should be called at the very end of usage.
     fn close(&mut self) -> Result<(), E>;
 }
 
+/// Base trait for a turn based environment shared by several agents.
+///
+/// Agents are identified by their index and act one after another as reported by
+/// `current_agent`.
+#[cfg(feature = "std")]
+pub trait MultiAgentEnvironment<E, R, I, D>
+where
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    /// Returns the number of agents taking part.
+    fn agent_count(&self) -> usize;
+
+    /// Returns the available boundaries for the actions of every agent.
+    fn action_space(&self) -> ActionSpace;
+
+    /// Returns the boundaries for the observable states of every agent.
+    fn observation_space(&self) -> ObservationSpace;
+
+    /// Resets the environment like `Environment::reset`.
+    fn reset(&mut self, seed: Option<Seed>, options: Option<ResetOptions>) -> Result<(), E>;
+
+    /// Returns the index of the agent whose action is expected next.
+    fn current_agent(&self) -> usize;
+
+    /// Returns the current state of the environment as observed by the given agent.
+    fn observation(&self, agent: usize) -> EnvironmentState;
+
+    /// Performs the action of the current agent and returns the rewards of every agent.
+    fn step(&mut self, action: &AgentAction) -> Result<(Vec<R>, bool, I), E>;
+
+    /// Overrides the environments state with the provided data structure containing a previous state.
+    fn load(&mut self, data: D) -> Result<(), E>;
+
+    /// Returns a serializable structure containing everything to reconstruct the environment at
+    /// the given state.
+    fn store(&self) -> D;
+
+    /// Cleans up resources of this environment.
+    fn close(&mut self) -> Result<(), E>;
+}
+
 /// Base trait for an agent.
+#[cfg(feature = "std")]
 pub trait Agent<E, R, D>
 where
     E: std::error::Error,
@@ -0,0 +1,114 @@
+//! Contains macros to write spaces and positions tersely.
+
+/// Creates a `Space` from a list of values convertible into `DimensionBoundaries`.
+///
+/// An optional shape can be given after a semicolon. If only a single boundary is given together
+/// with a shape, it is used for every element.
+///
+/// Panics if the boundaries are invalid or do not fit the shape.
+///
+/// ```
+/// use gymnarium_base::space;
+/// use gymnarium_base::space::{DimensionBoundaries, Space};
+///
+/// assert_eq!(
+///     Space::simple(vec![
+///         DimensionBoundaries::Integer(0, 3),
+///         DimensionBoundaries::Integer(0, 1),
+///         DimensionBoundaries::Float(-1f32, 1f32),
+///     ]),
+///     space![0..=3, 1, -1.0..=1.0]
+/// );
+/// assert_eq!(
+///     Space::all(DimensionBoundaries::Float(0f32, 1f32), vec![2, 2]),
+///     space![0.0..=1.0; shape = (2, 2)]
+/// );
+/// ```
+#[macro_export]
+macro_rules! space {
+    ($($boundaries:expr),+ ; shape = ($($dimension:expr),+ $(,)?)) => {
+        $crate::space::Space::builder()
+            $(.boundaries($crate::space::DimensionBoundaries::from($boundaries)))+
+            .shape(&[$($dimension),+])
+            .build()
+            .unwrap_or_else(|error| panic!("Invalid space literal ({})", error))
+    };
+    ($($boundaries:expr),+ $(,)?) => {
+        $crate::space::Space::builder()
+            $(.boundaries($crate::space::DimensionBoundaries::from($boundaries)))+
+            .build()
+            .unwrap_or_else(|error| panic!("Invalid space literal ({})", error))
+    };
+}
+
+/// Creates a `Position` from a list of values convertible into `DimensionValue`s.
+///
+/// An optional shape can be given after a semicolon. If only a single value is given together
+/// with a shape, it is used for every element.
+///
+/// Panics if the values do not fit the shape.
+///
+/// ```
+/// use gymnarium_base::position;
+/// use gymnarium_base::space::{DimensionValue, Position};
+///
+/// assert_eq!(
+///     Position::simple(vec![DimensionValue::Integer(2), DimensionValue::Float(0.5f32)]),
+///     position![2, 0.5]
+/// );
+/// assert_eq!(
+///     Position::new((1..=4).map(DimensionValue::from).collect(), vec![2, 2]).unwrap(),
+///     position![1, 2, 3, 4; shape = (2, 2)]
+/// );
+/// ```
+#[macro_export]
+macro_rules! position {
+    ($($value:expr),+ ; shape = ($($dimension:expr),+ $(,)?)) => {{
+        let values = $crate::__private::vec![$($crate::space::DimensionValue::from($value)),+];
+        let shape = $crate::__private::vec![$($dimension),+];
+        if values.len() == 1 {
+            $crate::space::Position::all(values[0], shape)
+        } else {
+            $crate::space::Position::new(values, shape)
+                .unwrap_or_else(|error| panic!("Invalid position literal ({})", error))
+        }
+    }};
+    ($($value:expr),+ $(,)?) => {
+        $crate::space::Position::simple($crate::__private::vec![$($crate::space::DimensionValue::from($value)),+])
+    };
+}
+
+/// Defines a fieldless enum and implements `mappers::DiscreteAction` for it.
+///
+/// The enum derives `Debug`, `PartialEq`, `Eq`, `Clone` and `Copy`. Its variants are mapped to
+/// the action values `0`, `1`, ... in order of declaration.
+///
+/// ```
+/// use gymnarium_base::discrete_action;
+/// use gymnarium_base::mappers::DiscreteAction;
+/// use gymnarium_base::space::{Position, Space};
+///
+/// discrete_action! {
+///     /// Moves of a grid world agent.
+///     enum Move { Up, Down, Left, Right }
+/// }
+///
+/// assert_eq!(Space::multi_discrete(&[4]).unwrap(), Move::action_space());
+/// assert_eq!(Position::from_indices(&[2]), Move::Left.to_action());
+/// assert_eq!(Ok(Move::Down), Move::from_action(&Position::from_indices(&[1])));
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! discrete_action {
+    ($(#[$meta:meta])* $visibility:vis enum $name:ident { $($variant:ident),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        $visibility enum $name {
+            $($variant),+
+        }
+
+        impl $crate::mappers::DiscreteAction for $name {
+            const VARIANTS: &'static [Self] = &[$(Self::$variant),+];
+        }
+    };
+}
@@ -0,0 +1,471 @@
+//! Standard implementations of [`ToActionMapper`](../trait.ToActionMapper.html).
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::space::{
+    DimensionBoundaries, DimensionValue, DiscreteError, DiscreteRanges, Position, Space,
+};
+use crate::{ActionSpace, AgentAction, FromActionMapper, ToActionMapper};
+
+/* --- --- --- MAPPER ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum MapperError {
+    NotDiscrete {
+        index: usize,
+    },
+    /// The actions of the space cannot be counted with `usize`.
+    TooManyActions,
+    IndexOutOfRange {
+        index: usize,
+        count: usize,
+    },
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    UnknownKey(String),
+    UndecodableAction {
+        action: Box<AgentAction>,
+    },
+}
+
+impl std::fmt::Display for MapperError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotDiscrete { index } => {
+                write!(f, "Action boundaries at index {} are not discrete", index)
+            }
+            Self::TooManyActions => write!(f, "Action space has too many actions to be counted"),
+            Self::IndexOutOfRange { index, count } => write!(
+                f,
+                "Index {} is out of range of the {} available actions",
+                index, count
+            ),
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "Expected {} values but got {}", expected, actual)
+            }
+            Self::UnknownKey(key) => write!(f, "No action is mapped to key {}", key),
+            Self::UndecodableAction { action } => write!(f, "Action {} can not be decoded", action),
+        }
+    }
+}
+
+impl std::error::Error for MapperError {}
+
+impl From<DiscreteError> for MapperError {
+    fn from(error: DiscreteError) -> Self {
+        match error {
+            DiscreteError::NotDiscrete { index } => Self::NotDiscrete { index },
+            DiscreteError::TooManyPositions => Self::TooManyActions,
+        }
+    }
+}
+
+/* --- --- --- INDEX TO DISCRETE ACTION --- --- --- */
+
+/// Maps an index, e.g. the arg max of a network output, to an action.
+///
+/// ```
+/// use gymnarium_base::ToActionMapper;
+/// use gymnarium_base::mappers::IndexToDiscreteAction;
+/// use gymnarium_base::space::{Position, Space};
+///
+/// let mut mapper = IndexToDiscreteAction::new(Space::multi_discrete(&[2, 3]).unwrap()).unwrap();
+/// assert_eq!(6, mapper.count());
+/// assert_eq!(Ok(Position::from_indices(&[1, 1])), mapper.map(&4));
+///
+/// let mut one_hot = IndexToDiscreteAction::one_hot(3);
+/// assert_eq!(Ok(Position::from_bools(&[false, true, false])), one_hot.map(&1));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct IndexToDiscreteAction {
+    action_space: ActionSpace,
+    ranges: DiscreteRanges,
+    count: usize,
+    one_hot: bool,
+}
+
+impl IndexToDiscreteAction {
+    /// Enumerates all actions of the space, the last dimension varying fastest.
+    pub fn new(action_space: ActionSpace) -> Result<Self, MapperError> {
+        let ranges = DiscreteRanges::of(&action_space)?;
+        let count = ranges.count().ok_or(MapperError::TooManyActions)?;
+        Ok(Self {
+            action_space,
+            ranges,
+            count,
+            one_hot: false,
+        })
+    }
+
+    /// Maps index `i` to an action of `Space::multi_binary(n)` with only flag `i` set.
+    pub fn one_hot(n: usize) -> Self {
+        let action_space = Space::multi_binary(n);
+        Self {
+            ranges: DiscreteRanges::of(&action_space).expect("Binary spaces are discrete"),
+            action_space,
+            count: n,
+            one_hot: true,
+        }
+    }
+
+    pub fn action_space(&self) -> &ActionSpace {
+        &self.action_space
+    }
+
+    /// Returns how many indices can be mapped.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl ToActionMapper<usize, MapperError> for IndexToDiscreteAction {
+    fn map(&mut self, input: &usize) -> Result<AgentAction, MapperError> {
+        let count = self.count();
+        if *input >= count {
+            return Err(MapperError::IndexOutOfRange {
+                index: *input,
+                count,
+            });
+        }
+        if self.one_hot {
+            let mut flags = vec![false; count];
+            flags[*input] = true;
+            return Ok(Position::from_bools(&flags));
+        }
+        let values = self
+            .ranges
+            .decode(*input)
+            .expect("Indices below the count are decodable");
+        Ok(
+            Position::new(values, self.action_space.dimensions().clone())
+                .expect("Mapped values always match the action space dimensions"),
+        )
+    }
+}
+
+impl FromActionMapper<usize, MapperError> for IndexToDiscreteAction {
+    fn map_from(&mut self, action: &AgentAction) -> Result<usize, MapperError> {
+        let undecodable = || MapperError::UndecodableAction {
+            action: Box::new(action.clone()),
+        };
+        if self.action_space.contains(action).is_err() {
+            return Err(undecodable());
+        }
+        let offsets = self
+            .ranges
+            .offsets(&action.values())
+            .ok_or_else(undecodable)?;
+        if self.one_hot {
+            return match offsets.iter().filter(|flag| **flag == 1).count() {
+                1 => Ok(offsets.iter().position(|flag| *flag == 1).unwrap()),
+                _ => Err(undecodable()),
+            };
+        }
+        self.ranges.encode(&action.values()).ok_or_else(undecodable)
+    }
+}
+
+/* --- --- --- DISCRETE ACTION --- --- --- */
+
+/// Fieldless enums usable as actions of `Space::multi_discrete(&[VARIANTS.len()])`.
+///
+/// Usually implemented with the `discrete_action!` macro.
+pub trait DiscreteAction: Sized + Clone + PartialEq + 'static {
+    /// All variants, their position being the value of the action.
+    const VARIANTS: &'static [Self];
+
+    /// # Panics
+    /// If `VARIANTS` is empty or holds more than `i32::MAX` variants.
+    fn action_space() -> ActionSpace {
+        Space::multi_discrete(&[Self::VARIANTS.len()])
+            .expect("VARIANTS has to hold between 1 and i32::MAX variants")
+    }
+
+    fn to_action(&self) -> AgentAction {
+        let index = Self::VARIANTS
+            .iter()
+            .position(|variant| variant == self)
+            .expect("VARIANTS has to contain every variant");
+        Position::from_indices(&[index])
+    }
+
+    fn from_action(action: &AgentAction) -> Result<Self, MapperError> {
+        match action.to_indices().as_deref() {
+            Some([index]) if *index < Self::VARIANTS.len() => Ok(Self::VARIANTS[*index].clone()),
+            _ => Err(MapperError::UndecodableAction {
+                action: Box::new(action.clone()),
+            }),
+        }
+    }
+}
+
+/// Maps a `DiscreteAction` to its action and back.
+///
+/// ```
+/// use gymnarium_base::{discrete_action, FromActionMapper, ToActionMapper};
+/// use gymnarium_base::mappers::{DiscreteAction, DiscreteActionMapper};
+/// use gymnarium_base::space::Position;
+///
+/// discrete_action! {
+///     pub enum CartPoleAction {
+///         Left,
+///         Right,
+///     }
+/// }
+///
+/// let mut mapper = DiscreteActionMapper::new();
+/// assert_eq!(Ok(Position::from_indices(&[1])), mapper.map(&CartPoleAction::Right));
+/// assert_eq!(
+///     Ok(CartPoleAction::Left),
+///     mapper.map_from(&Position::from_indices(&[0]))
+/// );
+/// assert!(CartPoleAction::action_space()
+///     .contains(&CartPoleAction::Left.to_action())
+///     .is_ok());
+/// ```
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct DiscreteActionMapper<A: DiscreteAction> {
+    phantom: PhantomData<A>,
+}
+
+impl<A: DiscreteAction> DiscreteActionMapper<A> {
+    pub fn new() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: DiscreteAction> ToActionMapper<A, MapperError> for DiscreteActionMapper<A> {
+    fn map(&mut self, input: &A) -> Result<AgentAction, MapperError> {
+        Ok(input.to_action())
+    }
+}
+
+impl<A: DiscreteAction> FromActionMapper<A, MapperError> for DiscreteActionMapper<A> {
+    fn map_from(&mut self, action: &AgentAction) -> Result<A, MapperError> {
+        A::from_action(action)
+    }
+}
+
+/* --- --- --- VEC F32 TO CONTINUOUS ACTION --- --- --- */
+
+/// Maps raw values, e.g. a network output, to an action by clipping them into the action space.
+///
+/// Values for integer dimensions are rounded before clipping.
+///
+/// ```
+/// use gymnarium_base::ToActionMapper;
+/// use gymnarium_base::mappers::VecF32ToContinuousAction;
+/// use gymnarium_base::space::{DimensionBoundaries, DimensionValue, Position, Space};
+///
+/// let mut mapper = VecF32ToContinuousAction::new(Space::simple(vec![
+///     DimensionBoundaries::from(-1f32..=1f32),
+///     DimensionBoundaries::from(0..=4),
+/// ]));
+/// assert_eq!(
+///     Ok(Position::simple(vec![DimensionValue::from(1f32), DimensionValue::from(3)])),
+///     mapper.map(&vec![1.7, 2.6])
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct VecF32ToContinuousAction {
+    action_space: ActionSpace,
+}
+
+impl VecF32ToContinuousAction {
+    pub fn new(action_space: ActionSpace) -> Self {
+        Self { action_space }
+    }
+
+    pub fn action_space(&self) -> &ActionSpace {
+        &self.action_space
+    }
+}
+
+impl ToActionMapper<Vec<f32>, MapperError> for VecF32ToContinuousAction {
+    fn map(&mut self, input: &Vec<f32>) -> Result<AgentAction, MapperError> {
+        let boundaries = self.action_space.get_boundaries();
+        if input.len() != boundaries.len() {
+            return Err(MapperError::LengthMismatch {
+                expected: boundaries.len(),
+                actual: input.len(),
+            });
+        }
+        let values = boundaries
+            .iter()
+            .zip(input.iter())
+            .map(|(boundaries, value)| match boundaries {
+                DimensionBoundaries::Float(min, max) => {
+                    DimensionValue::Float(value.clamp(*min, *max))
+                }
+                _ => {
+                    let (min, max) = boundaries
+                        .integer_range()
+                        .expect("Boundaries are not float");
+                    DimensionValue::Integer((value.round() as i32).clamp(min, max))
+                }
+            })
+            .collect();
+        Ok(
+            Position::new(values, self.action_space.dimensions().clone())
+                .expect("Mapped values always match the action space dimensions"),
+        )
+    }
+}
+
+/* --- --- --- KEYMAP ACTION MAPPER --- --- --- */
+
+/// Maps keys, e.g. pressed characters of a user interface, to fixed actions.
+///
+/// ```
+/// use gymnarium_base::ToActionMapper;
+/// use gymnarium_base::mappers::{KeymapActionMapper, MapperError};
+/// use gymnarium_base::space::Position;
+///
+/// let mut mapper = KeymapActionMapper::new()
+///     .with('a', Position::from_indices(&[0]))
+///     .with('d', Position::from_indices(&[2]));
+/// assert_eq!(Ok(Position::from_indices(&[2])), mapper.map(&'d'));
+/// assert_eq!(Err(MapperError::UnknownKey("'x'".to_string())), mapper.map(&'x'));
+///
+/// let mut mapper = mapper.with_default(Position::from_indices(&[1]));
+/// assert_eq!(Ok(Position::from_indices(&[1])), mapper.map(&'x'));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct KeymapActionMapper<K: Eq + Hash> {
+    actions: HashMap<K, AgentAction>,
+    default: Option<AgentAction>,
+}
+
+impl<K: Eq + Hash> Default for KeymapActionMapper<K> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::new(),
+            default: None,
+        }
+    }
+}
+
+impl<K: Eq + Hash> KeymapActionMapper<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: K, action: AgentAction) -> Self {
+        self.insert(key, action);
+        self
+    }
+
+    /// Sets the action returned for keys without an own action.
+    pub fn with_default(mut self, action: AgentAction) -> Self {
+        self.default = Some(action);
+        self
+    }
+
+    pub fn insert(&mut self, key: K, action: AgentAction) -> Option<AgentAction> {
+        self.actions.insert(key, action)
+    }
+}
+
+impl<K: Eq + Hash + Clone + Debug> ToActionMapper<K, MapperError> for KeymapActionMapper<K> {
+    fn map(&mut self, input: &K) -> Result<AgentAction, MapperError> {
+        self.actions
+            .get(input)
+            .or(self.default.as_ref())
+            .cloned()
+            .ok_or_else(|| MapperError::UnknownKey(format!("{:?}", input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_mapper_covers_every_action_once() {
+        let space = Space::simple(vec![
+            DimensionBoundaries::from(-1..=1),
+            DimensionBoundaries::from(2..=3),
+        ]);
+        let mut mapper = IndexToDiscreteAction::new(space.clone()).unwrap();
+        let actions: Vec<AgentAction> = (0..mapper.count())
+            .map(|index| mapper.map(&index).unwrap())
+            .collect();
+
+        assert!(actions.iter().all(|action| space.contains(action).is_ok()));
+        for (index, action) in actions.iter().enumerate() {
+            assert!(!actions[index + 1..].contains(action));
+        }
+        assert_eq!(
+            Err(MapperError::IndexOutOfRange { index: 6, count: 6 }),
+            mapper.map(&6)
+        );
+        assert_eq!(
+            Err(MapperError::NotDiscrete { index: 0 }),
+            IndexToDiscreteAction::new(Space::simple(vec![DimensionBoundaries::from(1f32)]))
+        );
+    }
+
+    #[test]
+    fn index_mapper_decodes_its_own_actions() {
+        let mut mapper =
+            IndexToDiscreteAction::new(Space::multi_discrete(&[3, 2, 4]).unwrap()).unwrap();
+        for index in 0..mapper.count() {
+            let action = mapper.map(&index).unwrap();
+            assert_eq!(Ok(index), mapper.map_from(&action));
+        }
+
+        let mut negative = IndexToDiscreteAction::new(Space::simple(vec![
+            DimensionBoundaries::from(-1..=1),
+            DimensionBoundaries::from(2..=3),
+        ]))
+        .unwrap();
+        for index in 0..negative.count() {
+            let action = negative.map(&index).unwrap();
+            assert_eq!(Ok(index), negative.map_from(&action));
+        }
+
+        let mut one_hot = IndexToDiscreteAction::one_hot(3);
+        assert_eq!(
+            Ok(2),
+            one_hot.map_from(&Position::from_bools(&[false, false, true]))
+        );
+        assert!(one_hot
+            .map_from(&Position::from_bools(&[true, false, true]))
+            .is_err());
+    }
+
+    #[test]
+    fn index_mapper_counts_full_integer_ranges() {
+        let mut mapper =
+            IndexToDiscreteAction::new(Space::simple(vec![DimensionBoundaries::from(
+                i32::MIN..=i32::MAX,
+            )]))
+            .unwrap();
+        assert_eq!(Ok(crate::position![i32::MIN]), mapper.map(&0));
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(1 << 32, mapper.count());
+    }
+
+    #[test]
+    fn continuous_mapper_checks_length() {
+        let mut mapper = VecF32ToContinuousAction::new(Space::multi_binary(2));
+        assert_eq!(
+            Err(MapperError::LengthMismatch {
+                expected: 2,
+                actual: 1
+            }),
+            mapper.map(&vec![0.0])
+        );
+        assert_eq!(
+            Ok(Position::from_bools(&[false, true])),
+            mapper.map(&vec![-3.0, 0.6])
+        );
+    }
+}
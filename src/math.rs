@@ -1,6 +1,46 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
+use core::convert::TryInto;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
 
-use serde::{Deserialize, Serialize};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::float::Float;
+use serde::de::Error as DeserializeError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub mod integrate;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_conversions;
+mod pose;
+mod single_precision;
+
+pub use pose::{Angle, Pose2D};
+pub use single_precision::{Position2Df32, Position3Df32, Vector2Df32, Vector3Df32};
+
+/* --- --- --- MATH ERROR --- --- --- */
+
+/// General errors for this module.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MathError {
+    SingularMatrix { determinant: f64 },
+}
+
+impl core::fmt::Display for MathError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::SingularMatrix { determinant } => write!(
+                f,
+                "Matrix is singular (determinant {}) and can not be inverted",
+                determinant
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MathError {}
 
 /* --- --- --- Position2D --- --- --- */
 
@@ -24,6 +64,34 @@ impl Position2D {
         Self { x: 1f64, y: 1f64 }
     }
 
+    /// Returns the component-wise minimum of both.
+    pub fn min(&self, other: &Position2D) -> Self {
+        Self::with(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Returns the component-wise maximum of both.
+    pub fn max(&self, other: &Position2D) -> Self {
+        Self::with(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// Restricts every component to the range given by the components of `low` and `high`.
+    pub fn clamp(&self, low: &Position2D, high: &Position2D) -> Self {
+        Self::with(self.x.clamp(low.x, high.x), self.y.clamp(low.y, high.y))
+    }
+
+    /// Returns the absolute value of every component.
+    pub fn abs(&self) -> Self {
+        Self::with(self.x.abs(), self.y.abs())
+    }
+
+    /// Interpolates linearly towards `other`, returning `self` for `t = 0` and `other` for `t = 1`.
+    pub fn lerp(&self, other: &Position2D, t: f64) -> Self {
+        Self::with(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+
     pub fn vector_to(&self, other: &Position2D) -> Vector2D {
         Vector2D::with(other.x - self.x, other.y - self.y)
     }
@@ -111,6 +179,47 @@ impl Position3D {
         }
     }
 
+    /// Returns the component-wise minimum of both.
+    pub fn min(&self, other: &Position3D) -> Self {
+        Self::with(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Returns the component-wise maximum of both.
+    pub fn max(&self, other: &Position3D) -> Self {
+        Self::with(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// Restricts every component to the range given by the components of `low` and `high`.
+    pub fn clamp(&self, low: &Position3D, high: &Position3D) -> Self {
+        Self::with(
+            self.x.clamp(low.x, high.x),
+            self.y.clamp(low.y, high.y),
+            self.z.clamp(low.z, high.z),
+        )
+    }
+
+    /// Returns the absolute value of every component.
+    pub fn abs(&self) -> Self {
+        Self::with(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// Interpolates linearly towards `other`, returning `self` for `t = 0` and `other` for `t = 1`.
+    pub fn lerp(&self, other: &Position3D, t: f64) -> Self {
+        Self::with(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+            self.z + (other.z - self.z) * t,
+        )
+    }
+
     pub fn vector_to(&self, other: &Position3D) -> Vector3D {
         Vector3D::with(other.x - self.x, other.y - self.y, other.z - self.z)
     }
@@ -133,7 +242,7 @@ impl Position3D {
     }
 
     pub fn rotate_x_around(&self, rotate_position: &Position3D, degree: f64) -> Self {
-        let radians = degree / 180f64 * std::f64::consts::PI;
+        let radians = degree / 180f64 * core::f64::consts::PI;
         let (radians_sin, radians_cos) = radians.sin_cos();
         Position3D::with(
             self.x,
@@ -145,7 +254,7 @@ impl Position3D {
     }
 
     pub fn rotate_y_around(&self, rotate_position: &Position3D, degree: f64) -> Self {
-        let radians = degree / 180f64 * std::f64::consts::PI;
+        let radians = degree / 180f64 * core::f64::consts::PI;
         let (radians_sin, radians_cos) = radians.sin_cos();
         Position3D::with(
             self.x * radians_cos + self.z * radians_sin + rotate_position.x * radians_cos
@@ -157,7 +266,7 @@ impl Position3D {
     }
 
     pub fn rotate_z_around(&self, rotate_position: &Position3D, degree: f64) -> Self {
-        let radians = degree / 180f64 * std::f64::consts::PI;
+        let radians = degree / 180f64 * core::f64::consts::PI;
         let (radians_sin, radians_cos) = radians.sin_cos();
         Position3D::with(
             self.x * radians_cos - self.y * radians_sin + rotate_position.x * radians_cos - rotate_position.y * radians_sin - rotate_position.x,
@@ -215,6 +324,7 @@ impl SubAssign<Vector3D> for Position3D {
 
 /// A vector inside the two dimensional space.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Vector2D {
     pub x: f64,
     pub y: f64,
@@ -233,6 +343,34 @@ impl Vector2D {
         Self { x: 1f64, y: 1f64 }
     }
 
+    /// Returns the component-wise minimum of both.
+    pub fn min(&self, other: &Vector2D) -> Self {
+        Self::with(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Returns the component-wise maximum of both.
+    pub fn max(&self, other: &Vector2D) -> Self {
+        Self::with(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// Restricts every component to the range given by the components of `low` and `high`.
+    pub fn clamp(&self, low: &Vector2D, high: &Vector2D) -> Self {
+        Self::with(self.x.clamp(low.x, high.x), self.y.clamp(low.y, high.y))
+    }
+
+    /// Returns the absolute value of every component.
+    pub fn abs(&self) -> Self {
+        Self::with(self.x.abs(), self.y.abs())
+    }
+
+    /// Interpolates linearly towards `other`, returning `self` for `t = 0` and `other` for `t = 1`.
+    pub fn lerp(&self, other: &Vector2D, t: f64) -> Self {
+        Self::with(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+
     pub fn length(&self) -> f64 {
         (self.x.powi(2) + self.y.powi(2)).sqrt()
     }
@@ -240,6 +378,43 @@ impl Vector2D {
     pub fn normalized(self) -> Self {
         self / self.length()
     }
+
+    pub fn dot(&self, other: &Vector2D) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns this vector rotated counterclockwise by 90 degrees.
+    pub fn perpendicular(&self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Returns the unsigned angle between both vectors in radians, `0` if either is the zero
+    /// vector.
+    pub fn angle_between(&self, other: &Vector2D) -> f64 {
+        let lengths = self.length() * other.length();
+        if lengths == 0f64 {
+            return 0f64;
+        }
+        (self.dot(other) / lengths).clamp(-1f64, 1f64).acos()
+    }
+
+    /// Returns the part of this vector pointing along `other`, the zero vector if `other` is
+    /// the zero vector.
+    pub fn projection_onto(&self, other: &Vector2D) -> Self {
+        let squared_length = other.dot(other);
+        if squared_length == 0f64 {
+            return Self::zero();
+        }
+        *other * (self.dot(other) / squared_length)
+    }
+
+    /// Returns the part of this vector perpendicular to `other`.
+    pub fn rejection_from(&self, other: &Vector2D) -> Self {
+        *self - self.projection_onto(other)
+    }
 }
 
 impl Add for Vector2D {
@@ -392,6 +567,47 @@ impl Vector3D {
         }
     }
 
+    /// Returns the component-wise minimum of both.
+    pub fn min(&self, other: &Vector3D) -> Self {
+        Self::with(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Returns the component-wise maximum of both.
+    pub fn max(&self, other: &Vector3D) -> Self {
+        Self::with(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// Restricts every component to the range given by the components of `low` and `high`.
+    pub fn clamp(&self, low: &Vector3D, high: &Vector3D) -> Self {
+        Self::with(
+            self.x.clamp(low.x, high.x),
+            self.y.clamp(low.y, high.y),
+            self.z.clamp(low.z, high.z),
+        )
+    }
+
+    /// Returns the absolute value of every component.
+    pub fn abs(&self) -> Self {
+        Self::with(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// Interpolates linearly towards `other`, returning `self` for `t = 0` and `other` for `t = 1`.
+    pub fn lerp(&self, other: &Vector3D, t: f64) -> Self {
+        Self::with(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+            self.z + (other.z - self.z) * t,
+        )
+    }
+
     pub fn length(&self) -> f64 {
         (self.x.powi(2) + self.y.powi(2) + self.z.powi(2)).sqrt()
     }
@@ -399,6 +615,43 @@ impl Vector3D {
     pub fn normalized(self) -> Self {
         self / self.length()
     }
+
+    pub fn dot(&self, other: &Vector3D) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Vector3D) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Returns the unsigned angle between both vectors in radians, `0` if either is the zero
+    /// vector.
+    pub fn angle_between(&self, other: &Vector3D) -> f64 {
+        let lengths = self.length() * other.length();
+        if lengths == 0f64 {
+            return 0f64;
+        }
+        (self.dot(other) / lengths).clamp(-1f64, 1f64).acos()
+    }
+
+    /// Returns the part of this vector pointing along `other`, the zero vector if `other` is
+    /// the zero vector.
+    pub fn projection_onto(&self, other: &Vector3D) -> Self {
+        let squared_length = other.dot(other);
+        if squared_length == 0f64 {
+            return Self::zero();
+        }
+        *other * (self.dot(other) / squared_length)
+    }
+
+    /// Returns the part of this vector perpendicular to `other`.
+    pub fn rejection_from(&self, other: &Vector3D) -> Self {
+        *self - self.projection_onto(other)
+    }
 }
 
 impl Add for Vector3D {
@@ -569,6 +822,60 @@ impl Size2D {
     }
 }
 
+/* --- --- --- Rect2D --- --- --- */
+
+/// A rectangle inside the two dimensional space, defined by its lower corner and its size.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rect2D {
+    pub position: Position2D,
+    pub size: Size2D,
+}
+
+impl Rect2D {
+    pub fn with(position: Position2D, size: Size2D) -> Self {
+        Self { position, size }
+    }
+
+    pub fn from_corners(corner_a: Position2D, corner_b: Position2D) -> Self {
+        AABB2D::with(corner_a, corner_b).into()
+    }
+
+    pub fn min(&self) -> Position2D {
+        self.position
+    }
+
+    pub fn max(&self) -> Position2D {
+        self.position + Vector2D::with(self.size.width, self.size.height)
+    }
+
+    pub fn center(&self) -> Position2D {
+        self.position + Vector2D::with(self.size.width / 2f64, self.size.height / 2f64)
+    }
+
+    pub fn aspect_ratio(&self) -> f64 {
+        self.size.width / self.size.height
+    }
+
+    pub fn contains(&self, position: &Position2D) -> bool {
+        AABB2D::from(*self).contains(position)
+    }
+}
+
+impl From<AABB2D> for Rect2D {
+    fn from(aabb: AABB2D) -> Self {
+        Self {
+            position: aabb.min,
+            size: aabb.size(),
+        }
+    }
+}
+
+impl From<Rect2D> for AABB2D {
+    fn from(rect: Rect2D) -> Self {
+        AABB2D::with(rect.min(), rect.max())
+    }
+}
+
 /* --- --- --- Size3D --- --- --- */
 
 /// A size inside the three dimensional space.
@@ -613,9 +920,472 @@ impl Size3D {
     }
 }
 
+/* --- --- --- AABB2D --- --- --- */
+
+/// An axis-aligned bounding box inside the two dimensional space.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AABB2D {
+    pub min: Position2D,
+    pub max: Position2D,
+}
+
+impl AABB2D {
+    /// Creates a box spanning both corners regardless of their order.
+    pub fn with(corner_a: Position2D, corner_b: Position2D) -> Self {
+        Self {
+            min: Position2D::with(corner_a.x.min(corner_b.x), corner_a.y.min(corner_b.y)),
+            max: Position2D::with(corner_a.x.max(corner_b.x), corner_a.y.max(corner_b.y)),
+        }
+    }
+
+    pub fn from_center(center: Position2D, size: Size2D) -> Self {
+        let half_size = Vector2D::with(size.width / 2f64, size.height / 2f64);
+        Self::with(center - half_size, center + half_size)
+    }
+
+    pub fn center(&self) -> Position2D {
+        self.min + self.min.vector_to(&self.max) / 2f64
+    }
+
+    pub fn size(&self) -> Size2D {
+        Size2D::with(self.max.x - self.min.x, self.max.y - self.min.y)
+    }
+
+    pub fn contains(&self, position: &Position2D) -> bool {
+        self.min.x <= position.x
+            && position.x <= self.max.x
+            && self.min.y <= position.y
+            && position.y <= self.max.y
+    }
+
+    pub fn contains_aabb(&self, other: &AABB2D) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// Returns true if both boxes share at least one point (touching counts).
+    pub fn overlaps(&self, other: &AABB2D) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    /// Returns the overlapping area of both boxes if there is one.
+    pub fn intersection(&self, other: &AABB2D) -> Option<Self> {
+        if self.overlaps(other) {
+            Some(Self {
+                min: Position2D::with(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+                max: Position2D::with(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest box containing both boxes.
+    pub fn union(&self, other: &AABB2D) -> Self {
+        Self {
+            min: Position2D::with(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Position2D::with(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// Returns the smallest box containing this box and the given position.
+    pub fn expanded_to(&self, position: &Position2D) -> Self {
+        self.union(&Self {
+            min: *position,
+            max: *position,
+        })
+    }
+
+    /// Returns this box grown by `margin` on every side.
+    pub fn expanded_by(&self, margin: f64) -> Self {
+        let margin = Vector2D::with(margin, margin);
+        Self::with(self.min - margin, self.max + margin)
+    }
+
+    /// Returns the ray parameter `t` at which the ray `origin + t * direction` first touches
+    /// this box, or `None` if it misses the box.
+    ///
+    /// If the origin lies inside the box `0` is returned.
+    pub fn intersects_ray(&self, origin: &Position2D, direction: &Vector2D) -> Option<f64> {
+        let (t_min, _) = intersect_ray_with_slab(
+            origin.x,
+            direction.x,
+            self.min.x,
+            self.max.x,
+            (0f64, f64::INFINITY),
+        )
+        .and_then(|t| intersect_ray_with_slab(origin.y, direction.y, self.min.y, self.max.y, t))?;
+        Some(t_min)
+    }
+}
+
+/* --- --- --- AABB3D --- --- --- */
+
+/// An axis-aligned bounding box inside the three dimensional space.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AABB3D {
+    pub min: Position3D,
+    pub max: Position3D,
+}
+
+impl AABB3D {
+    /// Creates a box spanning both corners regardless of their order.
+    pub fn with(corner_a: Position3D, corner_b: Position3D) -> Self {
+        Self {
+            min: Position3D::with(
+                corner_a.x.min(corner_b.x),
+                corner_a.y.min(corner_b.y),
+                corner_a.z.min(corner_b.z),
+            ),
+            max: Position3D::with(
+                corner_a.x.max(corner_b.x),
+                corner_a.y.max(corner_b.y),
+                corner_a.z.max(corner_b.z),
+            ),
+        }
+    }
+
+    pub fn from_center(center: Position3D, size: Size3D) -> Self {
+        let half_size = Vector3D::with(size.width / 2f64, size.height / 2f64, size.length / 2f64);
+        Self::with(center - half_size, center + half_size)
+    }
+
+    pub fn center(&self) -> Position3D {
+        self.min + self.min.vector_to(&self.max) / 2f64
+    }
+
+    pub fn size(&self) -> Size3D {
+        Size3D::with(
+            self.max.x - self.min.x,
+            self.max.y - self.min.y,
+            self.max.z - self.min.z,
+        )
+    }
+
+    pub fn contains(&self, position: &Position3D) -> bool {
+        self.min.x <= position.x
+            && position.x <= self.max.x
+            && self.min.y <= position.y
+            && position.y <= self.max.y
+            && self.min.z <= position.z
+            && position.z <= self.max.z
+    }
+
+    pub fn contains_aabb(&self, other: &AABB3D) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    /// Returns true if both boxes share at least one point (touching counts).
+    pub fn overlaps(&self, other: &AABB3D) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    /// Returns the overlapping volume of both boxes if there is one.
+    pub fn intersection(&self, other: &AABB3D) -> Option<Self> {
+        if self.overlaps(other) {
+            Some(Self {
+                min: Position3D::with(
+                    self.min.x.max(other.min.x),
+                    self.min.y.max(other.min.y),
+                    self.min.z.max(other.min.z),
+                ),
+                max: Position3D::with(
+                    self.max.x.min(other.max.x),
+                    self.max.y.min(other.max.y),
+                    self.max.z.min(other.max.z),
+                ),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest box containing both boxes.
+    pub fn union(&self, other: &AABB3D) -> Self {
+        Self {
+            min: Position3D::with(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Position3D::with(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    /// Returns the smallest box containing this box and the given position.
+    pub fn expanded_to(&self, position: &Position3D) -> Self {
+        self.union(&Self {
+            min: *position,
+            max: *position,
+        })
+    }
+
+    /// Returns this box grown by `margin` on every side.
+    pub fn expanded_by(&self, margin: f64) -> Self {
+        let margin = Vector3D::with(margin, margin, margin);
+        Self::with(self.min - margin, self.max + margin)
+    }
+
+    /// Returns the ray parameter `t` at which the ray `origin + t * direction` first touches
+    /// this box, or `None` if it misses the box.
+    ///
+    /// If the origin lies inside the box `0` is returned.
+    pub fn intersects_ray(&self, origin: &Position3D, direction: &Vector3D) -> Option<f64> {
+        let (t_min, _) = intersect_ray_with_slab(
+            origin.x,
+            direction.x,
+            self.min.x,
+            self.max.x,
+            (0f64, f64::INFINITY),
+        )
+        .and_then(|t| intersect_ray_with_slab(origin.y, direction.y, self.min.y, self.max.y, t))
+        .and_then(|t| intersect_ray_with_slab(origin.z, direction.z, self.min.z, self.max.z, t))?;
+        Some(t_min)
+    }
+}
+
+/// Narrows the ray parameter interval `(t_min, t_max)` to the part inside the slab `min..=max`
+/// of one axis.
+fn intersect_ray_with_slab(
+    origin: f64,
+    direction: f64,
+    min: f64,
+    max: f64,
+    (t_min, t_max): (f64, f64),
+) -> Option<(f64, f64)> {
+    if direction == 0f64 {
+        if min <= origin && origin <= max {
+            Some((t_min, t_max))
+        } else {
+            None
+        }
+    } else {
+        let t_a = (min - origin) / direction;
+        let t_b = (max - origin) / direction;
+        let t_min = t_min.max(t_a.min(t_b));
+        let t_max = t_max.min(t_a.max(t_b));
+        if t_min <= t_max {
+            Some((t_min, t_max))
+        } else {
+            None
+        }
+    }
+}
+
+/* --- --- --- LineSegment2D --- --- --- */
+
+/// A straight line between two positions inside the two dimensional space.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineSegment2D {
+    pub start: Position2D,
+    pub end: Position2D,
+}
+
+impl LineSegment2D {
+    pub fn with(start: Position2D, end: Position2D) -> Self {
+        Self { start, end }
+    }
+
+    pub fn direction(&self) -> Vector2D {
+        self.start.vector_to(&self.end)
+    }
+
+    pub fn length(&self) -> f64 {
+        self.direction().length()
+    }
+
+    pub fn aabb(&self) -> AABB2D {
+        AABB2D::with(self.start, self.end)
+    }
+
+    /// Returns the position on this segment which is closest to the given position.
+    pub fn closest_position_to(&self, position: &Position2D) -> Position2D {
+        let direction = self.direction();
+        let squared_length = direction.dot(&direction);
+        if squared_length == 0f64 {
+            self.start
+        } else {
+            let t =
+                (self.start.vector_to(position).dot(&direction) / squared_length).clamp(0f64, 1f64);
+            self.start + direction * t
+        }
+    }
+
+    pub fn distance_to(&self, position: &Position2D) -> f64 {
+        self.closest_position_to(position).distance_to(position)
+    }
+
+    /// Returns true if both segments share at least one point, including collinear overlaps.
+    pub fn intersects(&self, other: &LineSegment2D) -> bool {
+        let o1 = orientation(&self.start, &self.end, &other.start);
+        let o2 = orientation(&self.start, &self.end, &other.end);
+        let o3 = orientation(&other.start, &other.end, &self.start);
+        let o4 = orientation(&other.start, &other.end, &self.end);
+
+        (o1 * o2 < 0f64 && o3 * o4 < 0f64)
+            || (o1 == 0f64 && self.aabb().contains(&other.start))
+            || (o2 == 0f64 && self.aabb().contains(&other.end))
+            || (o3 == 0f64 && other.aabb().contains(&self.start))
+            || (o4 == 0f64 && other.aabb().contains(&self.end))
+    }
+
+    /// Returns the single crossing point of both segments.
+    ///
+    /// Parallel segments return `None` even if they overlap, use `intersects` for those.
+    pub fn intersection(&self, other: &LineSegment2D) -> Option<Position2D> {
+        let direction = self.direction();
+        let other_direction = other.direction();
+        let denominator = cross_2d(&direction, &other_direction);
+        if denominator == 0f64 {
+            return None;
+        }
+        let start_to_start = self.start.vector_to(&other.start);
+        let t = cross_2d(&start_to_start, &other_direction) / denominator;
+        let u = cross_2d(&start_to_start, &direction) / denominator;
+        if (0f64..=1f64).contains(&t) && (0f64..=1f64).contains(&u) {
+            Some(self.start + direction * t)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the z component of the cross product of both vectors.
+fn cross_2d(a: &Vector2D, b: &Vector2D) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Returns a positive value for counterclockwise, a negative for clockwise and zero for collinear
+/// positions.
+fn orientation(a: &Position2D, b: &Position2D, c: &Position2D) -> f64 {
+    cross_2d(&a.vector_to(b), &a.vector_to(c))
+}
+
+/* --- --- --- Circle2D --- --- --- */
+
+/// A circle inside the two dimensional space.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Circle2D {
+    pub center: Position2D,
+    pub radius: f64,
+}
+
+impl Circle2D {
+    pub fn with(center: Position2D, radius: f64) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn aabb(&self) -> AABB2D {
+        AABB2D::from_center(
+            self.center,
+            Size2D::with(self.radius * 2f64, self.radius * 2f64),
+        )
+    }
+
+    pub fn contains(&self, position: &Position2D) -> bool {
+        self.center.distance_to(position) <= self.radius
+    }
+
+    pub fn intersects_circle(&self, other: &Circle2D) -> bool {
+        self.center.distance_to(&other.center) <= self.radius + other.radius
+    }
+
+    pub fn intersects_line_segment(&self, line_segment: &LineSegment2D) -> bool {
+        line_segment.distance_to(&self.center) <= self.radius
+    }
+}
+
+/* --- --- --- Polygon2D --- --- --- */
+
+/// A closed polygon inside the two dimensional space.
+///
+/// The last vertex is implicitly connected to the first one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Polygon2D {
+    pub vertices: Vec<Position2D>,
+}
+
+impl Polygon2D {
+    pub fn with(vertices: Vec<Position2D>) -> Self {
+        Self { vertices }
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = LineSegment2D> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(start, end)| LineSegment2D::with(*start, *end))
+    }
+
+    pub fn aabb(&self) -> Option<AABB2D> {
+        let first = self.vertices.first()?;
+        Some(
+            self.vertices
+                .iter()
+                .fold(AABB2D::with(*first, *first), |aabb, vertex| {
+                    aabb.expanded_to(vertex)
+                }),
+        )
+    }
+
+    /// Returns true if the position lies inside this polygon (works for concave polygons too).
+    pub fn contains(&self, position: &Position2D) -> bool {
+        self.edges().fold(false, |inside, edge| {
+            if (edge.start.y > position.y) != (edge.end.y > position.y) {
+                let crossing_x = edge.start.x
+                    + (position.y - edge.start.y) / (edge.end.y - edge.start.y)
+                        * (edge.end.x - edge.start.x);
+                if position.x < crossing_x {
+                    return !inside;
+                }
+            }
+            inside
+        })
+    }
+
+    /// Checks with the separating axis theorem whether both polygons overlap.
+    ///
+    /// Only gives correct results for convex polygons. Polygons with fewer than three vertices
+    /// enclose no area and never intersect anything.
+    pub fn intersects_polygon(&self, other: &Polygon2D) -> bool {
+        if self.vertices.len() < 3 || other.vertices.len() < 3 {
+            return false;
+        }
+        self.edges()
+            .chain(other.edges())
+            .map(|edge| edge.direction().perpendicular())
+            .all(|axis| {
+                let (self_min, self_max) = self.project_onto(&axis);
+                let (other_min, other_max) = other.project_onto(&axis);
+                self_min <= other_max && other_min <= self_max
+            })
+    }
+
+    fn project_onto(&self, axis: &Vector2D) -> (f64, f64) {
+        self.vertices
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), vertex| {
+                let projected = Position2D::zero().vector_to(vertex).dot(axis);
+                (min.min(projected), max.max(projected))
+            })
+    }
+}
+
 /* --- --- --- Transformation2D --- --- --- */
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Transformation2D {
     Translation {
         direction: Vector2D,
@@ -722,7 +1492,30 @@ impl Transformation2D {
             vec![
                 Self::translation(rotation_position.vector_to(&Position2D::zero())),
                 Self::rotation(angle),
-                Self::translation(Position2D::zero().vector_to(&rotation_position)),
+                Self::translation(Position2D::zero().vector_to(rotation_position)),
+            ],
+        )
+    }
+
+    /// Maps the `source` rectangle onto the `destination` rectangle, e.g. world coordinates onto
+    /// viewer pixel coordinates.
+    ///
+    /// With `keep_aspect` both axes are scaled by the same factor and the result is centered
+    /// inside `destination`.
+    pub fn fit_rect_into(source: Rect2D, destination: Rect2D, keep_aspect: bool) -> Self {
+        let x_factor = destination.size.width / source.size.width;
+        let y_factor = destination.size.height / source.size.height;
+        let scale = if keep_aspect {
+            Self::isotropic_scale(x_factor.min(y_factor))
+        } else {
+            Self::scale(x_factor, y_factor)
+        };
+        Self::composition(
+            "FitRectInto".to_string(),
+            vec![
+                Self::translation(source.center().vector_to(&Position2D::zero())),
+                scale,
+                Self::translation(Position2D::zero().vector_to(&destination.center())),
             ],
         )
     }
@@ -797,80 +1590,465 @@ impl Transformation2D {
         matrix_3x3_as_matrix_3x2(self.transformation_matrix())
     }
 
-    pub fn reverse(self) -> Self {
-        match self {
-            Self::Composition {
-                name,
+    /// Returns the transformation undoing this one.
+    ///
+    /// Fails if the transformation collapses the space (e.g. a scale by zero) and therefore can
+    /// not be undone.
+    pub fn reverse(self) -> Result<Self, MathError> {
+        Ok(match self {
+            Self::Translation { direction } => Self::Translation {
+                direction: -direction,
+            },
+            Self::Identity => Self::Identity,
+            Self::Rotation { angle_in_degree } => Self::Rotation {
+                angle_in_degree: -angle_in_degree,
+            },
+            Self::Scale { x_factor, y_factor } if x_factor != 0f64 && y_factor != 0f64 => {
+                Self::Scale {
+                    x_factor: 1f64 / x_factor,
+                    y_factor: 1f64 / y_factor,
+                }
+            }
+            Self::IsotropicScale { factor } if factor != 0f64 => Self::IsotropicScale {
+                factor: 1f64 / factor,
+            },
+            Self::ReflectionX => Self::ReflectionX,
+            Self::ReflectionY => Self::ReflectionY,
+            Self::ShearX { amount } => Self::ShearX { amount: -amount },
+            Self::ShearY { amount } => Self::ShearY { amount: -amount },
+            Self::ShearXDegree { degree } => Self::ShearXDegree { degree: -degree },
+            Self::ShearYDegree { degree } => Self::ShearYDegree { degree: -degree },
+            Self::Composition {
+                name,
                 transformations,
             } => Self::Composition {
                 name: format!("Reverse-{:?}", name),
                 transformations: transformations
                     .into_iter()
+                    .rev()
                     .map(|transformation| transformation.reverse())
-                    .collect(),
+                    .collect::<Result<_, _>>()?,
             },
             t => Self::Custom {
                 name: format!("Reverse-{:?}", t),
-                transformation: inverse_of_matrix_3x3(t.transformation_matrix()),
+                transformation: Matrix3(t.transformation_matrix()).checked_inverse()?.0,
             },
+        })
+    }
+}
+
+/* --- --- --- Matrix3 --- --- --- */
+
+/// A 3x3 matrix stored row by row (`self.0[row][column]`).
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Matrix3(pub [[f64; 3]; 3]);
+
+impl Matrix3 {
+    pub fn from_rows(rows: [[f64; 3]; 3]) -> Self {
+        Self(rows)
+    }
+
+    pub fn zero() -> Self {
+        Self([[0f64; 3]; 3])
+    }
+
+    pub fn identity() -> Self {
+        Self([[1f64, 0f64, 0f64], [0f64, 1f64, 0f64], [0f64, 0f64, 1f64]])
+    }
+
+    pub fn rows(&self) -> [[f64; 3]; 3] {
+        self.0
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut output = [[0f64; 3]; 3];
+        for (row, output_row) in output.iter_mut().enumerate() {
+            for (column, value) in output_row.iter_mut().enumerate() {
+                *value = self.0[column][row];
+            }
+        }
+        Self(output)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Returns the inverse of this matrix.
+    ///
+    /// The result is not checked, so a singular matrix yields non-finite values.
+    pub fn inverse(&self) -> Self {
+        let m = &self.0;
+        let determinant = self.determinant();
+        Self([
+            [
+                (m[1][1] * m[2][2] - m[1][2] * m[2][1]) / determinant,
+                (m[0][2] * m[2][1] - m[0][1] * m[2][2]) / determinant,
+                (m[0][1] * m[1][2] - m[0][2] * m[1][1]) / determinant,
+            ],
+            [
+                (m[1][2] * m[2][0] - m[1][0] * m[2][2]) / determinant,
+                (m[0][0] * m[2][2] - m[0][2] * m[2][0]) / determinant,
+                (m[0][2] * m[1][0] - m[0][0] * m[1][2]) / determinant,
+            ],
+            [
+                (m[1][0] * m[2][1] - m[1][1] * m[2][0]) / determinant,
+                (m[0][1] * m[2][0] - m[0][0] * m[2][1]) / determinant,
+                (m[0][0] * m[1][1] - m[0][1] * m[1][0]) / determinant,
+            ],
+        ])
+    }
+
+    /// Returns the inverse of this matrix or an error if it is singular.
+    pub fn checked_inverse(&self) -> Result<Self, MathError> {
+        let determinant = self.determinant();
+        if determinant == 0f64 || !determinant.is_finite() {
+            Err(MathError::SingularMatrix { determinant })
+        } else {
+            Ok(self.inverse())
+        }
+    }
+}
+
+impl Default for Matrix3 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<[[f64; 3]; 3]> for Matrix3 {
+    fn from(rows: [[f64; 3]; 3]) -> Self {
+        Self(rows)
+    }
+}
+
+impl Index<usize> for Matrix3 {
+    type Output = [f64; 3];
+
+    fn index(&self, row: usize) -> &Self::Output {
+        &self.0[row]
+    }
+}
+
+impl IndexMut<usize> for Matrix3 {
+    fn index_mut(&mut self, row: usize) -> &mut Self::Output {
+        &mut self.0[row]
+    }
+}
+
+impl Index<(usize, usize)> for Matrix3 {
+    type Output = f64;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        &self.0[row][column]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix3 {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        &mut self.0[row][column]
+    }
+}
+
+impl Mul for Matrix3 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut output = [[0f64; 3]; 3];
+        for (row, output_row) in output.iter_mut().enumerate() {
+            for (column, value) in output_row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.0[row][k] * rhs.0[k][column]).sum();
+            }
+        }
+        Self(output)
+    }
+}
+
+impl Mul<VectorN<3>> for Matrix3 {
+    type Output = VectorN<3>;
+
+    fn mul(self, rhs: VectorN<3>) -> Self::Output {
+        let mut output = [0f64; 3];
+        for (row, value) in output.iter_mut().enumerate() {
+            *value = (0..3).map(|k| self.0[row][k] * rhs.0[k]).sum();
+        }
+        VectorN(output)
+    }
+}
+
+/* --- --- --- Matrix4 --- --- --- */
+
+/// A 4x4 matrix stored row by row (`self.0[row][column]`).
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Matrix4(pub [[f64; 4]; 4]);
+
+impl Matrix4 {
+    pub fn from_rows(rows: [[f64; 4]; 4]) -> Self {
+        Self(rows)
+    }
+
+    pub fn zero() -> Self {
+        Self([[0f64; 4]; 4])
+    }
+
+    pub fn identity() -> Self {
+        Self([
+            [1f64, 0f64, 0f64, 0f64],
+            [0f64, 1f64, 0f64, 0f64],
+            [0f64, 0f64, 1f64, 0f64],
+            [0f64, 0f64, 0f64, 1f64],
+        ])
+    }
+
+    pub fn rows(&self) -> [[f64; 4]; 4] {
+        self.0
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut output = [[0f64; 4]; 4];
+        for (row, output_row) in output.iter_mut().enumerate() {
+            for (column, value) in output_row.iter_mut().enumerate() {
+                *value = self.0[column][row];
+            }
         }
+        Self(output)
+    }
+
+    /// Returns the 3x3 matrix left after removing the given row and column.
+    pub fn minor(&self, row: usize, column: usize) -> Matrix3 {
+        let mut output = [[0f64; 3]; 3];
+        for (output_row, source_row) in (0..4).filter(|r| *r != row).enumerate() {
+            for (output_column, source_column) in (0..4).filter(|c| *c != column).enumerate() {
+                output[output_row][output_column] = self.0[source_row][source_column];
+            }
+        }
+        Matrix3(output)
+    }
+
+    pub fn cofactor(&self, row: usize, column: usize) -> f64 {
+        let sign = if (row + column).is_multiple_of(2) {
+            1f64
+        } else {
+            -1f64
+        };
+        sign * self.minor(row, column).determinant()
+    }
+
+    pub fn determinant(&self) -> f64 {
+        (0..4)
+            .map(|column| self.0[0][column] * self.cofactor(0, column))
+            .sum()
+    }
+
+    /// Returns the inverse of this matrix.
+    ///
+    /// The result is not checked, so a singular matrix yields non-finite values.
+    pub fn inverse(&self) -> Self {
+        let determinant = self.determinant();
+        let mut output = [[0f64; 4]; 4];
+        for (row, output_row) in output.iter_mut().enumerate() {
+            for (column, value) in output_row.iter_mut().enumerate() {
+                *value = self.cofactor(column, row) / determinant;
+            }
+        }
+        Self(output)
+    }
+
+    /// Returns the inverse of this matrix or an error if it is singular.
+    pub fn checked_inverse(&self) -> Result<Self, MathError> {
+        let determinant = self.determinant();
+        if determinant == 0f64 || !determinant.is_finite() {
+            Err(MathError::SingularMatrix { determinant })
+        } else {
+            Ok(self.inverse())
+        }
+    }
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<[[f64; 4]; 4]> for Matrix4 {
+    fn from(rows: [[f64; 4]; 4]) -> Self {
+        Self(rows)
+    }
+}
+
+impl Index<usize> for Matrix4 {
+    type Output = [f64; 4];
+
+    fn index(&self, row: usize) -> &Self::Output {
+        &self.0[row]
+    }
+}
+
+impl IndexMut<usize> for Matrix4 {
+    fn index_mut(&mut self, row: usize) -> &mut Self::Output {
+        &mut self.0[row]
+    }
+}
+
+impl Index<(usize, usize)> for Matrix4 {
+    type Output = f64;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        &self.0[row][column]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix4 {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        &mut self.0[row][column]
+    }
+}
+
+impl Mul for Matrix4 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut output = [[0f64; 4]; 4];
+        for (row, output_row) in output.iter_mut().enumerate() {
+            for (column, value) in output_row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.0[row][k] * rhs.0[k][column]).sum();
+            }
+        }
+        Self(output)
+    }
+}
+
+impl Mul<VectorN<4>> for Matrix4 {
+    type Output = VectorN<4>;
+
+    fn mul(self, rhs: VectorN<4>) -> Self::Output {
+        let mut output = [0f64; 4];
+        for (row, value) in output.iter_mut().enumerate() {
+            *value = (0..4).map(|k| self.0[row][k] * rhs.0[k]).sum();
+        }
+        VectorN(output)
+    }
+}
+
+/* --- --- --- VectorN --- --- --- */
+
+/// A column vector with `N` components.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct VectorN<const N: usize>(pub [f64; N]);
+
+impl<const N: usize> VectorN<N> {
+    pub fn zero() -> Self {
+        Self([0f64; N])
+    }
+
+    pub fn dot(&self, other: &VectorN<N>) -> f64 {
+        self.0.iter().zip(other.0.iter()).map(|(a, b)| a * b).sum()
+    }
+
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+impl<const N: usize> Default for VectorN<N> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<const N: usize> From<[f64; N]> for VectorN<N> {
+    fn from(values: [f64; N]) -> Self {
+        Self(values)
+    }
+}
+
+impl<const N: usize> Index<usize> for VectorN<N> {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for VectorN<N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<const N: usize> Add for VectorN<N> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.0
+            .iter_mut()
+            .zip(rhs.0.iter())
+            .for_each(|(a, b)| *a += b);
+        self
+    }
+}
+
+impl<const N: usize> Sub for VectorN<N> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self.0
+            .iter_mut()
+            .zip(rhs.0.iter())
+            .for_each(|(a, b)| *a -= b);
+        self
+    }
+}
+
+impl<const N: usize> Mul<f64> for VectorN<N> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: f64) -> Self::Output {
+        self.0.iter_mut().for_each(|a| *a *= rhs);
+        self
+    }
+}
+
+impl<const N: usize> Serialize for VectorN<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.as_ref().serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for VectorN<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<f64>::deserialize(deserializer)?;
+        let length = values.len();
+        values
+            .try_into()
+            .map(Self)
+            .map_err(|_| D::Error::invalid_length(length, &format!("{} values", N).as_str()))
     }
 }
 
 /* --- --- --- Matrix, Vector Things --- --- --- */
 
 pub fn radians_to_degrees(radians: f64) -> f64 {
-    (radians * 180f64) / std::f64::consts::PI
+    (radians * 180f64) / core::f64::consts::PI
 }
 
 pub fn degrees_to_radians(degree: f64) -> f64 {
-    (degree * std::f64::consts::PI) / 180f64
+    (degree * core::f64::consts::PI) / 180f64
 }
 
+/// Returns `matrix_b * matrix_a`, which is the matrix applying `matrix_a` first and `matrix_b`
+/// afterwards.
 pub fn multiply_matrices_3x3(matrix_a: [[f64; 3]; 3], matrix_b: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
-    [
-        [
-            matrix_a[0][0] * matrix_b[0][0]
-                + matrix_a[1][0] * matrix_b[0][1]
-                + matrix_a[2][0] * matrix_b[0][2],
-            matrix_a[0][1] * matrix_b[0][0]
-                + matrix_a[1][1] * matrix_b[0][1]
-                + matrix_a[2][1] * matrix_b[0][2],
-            matrix_a[0][2] * matrix_b[0][0]
-                + matrix_a[1][2] * matrix_b[0][1]
-                + matrix_a[2][2] * matrix_b[0][2],
-        ],
-        [
-            matrix_a[0][0] * matrix_b[1][0]
-                + matrix_a[1][0] * matrix_b[1][1]
-                + matrix_a[2][0] * matrix_b[1][2],
-            matrix_a[0][1] * matrix_b[1][0]
-                + matrix_a[1][1] * matrix_b[1][1]
-                + matrix_a[2][1] * matrix_b[1][2],
-            matrix_a[0][2] * matrix_b[1][0]
-                + matrix_a[1][2] * matrix_b[1][1]
-                + matrix_a[2][2] * matrix_b[1][2],
-        ],
-        [
-            matrix_a[0][0] * matrix_b[2][0]
-                + matrix_a[1][0] * matrix_b[2][1]
-                + matrix_a[2][0] * matrix_b[2][2],
-            matrix_a[0][1] * matrix_b[2][0]
-                + matrix_a[1][1] * matrix_b[2][1]
-                + matrix_a[2][1] * matrix_b[2][2],
-            matrix_a[0][2] * matrix_b[2][0]
-                + matrix_a[1][2] * matrix_b[2][1]
-                + matrix_a[2][2] * matrix_b[2][2],
-        ],
-    ]
+    (Matrix3(matrix_b) * Matrix3(matrix_a)).0
 }
 
+/// Returns `matrix * vector`.
 pub fn multiply_vector_1x3_and_matrix_3x3(vector: [f64; 3], matrix: [[f64; 3]; 3]) -> [f64; 3] {
-    [
-        vector[0] * matrix[0][0] + vector[1] * matrix[0][1] + vector[2] * matrix[0][2],
-        vector[0] * matrix[1][0] + vector[1] * matrix[1][1] + vector[2] * matrix[1][2],
-        vector[0] * matrix[2][0] + vector[1] * matrix[2][1] + vector[2] * matrix[2][2],
-    ]
+    (Matrix3(matrix) * VectorN(vector)).0
 }
 
 pub fn matrix_3x3_as_matrix_3x2(matrix: [[f64; 3]; 3]) -> [[f64; 3]; 2] {
@@ -897,47 +2075,50 @@ pub fn vector_1x2_as_homogeneous_vector_1x3(vector: [f64; 2]) -> [f64; 3] {
 }
 
 pub fn determinant_of_matrix_3x3(matrix: [[f64; 3]; 3]) -> f64 {
-    matrix[0][0] * matrix[1][1] * matrix[2][2]
-        + matrix[1][0] * matrix[2][1] * matrix[0][2]
-        + matrix[2][0] * matrix[0][1] * matrix[1][2]
-        - matrix[2][0] * matrix[1][1] * matrix[2][2]
-        - matrix[1][0] * matrix[0][1] * matrix[2][2]
-        - matrix[0][0] * matrix[2][1] * matrix[1][2]
+    Matrix3(matrix).determinant()
 }
 
 pub fn inverse_of_matrix_3x3(matrix: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
-    let determinant = determinant_of_matrix_3x3(matrix);
-    [
-        [
-            (matrix[1][1] * matrix[2][2] - matrix[1][2] * matrix[2][1]) / determinant,
-            (matrix[0][2] * matrix[2][1] - matrix[0][1] * matrix[2][2]) / determinant,
-            (matrix[0][1] * matrix[1][2] - matrix[0][2] * matrix[1][1]) / determinant,
-        ],
-        [
-            (matrix[1][2] * matrix[2][0] - matrix[1][0] * matrix[2][2]) / determinant,
-            (matrix[0][0] * matrix[2][2] - matrix[0][2] * matrix[2][0]) / determinant,
-            (matrix[0][2] * matrix[1][0] - matrix[0][0] * matrix[1][2]) / determinant,
-        ],
-        [
-            (matrix[1][0] * matrix[2][1] - matrix[1][1] * matrix[2][0]) / determinant,
-            (matrix[0][1] * matrix[2][0] - matrix[0][0] * matrix[2][1]) / determinant,
-            (matrix[0][0] * matrix[1][1] - matrix[0][1] * matrix[1][0]) / determinant,
-        ],
-    ]
+    Matrix3(matrix).inverse().0
+}
+
+pub fn checked_inverse_of_matrix_3x3(matrix: [[f64; 3]; 3]) -> Result<[[f64; 3]; 3], MathError> {
+    Matrix3(matrix).checked_inverse().map(|inverse| inverse.0)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn component_wise_operations_work() {
+        let a = Vector2D::with(-1f64, 4f64);
+        let b = Vector2D::with(2f64, -3f64);
+        assert_eq!(Vector2D::with(-1f64, -3f64), a.min(&b));
+        assert_eq!(Vector2D::with(2f64, 4f64), a.max(&b));
+        assert_eq!(Vector2D::with(1f64, 4f64), a.abs());
+        assert_eq!(
+            Vector2D::with(0f64, 2f64),
+            a.clamp(&Vector2D::zero(), &Vector2D::with(1f64, 2f64))
+        );
+
+        let start = Position3D::zero();
+        let end = Position3D::with(2f64, -4f64, 8f64);
+        assert_eq!(
+            Position3D::with(0.5f64, -1f64, 2f64),
+            start.lerp(&end, 0.25)
+        );
+        assert_eq!(end, start.lerp(&end, 1f64));
+    }
+
     #[test]
     fn test_radians_to_degrees() {
-        assert_eq!(180f64, radians_to_degrees(std::f64::consts::PI));
+        assert_eq!(180f64, radians_to_degrees(core::f64::consts::PI));
     }
 
     #[test]
     fn test_degrees_to_radians() {
-        assert_eq!(std::f64::consts::PI / 5f64, degrees_to_radians(36f64));
+        assert_eq!(core::f64::consts::PI / 5f64, degrees_to_radians(36f64));
     }
 
     #[test]
@@ -1020,6 +2201,320 @@ mod tests {
         assert_eq!(4f64, determinant_of_matrix_3x3(matrix));
     }
 
+    #[test]
+    fn vector_2d_dot_perpendicular_and_angle_work() {
+        let a = Vector2D::with(1f64, 0f64);
+        let b = Vector2D::with(0f64, 2f64);
+
+        assert_eq!(0f64, a.dot(&b));
+        assert_eq!(Vector2D::with(0f64, 1f64), a.perpendicular());
+        assert_eq!(core::f64::consts::FRAC_PI_2, a.angle_between(&b));
+    }
+
+    #[test]
+    fn vector_2d_projection_and_rejection_work() {
+        let a = Vector2D::with(3f64, 4f64);
+        let b = Vector2D::with(2f64, 0f64);
+
+        assert_eq!(Vector2D::with(3f64, 0f64), a.projection_onto(&b));
+        assert_eq!(Vector2D::with(0f64, 4f64), a.rejection_from(&b));
+    }
+
+    #[test]
+    fn zero_vectors_have_no_angle_and_no_projection() {
+        let a = Vector2D::with(3f64, 4f64);
+        assert_eq!(0f64, a.angle_between(&Vector2D::zero()));
+        assert_eq!(0f64, Vector2D::zero().angle_between(&a));
+        assert_eq!(Vector2D::zero(), a.projection_onto(&Vector2D::zero()));
+        assert_eq!(a, a.rejection_from(&Vector2D::zero()));
+
+        let b = Vector3D::with(1f64, 2f64, 2f64);
+        assert_eq!(0f64, b.angle_between(&Vector3D::zero()));
+        assert_eq!(Vector3D::zero(), b.projection_onto(&Vector3D::zero()));
+        assert_eq!(b, b.rejection_from(&Vector3D::zero()));
+    }
+
+    #[test]
+    fn vector_3d_cross_works() {
+        let x = Vector3D::with(1f64, 0f64, 0f64);
+        let y = Vector3D::with(0f64, 1f64, 0f64);
+
+        assert_eq!(Vector3D::with(0f64, 0f64, 1f64), x.cross(&y));
+        assert_eq!(Vector3D::with(0f64, 0f64, -1f64), y.cross(&x));
+        assert_eq!(0f64, x.cross(&y).dot(&x));
+    }
+
+    #[test]
+    fn aabb_2d_overlap_and_union_work() {
+        let a = AABB2D::with(Position2D::with(0f64, 0f64), Position2D::with(2f64, 2f64));
+        let b = AABB2D::with(Position2D::with(3f64, 3f64), Position2D::with(1f64, 1f64));
+        let c = AABB2D::with(Position2D::with(5f64, 5f64), Position2D::with(6f64, 6f64));
+
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&c));
+        assert_eq!(
+            Some(AABB2D::with(
+                Position2D::with(1f64, 1f64),
+                Position2D::with(2f64, 2f64)
+            )),
+            a.intersection(&b)
+        );
+        assert_eq!(
+            AABB2D::with(Position2D::with(0f64, 0f64), Position2D::with(6f64, 6f64)),
+            a.union(&c)
+        );
+        assert!(a.union(&c).contains_aabb(&b));
+    }
+
+    #[test]
+    fn aabb_2d_intersects_ray_works() {
+        let aabb = AABB2D::with(Position2D::with(1f64, -1f64), Position2D::with(3f64, 1f64));
+
+        assert_eq!(
+            Some(1f64),
+            aabb.intersects_ray(&Position2D::zero(), &Vector2D::with(1f64, 0f64))
+        );
+        assert_eq!(
+            None,
+            aabb.intersects_ray(&Position2D::zero(), &Vector2D::with(-1f64, 0f64))
+        );
+        assert_eq!(
+            None,
+            aabb.intersects_ray(&Position2D::with(0f64, 2f64), &Vector2D::with(1f64, 0f64))
+        );
+        assert_eq!(
+            Some(0f64),
+            aabb.intersects_ray(&Position2D::with(2f64, 0f64), &Vector2D::with(0f64, 1f64))
+        );
+    }
+
+    #[test]
+    fn aabb_3d_contains_and_intersects_ray_work() {
+        let aabb = AABB3D::from_center(Position3D::zero(), Size3D::with(2f64, 2f64, 2f64));
+
+        assert!(aabb.contains(&Position3D::with(1f64, -1f64, 0.5f64)));
+        assert!(!aabb.contains(&Position3D::with(1f64, -1f64, 1.5f64)));
+        assert_eq!(
+            Some(4f64),
+            aabb.intersects_ray(
+                &Position3D::with(0f64, 0f64, -5f64),
+                &Vector3D::with(0f64, 0f64, 1f64)
+            )
+        );
+        assert_eq!(
+            AABB3D::from_center(Position3D::zero(), Size3D::with(4f64, 4f64, 4f64)),
+            aabb.expanded_by(1f64)
+        );
+    }
+
+    #[test]
+    fn line_segment_2d_intersection_works() {
+        let a = LineSegment2D::with(Position2D::with(0f64, 0f64), Position2D::with(2f64, 2f64));
+        let b = LineSegment2D::with(Position2D::with(0f64, 2f64), Position2D::with(2f64, 0f64));
+        let c = LineSegment2D::with(Position2D::with(3f64, 3f64), Position2D::with(4f64, 4f64));
+        let d = LineSegment2D::with(Position2D::with(1f64, 1f64), Position2D::with(4f64, 4f64));
+
+        assert!(a.intersects(&b));
+        assert_eq!(Some(Position2D::with(1f64, 1f64)), a.intersection(&b));
+        assert!(!a.intersects(&c));
+        assert!(a.intersects(&d));
+        assert_eq!(None, a.intersection(&d));
+    }
+
+    #[test]
+    fn circle_2d_intersections_work() {
+        let circle = Circle2D::with(Position2D::zero(), 1f64);
+
+        assert!(circle.intersects_circle(&Circle2D::with(Position2D::with(1.5f64, 0f64), 0.5f64)));
+        assert!(!circle.intersects_circle(&Circle2D::with(Position2D::with(3f64, 0f64), 1f64)));
+        assert!(circle.intersects_line_segment(&LineSegment2D::with(
+            Position2D::with(-2f64, 0.5f64),
+            Position2D::with(2f64, 0.5f64)
+        )));
+        assert!(!circle.intersects_line_segment(&LineSegment2D::with(
+            Position2D::with(-2f64, 1.5f64),
+            Position2D::with(2f64, 1.5f64)
+        )));
+    }
+
+    #[test]
+    fn polygon_2d_contains_works() {
+        let concave = Polygon2D::with(vec![
+            Position2D::with(0f64, 0f64),
+            Position2D::with(4f64, 0f64),
+            Position2D::with(4f64, 4f64),
+            Position2D::with(2f64, 1f64),
+            Position2D::with(0f64, 4f64),
+        ]);
+
+        assert!(concave.contains(&Position2D::with(1f64, 0.5f64)));
+        assert!(!concave.contains(&Position2D::with(2f64, 3f64)));
+        assert!(!concave.contains(&Position2D::with(5f64, 1f64)));
+    }
+
+    #[test]
+    fn polygon_2d_intersects_polygon_works() {
+        let square = Polygon2D::with(vec![
+            Position2D::with(0f64, 0f64),
+            Position2D::with(2f64, 0f64),
+            Position2D::with(2f64, 2f64),
+            Position2D::with(0f64, 2f64),
+        ]);
+        let triangle = Polygon2D::with(vec![
+            Position2D::with(1f64, 1f64),
+            Position2D::with(3f64, 1f64),
+            Position2D::with(3f64, 3f64),
+        ]);
+        let distant_triangle = Polygon2D::with(vec![
+            Position2D::with(3f64, 0f64),
+            Position2D::with(5f64, 0f64),
+            Position2D::with(5f64, 2f64),
+        ]);
+
+        assert!(square.intersects_polygon(&triangle));
+        assert!(!square.intersects_polygon(&distant_triangle));
+    }
+
+    #[test]
+    fn polygon_2d_without_area_intersects_nothing() {
+        let square = Polygon2D::with(vec![
+            Position2D::with(0f64, 0f64),
+            Position2D::with(2f64, 0f64),
+            Position2D::with(2f64, 2f64),
+            Position2D::with(0f64, 2f64),
+        ]);
+        let empty = Polygon2D::with(Vec::new());
+        let segment = Polygon2D::with(vec![
+            Position2D::with(1f64, 1f64),
+            Position2D::with(1.5f64, 1.5f64),
+        ]);
+
+        assert!(!square.intersects_polygon(&empty));
+        assert!(!empty.intersects_polygon(&square));
+        assert!(!empty.intersects_polygon(&empty));
+        assert!(!square.intersects_polygon(&segment));
+    }
+
+    #[test]
+    fn determinant_of_asymmetric_matrix_3x3_works() {
+        let matrix = [[1f64, 2f64, 3f64], [0f64, 1f64, 4f64], [5f64, 6f64, 0f64]];
+
+        assert_eq!(1f64, determinant_of_matrix_3x3(matrix));
+    }
+
+    #[test]
+    fn matrix_3_transpose_and_multiplication_work() {
+        let matrix =
+            Matrix3::from_rows([[1f64, 2f64, 3f64], [4f64, 5f64, 6f64], [7f64, 8f64, 9f64]]);
+
+        assert_eq!(2f64, matrix[(0, 1)]);
+        assert_eq!(4f64, matrix.transpose()[(0, 1)]);
+        assert_eq!(matrix, matrix * Matrix3::identity());
+        assert_eq!(
+            VectorN([14f64, 32f64, 50f64]),
+            matrix * VectorN([1f64, 2f64, 3f64])
+        );
+    }
+
+    #[test]
+    fn matrix_4_determinant_and_inverse_work() {
+        let matrix = Matrix4::from_rows([
+            [2f64, 0f64, 0f64, 1f64],
+            [0f64, 4f64, 0f64, 2f64],
+            [0f64, 0f64, 8f64, 3f64],
+            [0f64, 0f64, 0f64, 1f64],
+        ]);
+
+        assert_eq!(64f64, matrix.determinant());
+        assert_eq!(Matrix4::identity(), matrix * matrix.inverse());
+    }
+
+    #[test]
+    fn vector_n_serde_round_trip_works() {
+        let vector = VectorN([1f64, 2f64, 3f64, 4f64]);
+        let serialized = serde_json::to_string(&vector).unwrap();
+
+        assert_eq!("[1.0,2.0,3.0,4.0]", serialized);
+        assert_eq!(vector, serde_json::from_str(&serialized).unwrap());
+        assert!(serde_json::from_str::<VectorN<3>>(&serialized).is_err());
+    }
+
+    #[test]
+    fn fit_rect_into_works() {
+        let world = Rect2D::with(Position2D::with(-10f64, -5f64), Size2D::with(20f64, 10f64));
+        let viewport = Rect2D::with(Position2D::zero(), Size2D::with(200f64, 200f64));
+
+        let stretched = Transformations2D {
+            transformations: vec![Transformation2D::fit_rect_into(world, viewport, false)],
+        };
+        assert_eq!(
+            Position2D::with(0f64, 0f64),
+            Position2D::with(-10f64, -5f64).transform(&stretched)
+        );
+        assert_eq!(
+            Position2D::with(200f64, 200f64),
+            Position2D::with(10f64, 5f64).transform(&stretched)
+        );
+
+        let kept_aspect = Transformations2D {
+            transformations: vec![Transformation2D::fit_rect_into(world, viewport, true)],
+        };
+        assert_eq!(
+            Position2D::with(0f64, 50f64),
+            Position2D::with(-10f64, -5f64).transform(&kept_aspect)
+        );
+        assert_eq!(
+            Position2D::with(200f64, 150f64),
+            Position2D::with(10f64, 5f64).transform(&kept_aspect)
+        );
+    }
+
+    #[test]
+    fn checked_inverse_of_singular_matrix_3x3_fails() {
+        let matrix = [[1f64, 2f64, 3f64], [2f64, 4f64, 6f64], [0f64, 0f64, 1f64]];
+
+        assert_eq!(
+            Err(MathError::SingularMatrix { determinant: 0f64 }),
+            checked_inverse_of_matrix_3x3(matrix)
+        );
+    }
+
+    #[test]
+    fn transformation_2d_reverse_works() {
+        let transformations = Transformations2D {
+            transformations: vec![
+                Transformation2D::translation(Vector2D::with(1f64, 2f64)),
+                Transformation2D::scale(2f64, 4f64),
+                Transformation2D::composition(
+                    "Composed".to_string(),
+                    vec![
+                        Transformation2D::shear_x(1f64),
+                        Transformation2D::translation(Vector2D::with(-3f64, 0f64)),
+                    ],
+                ),
+            ],
+        };
+        let position = Position2D::with(5f64, 7f64);
+
+        let transformed = position.transform(&transformations);
+        let reversed = transformations.reverse().unwrap();
+        assert_eq!(position, transformed.transform(&reversed));
+    }
+
+    #[test]
+    fn transformation_2d_reverse_of_zero_scale_fails() {
+        assert!(Transformation2D::scale(0f64, 1f64).reverse().is_err());
+        assert!(Transformation2D::composition(
+            "Collapsing".to_string(),
+            vec![
+                Transformation2D::rotation(45f64),
+                Transformation2D::isotropic_scale(0f64)
+            ]
+        )
+        .reverse()
+        .is_err());
+    }
+
     #[test]
     fn inverse_of_matrix_3x3_works() {
         let matrix = [
@@ -1062,15 +2557,15 @@ impl Transformations2D {
             .unwrap_or_else(|| Transformation2D::identity().transformation_matrix())
     }
 
-    pub fn reverse(mut self) -> Self {
+    pub fn reverse(mut self) -> Result<Self, MathError> {
         self.transformations.reverse();
-        Self {
+        Ok(Self {
             transformations: self
                 .transformations
                 .into_iter()
                 .map(|transformation| transformation.reverse())
-                .collect(),
-        }
+                .collect::<Result<_, _>>()?,
+        })
     }
 }
 
@@ -0,0 +1,96 @@
+//! Single step integrators for ordinary differential equations.
+//!
+//! The state can be any type supporting addition and scaling with `f64`, like `f64`, `Vector2D`
+//! or `VectorN`. Derivative functions receive the state and the time.
+
+use core::ops::{Add, Mul};
+
+/// State types the integrators can work with.
+pub trait IntegrableState: Clone + Add<Output = Self> + Mul<f64, Output = Self> {}
+
+impl<S> IntegrableState for S where S: Clone + Add<Output = S> + Mul<f64, Output = S> {}
+
+/// Performs an explicit Euler step from `time` to `time + dt`.
+pub fn euler<S, F>(state: &S, time: f64, dt: f64, derivative: F) -> S
+where
+    S: IntegrableState,
+    F: Fn(&S, f64) -> S,
+{
+    state.clone() + derivative(state, time) * dt
+}
+
+/// Performs a semi-implicit (symplectic) Euler step, updating the velocity first and the position
+/// with the new velocity afterwards.
+///
+/// Keeps the energy of oscillating systems bounded, unlike the explicit Euler step.
+///
+/// ```
+/// use gymnarium_base::math::integrate::semi_implicit_euler;
+///
+/// // A spring pulling towards zero.
+/// let (mut position, mut velocity) = (1.0, 0.0);
+/// for step in 0..1000 {
+///     let (p, v) = semi_implicit_euler(&position, &velocity, step as f64 * 0.01, 0.01, |p, _, _| -p);
+///     position = p;
+///     velocity = v;
+/// }
+/// assert!((position * position + velocity * velocity - 1.0_f64).abs() < 0.02);
+/// ```
+pub fn semi_implicit_euler<S, F>(
+    position: &S,
+    velocity: &S,
+    time: f64,
+    dt: f64,
+    acceleration: F,
+) -> (S, S)
+where
+    S: IntegrableState,
+    F: Fn(&S, &S, f64) -> S,
+{
+    let velocity = velocity.clone() + acceleration(position, velocity, time) * dt;
+    let position = position.clone() + velocity.clone() * dt;
+    (position, velocity)
+}
+
+/// Performs a classic fourth order Runge-Kutta step from `time` to `time + dt`.
+pub fn rk4<S, F>(state: &S, time: f64, dt: f64, derivative: F) -> S
+where
+    S: IntegrableState,
+    F: Fn(&S, f64) -> S,
+{
+    let half = dt / 2f64;
+    let k1 = derivative(state, time);
+    let k2 = derivative(&(state.clone() + k1.clone() * half), time + half);
+    let k3 = derivative(&(state.clone() + k2.clone() * half), time + half);
+    let k4 = derivative(&(state.clone() + k3.clone() * dt), time + dt);
+    state.clone() + (k1 + k2 * 2f64 + k3 * 2f64 + k4) * (dt / 6f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::VectorN;
+
+    #[test]
+    fn exponential_growth_is_approximated() {
+        let mut euler_state = 1f64;
+        let mut rk4_state = 1f64;
+        for step in 0..10 {
+            let time = step as f64 * 0.1;
+            euler_state = euler(&euler_state, time, 0.1, |y, _| *y);
+            rk4_state = rk4(&rk4_state, time, 0.1, |y, _| *y);
+        }
+        let e = core::f64::consts::E;
+        assert!((rk4_state - e).abs() < 1e-5);
+        assert!((euler_state - e).abs() > 0.1);
+    }
+
+    #[test]
+    fn vector_states_are_supported() {
+        // Harmonic oscillator as first order system of position and velocity.
+        let state = VectorN([1f64, 0f64]);
+        let next = rk4(&state, 0f64, 0.1, |s, _| VectorN([s[1], -s[0]]));
+        assert!((next[0] - 0.1f64.cos()).abs() < 1e-6);
+        assert!((next[1] + 0.1f64.sin()).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,164 @@
+//! Conversions between the types of this module and their `nalgebra` counterparts.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use nalgebra::{Point2, Point3, SMatrix, SVector};
+
+use super::{Matrix3, Matrix4, Position2D, Position3D, Transformation2D, Transformations2D};
+use super::{Vector2D, Vector3D, VectorN};
+
+/* --- --- --- Position2D --- --- --- */
+
+impl From<Position2D> for Point2<f64> {
+    fn from(position: Position2D) -> Self {
+        Point2::new(position.x, position.y)
+    }
+}
+
+impl From<Point2<f64>> for Position2D {
+    fn from(point: Point2<f64>) -> Self {
+        Position2D::with(point.x, point.y)
+    }
+}
+
+/* --- --- --- Position3D --- --- --- */
+
+impl From<Position3D> for Point3<f64> {
+    fn from(position: Position3D) -> Self {
+        Point3::new(position.x, position.y, position.z)
+    }
+}
+
+impl From<Point3<f64>> for Position3D {
+    fn from(point: Point3<f64>) -> Self {
+        Position3D::with(point.x, point.y, point.z)
+    }
+}
+
+/* --- --- --- Vector2D --- --- --- */
+
+impl From<Vector2D> for nalgebra::Vector2<f64> {
+    fn from(vector: Vector2D) -> Self {
+        nalgebra::Vector2::new(vector.x, vector.y)
+    }
+}
+
+impl From<nalgebra::Vector2<f64>> for Vector2D {
+    fn from(vector: nalgebra::Vector2<f64>) -> Self {
+        Vector2D::with(vector.x, vector.y)
+    }
+}
+
+/* --- --- --- Vector3D --- --- --- */
+
+impl From<Vector3D> for nalgebra::Vector3<f64> {
+    fn from(vector: Vector3D) -> Self {
+        nalgebra::Vector3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+impl From<nalgebra::Vector3<f64>> for Vector3D {
+    fn from(vector: nalgebra::Vector3<f64>) -> Self {
+        Vector3D::with(vector.x, vector.y, vector.z)
+    }
+}
+
+/* --- --- --- VectorN --- --- --- */
+
+impl<const N: usize> From<VectorN<N>> for SVector<f64, N> {
+    fn from(vector: VectorN<N>) -> Self {
+        SVector::from(vector.0)
+    }
+}
+
+impl<const N: usize> From<SVector<f64, N>> for VectorN<N> {
+    fn from(vector: SVector<f64, N>) -> Self {
+        VectorN(vector.into())
+    }
+}
+
+/* --- --- --- Matrix3 & Matrix4 --- --- --- */
+
+impl From<Matrix3> for nalgebra::Matrix3<f64> {
+    fn from(matrix: Matrix3) -> Self {
+        SMatrix::from_fn(|row, column| matrix.0[row][column])
+    }
+}
+
+impl From<nalgebra::Matrix3<f64>> for Matrix3 {
+    fn from(matrix: nalgebra::Matrix3<f64>) -> Self {
+        let mut rows = [[0f64; 3]; 3];
+        for (row, values) in rows.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = matrix[(row, column)];
+            }
+        }
+        Matrix3(rows)
+    }
+}
+
+impl From<Matrix4> for nalgebra::Matrix4<f64> {
+    fn from(matrix: Matrix4) -> Self {
+        SMatrix::from_fn(|row, column| matrix.0[row][column])
+    }
+}
+
+impl From<nalgebra::Matrix4<f64>> for Matrix4 {
+    fn from(matrix: nalgebra::Matrix4<f64>) -> Self {
+        let mut rows = [[0f64; 4]; 4];
+        for (row, values) in rows.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = matrix[(row, column)];
+            }
+        }
+        Matrix4(rows)
+    }
+}
+
+/* --- --- --- Transformation2D --- --- --- */
+
+impl From<&Transformation2D> for nalgebra::Matrix3<f64> {
+    fn from(transformation: &Transformation2D) -> Self {
+        Matrix3(transformation.transformation_matrix()).into()
+    }
+}
+
+impl From<&Transformations2D> for nalgebra::Matrix3<f64> {
+    fn from(transformations: &Transformations2D) -> Self {
+        Matrix3(transformations.transformation_matrix()).into()
+    }
+}
+
+impl From<nalgebra::Matrix3<f64>> for Transformation2D {
+    fn from(matrix: nalgebra::Matrix3<f64>) -> Self {
+        Transformation2D::custom("nalgebra".to_string(), Matrix3::from(matrix).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_and_vector_round_trips_work() {
+        let position = Position3D::with(1f64, 2f64, 3f64);
+        let vector = Vector2D::with(4f64, 5f64);
+
+        assert_eq!(position, Position3D::from(Point3::from(position)));
+        assert_eq!(vector, Vector2D::from(nalgebra::Vector2::from(vector)));
+    }
+
+    #[test]
+    fn transformation_matrix_keeps_row_order() {
+        let translation = Transformation2D::translation(Vector2D::with(2f64, 3f64));
+        let matrix = nalgebra::Matrix3::from(&translation);
+
+        assert_eq!(2f64, matrix[(0, 2)]);
+        assert_eq!(3f64, matrix[(1, 2)]);
+        assert_eq!(
+            nalgebra::Point2::new(3f64, 4f64),
+            nalgebra::Point2::from_homogeneous(matrix * nalgebra::Vector3::new(1f64, 1f64, 1f64))
+                .unwrap()
+        );
+    }
+}
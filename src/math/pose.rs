@@ -0,0 +1,215 @@
+//! Rigid body transforms in the plane.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use core::f64::consts::PI;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use super::{degrees_to_radians, radians_to_degrees, Position2D, Transformation2D, Vector2D};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::float::Float;
+
+/* --- --- --- Angle --- --- --- */
+
+/// An angle stored in radians, counterclockwise being positive.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+pub struct Angle(f64);
+
+impl Angle {
+    pub fn from_radians(radians: f64) -> Self {
+        Self(radians)
+    }
+
+    pub fn from_degrees(degrees: f64) -> Self {
+        Self(degrees_to_radians(degrees))
+    }
+
+    pub fn zero() -> Self {
+        Self(0f64)
+    }
+
+    pub fn radians(&self) -> f64 {
+        self.0
+    }
+
+    pub fn degrees(&self) -> f64 {
+        radians_to_degrees(self.0)
+    }
+
+    /// Returns the same angle within `(-PI, PI]`.
+    pub fn normalized(&self) -> Self {
+        let wrapped = (self.0 + PI).rem_euclid(2f64 * PI) - PI;
+        if wrapped == -PI {
+            Self(PI)
+        } else {
+            Self(wrapped)
+        }
+    }
+
+    pub fn sin(&self) -> f64 {
+        self.0.sin()
+    }
+
+    pub fn cos(&self) -> f64 {
+        self.0.cos()
+    }
+}
+
+impl Add for Angle {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Angle {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Angle {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+/* --- --- --- Pose2D --- --- --- */
+
+/// Position and orientation of a rigid body in the plane.
+///
+/// A pose maps points from the local frame of the body into the frame the pose is given in, by
+/// rotating around the origin first and translating afterwards.
+///
+/// ```
+/// # use gymnarium_base::math::{Angle, Pose2D, Position2D};
+/// let robot = Pose2D::new(Position2D::with(1.0, 0.0), Angle::from_degrees(90.0));
+/// let sensor = Pose2D::new(Position2D::with(2.0, 0.0), Angle::zero());
+///
+/// let sensor_in_world = robot.compose(&sensor);
+/// assert!(sensor_in_world.position.distance_to(&Position2D::with(1.0, 2.0)) < 1e-9);
+/// assert!(robot.inverse().compose(&sensor_in_world).position.distance_to(&sensor.position) < 1e-9);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pose2D {
+    pub position: Position2D,
+    pub orientation: Angle,
+}
+
+impl Pose2D {
+    pub fn new(position: Position2D, orientation: Angle) -> Self {
+        Self {
+            position,
+            orientation,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(Position2D::zero(), Angle::zero())
+    }
+
+    /// Returns the pose of `other`, given relative to this pose, in the frame of this pose.
+    pub fn compose(&self, other: &Pose2D) -> Self {
+        Self::new(
+            self.transform_point(&other.position),
+            (self.orientation + other.orientation).normalized(),
+        )
+    }
+
+    /// Returns the pose mapping points back into the local frame of this pose.
+    pub fn inverse(&self) -> Self {
+        let orientation = -self.orientation;
+        let rotated = Self::new(Position2D::zero(), orientation)
+            .transform_vector(&Vector2D::with(self.position.x, self.position.y));
+        Self::new(
+            Position2D::with(-rotated.x, -rotated.y),
+            orientation.normalized(),
+        )
+    }
+
+    /// Maps a point from the local frame into the frame of this pose.
+    pub fn transform_point(&self, point: &Position2D) -> Position2D {
+        self.position + self.transform_vector(&Vector2D::with(point.x, point.y))
+    }
+
+    /// Rotates a direction from the local frame into the frame of this pose.
+    pub fn transform_vector(&self, vector: &Vector2D) -> Vector2D {
+        let (sin, cos) = (self.orientation.sin(), self.orientation.cos());
+        Vector2D::with(
+            cos * vector.x - sin * vector.y,
+            sin * vector.x + cos * vector.y,
+        )
+    }
+
+    /// Returns the unit vector the pose is facing, i.e. the local x axis.
+    pub fn heading(&self) -> Vector2D {
+        self.transform_vector(&Vector2D::with(1f64, 0f64))
+    }
+
+    pub fn transformation_matrix(&self) -> [[f64; 3]; 3] {
+        let (sin, cos) = (self.orientation.sin(), self.orientation.cos());
+        [
+            [cos, -sin, self.position.x],
+            [sin, cos, self.position.y],
+            [0f64, 0f64, 1f64],
+        ]
+    }
+}
+
+impl Default for Pose2D {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul for Pose2D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+impl From<Pose2D> for Transformation2D {
+    fn from(pose: Pose2D) -> Self {
+        Transformation2D::custom("Pose2D".to_string(), pose.transformation_matrix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::multiply_vector_1x3_and_matrix_3x3;
+
+    #[test]
+    fn angles_normalize_into_half_open_range() {
+        assert_eq!(
+            Angle::from_radians(PI),
+            Angle::from_radians(-PI).normalized()
+        );
+        assert!((Angle::from_degrees(270.0).normalized().degrees() + 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pose_matches_its_transformation() {
+        let pose = Pose2D::new(Position2D::with(3.0, -1.0), Angle::from_degrees(30.0));
+        let point = Position2D::with(0.5, 2.0);
+        let expected = pose.transform_point(&point);
+        let transformed = multiply_vector_1x3_and_matrix_3x3(
+            [point.x, point.y, 1f64],
+            Transformation2D::from(pose).transformation_matrix(),
+        );
+        assert!(expected.distance_to(&Position2D::with(transformed[0], transformed[1])) < 1e-9);
+
+        let identity = pose * pose.inverse();
+        assert!(identity.position.distance_to(&Position2D::zero()) < 1e-9);
+        assert!(identity.orientation.radians().abs() < 1e-9);
+    }
+}
@@ -0,0 +1,334 @@
+//! Single precision counterparts of the position and vector types for render pipelines and GPU
+//! interop.
+//!
+//! Converting into the `f64` types is lossless, converting from them rounds to the nearest `f32`.
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Position2D, Position3D, Transformation2D, Transformations2D, Vector2D, Vector3D};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::float::Float;
+
+/* --- --- --- Position2Df32 --- --- --- */
+
+/// A position inside the two dimensional space with single precision.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Position2Df32 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Position2Df32 {
+    pub fn with(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn zero() -> Self {
+        Self { x: 0f32, y: 0f32 }
+    }
+
+    pub fn vector_to(&self, other: &Position2Df32) -> Vector2Df32 {
+        Vector2Df32::with(other.x - self.x, other.y - self.y)
+    }
+
+    /// Applies the transformations in double precision and rounds the result.
+    pub fn transform(&self, transformations: &Transformations2D) -> Self {
+        Position2D::from(*self).transform(transformations).into()
+    }
+}
+
+impl Add<Vector2Df32> for Position2Df32 {
+    type Output = Self;
+
+    fn add(self, rhs: Vector2Df32) -> Self::Output {
+        Self::with(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub<Vector2Df32> for Position2Df32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Vector2Df32) -> Self::Output {
+        Self::with(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl From<Position2Df32> for Position2D {
+    fn from(position: Position2Df32) -> Self {
+        Position2D::with(position.x as f64, position.y as f64)
+    }
+}
+
+impl From<Position2D> for Position2Df32 {
+    fn from(position: Position2D) -> Self {
+        Position2Df32::with(position.x as f32, position.y as f32)
+    }
+}
+
+/* --- --- --- Position3Df32 --- --- --- */
+
+/// A position inside the three dimensional space with single precision.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Position3Df32 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Position3Df32 {
+    pub fn with(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: 0f32,
+            y: 0f32,
+            z: 0f32,
+        }
+    }
+
+    pub fn vector_to(&self, other: &Position3Df32) -> Vector3Df32 {
+        Vector3Df32::with(other.x - self.x, other.y - self.y, other.z - self.z)
+    }
+}
+
+impl Add<Vector3Df32> for Position3Df32 {
+    type Output = Self;
+
+    fn add(self, rhs: Vector3Df32) -> Self::Output {
+        Self::with(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub<Vector3Df32> for Position3Df32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Vector3Df32) -> Self::Output {
+        Self::with(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl From<Position3Df32> for Position3D {
+    fn from(position: Position3Df32) -> Self {
+        Position3D::with(position.x as f64, position.y as f64, position.z as f64)
+    }
+}
+
+impl From<Position3D> for Position3Df32 {
+    fn from(position: Position3D) -> Self {
+        Position3Df32::with(position.x as f32, position.y as f32, position.z as f32)
+    }
+}
+
+/* --- --- --- Vector2Df32 --- --- --- */
+
+/// A vector inside the two dimensional space with single precision.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vector2Df32 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector2Df32 {
+    pub fn with(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn zero() -> Self {
+        Self { x: 0f32, y: 0f32 }
+    }
+
+    pub fn length(&self) -> f32 {
+        (self.x.powi(2) + self.y.powi(2)).sqrt()
+    }
+
+    pub fn dot(&self, other: &Vector2Df32) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl Add for Vector2Df32 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::with(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vector2Df32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::with(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f32> for Vector2Df32 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::with(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Div<f32> for Vector2Df32 {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Self::with(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl Neg for Vector2Df32 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::with(-self.x, -self.y)
+    }
+}
+
+impl From<Vector2Df32> for Vector2D {
+    fn from(vector: Vector2Df32) -> Self {
+        Vector2D::with(vector.x as f64, vector.y as f64)
+    }
+}
+
+impl From<Vector2D> for Vector2Df32 {
+    fn from(vector: Vector2D) -> Self {
+        Vector2Df32::with(vector.x as f32, vector.y as f32)
+    }
+}
+
+/* --- --- --- Vector3Df32 --- --- --- */
+
+/// A vector inside the three dimensional space with single precision.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vector3Df32 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vector3Df32 {
+    pub fn with(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            x: 0f32,
+            y: 0f32,
+            z: 0f32,
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        (self.x.powi(2) + self.y.powi(2) + self.z.powi(2)).sqrt()
+    }
+
+    pub fn dot(&self, other: &Vector3Df32) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl Add for Vector3Df32 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::with(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vector3Df32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::with(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f32> for Vector3Df32 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::with(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Div<f32> for Vector3Df32 {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Self::with(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl Neg for Vector3Df32 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::with(-self.x, -self.y, -self.z)
+    }
+}
+
+impl From<Vector3Df32> for Vector3D {
+    fn from(vector: Vector3Df32) -> Self {
+        Vector3D::with(vector.x as f64, vector.y as f64, vector.z as f64)
+    }
+}
+
+impl From<Vector3D> for Vector3Df32 {
+    fn from(vector: Vector3D) -> Self {
+        Vector3Df32::with(vector.x as f32, vector.y as f32, vector.z as f32)
+    }
+}
+
+/* --- --- --- Transformation Matrices --- --- --- */
+
+fn matrix_as_f32(matrix: [[f64; 3]; 3]) -> [[f32; 3]; 3] {
+    matrix.map(|row| row.map(|value| value as f32))
+}
+
+impl Transformation2D {
+    /// Returns the transformation matrix rounded to single precision, e.g. for shader uniforms.
+    pub fn transformation_matrix_f32(&self) -> [[f32; 3]; 3] {
+        matrix_as_f32(self.transformation_matrix())
+    }
+}
+
+impl Transformations2D {
+    /// Returns the combined transformation matrix rounded to single precision.
+    pub fn transformation_matrix_f32(&self) -> [[f32; 3]; 3] {
+        matrix_as_f32(self.transformation_matrix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_to_double_precision_are_lossless() {
+        let position = Position2Df32::with(0.1, -2.5);
+        assert_eq!(position, Position2Df32::from(Position2D::from(position)));
+
+        let vector = Vector3Df32::with(1.0, 2.0, 2.0);
+        assert_eq!(3f64, Vector3D::from(vector).length());
+        assert_eq!(3f32, vector.length());
+
+        let moved = Position2Df32::zero() + Vector2Df32::with(1.0, 2.0) * 2.0;
+        assert_eq!(Position2Df32::with(2.0, 4.0), moved);
+    }
+
+    #[test]
+    fn transformation_matrices_round_to_single_precision() {
+        let translation = Transformation2D::translation(Vector2D::with(2.0, 3.0));
+        assert_eq!(
+            [[1f32, 0f32, 2f32], [0f32, 1f32, 3f32], [0f32, 0f32, 1f32]],
+            translation.transformation_matrix_f32()
+        );
+    }
+}
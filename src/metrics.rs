@@ -0,0 +1,351 @@
+//! Sinks receiving training metrics, e.g. to write them into files or dashboards.
+//!
+//! Anything emitting metrics, like `RecordEpisodeStatistics` or a `MetricsCallback` attached to a
+//! runner, writes into a `MetricsSink`. Additional backends only need to implement the trait.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::callbacks::{Callback, Flow};
+use crate::runner::EpisodeResult;
+
+/* --- --- --- METRICS ERROR --- --- --- */
+
+/// Errors while writing metrics.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MetricsError {
+    Io(String),
+    Serialization(String),
+}
+
+impl std::fmt::Display for MetricsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(message) => write!(f, "Writing metrics failed: {}", message),
+            Self::Serialization(message) => {
+                write!(f, "Serializing metrics failed: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MetricsError {}
+
+impl From<std::io::Error> for MetricsError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for MetricsError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error.to_string())
+    }
+}
+
+/* --- --- --- METRICS SINK --- --- --- */
+
+/// Receives named metrics at a step, like the index of an episode or the number of steps taken.
+///
+/// Emitting never fails, so it fits into any loop. Sinks remember the first error instead and
+/// return it from `flush`.
+pub trait MetricsSink {
+    /// Records a single value.
+    fn scalar(&mut self, tag: &str, value: f64, step: u64);
+
+    /// Records a distribution of values.
+    fn histogram(&mut self, tag: &str, values: &[f64], step: u64);
+
+    /// Writes buffered metrics and returns the first error since the last flush.
+    fn flush(&mut self) -> Result<(), MetricsError>;
+}
+
+impl<S: MetricsSink + ?Sized> MetricsSink for Box<S> {
+    fn scalar(&mut self, tag: &str, value: f64, step: u64) {
+        (**self).scalar(tag, value, step)
+    }
+
+    fn histogram(&mut self, tag: &str, values: &[f64], step: u64) {
+        (**self).histogram(tag, values, step)
+    }
+
+    fn flush(&mut self) -> Result<(), MetricsError> {
+        (**self).flush()
+    }
+}
+
+/// Keeps the first error of several writes.
+fn remember(error: &mut Option<MetricsError>, result: Result<(), MetricsError>) {
+    if let (None, Err(new_error)) = (&error, result) {
+        *error = Some(new_error);
+    }
+}
+
+/* --- --- --- CSV SINK --- --- --- */
+
+/// Writes metrics as CSV rows `kind,tag,step,value`.
+///
+/// Histograms put their values separated by spaces into the value column.
+///
+/// ```
+/// use gymnarium_base::metrics::{CsvSink, MetricsSink};
+///
+/// let mut sink = CsvSink::new(Vec::new());
+/// sink.scalar("episode/return", 1.5, 0);
+/// sink.histogram("actions", &[0.0, 1.0], 0);
+/// sink.flush().unwrap();
+/// assert_eq!(
+///     "kind,tag,step,value\nscalar,episode/return,0,1.5\nhistogram,actions,0,0 1\n",
+///     String::from_utf8(sink.into_inner()).unwrap()
+/// );
+/// ```
+pub struct CsvSink<W: Write> {
+    writer: W,
+    header_written: bool,
+    error: Option<MetricsError>,
+}
+
+impl<W: Write> CsvSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            header_written: false,
+            error: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_row(&mut self, kind: &str, tag: &str, step: u64, value: &str) {
+        let mut result = Ok(());
+        if !self.header_written {
+            result = writeln!(self.writer, "kind,tag,step,value").map_err(MetricsError::from);
+            self.header_written = true;
+        }
+        let result = result.and_then(|_| {
+            writeln!(
+                self.writer,
+                "{},{},{},{}",
+                kind,
+                csv_field(tag),
+                step,
+                value
+            )
+            .map_err(MetricsError::from)
+        });
+        remember(&mut self.error, result);
+    }
+}
+
+impl CsvSink<BufWriter<File>> {
+    /// Creates the file at `path`, replacing an existing one.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, MetricsError> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+/// Quotes a field if it contains characters with a meaning in CSV.
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl<W: Write> MetricsSink for CsvSink<W> {
+    fn scalar(&mut self, tag: &str, value: f64, step: u64) {
+        self.write_row("scalar", tag, step, &value.to_string());
+    }
+
+    fn histogram(&mut self, tag: &str, values: &[f64], step: u64) {
+        let values = values
+            .iter()
+            .map(f64::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.write_row("histogram", tag, step, &values);
+    }
+
+    fn flush(&mut self) -> Result<(), MetricsError> {
+        let result = self.writer.flush().map_err(MetricsError::from);
+        remember(&mut self.error, result);
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+/* --- --- --- JSONL SINK --- --- --- */
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JsonlEntry<'a> {
+    Scalar {
+        tag: &'a str,
+        step: u64,
+        value: f64,
+    },
+    Histogram {
+        tag: &'a str,
+        step: u64,
+        values: &'a [f64],
+    },
+}
+
+/// Writes every metric as a JSON object on its own line.
+///
+/// ```
+/// use gymnarium_base::metrics::{JsonlSink, MetricsSink};
+///
+/// let mut sink = JsonlSink::new(Vec::new());
+/// sink.scalar("loss", 0.25, 3);
+/// sink.flush().unwrap();
+/// assert_eq!(
+///     "{\"kind\":\"scalar\",\"tag\":\"loss\",\"step\":3,\"value\":0.25}\n",
+///     String::from_utf8(sink.into_inner()).unwrap()
+/// );
+/// ```
+pub struct JsonlSink<W: Write> {
+    writer: W,
+    error: Option<MetricsError>,
+}
+
+impl<W: Write> JsonlSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_entry(&mut self, entry: &JsonlEntry<'_>) {
+        let writer = &mut self.writer;
+        let result = serde_json::to_writer(&mut *writer, entry)
+            .map_err(MetricsError::from)
+            .and_then(|_| writeln!(writer).map_err(MetricsError::from));
+        remember(&mut self.error, result);
+    }
+}
+
+impl JsonlSink<BufWriter<File>> {
+    /// Creates the file at `path`, replacing an existing one.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, MetricsError> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> MetricsSink for JsonlSink<W> {
+    fn scalar(&mut self, tag: &str, value: f64, step: u64) {
+        self.write_entry(&JsonlEntry::Scalar { tag, step, value });
+    }
+
+    fn histogram(&mut self, tag: &str, values: &[f64], step: u64) {
+        self.write_entry(&JsonlEntry::Histogram { tag, step, values });
+    }
+
+    fn flush(&mut self) -> Result<(), MetricsError> {
+        let result = self.writer.flush().map_err(MetricsError::from);
+        remember(&mut self.error, result);
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+/* --- --- --- METRICS CALLBACK --- --- --- */
+
+/// Callback emitting `episode/return` and `episode/length` of every episode of a runner, with the
+/// index of the episode as step.
+pub struct MetricsCallback<S> {
+    sink: S,
+}
+
+impl<S: MetricsSink> MetricsCallback<S> {
+    pub fn new(sink: S) -> Self {
+        Self { sink }
+    }
+
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+impl<S: MetricsSink> Callback for MetricsCallback<S> {
+    fn on_episode_end(&mut self, episode: u64, result: &EpisodeResult) -> Flow {
+        self.sink
+            .scalar("episode/return", result.total_reward, episode);
+        self.sink
+            .scalar("episode/length", result.steps as f64, episode);
+        Flow::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::test_agent::ConstantAgent;
+    use crate::runner::EpisodeRunner;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn runner_emits_episode_metrics() {
+        let sink = std::rc::Rc::new(std::cell::RefCell::new(MetricsCallback::new(
+            JsonlSink::new(Vec::new()),
+        )));
+        EpisodeRunner::new()
+            .with_max_steps(3)
+            .with_callback(sink.clone())
+            .run(
+                &mut CountingEnvironment::default(),
+                &mut ConstantAgent::new(1),
+                2,
+                None,
+            )
+            .unwrap();
+
+        let mut callback = sink.borrow_mut();
+        callback.sink_mut().flush().unwrap();
+        let lines = String::from_utf8(callback.sink().get_ref().clone()).unwrap();
+        let entries = lines
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(4, entries.len());
+        assert_eq!(serde_json::json!("episode/length"), entries[3]["tag"]);
+        assert_eq!(serde_json::json!(1), entries[3]["step"]);
+        assert_eq!(serde_json::json!(3.0), entries[3]["value"]);
+    }
+
+    #[test]
+    fn csv_fields_are_quoted() {
+        let mut sink = CsvSink::new(Vec::new());
+        sink.scalar("a,\"b\"", 1f64, 2);
+        sink.flush().unwrap();
+        assert_eq!(
+            "kind,tag,step,value\nscalar,\"a,\"\"b\"\"\",2,1\n",
+            String::from_utf8(sink.into_inner()).unwrap()
+        );
+    }
+}
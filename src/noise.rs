@@ -0,0 +1,235 @@
+//! Random perturbations of continuous actions for exploration.
+//!
+//! Every generator produces one value per element of its action space and owns a random number
+//! generator, which can be seeded for reproducible runs.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::space::{DimensionBoundaries, DimensionValue};
+use crate::{ActionSpace, AgentAction, Seed};
+
+fn rng_from(seed: Option<Seed>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::from_seed(seed.mixed()),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Samples from the standard normal distribution with the Box-Muller transform.
+pub(crate) fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let uniform: f64 = 1f64 - rng.gen::<f64>();
+    let angle: f64 = rng.gen::<f64>() * 2f64 * std::f64::consts::PI;
+    (-2f64 * uniform.ln()).sqrt() * angle.cos()
+}
+
+/* --- --- --- ACTION NOISE --- --- --- */
+
+/// Base trait for noise generators perturbing actions.
+pub trait ActionNoise {
+    /// Returns the action space the noise is sized to.
+    fn action_space(&self) -> &ActionSpace;
+
+    /// Returns the next perturbation with one value per element of the action space.
+    fn sample(&mut self) -> Vec<f64>;
+
+    /// Resets the internal state of correlated noise at the start of an episode.
+    fn reset(&mut self);
+
+    /// Resets the random number generator with the given seed or by entropy.
+    fn reseed(&mut self, seed: Option<Seed>);
+
+    /// Adds the next perturbation to the action and clamps the result into the action space.
+    ///
+    /// Integer values are rounded.
+    fn apply(&mut self, action: &AgentAction) -> AgentAction {
+        let noise = self.sample();
+        let values = action
+            .values()
+            .iter()
+            .zip(self.action_space().get_boundaries())
+            .zip(noise)
+            .map(|((value, boundaries), noise)| match (value, boundaries) {
+                (DimensionValue::Integer(value), DimensionBoundaries::Integer(low, high)) => {
+                    DimensionValue::Integer(
+                        ((*value as f64 + noise).round() as i32).clamp(*low, *high),
+                    )
+                }
+                (DimensionValue::Float(value), DimensionBoundaries::Float(low, high)) => {
+                    DimensionValue::Float((*value + noise as f32).clamp(*low, *high))
+                }
+                (value, _) => *value,
+            })
+            .collect();
+        AgentAction::new(values, action.dimensions().clone())
+            .expect("values of the action fit its own dimensions")
+    }
+}
+
+/* --- --- --- GAUSSIAN NOISE --- --- --- */
+
+/// Uncorrelated noise drawn from a normal distribution for every sample.
+///
+/// ```
+/// use gymnarium_base::noise::{ActionNoise, GaussianNoise};
+/// use gymnarium_base::space::{DimensionBoundaries, Space};
+/// use gymnarium_base::Seed;
+///
+/// let space = Space::simple_all(DimensionBoundaries::from(-1f32..=1f32), 2);
+/// let mut noise = GaussianNoise::new(space.clone(), 0.1, Some(Seed::from(7)));
+/// let action = noise.apply(&gymnarium_base::position![0.0f32, 1.0f32]);
+/// assert!(space.contains(&action).is_ok());
+/// ```
+pub struct GaussianNoise {
+    action_space: ActionSpace,
+    mean: f64,
+    standard_deviation: f64,
+    rng: StdRng,
+}
+
+impl GaussianNoise {
+    /// Creates zero-mean noise with the given standard deviation.
+    pub fn new(action_space: ActionSpace, standard_deviation: f64, seed: Option<Seed>) -> Self {
+        Self {
+            action_space,
+            mean: 0f64,
+            standard_deviation,
+            rng: rng_from(seed),
+        }
+    }
+
+    pub fn with_mean(mut self, mean: f64) -> Self {
+        self.mean = mean;
+        self
+    }
+}
+
+impl ActionNoise for GaussianNoise {
+    fn action_space(&self) -> &ActionSpace {
+        &self.action_space
+    }
+
+    fn sample(&mut self) -> Vec<f64> {
+        (0..self.action_space.get_boundaries().len())
+            .map(|_| self.mean + self.standard_deviation * standard_normal(&mut self.rng))
+            .collect()
+    }
+
+    fn reset(&mut self) {}
+
+    fn reseed(&mut self, seed: Option<Seed>) {
+        self.rng = rng_from(seed);
+    }
+}
+
+/* --- --- --- ORNSTEIN UHLENBECK NOISE --- --- --- */
+
+/// Temporally correlated noise drifting back towards a mean, commonly used for exploration in
+/// continuous control.
+///
+/// Every sample advances the process by `dt` with
+/// `x += theta * (mean - x) * dt + sigma * sqrt(dt) * N(0, 1)`.
+pub struct OrnsteinUhlenbeckNoise {
+    action_space: ActionSpace,
+    mean: f64,
+    theta: f64,
+    sigma: f64,
+    dt: f64,
+    state: Vec<f64>,
+    rng: StdRng,
+}
+
+impl OrnsteinUhlenbeckNoise {
+    /// Creates the process with zero mean, `theta = 0.15` and `dt = 0.01`.
+    pub fn new(action_space: ActionSpace, sigma: f64, seed: Option<Seed>) -> Self {
+        let state = vec![0f64; action_space.get_boundaries().len()];
+        Self {
+            action_space,
+            mean: 0f64,
+            theta: 0.15,
+            sigma,
+            dt: 0.01,
+            state,
+            rng: rng_from(seed),
+        }
+    }
+
+    pub fn with_mean(mut self, mean: f64) -> Self {
+        self.mean = mean;
+        self.reset();
+        self
+    }
+
+    pub fn with_theta(mut self, theta: f64) -> Self {
+        self.theta = theta;
+        self
+    }
+
+    pub fn with_dt(mut self, dt: f64) -> Self {
+        self.dt = dt;
+        self
+    }
+
+    /// Returns the current value of the process without advancing it.
+    pub fn state(&self) -> &[f64] {
+        &self.state
+    }
+}
+
+impl ActionNoise for OrnsteinUhlenbeckNoise {
+    fn action_space(&self) -> &ActionSpace {
+        &self.action_space
+    }
+
+    fn sample(&mut self) -> Vec<f64> {
+        let diffusion = self.sigma * self.dt.sqrt();
+        for value in self.state.iter_mut() {
+            *value += self.theta * (self.mean - *value) * self.dt
+                + diffusion * standard_normal(&mut self.rng);
+        }
+        self.state.clone()
+    }
+
+    fn reset(&mut self) {
+        let mean = self.mean;
+        self.state.iter_mut().for_each(|value| *value = mean);
+    }
+
+    fn reseed(&mut self, seed: Option<Seed>) {
+        self.rng = rng_from(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::Space;
+
+    #[test]
+    fn gaussian_noise_is_reproducible_and_has_expected_spread() {
+        let space = Space::simple_all(DimensionBoundaries::from(-1f32..=1f32), 1000);
+        let mut first = GaussianNoise::new(space.clone(), 2.0, Some(Seed::from(1)));
+        let mut second = GaussianNoise::new(space, 2.0, Some(Seed::from(1)));
+        let samples = first.sample();
+        assert_eq!(samples, second.sample());
+
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / 1000f64;
+        assert!(mean.abs() < 0.3);
+        assert!((variance.sqrt() - 2.0).abs() < 0.3);
+    }
+
+    #[test]
+    fn ornstein_uhlenbeck_noise_is_correlated_and_resets() {
+        let space = Space::simple(vec![DimensionBoundaries::from(-5..=5)]);
+        let mut noise = OrnsteinUhlenbeckNoise::new(space, 0.3, Some(Seed::from(2))).with_mean(1.0);
+        let first = noise.sample()[0];
+        let second = noise.sample()[0];
+        assert!((first - 1.0).abs() < 0.2);
+        assert!((second - first).abs() < 0.2);
+
+        noise.reset();
+        assert_eq!(&[1.0], noise.state());
+        assert_eq!(crate::position![5], noise.apply(&crate::position![5]));
+    }
+}
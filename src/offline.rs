@@ -0,0 +1,255 @@
+//! Datasets of previously collected transitions for offline reinforcement learning.
+//!
+//! Offline agents learn from a `Dataset` without interacting with the environment. An
+//! `InMemoryDataset` is filled from the recordings of `EpisodeRunner::record_episode`.
+//!
+//! ```
+//! use gymnarium_base::offline::{Dataset, InMemoryDataset};
+//! use gymnarium_base::position;
+//! use gymnarium_base::replay::{RecordedStep, Recording};
+//! use gymnarium_base::space::{DimensionBoundaries, Space};
+//! use rand::SeedableRng;
+//!
+//! let mut recording = Recording::new(None, position![0]);
+//! for state in 1..=3 {
+//!     recording.steps.push(RecordedStep {
+//!         action: position![1],
+//!         state: position![state],
+//!         reward: 1.0,
+//!         done: state == 3,
+//!         rng: None,
+//!     });
+//! }
+//!
+//! let mut dataset = InMemoryDataset::new(
+//!     Space::simple(vec![DimensionBoundaries::from(0..=3)]),
+//!     Space::simple(vec![DimensionBoundaries::from(0..=1)]),
+//! );
+//! dataset.add_recording(&recording).unwrap();
+//!
+//! assert_eq!(3, dataset.len());
+//! assert_eq!(1, dataset.metadata().episodes);
+//! assert_eq!(position![3], dataset.get(2).unwrap().next_observation);
+//!
+//! let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+//! assert_eq!(8, dataset.sample_batch(8, &mut rng).len());
+//! ```
+
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::replay::Recording;
+use crate::space::SpaceMismatch;
+use crate::{ActionSpace, AgentAction, EnvironmentState, ObservationSpace};
+
+/* --- --- --- TRANSITION --- --- --- */
+
+/// A single step: `action` was chosen while observing `observation` and led to
+/// `next_observation`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Transition {
+    pub observation: EnvironmentState,
+    pub action: AgentAction,
+    pub reward: f64,
+    pub next_observation: EnvironmentState,
+    /// Whether the episode ended with this step.
+    pub done: bool,
+}
+
+/* --- --- --- DATASET --- --- --- */
+
+/// Describes where the transitions of a dataset come from.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DatasetMetadata {
+    pub observation_space: ObservationSpace,
+    pub action_space: ActionSpace,
+    /// Identifier of the environment the transitions were collected in, if known.
+    pub environment_id: Option<String>,
+    pub episodes: usize,
+}
+
+/// A fixed collection of transitions to learn from.
+pub trait Dataset {
+    fn metadata(&self) -> &DatasetMetadata;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, index: usize) -> Option<Transition>;
+
+    /// Iterates over all transitions in the order they were collected.
+    fn transitions(&self) -> Box<dyn Iterator<Item = Transition> + '_> {
+        Box::new((0..self.len()).filter_map(move |index| self.get(index)))
+    }
+
+    /// Draws `batch_size` transitions uniformly at random with replacement.
+    ///
+    /// Returns an empty batch if the dataset is empty.
+    fn sample_batch(&self, batch_size: usize, rng: &mut dyn RngCore) -> Vec<Transition> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        (0..batch_size)
+            .filter_map(|_| self.get(rng.gen_range(0, self.len())))
+            .collect()
+    }
+}
+
+/* --- --- --- IN MEMORY DATASET --- --- --- */
+
+/// Keeps all transitions in memory, e.g. to be stored as JSON.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct InMemoryDataset {
+    metadata: DatasetMetadata,
+    transitions: Vec<Transition>,
+}
+
+impl InMemoryDataset {
+    pub fn new(observation_space: ObservationSpace, action_space: ActionSpace) -> Self {
+        Self {
+            metadata: DatasetMetadata {
+                observation_space,
+                action_space,
+                environment_id: None,
+                episodes: 0,
+            },
+            transitions: Vec::new(),
+        }
+    }
+
+    pub fn with_environment_id<S: Into<String>>(mut self, environment_id: S) -> Self {
+        self.metadata.environment_id = Some(environment_id.into());
+        self
+    }
+
+    /// Appends a transition, whose observations and action need to lie inside the spaces.
+    ///
+    /// A transition marked as `done` completes an episode.
+    pub fn push(&mut self, transition: Transition) -> Result<(), SpaceMismatch> {
+        self.metadata
+            .observation_space
+            .contains(&transition.observation)?;
+        self.metadata
+            .observation_space
+            .contains(&transition.next_observation)?;
+        self.metadata.action_space.contains(&transition.action)?;
+        if transition.done {
+            self.metadata.episodes += 1;
+        }
+        self.transitions.push(transition);
+        Ok(())
+    }
+
+    /// Appends every step of a recorded episode.
+    ///
+    /// Nothing is added if any of the steps does not fit the spaces. The recording counts as an
+    /// episode even if it was stopped before it was done.
+    pub fn add_recording(&mut self, recording: &Recording) -> Result<(), SpaceMismatch> {
+        let mut observation = &recording.initial_state;
+        let mut transitions = Vec::with_capacity(recording.steps.len());
+        for step in &recording.steps {
+            self.metadata.observation_space.contains(observation)?;
+            self.metadata.observation_space.contains(&step.state)?;
+            self.metadata.action_space.contains(&step.action)?;
+            transitions.push(Transition {
+                observation: observation.clone(),
+                action: step.action.clone(),
+                reward: step.reward,
+                next_observation: step.state.clone(),
+                done: step.done,
+            });
+            observation = &step.state;
+        }
+        if !transitions.is_empty() {
+            self.metadata.episodes += 1;
+            self.transitions.extend(transitions);
+        }
+        Ok(())
+    }
+}
+
+impl Dataset for InMemoryDataset {
+    fn metadata(&self) -> &DatasetMetadata {
+        &self.metadata
+    }
+
+    fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    fn get(&self, index: usize) -> Option<Transition> {
+        self.transitions.get(index).cloned()
+    }
+
+    fn transitions(&self) -> Box<dyn Iterator<Item = Transition> + '_> {
+        Box::new(self.transitions.iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position;
+    use crate::replay::RecordedStep;
+    use crate::space::{DimensionBoundaries, Space};
+    use rand::SeedableRng;
+
+    fn dataset() -> InMemoryDataset {
+        InMemoryDataset::new(
+            Space::simple(vec![DimensionBoundaries::from(0..=5)]),
+            Space::simple(vec![DimensionBoundaries::from(-1..=1)]),
+        )
+    }
+
+    #[test]
+    fn recordings_become_chained_transitions() {
+        let mut recording = Recording::new(None, position![2]);
+        for (action, state) in [(1, 3), (-1, 2)] {
+            recording.steps.push(RecordedStep {
+                action: position![action],
+                state: position![state],
+                reward: 0.5,
+                done: false,
+                rng: None,
+            });
+        }
+        let mut dataset = dataset();
+        dataset.add_recording(&recording).unwrap();
+        let transitions = dataset.transitions().collect::<Vec<_>>();
+        assert_eq!(2, transitions.len());
+        assert_eq!(transitions[0].next_observation, transitions[1].observation);
+        assert_eq!(position![-1], transitions[1].action);
+        assert_eq!(1, dataset.metadata().episodes);
+
+        recording.steps[1].state = position![9];
+        assert!(dataset.add_recording(&recording).is_err());
+        assert_eq!(2, dataset.len());
+    }
+
+    #[test]
+    fn batches_are_sampled_from_the_transitions() {
+        let mut dataset = dataset();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        assert!(dataset.sample_batch(4, &mut rng).is_empty());
+        for state in 0..5 {
+            dataset
+                .push(Transition {
+                    observation: position![state],
+                    action: position![1],
+                    reward: state as f64,
+                    next_observation: position![state + 1],
+                    done: state == 4,
+                })
+                .unwrap();
+        }
+        assert_eq!(1, dataset.metadata().episodes);
+        let batch = dataset.sample_batch(32, &mut rng);
+        assert_eq!(32, batch.len());
+        assert!(batch
+            .iter()
+            .all(|transition| dataset.transitions().any(|known| &known == transition)));
+    }
+}
@@ -0,0 +1,247 @@
+//! Progress of long runs with throughput and estimated time remaining.
+//!
+//! A `ProgressReporter` is a `Callback` for the `EpisodeRunner` and can be handed to the
+//! vectorized environments with `with_progress`. Loops driving environments themselves feed it
+//! with `record_steps` and `record_episodes` instead.
+
+use std::time::{Duration, Instant};
+
+use crate::callbacks::{Callback, Flow, StepInfo};
+use crate::runner::EpisodeResult;
+
+/// Snapshot of the progress of a run, to be rendered however suits the front-end.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Progress {
+    pub steps: u128,
+    pub episodes: u64,
+    pub elapsed: Duration,
+    pub steps_per_second: f64,
+    /// Completed share of the budget between 0 and 1, if a budget is configured.
+    pub fraction: Option<f64>,
+    /// Estimated time until the budget is used up, once progress was made.
+    pub remaining: Option<Duration>,
+}
+
+impl std::fmt::Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} steps, {} episodes, {:.1} steps/s",
+            self.steps, self.episodes, self.steps_per_second
+        )?;
+        if let Some(fraction) = self.fraction {
+            write!(f, ", {:.1}%", fraction * 100f64)?;
+        }
+        if let Some(remaining) = self.remaining {
+            write!(f, ", {}s remaining", remaining.as_secs())?;
+        }
+        Ok(())
+    }
+}
+
+type ReportFn = Box<dyn FnMut(&Progress)>;
+
+/// Tracks steps per second and completed episodes and estimates the time remaining until the
+/// configured number of steps or episodes is reached.
+///
+/// Steps and episodes are counted automatically as a callback of an `EpisodeRunner` or inside a
+/// vectorized environment; anywhere else they have to be recorded by hand.
+///
+/// ```
+/// use gymnarium_base::progress::ProgressReporter;
+///
+/// let mut reporter = ProgressReporter::new().with_step_budget(1000);
+/// reporter.record_steps(250);
+/// reporter.record_episodes(2);
+/// let progress = reporter.progress();
+/// assert_eq!(Some(0.25), progress.fraction);
+/// assert_eq!(2, progress.episodes);
+/// ```
+pub struct ProgressReporter {
+    started: Option<Instant>,
+    steps: u128,
+    episodes: u64,
+    step_budget: Option<u128>,
+    episode_budget: Option<u64>,
+    report: Option<(Duration, ReportFn)>,
+    last_report: Option<Instant>,
+}
+
+impl ProgressReporter {
+    pub fn new() -> Self {
+        Self {
+            started: None,
+            steps: 0,
+            episodes: 0,
+            step_budget: None,
+            episode_budget: None,
+            report: None,
+            last_report: None,
+        }
+    }
+
+    /// Estimates the remaining time against the given total number of steps.
+    pub fn with_step_budget(mut self, steps: u128) -> Self {
+        self.step_budget = Some(steps);
+        self
+    }
+
+    /// Estimates the remaining time against the given total number of episodes.
+    pub fn with_episode_budget(mut self, episodes: u64) -> Self {
+        self.episode_budget = Some(episodes);
+        self
+    }
+
+    /// Hands the progress to `report` whenever the given interval passed since the last report.
+    pub fn with_report<F: FnMut(&Progress) + 'static>(
+        mut self,
+        interval: Duration,
+        report: F,
+    ) -> Self {
+        self.report = Some((interval, Box::new(report)));
+        self
+    }
+
+    /// Adds finished steps, starting the clock with the first call.
+    pub fn record_steps(&mut self, steps: u128) {
+        self.start();
+        self.steps += steps;
+        self.report_if_due();
+    }
+
+    /// Adds finished episodes, starting the clock with the first call.
+    pub fn record_episodes(&mut self, episodes: u64) {
+        self.start();
+        self.episodes += episodes;
+        self.report_if_due();
+    }
+
+    /// Adds a step for every environment stepped at once and an episode for every one which is
+    /// done, starting the clock with the first call.
+    pub fn record_vec_step(&mut self, dones: &[bool]) {
+        self.start();
+        self.steps += dones.len() as u128;
+        self.episodes += dones.iter().filter(|done| **done).count() as u64;
+        self.report_if_due();
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.progress_after(
+            self.started
+                .map_or(Duration::from_secs(0), |started| started.elapsed()),
+        )
+    }
+
+    fn start(&mut self) {
+        if self.started.is_none() {
+            let now = Instant::now();
+            self.started = Some(now);
+            self.last_report = Some(now);
+        }
+    }
+
+    fn report_if_due(&mut self) {
+        let due = match (&self.report, self.last_report) {
+            (Some((interval, _)), Some(last_report)) => last_report.elapsed() >= *interval,
+            _ => false,
+        };
+        if due {
+            let progress = self.progress();
+            self.last_report = Some(Instant::now());
+            if let Some((_, report)) = self.report.as_mut() {
+                report(&progress);
+            }
+        }
+    }
+
+    fn progress_after(&self, elapsed: Duration) -> Progress {
+        let step_fraction = self
+            .step_budget
+            .map(|budget| self.steps as f64 / budget.max(1) as f64);
+        let episode_fraction = self
+            .episode_budget
+            .map(|budget| self.episodes as f64 / budget.max(1) as f64);
+        let fraction = match (step_fraction, episode_fraction) {
+            (Some(steps), Some(episodes)) => Some(steps.max(episodes)),
+            (fraction, None) | (None, fraction) => fraction,
+        }
+        .map(|fraction| fraction.min(1f64));
+
+        let seconds = elapsed.as_secs_f64();
+        Progress {
+            steps: self.steps,
+            episodes: self.episodes,
+            elapsed,
+            steps_per_second: if seconds > 0f64 {
+                self.steps as f64 / seconds
+            } else {
+                0f64
+            },
+            fraction,
+            remaining: fraction
+                .filter(|fraction| *fraction > 0f64)
+                .map(|fraction| Duration::from_secs_f64(seconds * (1f64 - fraction) / fraction)),
+        }
+    }
+}
+
+impl Default for ProgressReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Callback for ProgressReporter {
+    fn on_step(&mut self, _step: &StepInfo<'_>) -> Flow {
+        self.record_steps(1);
+        Flow::Continue
+    }
+
+    fn on_episode_end(&mut self, _episode: u64, _result: &EpisodeResult) -> Flow {
+        self.record_episodes(1);
+        Flow::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::test_agent::ConstantAgent;
+    use crate::runner::EpisodeRunner;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn estimates_remaining_time_from_closest_budget() {
+        let mut reporter = ProgressReporter::new()
+            .with_step_budget(100)
+            .with_episode_budget(4);
+        reporter.record_steps(10);
+        reporter.record_episodes(1);
+
+        let progress = reporter.progress_after(Duration::from_secs(5));
+        assert_eq!(Some(0.25), progress.fraction);
+        assert_eq!(Some(Duration::from_secs(15)), progress.remaining);
+        assert_eq!(2f64, progress.steps_per_second);
+    }
+
+    #[test]
+    fn runner_reports_progress() {
+        let reporter = std::rc::Rc::new(std::cell::RefCell::new(
+            ProgressReporter::new().with_episode_budget(4),
+        ));
+        EpisodeRunner::new()
+            .with_callback(reporter.clone())
+            .run(
+                &mut CountingEnvironment::default(),
+                &mut ConstantAgent::new(1),
+                2,
+                None,
+            )
+            .unwrap();
+        let progress = reporter.borrow().progress();
+        assert_eq!(
+            (10, 2, Some(0.5)),
+            (progress.steps, progress.episodes, progress.fraction)
+        );
+    }
+}
@@ -0,0 +1,762 @@
+//! Builds environments by versioned identifiers like `"CartPole-v2"` instead of by type.
+//!
+//! A `Registry` stores a constructor and the `EnvironmentSpec` of every registered version,
+//! which `Registry::register` takes from `Environment::spec` of an environment built once.
+//! `Registry::register_with_spec` gives the spec instead, without building anything. Environments
+//! implementing `config::FromConfig` are registered with their configuration format and built by
+//! `Registry::make_from_config`, so configuration files choose the environment as well as its
+//! parameters. All environments of a registry share their error, reward, info and data types,
+//! and are built as `RegisteredEnvironment`s hiding their concrete type. Tooling lists the
+//! registered environments by their specs without building any of them.
+//!
+//! ```
+//! use gymnarium_base::registry::{Registry, RegistryError};
+//! use gymnarium_base::spec::EnvironmentSpec;
+//! # use gymnarium_base::space::{DimensionBoundaries, Space};
+//! # use gymnarium_base::*;
+//! #
+//! # #[derive(Default)]
+//! # struct Corridor {
+//! #     length: i32,
+//! # }
+//! #
+//! # impl Environment<std::fmt::Error, f64, (), i32> for Corridor {
+//! #     fn action_space(&self) -> ActionSpace {
+//! #         Space::simple(vec![DimensionBoundaries::from(-1..=1)])
+//! #     }
+//! #     fn observation_space(&self) -> ObservationSpace {
+//! #         Space::simple(vec![DimensionBoundaries::from(0..=self.length)])
+//! #     }
+//! #     fn suggested_episode_steps_count(&self) -> Option<u128> {
+//! #         None
+//! #     }
+//! #     fn reset(&mut self, _: Option<Seed>, _: Option<ResetOptions>) -> Result<EnvironmentState, std::fmt::Error> {
+//! #         Ok(self.state())
+//! #     }
+//! #     fn state(&self) -> EnvironmentState {
+//! #         position![0]
+//! #     }
+//! #     fn step(&mut self, _: &AgentAction) -> Result<(EnvironmentState, f64, bool, ()), std::fmt::Error> {
+//! #         Ok((self.state(), 0.0, true, ()))
+//! #     }
+//! #     fn load(&mut self, length: i32) -> Result<(), std::fmt::Error> {
+//! #         self.length = length;
+//! #         Ok(())
+//! #     }
+//! #     fn store(&self) -> i32 {
+//! #         self.length
+//! #     }
+//! #     fn close(&mut self) -> Result<(), std::fmt::Error> {
+//! #         Ok(())
+//! #     }
+//! #     fn spec(&self) -> EnvironmentSpec {
+//! #         EnvironmentSpec::new("Corridor").with_version(1)
+//! #     }
+//! # }
+//!
+//! let mut registry = Registry::<std::fmt::Error, f64, (), i32>::default();
+//! registry
+//!     .register_with_spec(
+//!         EnvironmentSpec::new("Corridor").deprecated("use Corridor-v1 with a longer corridor"),
+//!         || Ok(Corridor { length: 5 }),
+//!     )
+//!     .unwrap();
+//! // `Corridor::spec` returns the id "Corridor" with version 1.
+//! registry.register(|| Ok(Corridor { length: 10 })).unwrap();
+//!
+//! let ids = registry.specs().map(|spec| spec.versioned_id()).collect::<Vec<_>>();
+//! assert_eq!(vec!["Corridor-v0", "Corridor-v1"], ids);
+//!
+//! let corridor = registry.make_latest("Corridor").unwrap();
+//! assert_eq!(10, corridor.store());
+//! assert!(matches!(
+//!     registry.make("Corridor-v0"),
+//!     Err(RegistryError::Deprecated { .. })
+//! ));
+//! assert!(matches!(
+//!     registry.make("Corridor-v3"),
+//!     Err(RegistryError::NotFound { closest, .. }) if closest == ["Corridor-v1", "Corridor-v0"]
+//! ));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::config::{ConfigError, FromConfig};
+use crate::snapshot::{Snapshot, SnapshotError};
+use crate::space::Format;
+use crate::spec::{split_versioned_id, EnvironmentSpec};
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+type TypeParameters<E, R, I, D> = fn() -> (E, R, I, D);
+
+type Constructor<E, R, I, D> =
+    Box<dyn Fn() -> Result<RegisteredEnvironment<E, R, I, D>, E> + Send + Sync>;
+
+type ConfigConstructor<E, R, I, D> =
+    Box<dyn Fn(&str) -> Result<RegisteredEnvironment<E, R, I, D>, ConfigError<E>> + Send + Sync>;
+
+/* --- --- --- REGISTRY ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum RegistryError<E> {
+    /// No environment is registered under the identifier.
+    ///
+    /// `closest` holds the versioned identifiers registered under the same name, the nearest
+    /// version first.
+    NotFound { id: String, closest: Vec<String> },
+    /// The version is deprecated and only built by `make_deprecated`.
+    Deprecated {
+        id: String,
+        note: String,
+        latest: Option<String>,
+    },
+    /// Another environment is already registered under the versioned identifier.
+    AlreadyRegistered(String),
+    /// The environment was registered with `register_configurable` and is only built by
+    /// `make_from_config`.
+    ConfigurationRequired(String),
+    /// The environment was registered with `register` and cannot be built from a configuration.
+    NotConfigurable(String),
+    /// The configuration is invalid, never holding `ConfigError::Environment`.
+    Config(ConfigError<E>),
+    /// The constructor of the environment failed.
+    Environment(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for RegistryError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound { id, closest } if closest.is_empty() => {
+                write!(f, "Environment \"{}\" is not registered", id)
+            }
+            Self::NotFound { id, closest } => write!(
+                f,
+                "Environment \"{}\" is not registered, did you mean \"{}\"?",
+                id,
+                closest.join("\", \"")
+            ),
+            Self::Deprecated { id, note, .. } => {
+                write!(f, "Environment \"{}\" is deprecated: {}", id, note)
+            }
+            Self::AlreadyRegistered(id) => {
+                write!(f, "Environment \"{}\" is already registered", id)
+            }
+            Self::ConfigurationRequired(id) => {
+                write!(f, "Environment \"{}\" needs a configuration", id)
+            }
+            Self::NotConfigurable(id) => {
+                write!(f, "Environment \"{}\" cannot be configured", id)
+            }
+            Self::Config(error) => write!(f, "Config Error \"{}\" occurred", error),
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for RegistryError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            Self::Config(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/* --- --- --- REGISTRY --- --- --- */
+
+/// Constructors of environments by name and version.
+pub struct Registry<E, R, I, D> {
+    entries: BTreeMap<(String, u32), Entry<E, R, I, D>>,
+}
+
+struct Entry<E, R, I, D> {
+    spec: EnvironmentSpec,
+    constructor: Option<Constructor<E, R, I, D>>,
+    config: Option<(Format, ConfigConstructor<E, R, I, D>)>,
+}
+
+impl<E, R, I, D> Entry<E, R, I, D> {
+    fn build(&self) -> Result<RegisteredEnvironment<E, R, I, D>, RegistryError<E>> {
+        match &self.constructor {
+            Some(constructor) => constructor().map_err(RegistryError::Environment),
+            None => Err(RegistryError::ConfigurationRequired(
+                self.spec.versioned_id(),
+            )),
+        }
+    }
+}
+
+impl<E, R, I, D> Default for Registry<E, R, I, D> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<E, R, I, D> Registry<E, R, I, D>
+where
+    E: std::error::Error + 'static,
+    R: Reward + 'static,
+    I: Debug + 'static,
+    D: Serialize + DeserializeOwned + 'static,
+{
+    /// Registers the constructor under the spec returned by `Environment::spec`, for which one
+    /// environment is built and dropped right away.
+    pub fn register<Env, F>(&mut self, constructor: F) -> Result<(), RegistryError<E>>
+    where
+        Env: Environment<E, R, I, D> + Send + 'static,
+        F: Fn() -> Result<Env, E> + Send + Sync + 'static,
+    {
+        let spec = constructor().map_err(RegistryError::Environment)?.spec();
+        self.register_with_spec(spec, constructor)
+    }
+
+    /// Registers the constructor under the id and version of the given spec, which replaces the
+    /// one of the environment, without building any environment.
+    pub fn register_with_spec<Env, F>(
+        &mut self,
+        spec: EnvironmentSpec,
+        constructor: F,
+    ) -> Result<(), RegistryError<E>>
+    where
+        Env: Environment<E, R, I, D> + Send + 'static,
+        F: Fn() -> Result<Env, E> + Send + Sync + 'static,
+    {
+        let built_spec = spec.clone();
+        let constructor: Constructor<E, R, I, D> = Box::new(move || {
+            constructor().map(|environment| RegisteredEnvironment::new(environment, &built_spec))
+        });
+        self.insert(Entry {
+            spec,
+            constructor: Some(constructor),
+            config: None,
+        })
+    }
+
+    /// Registers an environment built from configurations following `Env::config_format`,
+    /// under the id and version of the spec.
+    pub fn register_configurable<Env>(
+        &mut self,
+        spec: EnvironmentSpec,
+    ) -> Result<(), RegistryError<E>>
+    where
+        Env: FromConfig<Error = E> + Environment<E, R, I, D> + Send + 'static,
+    {
+        let built_spec = spec.clone();
+        let constructor: ConfigConstructor<E, R, I, D> = Box::new(move |source| {
+            Env::from_config_str(source)
+                .map(|environment| RegisteredEnvironment::new(environment, &built_spec))
+        });
+        self.insert(Entry {
+            spec,
+            constructor: None,
+            config: Some((Env::config_format(), constructor)),
+        })
+    }
+
+    fn insert(&mut self, entry: Entry<E, R, I, D>) -> Result<(), RegistryError<E>> {
+        let key = (entry.spec.id.clone(), entry.spec.version);
+        if self.entries.contains_key(&key) {
+            return Err(RegistryError::AlreadyRegistered(entry.spec.versioned_id()));
+        }
+        self.entries.insert(key, entry);
+        Ok(())
+    }
+
+    /// Returns the spec registered under a versioned identifier without building the environment.
+    pub fn spec(&self, versioned_id: &str) -> Result<&EnvironmentSpec, RegistryError<E>> {
+        self.entry(versioned_id).map(|entry| &entry.spec)
+    }
+
+    /// Returns the specs of every registered version, ordered by name and version.
+    pub fn specs(&self) -> impl Iterator<Item = &EnvironmentSpec> + '_ {
+        self.entries.values().map(|entry| &entry.spec)
+    }
+
+    /// Returns the format configurations of the environment registered under a versioned
+    /// identifier have to follow, or `None` if it cannot be configured.
+    pub fn config_format(&self, versioned_id: &str) -> Result<Option<&Format>, RegistryError<E>> {
+        self.entry(versioned_id)
+            .map(|entry| entry.config.as_ref().map(|(format, _)| format))
+    }
+
+    /// Builds the environment registered under a versioned identifier like `"CartPole-v2"`.
+    ///
+    /// Deprecated versions are refused, naming the latest version which is not deprecated.
+    pub fn make(
+        &self,
+        versioned_id: &str,
+    ) -> Result<RegisteredEnvironment<E, R, I, D>, RegistryError<E>> {
+        self.available_entry(versioned_id)?.build()
+    }
+
+    /// Builds the environment registered under a versioned identifier from a configuration, so
+    /// that configuration files choose the environment as well.
+    ///
+    /// The configuration is checked against the format declared at registration like
+    /// `FromConfig::from_config_str` does. Deprecated versions are refused like by `make`.
+    pub fn make_from_config(
+        &self,
+        versioned_id: &str,
+        source: &str,
+    ) -> Result<RegisteredEnvironment<E, R, I, D>, RegistryError<E>> {
+        match &self.available_entry(versioned_id)?.config {
+            Some((_, constructor)) => constructor(source).map_err(|error| match error {
+                ConfigError::Environment(error) => RegistryError::Environment(error),
+                error => RegistryError::Config(error),
+            }),
+            None => Err(RegistryError::NotConfigurable(versioned_id.to_string())),
+        }
+    }
+
+    /// Builds the environment registered under a versioned identifier even if it is deprecated,
+    /// e.g. to reproduce former results.
+    pub fn make_deprecated(
+        &self,
+        versioned_id: &str,
+    ) -> Result<RegisteredEnvironment<E, R, I, D>, RegistryError<E>> {
+        self.entry(versioned_id)?.build()
+    }
+
+    /// Builds the highest version registered under the name which is not deprecated.
+    pub fn make_latest(
+        &self,
+        name: &str,
+    ) -> Result<RegisteredEnvironment<E, R, I, D>, RegistryError<E>> {
+        match self.latest(name) {
+            Some(entry) => entry.build(),
+            None => match self.versions(name).next_back() {
+                Some(entry) => self.make(&entry.spec.versioned_id()),
+                None => Err(RegistryError::NotFound {
+                    id: name.to_string(),
+                    closest: Vec::new(),
+                }),
+            },
+        }
+    }
+
+    fn entry(&self, versioned_id: &str) -> Result<&Entry<E, R, I, D>, RegistryError<E>> {
+        let (name, version) = match split_versioned_id(versioned_id) {
+            Some((name, version)) => (name, Some(version)),
+            None => (versioned_id, None),
+        };
+        if let Some(entry) =
+            version.and_then(|version| self.entries.get(&(name.to_string(), version)))
+        {
+            return Ok(entry);
+        }
+        let mut closest = self
+            .versions(name)
+            .map(|entry| &entry.spec)
+            .collect::<Vec<_>>();
+        // Versions below and above with equal distance list the higher one first.
+        closest.reverse();
+        if let Some(version) = version {
+            closest.sort_by_key(|spec| spec.version.abs_diff(version));
+        }
+        Err(RegistryError::NotFound {
+            id: versioned_id.to_string(),
+            closest: closest.iter().map(|spec| spec.versioned_id()).collect(),
+        })
+    }
+
+    fn available_entry(&self, versioned_id: &str) -> Result<&Entry<E, R, I, D>, RegistryError<E>> {
+        let entry = self.entry(versioned_id)?;
+        match &entry.spec.deprecation {
+            Some(note) => Err(RegistryError::Deprecated {
+                id: versioned_id.to_string(),
+                note: note.clone(),
+                latest: self
+                    .latest(&entry.spec.id)
+                    .map(|latest| latest.spec.versioned_id()),
+            }),
+            None => Ok(entry),
+        }
+    }
+
+    fn latest(&self, name: &str) -> Option<&Entry<E, R, I, D>> {
+        self.versions(name)
+            .rev()
+            .find(|entry| !entry.spec.is_deprecated())
+    }
+
+    fn versions<'a>(
+        &'a self,
+        name: &str,
+    ) -> impl DoubleEndedIterator<Item = &'a Entry<E, R, I, D>> + 'a {
+        self.entries
+            .range((name.to_string(), 0)..=(name.to_string(), u32::MAX))
+            .map(|(_, entry)| entry)
+    }
+}
+
+/* --- --- --- REGISTERED ENVIRONMENT --- --- --- */
+
+/// Environment built by a `Registry`, forwarding to the registered environment.
+///
+/// Snapshots carry the identifier of the registered environment, so that they are compatible with
+/// snapshots of the environment built without registry.
+pub struct RegisteredEnvironment<E, R, I, D> {
+    environment: Box<dyn Erased<E, R, I, D> + Send>,
+    spec: EnvironmentSpec,
+}
+
+impl<E, R, I, D> RegisteredEnvironment<E, R, I, D>
+where
+    E: std::error::Error + 'static,
+    R: Reward + 'static,
+    I: Debug + 'static,
+    D: Serialize + DeserializeOwned + 'static,
+{
+    fn new<Env>(environment: Env, spec: &EnvironmentSpec) -> Self
+    where
+        Env: Environment<E, R, I, D> + Send + 'static,
+    {
+        Self {
+            environment: Box::new(Erasing {
+                environment,
+                phantom: PhantomData,
+            }),
+            spec: spec.clone(),
+        }
+    }
+}
+
+impl<E, R, I, D> Environment<E, R, I, D> for RegisteredEnvironment<E, R, I, D>
+where
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    /// Returns the spec the environment was registered with.
+    fn spec(&self) -> EnvironmentSpec {
+        self.spec.clone()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.environment.reset(seed, options)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        self.environment.step(action)
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn snapshot(&self) -> Snapshot<D> {
+        Snapshot::new(self.environment.environment_id(), self.store())
+    }
+
+    fn load_snapshot(&mut self, snapshot: Snapshot<D>) -> Result<(), SnapshotError<E>> {
+        snapshot.check_compatibility(&self.environment.environment_id())?;
+        self.load(snapshot.into_data())
+            .map_err(SnapshotError::Environment)
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+/// Object safe part of `Environment`, as `environment_id` is no method.
+trait Erased<E, R, I, D> {
+    fn environment_id(&self) -> String;
+    fn action_space(&self) -> ActionSpace;
+    fn observation_space(&self) -> ObservationSpace;
+    fn suggested_episode_steps_count(&self) -> Option<u128>;
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E>;
+    fn state(&self) -> EnvironmentState;
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E>;
+    fn load(&mut self, data: D) -> Result<(), E>;
+    fn store(&self) -> D;
+    fn close(&mut self) -> Result<(), E>;
+}
+
+struct Erasing<Env, E, R, I, D> {
+    environment: Env,
+    phantom: PhantomData<TypeParameters<E, R, I, D>>,
+}
+
+impl<Env, E, R, I, D> Erased<E, R, I, D> for Erasing<Env, E, R, I, D>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn environment_id(&self) -> String {
+        Env::environment_id()
+    }
+
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.environment.reset(seed, options)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        self.environment.step(action)
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::Info;
+
+    fn registry() -> Registry<std::fmt::Error, f64, Info, i32> {
+        let mut registry = Registry::default();
+        for version in [0, 2, 5] {
+            let mut spec = EnvironmentSpec::new("Counting").with_version(version);
+            if version == 5 {
+                spec = spec.deprecated("counts from the wrong start");
+            }
+            registry
+                .register_with_spec(spec, move || {
+                    Ok(CountingEnvironment {
+                        count: version as i32,
+                    })
+                })
+                .unwrap();
+        }
+        registry
+    }
+
+    #[test]
+    fn the_latest_version_which_is_not_deprecated_is_built() {
+        let registry = registry();
+        let environment = registry.make_latest("Counting").unwrap();
+        assert_eq!(2, environment.store());
+        assert_eq!("Counting-v2", environment.spec().versioned_id());
+        assert_eq!(0, registry.make("Counting-v0").unwrap().store());
+        assert_eq!(
+            Err(RegistryError::Deprecated {
+                id: "Counting-v5".to_string(),
+                note: "counts from the wrong start".to_string(),
+                latest: Some("Counting-v2".to_string()),
+            }),
+            registry.make("Counting-v5").map(|_| ())
+        );
+        assert_eq!(5, registry.make_deprecated("Counting-v5").unwrap().store());
+    }
+
+    #[test]
+    fn missing_versions_suggest_the_closest_ones() {
+        let registry = registry();
+        assert_eq!(
+            Err(RegistryError::NotFound {
+                id: "Counting-v1".to_string(),
+                closest: vec![
+                    "Counting-v2".to_string(),
+                    "Counting-v0".to_string(),
+                    "Counting-v5".to_string(),
+                ],
+            }),
+            registry.make("Counting-v1").map(|_| ())
+        );
+        assert_eq!(
+            Err(RegistryError::NotFound {
+                id: "Counting".to_string(),
+                closest: vec![
+                    "Counting-v5".to_string(),
+                    "Counting-v2".to_string(),
+                    "Counting-v0".to_string(),
+                ],
+            }),
+            registry.make("Counting").map(|_| ())
+        );
+        assert_eq!(
+            Err(RegistryError::NotFound {
+                id: "Corridor".to_string(),
+                closest: Vec::new(),
+            }),
+            registry.make_latest("Corridor").map(|_| ())
+        );
+        let mut registry = registry;
+        assert_eq!(
+            Err(RegistryError::AlreadyRegistered("Counting-v2".to_string())),
+            registry.register_with_spec(EnvironmentSpec::new("Counting").with_version(2), || {
+                Ok(CountingEnvironment::default())
+            })
+        );
+    }
+
+    #[test]
+    fn specs_are_taken_from_the_environments() {
+        let mut registry = Registry::<std::fmt::Error, f64, Info, i32>::default();
+        registry
+            .register(|| Ok(CountingEnvironment::default()))
+            .unwrap();
+        let spec = registry.specs().next().unwrap().clone();
+        assert!(spec.id.ends_with("CountingEnvironment"));
+        assert_eq!(Some(10), spec.max_episode_steps);
+        assert_eq!(spec, registry.make(&spec.versioned_id()).unwrap().spec());
+        assert_eq!(
+            Err(RegistryError::Environment(std::fmt::Error)),
+            registry.register(|| -> Result<CountingEnvironment, _> { Err(std::fmt::Error) })
+        );
+    }
+
+    #[test]
+    fn specs_are_listed_without_building_environments() {
+        let mut registry = registry();
+        registry
+            .register_with_spec(
+                EnvironmentSpec::new("Broken").with_max_episode_steps(3),
+                || -> Result<CountingEnvironment, _> { panic!("built while listing") },
+            )
+            .unwrap();
+        assert_eq!(
+            vec!["Broken-v0", "Counting-v0", "Counting-v2", "Counting-v5"],
+            registry
+                .specs()
+                .map(|spec| spec.versioned_id())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(3),
+            registry.spec("Broken-v0").unwrap().max_episode_steps
+        );
+        assert!(registry.spec("Counting-v5").unwrap().is_deprecated());
+        assert!(registry.spec("Broken-v1").is_err());
+    }
+
+    #[test]
+    fn configurable_environments_are_built_from_configurations_by_id() {
+        let mut registry = registry();
+        registry
+            .register_configurable::<CountingEnvironment>(EnvironmentSpec::new("Configured"))
+            .unwrap();
+        assert_eq!(
+            5,
+            registry
+                .config_format("Configured-v0")
+                .unwrap()
+                .unwrap()
+                .len()
+        );
+        assert!(registry.config_format("Counting-v0").unwrap().is_none());
+
+        let environment = registry
+            .make_from_config(
+                "Configured-v0",
+                r#"{"start": 3, "weights": [[1.0, 0.0], [0.0, 1.0]]}"#,
+            )
+            .unwrap();
+        assert_eq!(3, environment.store());
+        assert_eq!("Configured-v0", environment.spec().versioned_id());
+        assert_eq!(
+            Err(RegistryError::Config(ConfigError::MissingKey(
+                "weights".to_string()
+            ))),
+            registry
+                .make_from_config("Configured-v0", r#"{"start": 3}"#)
+                .map(|_| ())
+        );
+        assert_eq!(
+            Err(RegistryError::Environment(std::fmt::Error)),
+            registry
+                .make_from_config(
+                    "Configured-v0",
+                    r#"{"start": 6, "weights": [[1.0, 0.0], [0.0, 1.0]]}"#
+                )
+                .map(|_| ())
+        );
+        assert_eq!(
+            Err(RegistryError::ConfigurationRequired(
+                "Configured-v0".to_string()
+            )),
+            registry.make("Configured-v0").map(|_| ())
+        );
+        assert_eq!(
+            Err(RegistryError::NotConfigurable("Counting-v0".to_string())),
+            registry.make_from_config("Counting-v0", "{}").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn registered_environments_keep_their_snapshot_identifier() {
+        let mut environment = registry().make("Counting-v2").unwrap();
+        let snapshot = environment.snapshot();
+        assert_eq!(
+            snapshot,
+            Environment::<std::fmt::Error, f64, Info, i32>::snapshot(&CountingEnvironment {
+                count: 2
+            })
+        );
+        environment.step(&crate::position![1]).unwrap();
+        environment.load_snapshot(snapshot).unwrap();
+        assert_eq!(2, environment.store());
+    }
+}
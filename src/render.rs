@@ -0,0 +1,359 @@
+//! Rendering episodes into RGB frames for qualitative inspection.
+//!
+//! Environments implementing `Renderable` can be played with `EpisodeRunner::render_to_frames`,
+//! which collects one frame after the reset and one after every step. With the `image` feature
+//! the frames of an episode can be written as an animated GIF or as a sequence of PNG files.
+//! Grid worlds can be drawn into a terminal with `ansi::AnsiRenderer` instead.
+//!
+//! ```
+//! use gymnarium_base::render::{Frame, Renderable};
+//! use gymnarium_base::runner::EpisodeRunner;
+//! # use gymnarium_base::{AgentAction, Environment, EnvironmentState, ResetOptions, Seed};
+//! # use gymnarium_base::space::{DimensionBoundaries, Space};
+//! # struct Lamp(i32);
+//! # impl Environment<std::fmt::Error, f64, (), ()> for Lamp {
+//! #     fn action_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(0..=1)]) }
+//! #     fn observation_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(0..=3)]) }
+//! #     fn suggested_episode_steps_count(&self) -> Option<u128> { None }
+//! #     fn reset(&mut self, _: Option<Seed>, _: Option<ResetOptions>) -> Result<EnvironmentState, std::fmt::Error> { self.0 = 0; Ok(self.state()) }
+//! #     fn state(&self) -> EnvironmentState { gymnarium_base::position![self.0] }
+//! #     fn step(&mut self, _: &AgentAction) -> Result<(EnvironmentState, f64, bool, ()), std::fmt::Error> {
+//! #         self.0 += 1;
+//! #         Ok((self.state(), 1.0, self.0 == 3, ()))
+//! #     }
+//! #     fn load(&mut self, _: ()) -> Result<(), std::fmt::Error> { Ok(()) }
+//! #     fn store(&self) {}
+//! #     fn close(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+//! # }
+//! # struct Switch;
+//! # impl gymnarium_base::Agent<std::fmt::Error, f64, ()> for Switch {
+//! #     fn reseed(&mut self, _: Option<Seed>) -> Result<(), std::fmt::Error> { Ok(()) }
+//! #     fn reset(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+//! #     fn choose_action(&mut self, _: &EnvironmentState) -> Result<AgentAction, std::fmt::Error> { Ok(gymnarium_base::position![1]) }
+//! #     fn process_reward(&mut self, _: &EnvironmentState, _: &AgentAction, _: &EnvironmentState, _: f64, _: bool) -> Result<(), std::fmt::Error> { Ok(()) }
+//! #     fn load(&mut self, _: ()) -> Result<(), std::fmt::Error> { Ok(()) }
+//! #     fn store(&self) {}
+//! #     fn close(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+//! # }
+//!
+//! impl Renderable for Lamp {
+//!     fn render_frame(&self) -> Frame {
+//!         let brightness = (self.0 * 85) as u8;
+//!         Frame::new(2, 1, vec![brightness; 6]).unwrap()
+//!     }
+//! }
+//!
+//! let (result, frames) = EpisodeRunner::new()
+//!     .render_to_frames(&mut Lamp(0), &mut Switch, None)
+//!     .unwrap();
+//!
+//! assert_eq!(3, result.steps);
+//! assert_eq!(4, frames.len());
+//! assert_eq!(&[255; 6][..], frames[3].pixels());
+//! ```
+
+#[cfg(feature = "image")]
+use std::fs::File;
+#[cfg(feature = "image")]
+use std::io::{BufWriter, Write};
+#[cfg(feature = "image")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "image")]
+use std::time::Duration;
+
+use crate::space::{Position, SpaceError, SpaceMismatch};
+
+pub mod ansi;
+
+/* --- --- --- RENDER ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum RenderError {
+    /// The pixels do not fill `width * height` RGB pixels.
+    InvalidFrame {
+        width: u32,
+        height: u32,
+        pixels: usize,
+    },
+    /// Frame `index` differs in size from the first frame.
+    FrameSizeMismatch {
+        index: usize,
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    NoFrames,
+    Io(String),
+    Encoding(String),
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidFrame {
+                width,
+                height,
+                pixels,
+            } => write!(
+                f,
+                "A frame of {}x{} pixels needs {} bytes but got {}",
+                width,
+                height,
+                *width as usize * *height as usize * 3,
+                pixels
+            ),
+            Self::FrameSizeMismatch {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Frame {} has {}x{} pixels instead of {}x{}",
+                index, actual.0, actual.1, expected.0, expected.1
+            ),
+            Self::NoFrames => write!(f, "There are no frames to write"),
+            Self::Io(error) => write!(f, "File could not be accessed ({})", error),
+            Self::Encoding(error) => write!(f, "Encoding Error \"{}\" occurred", error),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<std::io::Error> for RenderError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for RenderError {
+    fn from(error: image::ImageError) -> Self {
+        match error {
+            image::ImageError::IoError(error) => Self::Io(error.to_string()),
+            error => Self::Encoding(error.to_string()),
+        }
+    }
+}
+
+/* --- --- --- FRAME --- --- --- */
+
+/// An RGB image with one byte per channel, stored row by row.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Frame {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, RenderError> {
+        if width as usize * height as usize * 3 == pixels.len() {
+            Ok(Self {
+                width,
+                height,
+                pixels,
+            })
+        } else {
+            Err(RenderError::InvalidFrame {
+                width,
+                height,
+                pixels: pixels.len(),
+            })
+        }
+    }
+
+    /// Reads a frame from a `[height, width, 3]` position of integers in `0..=255`.
+    pub fn from_position(position: &Position) -> Result<Self, SpaceMismatch> {
+        let pixels = position.to_rgb_image_buffer()?;
+        Ok(Self {
+            width: position.dimensions()[1] as u32,
+            height: position.dimensions()[0] as u32,
+            pixels,
+        })
+    }
+
+    pub fn to_position(&self) -> Result<Position, SpaceError> {
+        Position::from_rgb_image_buffer(self.width as usize, self.height as usize, &self.pixels)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+}
+
+/* --- --- --- RENDERABLE --- --- --- */
+
+/// Environments which can draw their current state.
+pub trait Renderable {
+    fn render_frame(&self) -> Frame;
+}
+
+/* --- --- --- EXPORT --- --- --- */
+
+#[cfg(feature = "image")]
+fn check_sizes(frames: &[Frame]) -> Result<(u32, u32), RenderError> {
+    let first = frames.first().ok_or(RenderError::NoFrames)?;
+    let expected = (first.width, first.height);
+    match frames
+        .iter()
+        .position(|frame| (frame.width, frame.height) != expected)
+    {
+        Some(index) => Err(RenderError::FrameSizeMismatch {
+            index,
+            expected,
+            actual: (frames[index].width, frames[index].height),
+        }),
+        None => Ok(expected),
+    }
+}
+
+/// Writes the frames as an endlessly looping animated GIF, showing each for `frame_delay`.
+///
+/// All frames need to have the same size.
+#[cfg(feature = "image")]
+pub fn write_gif<W: Write>(
+    frames: &[Frame],
+    writer: W,
+    frame_delay: Duration,
+) -> Result<(), RenderError> {
+    use image::codecs::gif::{GifEncoder, Repeat};
+
+    check_sizes(frames)?;
+    let mut encoder = GifEncoder::new(writer);
+    encoder.set_repeat(Repeat::Infinite)?;
+    for frame in frames {
+        let rgba = frame
+            .pixels
+            .chunks(3)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+            .collect();
+        let image = image::RgbaImage::from_raw(frame.width, frame.height, rgba)
+            .expect("frame has exactly width * height pixels");
+        encoder.encode_frame(image::Frame::from_parts(
+            image,
+            0,
+            0,
+            image::Delay::from_saturating_duration(frame_delay),
+        ))?;
+    }
+    Ok(())
+}
+
+/// Writes the frames as an animated GIF into the file at `path`, replacing an existing one.
+#[cfg(feature = "image")]
+pub fn save_gif<P: AsRef<Path>>(
+    frames: &[Frame],
+    path: P,
+    frame_delay: Duration,
+) -> Result<(), RenderError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_gif(frames, &mut writer, frame_delay)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes a single frame as PNG.
+#[cfg(feature = "image")]
+pub fn write_png<W: Write>(frame: &Frame, writer: W) -> Result<(), RenderError> {
+    use image::codecs::png::PngEncoder;
+    use image::ImageEncoder;
+
+    PngEncoder::new(writer).write_image(
+        &frame.pixels,
+        frame.width,
+        frame.height,
+        image::ExtendedColorType::Rgb8,
+    )?;
+    Ok(())
+}
+
+/// Writes every frame as `frame-00000.png`, `frame-00001.png` and so on into `directory`,
+/// creating it if necessary, and returns the paths of the files.
+#[cfg(feature = "image")]
+pub fn save_png_sequence<P: AsRef<Path>>(
+    frames: &[Frame],
+    directory: P,
+) -> Result<Vec<PathBuf>, RenderError> {
+    check_sizes(frames)?;
+    std::fs::create_dir_all(directory.as_ref())?;
+    frames
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| {
+            let path = directory.as_ref().join(format!("frame-{:05}.png", index));
+            let mut writer = BufWriter::new(File::create(&path)?);
+            write_png(frame, &mut writer)?;
+            writer.flush()?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::DimensionValue;
+
+    #[cfg(feature = "image")]
+    fn frame(value: u8) -> Frame {
+        Frame::new(2, 2, vec![value; 12]).unwrap()
+    }
+
+    #[test]
+    fn frames_convert_from_and_to_positions() {
+        assert_eq!(
+            Err(RenderError::InvalidFrame {
+                width: 2,
+                height: 1,
+                pixels: 5,
+            }),
+            Frame::new(2, 1, vec![0; 5])
+        );
+        let frame = Frame::new(3, 1, (0..9).collect()).unwrap();
+        let position = frame.to_position().unwrap();
+        assert_eq!(DimensionValue::Integer(7), position.value(&[0, 2, 1]));
+        assert_eq!(Ok(frame), Frame::from_position(&position));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn frames_need_the_same_size() {
+        assert_eq!(Err(RenderError::NoFrames), check_sizes(&[]));
+        assert_eq!(
+            Err(RenderError::FrameSizeMismatch {
+                index: 1,
+                expected: (2, 2),
+                actual: (1, 1),
+            }),
+            check_sizes(&[frame(0), Frame::new(1, 1, vec![0; 3]).unwrap()])
+        );
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn episodes_are_exported_as_gif_and_png() {
+        let frames = vec![frame(0), frame(128), frame(255)];
+        let mut gif = Vec::new();
+        write_gif(&frames, &mut gif, Duration::from_millis(50)).unwrap();
+        assert_eq!(b"GIF89a", &gif[..6]);
+
+        let directory = std::env::temp_dir().join("gymnarium_render_png_sequence");
+        let paths = save_png_sequence(&frames, &directory).unwrap();
+        assert_eq!(3, paths.len());
+        let image = image::open(&paths[1]).unwrap().into_rgb8();
+        assert_eq!(frames[1].pixels(), image.as_raw().as_slice());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,267 @@
+//! Draws grid worlds into terminals supporting ANSI escape codes.
+//!
+//! Every cell of an integer-valued `[rows, columns]` position is drawn as the character and
+//! color the `Palette` assigns to its value.
+//!
+//! ```
+//! use gymnarium_base::render::ansi::{AnsiRenderer, Color, Palette};
+//! use gymnarium_base::space::Position;
+//!
+//! let palette = Palette::new(".#@").with_color(2, Color::Red);
+//! let mut grid = Position::from_i32_values(vec![1, 1, 1, 0, 2, 0], vec![2, 3]).unwrap();
+//!
+//! assert_eq!("###\n.\u{1b}[31m@\u{1b}[0m.\n", palette.render(&grid).unwrap());
+//!
+//! let mut renderer = AnsiRenderer::new(palette);
+//! let first = renderer.redraw(&grid).unwrap();
+//! assert!(first.starts_with("\u{1b}[2J"));
+//!
+//! grid.set_value(&[1, 1], 0.into());
+//! // Only the changed second row is drawn again, leaving the cursor below the grid.
+//! assert_eq!(
+//!     "\u{1b}[2;1H...\u{1b}[K\u{1b}[3;1H",
+//!     renderer.redraw(&grid).unwrap()
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::space::{DimensionBoundaries, DimensionValue, Position, SpaceMismatch};
+
+/* --- --- --- COLOR --- --- --- */
+
+/// Foreground colors of a cell.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    /// 24 bit color, which not every terminal supports.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn write_escape_code(&self, frame: &mut String) {
+        let code = match self {
+            Self::Black => 30,
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Magenta => 35,
+            Self::Cyan => 36,
+            Self::White => 37,
+            Self::Rgb(red, green, blue) => {
+                let _ = write!(frame, "\x1b[38;2;{};{};{}m", red, green, blue);
+                return;
+            }
+        };
+        let _ = write!(frame, "\x1b[{}m", code);
+    }
+}
+
+/* --- --- --- PALETTE --- --- --- */
+
+/// Assigns a character and optionally a color to the values of the cells.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Palette {
+    cells: BTreeMap<i32, (char, Option<Color>)>,
+    unknown: char,
+}
+
+impl Palette {
+    /// Draws the value `0` as the first character of `symbols`, `1` as the second one and so on.
+    ///
+    /// Other values are drawn as `?`.
+    pub fn new(symbols: &str) -> Self {
+        Self {
+            cells: symbols
+                .chars()
+                .enumerate()
+                .map(|(value, symbol)| (value as i32, (symbol, None)))
+                .collect(),
+            unknown: '?',
+        }
+    }
+
+    pub fn with_symbol(mut self, value: i32, symbol: char) -> Self {
+        self.cells.entry(value).or_insert((symbol, None)).0 = symbol;
+        self
+    }
+
+    /// Colors the cells of `value`, which need a symbol from `new` or `with_symbol`.
+    pub fn with_color(mut self, value: i32, color: Color) -> Self {
+        if let Some(cell) = self.cells.get_mut(&value) {
+            cell.1 = Some(color);
+        }
+        self
+    }
+
+    /// Draws values without a symbol as `symbol` instead of `?`.
+    pub fn with_unknown(mut self, symbol: char) -> Self {
+        self.unknown = symbol;
+        self
+    }
+
+    /// Returns every row of the grid as a line ending with a line break.
+    pub fn render(&self, grid: &Position) -> Result<String, SpaceMismatch> {
+        Ok(self
+            .render_rows(grid)?
+            .into_iter()
+            .fold(String::new(), |mut frame, row| {
+                frame.push_str(&row);
+                frame.push('\n');
+                frame
+            }))
+    }
+
+    fn render_rows(&self, grid: &Position) -> Result<Vec<String>, SpaceMismatch> {
+        let columns = match grid.dimensions().as_slice() {
+            [_, columns] => *columns,
+            dimensions => {
+                return Err(SpaceMismatch::ShapeMismatch {
+                    expected: vec![dimensions.first().copied().unwrap_or(0), 0],
+                    actual: dimensions.to_vec(),
+                })
+            }
+        };
+        let values = grid.values();
+        if columns == 0 {
+            return Ok(vec![String::new(); grid.dimensions()[0]]);
+        }
+        values
+            .chunks(columns)
+            .enumerate()
+            .map(|(row, cells)| {
+                let mut line = String::new();
+                for (column, value) in cells.iter().enumerate() {
+                    let value = match value {
+                        DimensionValue::Integer(value) => *value,
+                        DimensionValue::Float(_) => {
+                            return Err(SpaceMismatch::ValueTypeMismatch {
+                                index: row * columns + column,
+                                boundaries: DimensionBoundaries::Integer(i32::MIN, i32::MAX),
+                                value: *value,
+                            })
+                        }
+                    };
+                    match self.cells.get(&value) {
+                        Some((symbol, Some(color))) => {
+                            color.write_escape_code(&mut line);
+                            line.push(*symbol);
+                            line.push_str("\x1b[0m");
+                        }
+                        Some((symbol, None)) => line.push(*symbol),
+                        None => line.push(self.unknown),
+                    }
+                }
+                Ok(line)
+            })
+            .collect()
+    }
+}
+
+/* --- --- --- ANSI RENDERER --- --- --- */
+
+/// Redraws a grid in place, only sending the rows which changed since the last frame.
+///
+/// The first frame, and every frame after the size of the grid changed, clears the screen.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AnsiRenderer {
+    palette: Palette,
+    previous: Option<Vec<String>>,
+}
+
+impl AnsiRenderer {
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            previous: None,
+        }
+    }
+
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Makes the next frame clear the screen and draw every row, e.g. after something else was
+    /// printed.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    /// Returns the escape codes and rows which turn the last frame into the given grid.
+    pub fn redraw(&mut self, grid: &Position) -> Result<String, SpaceMismatch> {
+        let rows = self.palette.render_rows(grid)?;
+        let mut frame = String::new();
+        match &self.previous {
+            Some(previous) if previous.len() == rows.len() => {
+                for (index, (row, previous)) in rows.iter().zip(previous).enumerate() {
+                    if row != previous {
+                        let _ = write!(frame, "\x1b[{};1H{}\x1b[K", index + 1, row);
+                    }
+                }
+            }
+            _ => {
+                frame.push_str("\x1b[2J\x1b[H");
+                for row in &rows {
+                    frame.push_str(row);
+                    frame.push('\n');
+                }
+            }
+        }
+        if !frame.is_empty() {
+            let _ = write!(frame, "\x1b[{};1H", rows.len() + 1);
+        }
+        self.previous = Some(rows);
+        Ok(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palettes_draw_symbols_and_colors() {
+        let palette = Palette::new(" #")
+            .with_symbol(5, 'G')
+            .with_color(5, Color::Rgb(0, 200, 0))
+            .with_unknown('*');
+        let grid = Position::from_i32_values(vec![0, 1, 5, 7], vec![2, 2]).unwrap();
+        assert_eq!(
+            " #\n\u{1b}[38;2;0;200;0mG\u{1b}[0m*\n",
+            palette.render(&grid).unwrap()
+        );
+        assert!(matches!(
+            palette.render(&Position::from_i32_values(vec![0], vec![1]).unwrap()),
+            Err(SpaceMismatch::ShapeMismatch { .. })
+        ));
+        assert!(matches!(
+            palette.render(&Position::from_f32_values(vec![0.0], vec![1, 1]).unwrap()),
+            Err(SpaceMismatch::ValueTypeMismatch { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn unchanged_frames_are_not_redrawn() {
+        let mut renderer = AnsiRenderer::new(Palette::new(".#"));
+        let grid = Position::from_i32_values(vec![0, 1], vec![2, 1]).unwrap();
+        assert_eq!(
+            "\u{1b}[2J\u{1b}[H.\n#\n\u{1b}[3;1H",
+            renderer.redraw(&grid).unwrap()
+        );
+        assert_eq!("", renderer.redraw(&grid).unwrap());
+
+        let taller = Position::from_i32_values(vec![0, 1, 1], vec![3, 1]).unwrap();
+        assert!(renderer.redraw(&taller).unwrap().starts_with("\u{1b}[2J"));
+        renderer.reset();
+        assert!(renderer.redraw(&taller).unwrap().starts_with("\u{1b}[2J"));
+    }
+}
@@ -0,0 +1,448 @@
+//! Deterministic re-execution of recorded episodes for debugging.
+//!
+//! `EpisodeRunner::record_episode` writes down the seed, every action the agent chose and every
+//! outcome of the environment. A `Replayer` feeds the same actions into an environment again, step
+//! by step, and reports the first point where the environment behaves differently.
+//!
+//! ```
+//! # use gymnarium_base::{AgentAction, Environment, EnvironmentState, ResetOptions, Seed};
+//! # use gymnarium_base::replay::{Divergence, RecordedStep, Recording, ReplayError, Replayer};
+//! # use gymnarium_base::space::{DimensionBoundaries, DimensionValue, Space};
+//! # #[derive(Default)]
+//! # struct Walk(i32);
+//! # impl Environment<std::fmt::Error, f64, (), i32> for Walk {
+//! #     fn action_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(-1..=1)]) }
+//! #     fn observation_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(-9..=9)]) }
+//! #     fn suggested_episode_steps_count(&self) -> Option<u128> { None }
+//! #     fn reset(&mut self, _: Option<Seed>, _: Option<ResetOptions>) -> Result<EnvironmentState, std::fmt::Error> { self.0 = 0; Ok(self.state()) }
+//! #     fn state(&self) -> EnvironmentState { gymnarium_base::position![self.0] }
+//! #     fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, f64, bool, ()), std::fmt::Error> {
+//! #         self.0 += action.value(&[0]).expect_integer();
+//! #         Ok((self.state(), 0f64, false, ()))
+//! #     }
+//! #     fn load(&mut self, data: i32) -> Result<(), std::fmt::Error> { self.0 = data; Ok(()) }
+//! #     fn store(&self) -> i32 { self.0 }
+//! #     fn close(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+//! # }
+//! let mut recording = Recording::new(None, gymnarium_base::position![0]);
+//! recording.steps.push(RecordedStep {
+//!     action: gymnarium_base::position![1],
+//!     state: gymnarium_base::position![2],
+//!     reward: 0f64,
+//!     done: false,
+//!     rng: None,
+//! });
+//!
+//! let mut replayer = Replayer::new(recording);
+//! let mut environment = Walk::default();
+//! replayer.reset(&mut environment).unwrap();
+//! match replayer.step(&mut environment) {
+//!     Err(ReplayError::Diverged(divergence)) => match *divergence {
+//!         Divergence::State { step, actual, .. } => {
+//!             assert_eq!(0, step);
+//!             assert_eq!(gymnarium_base::position![1], actual);
+//!         }
+//!         other => panic!("Expected a state divergence, got {}", other),
+//!     },
+//!     other => panic!("Expected a divergence, got {:?}", other),
+//! }
+//! ```
+
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::rng::RngState;
+use crate::{AgentAction, Environment, EnvironmentState, Reward, Seed};
+
+/* --- --- --- RECORDING --- --- --- */
+
+/// Everything happening within one step of a recorded episode.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RecordedStep {
+    pub action: AgentAction,
+    /// State returned by the environment after the action.
+    pub state: EnvironmentState,
+    pub reward: f64,
+    pub done: bool,
+    /// State of the random number generator of the environment after the step, if recorded.
+    pub rng: Option<RngState>,
+}
+
+/// A recorded episode, which can be stored as JSON and replayed later.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    /// Seed the environment was reset with.
+    pub seed: Option<Seed>,
+    pub initial_state: EnvironmentState,
+    pub steps: Vec<RecordedStep>,
+}
+
+impl Recording {
+    pub fn new(seed: Option<Seed>, initial_state: EnvironmentState) -> Self {
+        Self {
+            seed,
+            initial_state,
+            steps: Vec::new(),
+        }
+    }
+}
+
+/* --- --- --- DIVERGENCE --- --- --- */
+
+/// First difference between a replayed episode and its recording.
+///
+/// `step` is the index of the step inside the recording.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Divergence {
+    InitialState {
+        expected: EnvironmentState,
+        actual: EnvironmentState,
+    },
+    State {
+        step: usize,
+        expected: EnvironmentState,
+        actual: EnvironmentState,
+    },
+    Reward {
+        step: usize,
+        expected: f64,
+        actual: f64,
+    },
+    Done {
+        step: usize,
+        expected: bool,
+        actual: bool,
+    },
+    Rng {
+        step: usize,
+        expected: RngState,
+        actual: RngState,
+    },
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InitialState { expected, actual } => write!(
+                f,
+                "Initial state {} differs from the recorded {}",
+                actual, expected
+            ),
+            Self::State {
+                step,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "State {} after step {} differs from the recorded {}",
+                actual, step, expected
+            ),
+            Self::Reward {
+                step,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Reward {} of step {} differs from the recorded {}",
+                actual, step, expected
+            ),
+            Self::Done {
+                step,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Done flag {} of step {} differs from the recorded {}",
+                actual, step, expected
+            ),
+            Self::Rng {
+                step,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Random number generator drew {} words until step {} instead of the recorded {}",
+                actual.draws, step, expected.draws
+            ),
+        }
+    }
+}
+
+/* --- --- --- REPLAY ERROR --- --- --- */
+
+/// Errors while replaying a recording.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ReplayError<E> {
+    Environment(E),
+    Diverged(Box<Divergence>),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ReplayError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::Diverged(divergence) => write!(f, "Replay diverged: {}", divergence),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ReplayError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            Self::Diverged(_) => None,
+        }
+    }
+}
+
+/* --- --- --- REPLAYER --- --- --- */
+
+/// Re-executes a recording inside an environment.
+pub struct Replayer<Env> {
+    recording: Recording,
+    position: usize,
+    rng_probe: Option<fn(&Env) -> RngState>,
+}
+
+impl<Env> Replayer<Env> {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            position: 0,
+            rng_probe: None,
+        }
+    }
+
+    /// Compares the random number generator states recorded with the ones returned by `probe`.
+    pub fn with_rng_probe(mut self, probe: fn(&Env) -> RngState) -> Self {
+        self.rng_probe = Some(probe);
+        self
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Returns the index of the next step to replay.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.recording.steps.len()
+    }
+
+    /// Resets the environment with the recorded seed and checks the initial state.
+    pub fn reset<E, R, I, D>(&mut self, environment: &mut Env) -> Result<(), ReplayError<E>>
+    where
+        Env: Environment<E, R, I, D>,
+        E: std::error::Error,
+        R: Reward,
+        I: Debug,
+        D: Serialize + DeserializeOwned,
+    {
+        self.position = 0;
+        let state = environment
+            .reset(self.recording.seed.clone(), None)
+            .map_err(ReplayError::Environment)?;
+        if state != self.recording.initial_state {
+            return Err(ReplayError::Diverged(Box::new(Divergence::InitialState {
+                expected: self.recording.initial_state.clone(),
+                actual: state,
+            })));
+        }
+        Ok(())
+    }
+
+    /// Executes the next recorded action and checks the outcome against the recording.
+    ///
+    /// Returns the replayed step or `None` if the recording is finished.
+    pub fn step<E, R, I, D>(
+        &mut self,
+        environment: &mut Env,
+    ) -> Result<Option<&RecordedStep>, ReplayError<E>>
+    where
+        Env: Environment<E, R, I, D>,
+        E: std::error::Error,
+        R: Reward,
+        I: Debug,
+        D: Serialize + DeserializeOwned,
+    {
+        let step = self.position;
+        let recorded = match self.recording.steps.get(step) {
+            Some(recorded) => recorded,
+            None => return Ok(None),
+        };
+        let (state, reward, done, _) = environment
+            .step(&recorded.action)
+            .map_err(ReplayError::Environment)?;
+        self.position += 1;
+
+        let divergence = if state != recorded.state {
+            Some(Divergence::State {
+                step,
+                expected: recorded.state.clone(),
+                actual: state,
+            })
+        } else if reward.value().to_bits() != recorded.reward.to_bits() {
+            Some(Divergence::Reward {
+                step,
+                expected: recorded.reward,
+                actual: reward.value(),
+            })
+        } else if done != recorded.done {
+            Some(Divergence::Done {
+                step,
+                expected: recorded.done,
+                actual: done,
+            })
+        } else {
+            match (&recorded.rng, self.rng_probe) {
+                (Some(expected), Some(probe)) if *expected != probe(environment) => {
+                    Some(Divergence::Rng {
+                        step,
+                        expected: expected.clone(),
+                        actual: probe(environment),
+                    })
+                }
+                _ => None,
+            }
+        };
+        match divergence {
+            Some(divergence) => Err(ReplayError::Diverged(Box::new(divergence))),
+            None => Ok(Some(recorded)),
+        }
+    }
+
+    /// Resets the environment and replays the whole recording, returning the number of steps.
+    pub fn replay<E, R, I, D>(&mut self, environment: &mut Env) -> Result<usize, ReplayError<E>>
+    where
+        Env: Environment<E, R, I, D>,
+        E: std::error::Error,
+        R: Reward,
+        I: Debug,
+        D: Serialize + DeserializeOwned,
+    {
+        self.reset(environment)?;
+        while self.step(environment)?.is_some() {}
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::rng::RngProvider;
+    use crate::runner::test_agent::ConstantAgent;
+    use crate::runner::EpisodeRunner;
+    use crate::space::{DimensionBoundaries, DimensionValue, Space};
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::{ActionSpace, ObservationSpace, ResetOptions};
+
+    /// Environment whose reward is drawn from its random number generator.
+    struct LotteryEnvironment {
+        rng: RngProvider,
+        extra_draw: bool,
+    }
+
+    impl Environment<std::fmt::Error, f64, (), ()> for LotteryEnvironment {
+        fn action_space(&self) -> ActionSpace {
+            Space::simple(vec![DimensionBoundaries::from(0..=1)])
+        }
+
+        fn observation_space(&self) -> ObservationSpace {
+            Space::simple(vec![DimensionBoundaries::from(0..=0)])
+        }
+
+        fn suggested_episode_steps_count(&self) -> Option<u128> {
+            None
+        }
+
+        fn reset(
+            &mut self,
+            seed: Option<Seed>,
+            _options: Option<ResetOptions>,
+        ) -> Result<EnvironmentState, std::fmt::Error> {
+            self.rng.reseed(seed);
+            Ok(self.state())
+        }
+
+        fn state(&self) -> EnvironmentState {
+            EnvironmentState::simple(vec![DimensionValue::from(0)])
+        }
+
+        fn step(
+            &mut self,
+            _action: &AgentAction,
+        ) -> Result<(EnvironmentState, f64, bool, ()), std::fmt::Error> {
+            if self.extra_draw {
+                self.rng.gen::<u32>();
+            }
+            Ok((self.state(), (self.rng.gen::<u32>() % 2) as f64, false, ()))
+        }
+
+        fn load(&mut self, _data: ()) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn store(&self) {}
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+
+    fn rng_state(environment: &LotteryEnvironment) -> RngState {
+        environment.rng.state()
+    }
+
+    #[test]
+    fn recorded_episode_replays_without_divergence() {
+        let mut environment = CountingEnvironment::default();
+        let (result, recording) = EpisodeRunner::new()
+            .record_episode(&mut environment, &mut ConstantAgent::new(1), None, None)
+            .unwrap();
+        assert_eq!(5, recording.steps.len());
+        assert_eq!(15f64, result.total_reward);
+
+        let json = serde_json::to_string(&recording).unwrap();
+        let recording: Recording = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            Ok(5),
+            Replayer::new(recording).replay(&mut CountingEnvironment::default())
+        );
+    }
+
+    #[test]
+    fn reports_first_rng_divergence() {
+        let mut environment = LotteryEnvironment {
+            rng: RngProvider::new(Seed::from(1)),
+            extra_draw: false,
+        };
+        let (_, recording) = EpisodeRunner::new()
+            .with_max_steps(4)
+            .record_episode(
+                &mut environment,
+                &mut ConstantAgent::new(0),
+                Some(Seed::from(7)),
+                Some(rng_state),
+            )
+            .unwrap();
+
+        let mut replayer = Replayer::new(recording).with_rng_probe(rng_state);
+        assert_eq!(Ok(4), replayer.replay(&mut environment));
+
+        environment.extra_draw = true;
+        match replayer.replay(&mut environment) {
+            Err(ReplayError::Diverged(divergence)) => assert!(matches!(
+                *divergence,
+                Divergence::Reward { step: 0, .. } | Divergence::Rng { step: 0, .. }
+            )),
+            other => panic!("Expected a divergence, got {:?}", other),
+        }
+    }
+}
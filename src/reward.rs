@@ -0,0 +1,126 @@
+//! Reward types beyond plain numbers.
+
+use std::ops::{Add, AddAssign, Mul};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Reward;
+
+/// Name of the component created when converting a plain number into a `CompositeReward`.
+pub const BASE_COMPONENT: &str = "base";
+
+/// Reward made of named components, whose sum is the value of the reward.
+///
+/// Components keep their insertion order. Adding two composite rewards adds components with the
+/// same name and appends the others.
+///
+/// ```
+/// use gymnarium_base::Reward;
+/// use gymnarium_base::reward::CompositeReward;
+///
+/// let reward = CompositeReward::new()
+///     .with("distance", 1.5)
+///     .with("time", -0.1)
+///     + CompositeReward::new().with("time", -0.1).with("crash", -10.0);
+///
+/// assert_eq!(Some(-0.2), reward.component("time"));
+/// assert_eq!(1.5 - 0.2 - 10.0, reward.value());
+/// assert_eq!(Some(3.0), (reward * 2.0).component("distance"));
+/// ```
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct CompositeReward {
+    components: Vec<(String, f64)>,
+}
+
+impl CompositeReward {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the value to the component with the given name.
+    pub fn with<S: Into<String>>(mut self, name: S, value: f64) -> Self {
+        self.add_component(name, value);
+        self
+    }
+
+    /// Adds the value to the component with the given name, creating it if it does not exist.
+    pub fn add_component<S: Into<String>>(&mut self, name: S, value: f64) {
+        let name = name.into();
+        match self.components.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, component)) => *component += value,
+            None => self.components.push((name, value)),
+        }
+    }
+
+    pub fn component(&self, name: &str) -> Option<f64> {
+        self.components
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn components(&self) -> &[(String, f64)] {
+        &self.components
+    }
+
+    /// Multiplies every component with the given factor.
+    pub fn scaled(mut self, factor: f64) -> Self {
+        self.components
+            .iter_mut()
+            .for_each(|(_, value)| *value *= factor);
+        self
+    }
+}
+
+impl Reward for CompositeReward {
+    fn value(&self) -> f64 {
+        self.components.iter().map(|(_, value)| value).sum()
+    }
+}
+
+impl From<f64> for CompositeReward {
+    fn from(value: f64) -> Self {
+        Self::new().with(BASE_COMPONENT, value)
+    }
+}
+
+impl AddAssign for CompositeReward {
+    fn add_assign(&mut self, other: Self) {
+        for (name, value) in other.components {
+            self.add_component(name, value);
+        }
+    }
+}
+
+impl Add for CompositeReward {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl Mul<f64> for CompositeReward {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        self.scaled(factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_numbers_are_rewards_and_convert_into_components() {
+        assert_eq!(2.5, 2.5f32.value());
+        assert_eq!(-3.0, (-3i32).value());
+
+        let mut reward = CompositeReward::from(1.0);
+        reward += CompositeReward::from(2.0);
+        assert_eq!(vec![(BASE_COMPONENT.to_string(), 3.0)], reward.components());
+        assert_eq!(0.0, CompositeReward::new().value());
+    }
+}
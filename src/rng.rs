@@ -0,0 +1,168 @@
+//! Reproducible random number generation shared by environments and agents.
+
+use rand::{Error, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Seed;
+
+/// Serializable state of a `RngProvider`, e.g. to be part of the data returned by `store`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct RngState {
+    pub seed: Seed,
+    /// Number of 32 bit words drawn since seeding.
+    pub draws: u64,
+}
+
+/// Random number generator created from a `Seed`, which counts its draws to be able to resume at
+/// the exact same point.
+///
+/// The generator is ChaCha20, the one behind `StdRng`, so resuming jumps straight to the position
+/// of the draws instead of repeating them.
+///
+/// ```
+/// use gymnarium_base::rng::RngProvider;
+/// use gymnarium_base::Seed;
+/// use gymnarium_base::rand::Rng;
+///
+/// let mut rng = RngProvider::new(Seed::from(42));
+/// let _: f64 = rng.gen();
+///
+/// let mut resumed = RngProvider::from_state(rng.state());
+/// assert_eq!(rng.gen::<u64>(), resumed.gen::<u64>());
+///
+/// let mut physics = rng.fork("physics");
+/// assert_eq!(physics.gen::<u32>(), rng.fork("physics").gen::<u32>());
+/// ```
+pub struct RngProvider {
+    seed: Seed,
+    rng: ChaCha20Rng,
+    draws: u64,
+}
+
+impl RngProvider {
+    pub fn new(seed: Seed) -> Self {
+        Self {
+            rng: ChaCha20Rng::from_seed(seed.mixed()),
+            seed,
+            draws: 0,
+        }
+    }
+
+    /// Creates the provider with a random seed, which is still recorded for later inspection.
+    pub fn from_entropy() -> Self {
+        Self::new(Seed::new_random())
+    }
+
+    /// Recreates a provider at the exact position described by the state.
+    pub fn from_state(state: RngState) -> Self {
+        let mut provider = Self::new(state.seed);
+        provider.rng.set_word_pos(u128::from(state.draws));
+        provider.draws = state.draws;
+        provider
+    }
+
+    pub fn state(&self) -> RngState {
+        RngState {
+            seed: self.seed.clone(),
+            draws: self.draws,
+        }
+    }
+
+    pub fn seed(&self) -> &Seed {
+        &self.seed
+    }
+
+    /// Returns the number of 32 bit words drawn since seeding.
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Restarts the provider from the given seed or by entropy.
+    pub fn reseed(&mut self, seed: Option<Seed>) {
+        *self = match seed {
+            Some(seed) => Self::new(seed),
+            None => Self::from_entropy(),
+        };
+    }
+
+    /// Returns an independent provider for the named sub-stream.
+    ///
+    /// The sub-stream only depends on the seed and the name, not on the draws so far, so adding
+    /// draws to one sub-stream does not change any other.
+    pub fn fork(&self, name: &str) -> RngProvider {
+        let mut seed_value = self.seed.seed_value.clone();
+        seed_value.push(b'/');
+        seed_value.extend_from_slice(name.as_bytes());
+        Self::new(Seed::from(seed_value))
+    }
+}
+
+impl RngCore for RngProvider {
+    fn next_u32(&mut self) -> u32 {
+        self.draws += 1;
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 2;
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draws += dest.len().div_ceil(4) as u64;
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.draws += dest.len().div_ceil(4) as u64;
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn resuming_from_state_continues_the_same_stream() {
+        let mut rng = RngProvider::new(Seed::from("resume"));
+        let mut bytes = [0u8; 7];
+        rng.fill_bytes(&mut bytes);
+        let _: u64 = rng.gen();
+        let _: f32 = rng.gen();
+        assert_eq!(5, rng.draws());
+
+        let mut resumed = RngProvider::from_state(rng.state());
+        let expected: Vec<u32> = (0..100).map(|_| rng.next_u32()).collect();
+        let actual: Vec<u32> = (0..100).map(|_| resumed.next_u32()).collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn resuming_jumps_to_the_position_of_the_draws() {
+        let seed = Seed::from("jump");
+        let mut std_rng = rand::rngs::StdRng::from_seed(seed.mixed());
+        let mut replayed = RngProvider::new(seed.clone());
+        for draws in 0..200 {
+            let mut resumed = RngProvider::from_state(replayed.state());
+            assert_eq!(draws, resumed.draws());
+            assert_eq!(std_rng.next_u32(), resumed.next_u32());
+            replayed.next_u32();
+        }
+
+        let far = RngProvider::from_state(RngState {
+            seed,
+            draws: u64::MAX,
+        });
+        assert_eq!(u64::MAX, far.draws());
+    }
+
+    #[test]
+    fn forks_differ_by_name() {
+        let rng = RngProvider::new(Seed::from(5));
+        assert_ne!(rng.fork("a").next_u64(), rng.fork("b").next_u64());
+    }
+}
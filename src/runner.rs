@@ -0,0 +1,798 @@
+//! Runs episodes of an agent acting inside an environment.
+//!
+//! `EpisodeRunner` contains the loop every training or evaluation otherwise writes itself:
+//! resetting both sides, letting the agent choose actions and handing it the rewards until the
+//! environment is done or the step limit is reached. `Callback`s hook into this loop.
+
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::budget::{RemainingBudget, TrainingBudget};
+use crate::callbacks::{Callback, Flow, StepInfo};
+use crate::checkpoint::{Checkpoint, CheckpointError};
+use crate::render::{Frame, Renderable};
+use crate::replay::{RecordedStep, Recording};
+use crate::rng::RngState;
+use crate::shaping::RewardShaper;
+use crate::{Agent, Environment, Reward, Seed};
+
+/* --- --- --- RUNNER ERROR --- --- --- */
+
+/// Errors while running an episode.
+#[derive(Debug, PartialEq, Clone)]
+pub enum RunnerError<EE, AE> {
+    Environment(EE),
+    Agent(AE),
+    /// Saving a periodic checkpoint failed.
+    Checkpoint(CheckpointError),
+}
+
+impl<EE: std::fmt::Display, AE: std::fmt::Display> std::fmt::Display for RunnerError<EE, AE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::Agent(error) => write!(f, "Agent Error \"{}\" occurred", error),
+            Self::Checkpoint(error) => write!(f, "Checkpoint Error \"{}\" occurred", error),
+        }
+    }
+}
+
+impl<EE, AE> std::error::Error for RunnerError<EE, AE>
+where
+    EE: std::error::Error + 'static,
+    AE: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            Self::Agent(error) => Some(error),
+            Self::Checkpoint(error) => Some(error),
+        }
+    }
+}
+
+/// Recording of the episode and how to probe the state of the random number generator.
+type StepRecorder<'a, Env> = (&'a mut Recording, Option<fn(&Env) -> RngState>);
+
+/// Frames rendered so far and how to render the next one.
+type FrameCollector<'a, Env> = (&'a mut Vec<Frame>, fn(&Env) -> Frame);
+
+/// Shaper applied to the rewards of an episode, together with the conversion of its output back
+/// into the reward type.
+type RewardShaping<'a, R> = (&'a mut dyn RewardShaper, fn(f64) -> R);
+
+/* --- --- --- EPISODE RESULT --- --- --- */
+
+/// Summary of a finished episode.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct EpisodeResult {
+    pub steps: u128,
+    /// Sum of the values of all rewards.
+    pub total_reward: f64,
+    /// Whether the environment reported the episode as done, instead of the step limit ending it.
+    pub done: bool,
+}
+
+/// Derives the seed of the episode with the given index from the seed of a whole run.
+pub(crate) fn episode_seed(seed: &Seed, episode: u64) -> Seed {
+    let mut seed_value = seed.seed_value.clone();
+    seed_value.extend_from_slice(format!("/episode/{}", episode).as_bytes());
+    Seed::from(seed_value)
+}
+
+/* --- --- --- EPISODE RUNNER --- --- --- */
+
+/// Plays episodes of an agent inside an environment.
+///
+/// Only the environment is reset with the seed given to an episode; seeding the agent is left to
+/// the caller. The runner counts the episodes and steps over its whole lifetime.
+///
+/// With a `TrainingBudget`, the runner ends the current episode and stops once any of its limits
+/// is used up.
+#[derive(Default)]
+pub struct EpisodeRunner {
+    max_steps: Option<u128>,
+    budget: Option<TrainingBudget>,
+    callbacks: Vec<Box<dyn Callback>>,
+    checkpoints: Option<(u64, PathBuf)>,
+    episodes: u64,
+    total_steps: u128,
+    stopped: bool,
+}
+
+impl EpisodeRunner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ends every episode after the given number of steps at the latest.
+    pub fn with_max_steps(mut self, max_steps: u128) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Stops running once the budget is used up, including what was used of it before, e.g. by
+    /// the run a checkpoint was saved from.
+    pub fn with_budget(mut self, budget: TrainingBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Adds a callback, which is called after the ones added before.
+    pub fn with_callback<C: Callback + 'static>(mut self, callback: C) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Adds several callbacks at once.
+    pub fn with_callbacks(mut self, callbacks: Vec<Box<dyn Callback>>) -> Self {
+        self.callbacks.extend(callbacks);
+        self
+    }
+
+    /// Lets `run` save a checkpoint into the directory after every `interval` episodes.
+    ///
+    /// The files are named `checkpoint-<episodes>.json`. Panics if the interval is 0.
+    pub fn with_checkpoints<P: Into<PathBuf>>(mut self, interval: u64, directory: P) -> Self {
+        assert!(interval > 0, "Checkpoint interval must be at least 1");
+        self.checkpoints = Some((interval, directory.into()));
+        self
+    }
+
+    pub fn max_steps(&self) -> Option<u128> {
+        self.max_steps
+    }
+
+    pub fn budget(&self) -> Option<&TrainingBudget> {
+        self.budget.as_ref()
+    }
+
+    pub fn remaining_budget(&self) -> Option<RemainingBudget> {
+        self.budget.as_ref().map(TrainingBudget::remaining)
+    }
+
+    /// Returns whether the budget of this runner is used up.
+    pub fn is_budget_exhausted(&self) -> bool {
+        self.budget
+            .as_ref()
+            .is_some_and(TrainingBudget::is_exhausted)
+    }
+
+    /// Returns the number of episodes played by this runner.
+    pub fn episodes(&self) -> u64 {
+        self.episodes
+    }
+
+    /// Returns the number of steps taken by this runner.
+    pub fn total_steps(&self) -> u128 {
+        self.total_steps
+    }
+
+    /// Returns whether a callback stopped the last run.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Plays a single episode.
+    pub fn run_episode<Env, A, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        seed: Option<Seed>,
+    ) -> Result<EpisodeResult, RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        A: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        self.stopped = false;
+        self.play(environment, agent, seed, None, None, None)
+    }
+
+    /// Plays a single episode, handing the agent the rewards shaped by the given shaper.
+    ///
+    /// Callbacks and the episode result see the shaped rewards as well. The shaped rewards are
+    /// converted into the reward type with `From<f64>`.
+    pub fn run_shaped_episode<Env, A, S, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        shaper: &mut S,
+        seed: Option<Seed>,
+    ) -> Result<EpisodeResult, RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        A: Agent<AE, R, AD>,
+        S: RewardShaper,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward + From<f64>,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        self.stopped = false;
+        let shaping: RewardShaping<'_, R> = (shaper, R::from);
+        self.play(environment, agent, seed, None, None, Some(shaping))
+    }
+
+    /// Plays the given number of episodes unless a callback stops earlier or the budget is used up.
+    ///
+    /// Every episode is reset with its own seed derived from the given one and the index of the
+    /// episode, so continuing a run with the same runner continues the sequence of seeds.
+    pub fn run<Env, A, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        episodes: u64,
+        seed: Option<Seed>,
+    ) -> Result<Vec<EpisodeResult>, RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        A: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        self.run_episodes(environment, agent, episodes, seed, None)
+    }
+
+    /// Plays episodes like `run`, handing the agent the rewards shaped by the given shaper like
+    /// `run_shaped_episode`.
+    pub fn run_shaped<Env, A, S, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        shaper: &mut S,
+        episodes: u64,
+        seed: Option<Seed>,
+    ) -> Result<Vec<EpisodeResult>, RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        A: Agent<AE, R, AD>,
+        S: RewardShaper,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward + From<f64>,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        self.run_episodes(environment, agent, episodes, seed, Some((shaper, R::from)))
+    }
+
+    fn run_episodes<Env, A, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        episodes: u64,
+        seed: Option<Seed>,
+        mut shaping: Option<RewardShaping<'_, R>>,
+    ) -> Result<Vec<EpisodeResult>, RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        A: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        self.stopped = false;
+        let mut results = Vec::new();
+        for _ in 0..episodes {
+            if self.is_budget_exhausted() {
+                break;
+            }
+            let episode_seed = seed.as_ref().map(|seed| episode_seed(seed, self.episodes));
+            let episode_shaping = shaping
+                .as_mut()
+                .map(|(shaper, to_reward)| (&mut **shaper as &mut dyn RewardShaper, *to_reward));
+            results.push(self.play(
+                environment,
+                agent,
+                episode_seed,
+                None,
+                None,
+                episode_shaping,
+            )?);
+            self.save_checkpoint(environment, agent, seed.as_ref())?;
+            if self.stopped {
+                break;
+            }
+        }
+        Ok(results)
+    }
+
+    /// Plays a single episode and records every action and outcome, so the episode can be
+    /// re-executed with a `Replayer`.
+    ///
+    /// If the environment draws its randomness from a `RngProvider`, passing a function returning
+    /// its state records the draws made within each step as well.
+    pub fn record_episode<Env, A, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        seed: Option<Seed>,
+        rng_probe: Option<fn(&Env) -> RngState>,
+    ) -> Result<(EpisodeResult, Recording), RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        A: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        self.stopped = false;
+        let mut recording = Recording::new(seed.clone(), Default::default());
+        let result = self.play(
+            environment,
+            agent,
+            seed,
+            Some((&mut recording, rng_probe)),
+            None,
+            None,
+        )?;
+        Ok((result, recording))
+    }
+
+    /// Plays a single episode and renders a frame after the reset and after every step.
+    pub fn render_to_frames<Env, A, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        seed: Option<Seed>,
+    ) -> Result<(EpisodeResult, Vec<Frame>), RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED> + Renderable,
+        A: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        self.stopped = false;
+        let mut frames = Vec::new();
+        let result = self.play(
+            environment,
+            agent,
+            seed,
+            None,
+            Some((&mut frames, Env::render_frame)),
+            None,
+        )?;
+        Ok((result, frames))
+    }
+
+    fn notify<F: FnMut(&mut dyn Callback) -> Flow>(&mut self, mut event: F) {
+        let flow = self
+            .callbacks
+            .iter_mut()
+            .fold(Flow::Continue, |flow, callback| {
+                flow.and(event(callback.as_mut()))
+            });
+        if flow == Flow::Stop {
+            self.stopped = true;
+        }
+    }
+
+    fn save_checkpoint<Env, A, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &Env,
+        agent: &A,
+        seed: Option<&Seed>,
+    ) -> Result<(), RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        A: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        let path = match &self.checkpoints {
+            Some((interval, directory)) if self.episodes.is_multiple_of(*interval) => {
+                directory.join(format!("checkpoint-{}.json", self.episodes))
+            }
+            _ => return Ok(()),
+        };
+        let mut checkpoint = Checkpoint::capture(environment, agent).with_steps(
+            self.episodes,
+            u64::try_from(self.total_steps).unwrap_or(u64::MAX),
+        );
+        if let Some(seed) = seed {
+            checkpoint = checkpoint.with_seed(seed.clone());
+        }
+        if let Some(budget) = self.budget {
+            checkpoint = checkpoint.with_budget(budget);
+        }
+        checkpoint.save(&path).map_err(RunnerError::Checkpoint)?;
+
+        let episodes = self.episodes;
+        for callback in self.callbacks.iter_mut() {
+            callback.on_checkpoint(episodes, &path);
+        }
+        Ok(())
+    }
+
+    /// Adds the time since `last_tick` to the budget.
+    fn consume_time(&mut self, last_tick: &mut Instant) {
+        let now = Instant::now();
+        if let Some(budget) = self.budget.as_mut() {
+            budget.consume_time(now.duration_since(*last_tick));
+        }
+        *last_tick = now;
+    }
+
+    fn play<Env, A, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        seed: Option<Seed>,
+        mut recorder: Option<StepRecorder<'_, Env>>,
+        mut frames: Option<FrameCollector<'_, Env>>,
+        mut shaping: Option<RewardShaping<'_, R>>,
+    ) -> Result<EpisodeResult, RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED>,
+        A: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        let episode = self.episodes;
+        let mut last_tick = Instant::now();
+        let mut state = environment
+            .reset(seed, None)
+            .map_err(RunnerError::Environment)?;
+        agent.reset().map_err(RunnerError::Agent)?;
+        if let Some((recording, _)) = recorder.as_mut() {
+            recording.initial_state = state.clone();
+        }
+        if let Some((frames, render)) = frames.as_mut() {
+            frames.push(render(environment));
+        }
+        self.notify(|callback| callback.on_episode_start(episode, &state));
+
+        let mut result = EpisodeResult::default();
+        while !result.done
+            && !self.stopped
+            && !self.is_budget_exhausted()
+            && self
+                .max_steps
+                .is_none_or(|max_steps| result.steps < max_steps)
+        {
+            let action = agent.choose_action(&state).map_err(RunnerError::Agent)?;
+            let (new_state, reward, done, _) = environment
+                .step(&action)
+                .map_err(RunnerError::Environment)?;
+            let base_reward = reward.value();
+            let reward = match shaping.as_mut() {
+                Some((shaper, to_reward)) => to_reward(shaper.shape(
+                    &state,
+                    &action,
+                    &new_state,
+                    base_reward,
+                    u64::try_from(result.steps).unwrap_or(u64::MAX),
+                )),
+                None => reward,
+            };
+            let reward_value = reward.value();
+            agent
+                .process_reward(&state, &action, &new_state, reward, done)
+                .map_err(RunnerError::Agent)?;
+
+            self.total_steps += 1;
+            self.consume_time(&mut last_tick);
+            if let Some(budget) = self.budget.as_mut() {
+                budget.consume_steps(1);
+            }
+            let step = StepInfo {
+                episode,
+                step: result.steps,
+                total_steps: self.total_steps,
+                old_state: &state,
+                action: &action,
+                new_state: &new_state,
+                reward: reward_value,
+                done,
+                budget: self.remaining_budget(),
+            };
+            self.notify(|callback| callback.on_step(&step));
+
+            if let Some((recording, rng_probe)) = recorder.as_mut() {
+                recording.steps.push(RecordedStep {
+                    action,
+                    state: new_state.clone(),
+                    reward: base_reward,
+                    done,
+                    rng: rng_probe.map(|probe| probe(environment)),
+                });
+            }
+            if let Some((frames, render)) = frames.as_mut() {
+                frames.push(render(environment));
+            }
+            result.steps += 1;
+            result.total_reward += reward_value;
+            result.done = done;
+            state = new_state;
+        }
+
+        self.episodes += 1;
+        self.consume_time(&mut last_tick);
+        if let Some(budget) = self.budget.as_mut() {
+            budget.consume_episodes(1);
+        }
+        self.notify(|callback| callback.on_episode_end(episode, &result));
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_agent {
+    use crate::space::DimensionValue;
+    use crate::{Agent, AgentAction, EnvironmentState, Seed};
+
+    /// Agent always choosing the same integer action and remembering the rewards it got.
+    pub struct ConstantAgent {
+        pub action: i32,
+        pub rewards: Vec<f64>,
+    }
+
+    impl ConstantAgent {
+        pub fn new(action: i32) -> Self {
+            Self {
+                action,
+                rewards: Vec::new(),
+            }
+        }
+    }
+
+    impl Agent<std::fmt::Error, f64, ()> for ConstantAgent {
+        fn reseed(&mut self, _random_seed: Option<Seed>) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn reset(&mut self) -> Result<(), std::fmt::Error> {
+            self.rewards.clear();
+            Ok(())
+        }
+
+        fn choose_action(
+            &mut self,
+            _state: &EnvironmentState,
+        ) -> Result<AgentAction, std::fmt::Error> {
+            Ok(AgentAction::simple(vec![DimensionValue::from(self.action)]))
+        }
+
+        fn process_reward(
+            &mut self,
+            _old_state: &EnvironmentState,
+            _last_action: &AgentAction,
+            _new_state: &EnvironmentState,
+            reward: f64,
+            _is_done: bool,
+        ) -> Result<(), std::fmt::Error> {
+            self.rewards.push(reward);
+            Ok(())
+        }
+
+        fn load(&mut self, _data: ()) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn store(&self) {}
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_agent::ConstantAgent;
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::{AgentAction, EnvironmentState};
+
+    #[test]
+    fn runs_until_done_or_step_limit() {
+        let mut environment = CountingEnvironment::default();
+        let mut agent = ConstantAgent::new(1);
+
+        let result = EpisodeRunner::new()
+            .run_episode(&mut environment, &mut agent, None)
+            .unwrap();
+        assert_eq!(
+            EpisodeResult {
+                steps: 5,
+                total_reward: 15f64,
+                done: true
+            },
+            result
+        );
+        assert_eq!(vec![1f64, 2f64, 3f64, 4f64, 5f64], agent.rewards);
+
+        let result = EpisodeRunner::new()
+            .with_max_steps(3)
+            .run_episode(&mut environment, &mut agent, None)
+            .unwrap();
+        assert_eq!(3, result.steps);
+        assert!(!result.done);
+    }
+
+    #[test]
+    fn reward_shapers_change_the_rewards_of_the_agent() {
+        let rewards = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut runner = EpisodeRunner::new().with_callback(RewardLog(rewards.clone()));
+        let mut shaper =
+            |_: &EnvironmentState, _: &AgentAction, _: &EnvironmentState, r: f64, s: u64| {
+                r - s as f64
+            };
+        let mut agent = ConstantAgent::new(1);
+
+        let result = runner
+            .run_shaped_episode(
+                &mut CountingEnvironment::default(),
+                &mut agent,
+                &mut shaper,
+                None,
+            )
+            .unwrap();
+        assert_eq!(5f64, result.total_reward);
+        assert_eq!(vec![1f64; 5], *rewards.borrow());
+        assert_eq!(vec![1f64; 5], agent.rewards);
+
+        let results = runner
+            .run_shaped(
+                &mut CountingEnvironment::default(),
+                &mut agent,
+                &mut shaper,
+                2,
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            vec![5f64, 5f64],
+            results.iter().map(|r| r.total_reward).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![1f64; 5], agent.rewards);
+    }
+
+    struct RewardLog(std::rc::Rc<std::cell::RefCell<Vec<f64>>>);
+
+    impl Callback for RewardLog {
+        fn on_step(&mut self, step: &StepInfo<'_>) -> Flow {
+            self.0.borrow_mut().push(step.reward);
+            Flow::Continue
+        }
+    }
+
+    #[derive(Default)]
+    struct StepLimit {
+        steps: u128,
+        episodes_ended: Vec<u64>,
+        checkpoints: Vec<std::path::PathBuf>,
+    }
+
+    impl Callback for StepLimit {
+        fn on_step(&mut self, step: &StepInfo<'_>) -> Flow {
+            if step.total_steps >= self.steps {
+                Flow::Stop
+            } else {
+                Flow::Continue
+            }
+        }
+
+        fn on_episode_end(&mut self, episode: u64, _result: &EpisodeResult) -> Flow {
+            self.episodes_ended.push(episode);
+            Flow::Continue
+        }
+
+        fn on_checkpoint(&mut self, _episode: u64, path: &std::path::Path) {
+            self.checkpoints.push(path.to_path_buf());
+        }
+    }
+
+    #[test]
+    fn callbacks_observe_and_stop_runs() {
+        let directory = std::env::temp_dir().join("gymnarium_runner_checkpoints");
+        std::fs::create_dir_all(&directory).unwrap();
+        let limit = std::rc::Rc::new(std::cell::RefCell::new(StepLimit {
+            steps: 12,
+            ..StepLimit::default()
+        }));
+        let mut runner = EpisodeRunner::new()
+            .with_callback(limit.clone())
+            .with_checkpoints(2, &directory);
+
+        let results = runner
+            .run(
+                &mut CountingEnvironment::default(),
+                &mut ConstantAgent::new(1),
+                10,
+                Some(Seed::from(1)),
+            )
+            .unwrap();
+        assert_eq!(
+            vec![5, 5, 2],
+            results.iter().map(|r| r.steps).collect::<Vec<_>>()
+        );
+        assert!(runner.is_stopped());
+        assert_eq!((3, 12), (runner.episodes(), runner.total_steps()));
+
+        let limit = limit.borrow();
+        assert_eq!(vec![0, 1, 2], limit.episodes_ended);
+        assert_eq!(vec![directory.join("checkpoint-2.json")], limit.checkpoints);
+        let checkpoint: Checkpoint<i32, ()> = Checkpoint::load(&limit.checkpoints[0]).unwrap();
+        assert_eq!(
+            (2, 10, 5),
+            (
+                checkpoint.episodes,
+                checkpoint.steps,
+                checkpoint.environment
+            )
+        );
+        std::fs::remove_file(&limit.checkpoints[0]).unwrap();
+    }
+
+    #[test]
+    fn budget_limits_runs_and_resumes_from_checkpoints() {
+        let path = std::env::temp_dir().join("gymnarium_runner_budget");
+        std::fs::create_dir_all(&path).unwrap();
+        let mut runner = EpisodeRunner::new()
+            .with_budget(TrainingBudget::new().with_max_steps(12))
+            .with_checkpoints(1, &path);
+        let results = runner
+            .run(
+                &mut CountingEnvironment::default(),
+                &mut ConstantAgent::new(1),
+                2,
+                None,
+            )
+            .unwrap();
+        assert_eq!(2, results.len());
+        assert_eq!(Some(2), runner.remaining_budget().unwrap().steps);
+
+        let checkpoint: Checkpoint<i32, ()> =
+            Checkpoint::load(path.join("checkpoint-2.json")).unwrap();
+        let mut resumed = EpisodeRunner::new().with_budget(checkpoint.budget.unwrap());
+        let results = resumed
+            .run(
+                &mut CountingEnvironment::default(),
+                &mut ConstantAgent::new(1),
+                10,
+                None,
+            )
+            .unwrap();
+        assert_eq!(vec![2], results.iter().map(|r| r.steps).collect::<Vec<_>>());
+        assert!(resumed.is_budget_exhausted());
+        assert_eq!(3, resumed.budget().unwrap().used().episodes);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+//! Text encodings of seeds, so seeds can be printed in logs and passed back on command lines.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+use core::str::FromStr;
+
+use crate::Seed;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/* --- --- --- SEED PARSE ERROR --- --- --- */
+
+/// Errors while decoding a seed from text.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SeedParseError {
+    /// The character at the given byte index is no valid digit of the encoding.
+    InvalidCharacter { index: usize, character: char },
+    /// The text has a length impossible for the encoding.
+    InvalidLength { length: usize },
+}
+
+impl core::fmt::Display for SeedParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidCharacter { index, character } => write!(
+                f,
+                "Invalid character '{}' at index {} of encoded seed",
+                character, index
+            ),
+            Self::InvalidLength { length } => {
+                write!(f, "Encoded seed has invalid length {}", length)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SeedParseError {}
+
+fn invalid_character(text: &str, index: usize) -> SeedParseError {
+    SeedParseError::InvalidCharacter {
+        index,
+        character: text[index..].chars().next().unwrap_or('?'),
+    }
+}
+
+/* --- --- --- SEED ENCODINGS --- --- --- */
+
+impl Seed {
+    /// Returns the seed value as lowercase hexadecimal digits.
+    ///
+    /// ```
+    /// # use gymnarium_base::Seed;
+    /// let seed = Seed::from(vec![0, 171, 255]);
+    /// assert_eq!("00abff", seed.to_hex());
+    /// assert_eq!(Ok(seed), Seed::from_hex("00ABff"));
+    /// ```
+    pub fn to_hex(&self) -> String {
+        self.seed_value
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Decodes a seed from hexadecimal digits of any case.
+    pub fn from_hex(text: &str) -> Result<Self, SeedParseError> {
+        if !text.len().is_multiple_of(2) {
+            return Err(SeedParseError::InvalidLength { length: text.len() });
+        }
+        let digits = text.as_bytes();
+        let digit = |index: usize| {
+            (digits[index] as char)
+                .to_digit(16)
+                .map(|digit| digit as u8)
+                .ok_or_else(|| invalid_character(text, index))
+        };
+        (0..digits.len())
+            .step_by(2)
+            .map(|index| Ok(digit(index)? << 4 | digit(index + 1)?))
+            .collect::<Result<Vec<u8>, _>>()
+            .map(Seed::from)
+    }
+
+    /// Returns the seed value encoded with the standard base64 alphabet and padding.
+    ///
+    /// ```
+    /// # use gymnarium_base::Seed;
+    /// let seed = Seed::from("gym");
+    /// assert_eq!("Z3lt", seed.to_base64());
+    /// assert_eq!(Ok(Seed::from("gy")), Seed::from_base64("Z3k="));
+    /// ```
+    pub fn to_base64(&self) -> String {
+        let mut text = String::with_capacity(self.seed_value.len().div_ceil(3) * 4);
+        for chunk in self.seed_value.chunks(3) {
+            let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, byte)| {
+                bits | (*byte as u32) << (16 - 8 * index)
+            });
+            for index in 0..4 {
+                if index <= chunk.len() {
+                    text.push(BASE64_ALPHABET[(bits >> (18 - 6 * index) & 0x3f) as usize] as char);
+                } else {
+                    text.push('=');
+                }
+            }
+        }
+        text
+    }
+
+    /// Decodes a seed from standard base64 with padding.
+    pub fn from_base64(text: &str) -> Result<Self, SeedParseError> {
+        if !text.len().is_multiple_of(4) {
+            return Err(SeedParseError::InvalidLength { length: text.len() });
+        }
+        let characters = text.as_bytes();
+        let padding = characters.iter().rev().take_while(|c| **c == b'=').count();
+        if padding > 2 {
+            return Err(invalid_character(text, text.len() - padding));
+        }
+        let mut seed_value = Vec::with_capacity(text.len() / 4 * 3);
+        for (chunk_index, chunk) in characters.chunks(4).enumerate() {
+            let mut bits = 0u32;
+            for (offset, character) in chunk.iter().enumerate() {
+                let index = chunk_index * 4 + offset;
+                let value = if index >= text.len() - padding {
+                    0
+                } else {
+                    BASE64_ALPHABET
+                        .iter()
+                        .position(|c| c == character)
+                        .ok_or_else(|| invalid_character(text, index))? as u32
+                };
+                bits = bits << 6 | value;
+            }
+            seed_value.extend_from_slice(&bits.to_be_bytes()[1..]);
+        }
+        seed_value.truncate(seed_value.len() - padding);
+        Ok(Seed::from(seed_value))
+    }
+}
+
+impl core::fmt::Display for Seed {
+    /// Displays the seed as hexadecimal digits, which `FromStr` parses again.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl FromStr for Seed {
+    type Err = SeedParseError;
+
+    /// Parses hexadecimal digits as written by `Display`.
+    ///
+    /// Unlike `Seed::from(&str)`, which takes the bytes of the text itself as seed value.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Seed::from_hex(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[test]
+    fn encodings_round_trip() {
+        for length in 0..10 {
+            let seed = Seed::from(
+                (0..length)
+                    .map(|v: u8| v.wrapping_mul(37) + 5)
+                    .collect::<Vec<u8>>(),
+            );
+            assert_eq!(Ok(seed.clone()), Seed::from_base64(&seed.to_base64()));
+            assert_eq!(Ok(seed.clone()), seed.to_string().parse());
+        }
+        assert_eq!("Zm9vYmFy", Seed::from("foobar").to_base64());
+        assert_eq!("Zm9vYg==", Seed::from("foob").to_base64());
+    }
+
+    #[test]
+    fn invalid_text_is_reported() {
+        assert_eq!(
+            Err(SeedParseError::InvalidLength { length: 3 }),
+            Seed::from_hex("abc")
+        );
+        assert_eq!(
+            Err(SeedParseError::InvalidCharacter {
+                index: 1,
+                character: 'g'
+            }),
+            Seed::from_hex("ag")
+        );
+        assert_eq!(
+            Err(SeedParseError::InvalidCharacter {
+                index: 2,
+                character: '-'
+            }),
+            Seed::from_base64("Zm-v")
+        );
+    }
+}
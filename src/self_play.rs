@@ -0,0 +1,457 @@
+//! Training a single agent against earlier versions of itself.
+//!
+//! `SelfPlay` drives two-agent zero-sum `MultiAgentEnvironment`s: the learning agent plays against
+//! an opponent loaded from a pool of stored snapshots of the learning agent, while the win rates
+//! against every snapshot are tracked.
+
+use std::fmt::Debug;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{Agent, EnvironmentState, MultiAgentEnvironment, Reward, Seed};
+
+/* --- --- --- SELF PLAY ERROR --- --- --- */
+
+/// Errors while playing an episode.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SelfPlayError<EE, AE> {
+    Environment(EE),
+    Agent(AE),
+    /// The environment is not made for exactly two agents.
+    UnsupportedAgentCount(usize),
+}
+
+impl<EE: std::fmt::Display, AE: std::fmt::Display> std::fmt::Display for SelfPlayError<EE, AE> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::Agent(error) => write!(f, "Agent Error \"{}\" occurred", error),
+            Self::UnsupportedAgentCount(count) => write!(
+                f,
+                "Self play needs exactly two agents, but the environment has {}",
+                count
+            ),
+        }
+    }
+}
+
+impl<EE, AE> std::error::Error for SelfPlayError<EE, AE>
+where
+    EE: std::error::Error + 'static,
+    AE: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            Self::Agent(error) => Some(error),
+            Self::UnsupportedAgentCount(_) => None,
+        }
+    }
+}
+
+/* --- --- --- RESULTS --- --- --- */
+
+/// Wins, losses and draws of the learning agent.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct WinRecord {
+    pub wins: u64,
+    pub losses: u64,
+    pub draws: u64,
+}
+
+impl WinRecord {
+    pub fn games(&self) -> u64 {
+        self.wins + self.losses + self.draws
+    }
+
+    /// Returns the share of won games, counting draws as half a win.
+    pub fn win_rate(&self) -> Option<f64> {
+        if self.games() == 0 {
+            None
+        } else {
+            Some((self.wins as f64 + self.draws as f64 / 2f64) / self.games() as f64)
+        }
+    }
+
+    fn record(&mut self, learner_return: f64, opponent_return: f64) {
+        if learner_return > opponent_return {
+            self.wins += 1;
+        } else if learner_return < opponent_return {
+            self.losses += 1;
+        } else {
+            self.draws += 1;
+        }
+    }
+}
+
+/// Result of a single self play episode.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EpisodeOutcome {
+    /// Index of the learning agent within the environment.
+    pub learner_seat: usize,
+    /// Index of the opponent snapshot within the pool at the time of playing.
+    pub snapshot: usize,
+    pub learner_return: f64,
+    pub opponent_return: f64,
+    pub steps: u64,
+}
+
+/* --- --- --- SELF PLAY --- --- --- */
+
+/// Plays a learning agent against snapshots of itself.
+///
+/// The learning agent switches seats every episode. After every `snapshot_interval` episodes its
+/// stored data is added to the pool, dropping the oldest snapshot once `pool_size` is exceeded.
+pub struct SelfPlay<A, D> {
+    learner: A,
+    opponent: A,
+    pool: Vec<(D, WinRecord)>,
+    pool_size: usize,
+    snapshot_interval: u64,
+    episodes: u64,
+    record: WinRecord,
+    rng: StdRng,
+}
+
+impl<A, D> SelfPlay<A, D>
+where
+    D: Clone + Serialize + DeserializeOwned,
+{
+    /// Creates the harness with the current learner as the first snapshot.
+    ///
+    /// The opponent is only used to load snapshots into.
+    pub fn new<AE, R>(learner: A, opponent: A, pool_size: usize) -> Self
+    where
+        A: Agent<AE, R, D>,
+        AE: std::error::Error,
+        R: Reward,
+    {
+        let first = learner.store();
+        Self {
+            learner,
+            opponent,
+            pool: vec![(first, WinRecord::default())],
+            pool_size: pool_size.max(1),
+            snapshot_interval: 100,
+            episodes: 0,
+            record: WinRecord::default(),
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    pub fn with_snapshot_interval(mut self, snapshot_interval: u64) -> Self {
+        self.snapshot_interval = snapshot_interval.max(1);
+        self
+    }
+
+    /// Seeds the choice of opponent snapshots.
+    pub fn with_seed(mut self, seed: Seed) -> Self {
+        self.rng = StdRng::from_seed(seed.mixed());
+        self
+    }
+
+    pub fn learner(&self) -> &A {
+        &self.learner
+    }
+
+    pub fn learner_mut(&mut self) -> &mut A {
+        &mut self.learner
+    }
+
+    pub fn into_learner(self) -> A {
+        self.learner
+    }
+
+    /// Returns the number of snapshots in the pool.
+    pub fn pool_len(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Returns the record against all opponents.
+    pub fn record(&self) -> WinRecord {
+        self.record
+    }
+
+    /// Returns the records against every snapshot in the pool, oldest first.
+    pub fn snapshot_records(&self) -> Vec<WinRecord> {
+        self.pool.iter().map(|(_, record)| *record).collect()
+    }
+
+    /// Adds the current state of the learning agent to the pool.
+    pub fn snapshot<AE, R>(&mut self)
+    where
+        A: Agent<AE, R, D>,
+        AE: std::error::Error,
+        R: Reward,
+    {
+        self.pool.push((self.learner.store(), WinRecord::default()));
+        if self.pool.len() > self.pool_size {
+            self.pool.remove(0);
+        }
+    }
+
+    /// Plays one episode against a randomly chosen snapshot and lets the learning agent process
+    /// its rewards.
+    ///
+    /// Rewards received during the turns of the opponent are added to the next transition of the
+    /// learning agent.
+    pub fn play_episode<Env, EE, AE, R, I, ED>(
+        &mut self,
+        environment: &mut Env,
+    ) -> Result<EpisodeOutcome, SelfPlayError<EE, AE>>
+    where
+        Env: MultiAgentEnvironment<EE, R, I, ED>,
+        A: Agent<AE, R, D>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward + From<f64>,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+    {
+        if environment.agent_count() != 2 {
+            return Err(SelfPlayError::UnsupportedAgentCount(
+                environment.agent_count(),
+            ));
+        }
+        let snapshot = self.rng.gen_range(0, self.pool.len());
+        self.opponent
+            .load(self.pool[snapshot].0.clone())
+            .map_err(SelfPlayError::Agent)?;
+        let learner_seat = (self.episodes % 2) as usize;
+
+        environment
+            .reset(None, None)
+            .map_err(SelfPlayError::Environment)?;
+        self.learner.reset().map_err(SelfPlayError::Agent)?;
+        self.opponent.reset().map_err(SelfPlayError::Agent)?;
+
+        let mut returns = [0f64; 2];
+        let mut steps = 0;
+        let mut pending: Option<(EnvironmentState, EnvironmentState, f64)> = None;
+        loop {
+            let seat = environment.current_agent();
+            let observation = environment.observation(seat);
+            if seat == learner_seat {
+                if let Some((old_state, action, reward)) = pending.take() {
+                    self.learner
+                        .process_reward(&old_state, &action, &observation, R::from(reward), false)
+                        .map_err(SelfPlayError::Agent)?;
+                }
+            }
+            let action = if seat == learner_seat {
+                self.learner.choose_action(&observation)
+            } else {
+                self.opponent.choose_action(&observation)
+            }
+            .map_err(SelfPlayError::Agent)?;
+
+            let (rewards, done, _) = environment
+                .step(&action)
+                .map_err(SelfPlayError::Environment)?;
+            steps += 1;
+            for (total, reward) in returns.iter_mut().zip(&rewards) {
+                *total += reward.value();
+            }
+            let learner_reward = rewards.get(learner_seat).map_or(0f64, Reward::value);
+            if seat == learner_seat {
+                pending = Some((observation, action, learner_reward));
+            } else if let Some((_, _, reward)) = pending.as_mut() {
+                *reward += learner_reward;
+            }
+            if done {
+                break;
+            }
+        }
+        if let Some((old_state, action, reward)) = pending {
+            let observation = environment.observation(learner_seat);
+            self.learner
+                .process_reward(&old_state, &action, &observation, R::from(reward), true)
+                .map_err(SelfPlayError::Agent)?;
+        }
+
+        let outcome = EpisodeOutcome {
+            learner_seat,
+            snapshot,
+            learner_return: returns[learner_seat],
+            opponent_return: returns[1 - learner_seat],
+            steps,
+        };
+        self.record
+            .record(outcome.learner_return, outcome.opponent_return);
+        self.pool[snapshot]
+            .1
+            .record(outcome.learner_return, outcome.opponent_return);
+        self.episodes += 1;
+        if self.episodes.is_multiple_of(self.snapshot_interval) {
+            self.snapshot();
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::{DimensionBoundaries, Space};
+    use crate::{ActionSpace, AgentAction, ObservationSpace, ResetOptions};
+
+    /// Players alternately add 1 or 2, whoever reaches 5 wins.
+    #[derive(Default)]
+    struct RaceToFive {
+        total: i32,
+        turn: usize,
+    }
+
+    impl MultiAgentEnvironment<std::fmt::Error, f64, (), i32> for RaceToFive {
+        fn agent_count(&self) -> usize {
+            2
+        }
+
+        fn action_space(&self) -> ActionSpace {
+            Space::simple(vec![DimensionBoundaries::from(1..=2)])
+        }
+
+        fn observation_space(&self) -> ObservationSpace {
+            Space::simple(vec![DimensionBoundaries::from(0..=6)])
+        }
+
+        fn reset(
+            &mut self,
+            _seed: Option<Seed>,
+            _options: Option<ResetOptions>,
+        ) -> Result<(), std::fmt::Error> {
+            self.total = 0;
+            self.turn = 0;
+            Ok(())
+        }
+
+        fn current_agent(&self) -> usize {
+            self.turn
+        }
+
+        fn observation(&self, _agent: usize) -> EnvironmentState {
+            crate::position![self.total]
+        }
+
+        fn step(&mut self, action: &AgentAction) -> Result<(Vec<f64>, bool, ()), std::fmt::Error> {
+            self.total += action.value(&[0]).expect_integer();
+            let mut rewards = vec![0f64; 2];
+            let done = self.total >= 5;
+            if done {
+                rewards[self.turn] = 1f64;
+                rewards[1 - self.turn] = -1f64;
+            }
+            self.turn = 1 - self.turn;
+            Ok((rewards, done, ()))
+        }
+
+        fn load(&mut self, data: i32) -> Result<(), std::fmt::Error> {
+            self.total = data;
+            Ok(())
+        }
+
+        fn store(&self) -> i32 {
+            self.total
+        }
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+
+    /// Always adds the same amount and remembers the rewards it processed.
+    struct FixedAgent {
+        amount: i32,
+        rewards: Vec<(f64, bool)>,
+    }
+
+    impl Agent<std::fmt::Error, f64, i32> for FixedAgent {
+        fn reseed(&mut self, _random_seed: Option<Seed>) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn reset(&mut self) -> Result<(), std::fmt::Error> {
+            self.rewards.clear();
+            Ok(())
+        }
+
+        fn choose_action(
+            &mut self,
+            _state: &EnvironmentState,
+        ) -> Result<AgentAction, std::fmt::Error> {
+            Ok(crate::position![self.amount])
+        }
+
+        fn process_reward(
+            &mut self,
+            _old_state: &EnvironmentState,
+            _last_action: &AgentAction,
+            _new_state: &EnvironmentState,
+            reward: f64,
+            is_done: bool,
+        ) -> Result<(), std::fmt::Error> {
+            self.rewards.push((reward, is_done));
+            Ok(())
+        }
+
+        fn load(&mut self, data: i32) -> Result<(), std::fmt::Error> {
+            self.amount = data;
+            Ok(())
+        }
+
+        fn store(&self) -> i32 {
+            self.amount
+        }
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+
+    fn agent(amount: i32) -> FixedAgent {
+        FixedAgent {
+            amount,
+            rewards: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn learner_switches_seats_and_records_results() {
+        let mut self_play = SelfPlay::new(agent(2), agent(0), 2)
+            .with_snapshot_interval(1)
+            .with_seed(Seed::from(3));
+        let mut environment = RaceToFive::default();
+
+        // Seat 0 with 2 against 2: 2, 4, 6 -> seat 0 wins.
+        let outcome = self_play.play_episode(&mut environment).unwrap();
+        assert_eq!(
+            (0, 1f64, 3),
+            (outcome.learner_seat, outcome.learner_return, outcome.steps)
+        );
+        assert_eq!(
+            vec![(0f64, false), (1f64, true)],
+            self_play.learner().rewards
+        );
+
+        // Seat 1 loses, the last reward arrives during the opponent's turn.
+        self_play.learner_mut().amount = 1;
+        let outcome = self_play.play_episode(&mut environment).unwrap();
+        assert_eq!((1, -1f64), (outcome.learner_seat, outcome.learner_return));
+        assert_eq!(Some(&(-1f64, true)), self_play.learner().rewards.last());
+
+        assert_eq!(
+            WinRecord {
+                wins: 1,
+                losses: 1,
+                draws: 0
+            },
+            self_play.record()
+        );
+        assert_eq!(Some(0.5), self_play.record().win_rate());
+        assert_eq!(2, self_play.pool_len());
+    }
+}
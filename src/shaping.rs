@@ -0,0 +1,95 @@
+//! Hooks to shape the rewards returned by environments.
+//!
+//! Shapers are applied by the `ShapedReward` wrapper or `EpisodeRunner::run_shaped_episode`.
+//! Prefer `PotentialBasedShaping`, as shaping with the difference of a potential does not change
+//! the optimal policy.
+
+use crate::{AgentAction, EnvironmentState};
+
+/// Base trait for anything modifying the reward of a single step.
+///
+/// Implemented for closures taking the same arguments as `shape`.
+pub trait RewardShaper {
+    /// Returns the shaped reward of a step from `old_state` to `new_state` with `action`.
+    ///
+    /// `step` counts the steps since the last reset starting at zero.
+    fn shape(
+        &mut self,
+        old_state: &EnvironmentState,
+        action: &AgentAction,
+        new_state: &EnvironmentState,
+        base_reward: f64,
+        step: u64,
+    ) -> f64;
+}
+
+impl<F> RewardShaper for F
+where
+    F: FnMut(&EnvironmentState, &AgentAction, &EnvironmentState, f64, u64) -> f64,
+{
+    fn shape(
+        &mut self,
+        old_state: &EnvironmentState,
+        action: &AgentAction,
+        new_state: &EnvironmentState,
+        base_reward: f64,
+        step: u64,
+    ) -> f64 {
+        self(old_state, action, new_state, base_reward, step)
+    }
+}
+
+/// Adds `discount * potential(new_state) - potential(old_state)` to the base reward.
+///
+/// ```
+/// use gymnarium_base::shaping::{PotentialBasedShaping, RewardShaper};
+///
+/// let mut shaper = PotentialBasedShaping::new(0.5, |state: &gymnarium_base::EnvironmentState| {
+///     state.value(&[0]).expect_integer() as f64
+/// });
+/// let shaped = shaper.shape(
+///     &gymnarium_base::position![2],
+///     &gymnarium_base::position![1],
+///     &gymnarium_base::position![3],
+///     1.0,
+///     0,
+/// );
+/// assert_eq!(1.0 + 0.5 * 3.0 - 2.0, shaped);
+/// ```
+pub struct PotentialBasedShaping<P> {
+    discount: f64,
+    potential: P,
+}
+
+impl<P> PotentialBasedShaping<P>
+where
+    P: Fn(&EnvironmentState) -> f64,
+{
+    /// Creates the shaper with the discount factor of the learning agent.
+    pub fn new(discount: f64, potential: P) -> Self {
+        Self {
+            discount,
+            potential,
+        }
+    }
+
+    pub fn discount(&self) -> f64 {
+        self.discount
+    }
+}
+
+impl<P> RewardShaper for PotentialBasedShaping<P>
+where
+    P: Fn(&EnvironmentState) -> f64,
+{
+    fn shape(
+        &mut self,
+        old_state: &EnvironmentState,
+        _action: &AgentAction,
+        new_state: &EnvironmentState,
+        base_reward: f64,
+        _step: u64,
+    ) -> f64 {
+        base_reward + self.discount * (self.potential)(new_state) - (self.potential)(old_state)
+    }
+}
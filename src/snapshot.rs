@@ -0,0 +1,164 @@
+//! Versioned envelope around the data returned by `Environment::store`.
+//!
+//! A `Snapshot` remembers which crate version and which environment created the stored data, so
+//! loading it into an incompatible environment fails with an explicit error.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Version of this crate written into every snapshot.
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/* --- --- --- SNAPSHOT ERROR --- --- --- */
+
+/// Errors occurring while loading a `Snapshot` into an environment.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SnapshotError<E> {
+    /// The environment returned an error itself while loading the data.
+    Environment(E),
+    /// The snapshot was created by a crate version with an incompatible storage format.
+    IncompatibleVersion { found: String, current: String },
+    /// The snapshot was created by another environment.
+    EnvironmentMismatch { found: String, expected: String },
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for SnapshotError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::IncompatibleVersion { found, current } => write!(
+                f,
+                "Snapshot of version {} is incompatible with version {}",
+                found, current
+            ),
+            Self::EnvironmentMismatch { found, expected } => write!(
+                f,
+                "Snapshot of environment \"{}\" cannot be loaded into \"{}\"",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for SnapshotError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/* --- --- --- SNAPSHOT --- --- --- */
+
+/// Stored environment data together with its origin.
+///
+/// ```
+/// use gymnarium_base::snapshot::{Snapshot, SnapshotError};
+///
+/// let snapshot = Snapshot::new("Corridor", 5);
+/// assert!(snapshot.check_compatibility::<std::fmt::Error>("Corridor").is_ok());
+///
+/// let old = Snapshot { crate_version: "0.0.1".to_string(), ..snapshot };
+/// assert!(matches!(
+///     old.check_compatibility::<std::fmt::Error>("Corridor"),
+///     Err(SnapshotError::IncompatibleVersion { .. })
+/// ));
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Snapshot<D> {
+    pub crate_version: String,
+    pub environment_id: String,
+    /// Seconds since the unix epoch at creation.
+    pub timestamp: u64,
+    pub data: D,
+}
+
+impl<D> Snapshot<D> {
+    /// Wraps the data with the current crate version and time.
+    pub fn new<S: Into<String>>(environment_id: S, data: D) -> Self {
+        Self {
+            crate_version: CRATE_VERSION.to_string(),
+            environment_id: environment_id.into(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+            data,
+        }
+    }
+
+    /// Checks whether this snapshot can be loaded into the environment with the given id by the
+    /// current crate version.
+    ///
+    /// Versions are compatible if they share the major version, or the minor version below 1.0.
+    pub fn check_compatibility<E>(&self, environment_id: &str) -> Result<(), SnapshotError<E>> {
+        if compatibility_prefix(&self.crate_version) != compatibility_prefix(CRATE_VERSION) {
+            return Err(SnapshotError::IncompatibleVersion {
+                found: self.crate_version.clone(),
+                current: CRATE_VERSION.to_string(),
+            });
+        }
+        if self.environment_id != environment_id {
+            return Err(SnapshotError::EnvironmentMismatch {
+                found: self.environment_id.clone(),
+                expected: environment_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn into_data(self) -> D {
+        self.data
+    }
+}
+
+fn compatibility_prefix(version: &str) -> Vec<&str> {
+    let mut parts = version.split(['.', '-', '+']);
+    match parts.next() {
+        Some("0") => vec!["0", parts.next().unwrap_or("")],
+        major => vec![major.unwrap_or("")],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::wrappers::ValidatedEnvironment;
+    use crate::Environment;
+
+    #[test]
+    fn compatibility_follows_semantic_versioning() {
+        assert_eq!(vec!["0", "1"], compatibility_prefix("0.1.7"));
+        assert_eq!(vec!["2"], compatibility_prefix("2.4.0-beta"));
+        assert_ne!(compatibility_prefix("0.2.0"), compatibility_prefix("0.1.0"));
+    }
+
+    #[test]
+    fn snapshots_load_only_into_matching_environments() {
+        let mut environment = CountingEnvironment { count: 4 };
+        let snapshot = environment.snapshot();
+        assert_eq!(
+            CountingEnvironment::environment_id(),
+            snapshot.environment_id
+        );
+
+        let mut validated = ValidatedEnvironment::new(CountingEnvironment::default());
+        validated.load_snapshot(snapshot.clone()).unwrap();
+        assert_eq!(4, validated.store());
+
+        let foreign = Snapshot {
+            environment_id: "Other".to_string(),
+            ..snapshot
+        };
+        assert_eq!(
+            Err(SnapshotError::EnvironmentMismatch {
+                found: "Other".to_string(),
+                expected: CountingEnvironment::environment_id()
+            }),
+            environment.load_snapshot(foreign)
+        );
+    }
+}
@@ -198,36 +198,258 @@
 //!
 //! // Get a single value from this position:
 //! assert_eq!(
-//!     format.get_value(&position, &"ext_controller".to_string(), &vec![2]),
-//!     Ok(&DimensionValue::Integer(0))
+//!     format.value(&position, &"ext_controller".to_string(), &vec![2]),
+//!     Ok(DimensionValue::Integer(0))
 //! );
 //! ```
 //!
 
-use std::collections::HashMap;
-
-use std::ops::{Index, IndexMut, RangeInclusive};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, boxed::Box, format, string::String, string::ToString, vec, vec::Vec};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut, Range, RangeInclusive};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
 
+use once_cell::race::OnceBox;
 use rand::distributions::{Distribution, Uniform};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use serde::de::{Error as DeserializeError, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Seed;
 
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod batch;
+mod catalog;
+mod conversion;
+mod discrete;
+mod distribution;
+mod graph;
+mod gym;
+mod image;
+#[cfg(feature = "wasm")]
+mod js;
+mod sequence;
+mod typed;
+mod values;
+
+pub use batch::{PositionBatch, PositionBatchView};
+pub use catalog::ActionCatalog;
+pub use conversion::{FromPosition, PositionField, ToPosition};
+#[cfg(feature = "std")]
+pub(crate) use discrete::{DiscreteError, DiscreteRanges};
+pub use distribution::{DiscreteDistribution, DistributionError};
+pub use graph::{GraphMismatch, GraphPosition, GraphSpace};
+pub use gym::GymJsonError;
+#[cfg(feature = "derive")]
+pub use gymnarium_derive::{FromPosition, ToPosition};
+pub use sequence::{SequenceMismatch, SequenceSpace};
+pub use typed::{TypedPosition, TypedSpace};
+use values::{Values, ValuesRef};
 
 /* --- --- --- INDEX --- --- --- */
 
+/// Calculates the row-major strides of a n-dimensional Vec stored inside a one-dimensional Vec.
+///
+/// The last dimension is stored contiguously, so its stride is always 1.
+fn calculate_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
 /// Calculates the index inside a n-dimensional Vec stored inside a one-dimensional Vec.
 fn calculate_index(shape: &[usize], index: &[usize]) -> Result<usize, SpaceError> {
-    if index.iter().zip(shape.iter()).any(|(a, b)| a >= b) {
-        Err(SpaceError::IndexOutOfBounds)
-    } else if index.is_empty() {
-        Ok(0)
+    if index.len() != shape.len() || index.iter().zip(shape.iter()).any(|(a, b)| a >= b) {
+        Err(SpaceError::IndexOutOfBounds {
+            dimensions: shape.to_vec(),
+            index: index.to_vec(),
+        })
+    } else {
+        Ok(index
+            .iter()
+            .zip(calculate_strides(shape).iter())
+            .map(|(index, stride)| index * stride)
+            .sum())
+    }
+}
+
+/// Calls `f` for every index inside the given shape, stopping at the first error.
+fn try_for_each_index<E, F: FnMut(&[usize]) -> Result<(), E>>(
+    shape: &[usize],
+    mut f: F,
+) -> Result<(), E> {
+    if shape.contains(&0) {
+        return Ok(());
+    }
+    let mut index = vec![0; shape.len()];
+    loop {
+        f(&index)?;
+        let mut axis = shape.len();
+        loop {
+            if axis == 0 {
+                return Ok(());
+            }
+            axis -= 1;
+            index[axis] += 1;
+            if index[axis] < shape[axis] {
+                break;
+            }
+            index[axis] = 0;
+        }
+    }
+}
+
+/// Copies the part `range` of dimension `axis` out of a n-dimensional Vec stored inside a
+/// one-dimensional Vec.
+fn slice_values<T: Copy>(
+    values: &[T],
+    dimensions: &[usize],
+    axis: usize,
+    range: Range<usize>,
+) -> Result<(Vec<T>, Vec<usize>), SpaceError> {
+    if axis >= dimensions.len() || range.start > range.end || range.end > dimensions[axis] {
+        return Err(SpaceError::SliceOutOfBounds {
+            dimensions: dimensions.to_vec(),
+            axis,
+            range,
+        });
+    }
+    let mut sliced_dimensions = dimensions.to_vec();
+    sliced_dimensions[axis] = range.end - range.start;
+    let mut sliced_values = match values.first() {
+        Some(value) => vec![*value; sliced_dimensions.iter().product()],
+        None => Vec::new(),
+    };
+    let mut source_index = vec![0; dimensions.len()];
+    try_for_each_index(&sliced_dimensions, |index| {
+        source_index.copy_from_slice(index);
+        source_index[axis] += range.start;
+        sliced_values[calculate_index(&sliced_dimensions, index)?] =
+            values[calculate_index(dimensions, &source_index)?];
+        Ok(())
+    })?;
+    Ok((sliced_values, sliced_dimensions))
+}
+
+/// Rearranges a n-dimensional Vec stored inside a one-dimensional Vec, so that dimension `i` of
+/// the output is dimension `axes[i]` of the input.
+fn transpose_values<T: Copy>(
+    values: &[T],
+    dimensions: &[usize],
+    axes: &[usize],
+) -> Result<(Vec<T>, Vec<usize>), SpaceError> {
+    let mut sorted_axes = axes.to_vec();
+    sorted_axes.sort_unstable();
+    if !sorted_axes.iter().copied().eq(0..dimensions.len()) {
+        return Err(SpaceError::InvalidAxes {
+            dimensions: dimensions.to_vec(),
+            axes: axes.to_vec(),
+        });
+    }
+    let transposed_dimensions: Vec<usize> = axes.iter().map(|axis| dimensions[*axis]).collect();
+    let mut transposed_values = values.to_vec();
+    let mut source_index = vec![0; dimensions.len()];
+    try_for_each_index(&transposed_dimensions, |index| {
+        for (position, axis) in axes.iter().enumerate() {
+            source_index[*axis] = index[position];
+        }
+        transposed_values[calculate_index(&transposed_dimensions, index)?] =
+            values[calculate_index(dimensions, &source_index)?];
+        Ok(())
+    })?;
+    Ok((transposed_values, transposed_dimensions))
+}
+
+/// Calculates the dimensions of two structures joined along their first dimension.
+fn concatenate_dimensions(
+    dimensions_a: &[usize],
+    dimensions_b: &[usize],
+) -> Result<Vec<usize>, SpaceError> {
+    match (dimensions_a.split_first(), dimensions_b.split_first()) {
+        (Some((first_a, rest_a)), Some((first_b, rest_b))) if rest_a == rest_b => {
+            let mut dimensions = vec![first_a + first_b];
+            dimensions.extend_from_slice(rest_a);
+            Ok(dimensions)
+        }
+        _ => Err(SpaceError::IncompatibleDimensions {
+            first: dimensions_a.to_vec(),
+            second: dimensions_b.to_vec(),
+        }),
+    }
+}
+
+/* --- --- --- DISPLAY --- --- --- */
+
+/// How many values `Space` and `Position` print before truncating, unless formatted with `{:#}`.
+const DISPLAY_VALUE_LIMIT: usize = 8;
+
+fn write_shape(f: &mut core::fmt::Formatter<'_>, dimensions: &[usize]) -> core::fmt::Result {
+    let shape = dimensions
+        .iter()
+        .map(|dimension| dimension.to_string())
+        .collect::<Vec<_>>()
+        .join("x");
+    write!(f, "{}", shape)
+}
+
+fn write_truncated<T: core::fmt::Display>(
+    f: &mut core::fmt::Formatter<'_>,
+    values: impl ExactSizeIterator<Item = T>,
+) -> core::fmt::Result {
+    let length = values.len();
+    let limit = if f.alternate() {
+        length
     } else {
-        let mut output_index = index[0];
-        for i in 1..index.len() {
-            output_index += index[i] * shape[i - 1];
+        DISPLAY_VALUE_LIMIT
+    };
+    for (index, value) in values.take(limit).enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
         }
-        Ok(output_index)
+        write!(f, "{}", value)?;
     }
+    if length > limit {
+        write!(f, ", ... ({} total)", length)?;
+    }
+    Ok(())
+}
+
+/* --- --- --- STORAGE --- --- --- */
+
+/// The container holding the boundaries of a `Space` and the values of a `Position`.
+///
+/// With the feature `smallvec` up to `INLINE_STORAGE_CAPACITY` elements are stored without any
+/// heap allocation.
+#[cfg(not(feature = "smallvec"))]
+pub(crate) type Storage<T> = Vec<T>;
+
+#[cfg(feature = "smallvec")]
+pub(crate) type Storage<T> = smallvec::SmallVec<[T; INLINE_STORAGE_CAPACITY]>;
+
+#[cfg(feature = "smallvec")]
+const INLINE_STORAGE_CAPACITY: usize = 8;
+
+#[cfg(not(feature = "smallvec"))]
+fn into_storage<T>(values: Vec<T>) -> Storage<T> {
+    values
+}
+
+#[cfg(feature = "smallvec")]
+fn into_storage<T>(values: Vec<T>) -> Storage<T> {
+    Storage::from_vec(values)
 }
 
 /* --- --- --- SPACE ERROR --- --- --- */
@@ -235,20 +457,170 @@ fn calculate_index(shape: &[usize], index: &[usize]) -> Result<usize, SpaceError
 /// General errors for this module.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum SpaceError {
-    GivenDimensionsDoNotMatch,
-    IndexOutOfBounds,
+    GivenDimensionsDoNotMatch {
+        dimensions: Vec<usize>,
+        expected_length: usize,
+        actual_length: usize,
+    },
+    IncompatibleDimensions {
+        first: Vec<usize>,
+        second: Vec<usize>,
+    },
+    InvalidAxes {
+        dimensions: Vec<usize>,
+        axes: Vec<usize>,
+    },
+    IndexOutOfBounds {
+        dimensions: Vec<usize>,
+        index: Vec<usize>,
+    },
+    SliceOutOfBounds {
+        dimensions: Vec<usize>,
+        axis: usize,
+        range: Range<usize>,
+    },
+    InvalidBoundaries {
+        index: usize,
+    },
 }
 
-impl std::fmt::Display for SpaceError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for SpaceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::GivenDimensionsDoNotMatch => write!(f, "Given dimensions do not match"),
-            Self::IndexOutOfBounds => write!(f, "Given index is out of bounds"),
+            Self::GivenDimensionsDoNotMatch {
+                dimensions,
+                expected_length,
+                actual_length,
+            } => write!(
+                f,
+                "Given dimensions {:?} need {} elements but {} were given",
+                dimensions, expected_length, actual_length
+            ),
+            Self::IncompatibleDimensions { first, second } => write!(
+                f,
+                "Dimensions {:?} and {:?} can not be joined along their first dimension",
+                first, second
+            ),
+            Self::InvalidAxes { dimensions, axes } => write!(
+                f,
+                "Axes {:?} are no permutation of the dimensions {:?}",
+                axes, dimensions
+            ),
+            Self::IndexOutOfBounds { dimensions, index } => write!(
+                f,
+                "Given index {:?} is out of bounds of dimensions {:?}",
+                index, dimensions
+            ),
+            Self::SliceOutOfBounds {
+                dimensions,
+                axis,
+                range,
+            } => write!(
+                f,
+                "Range {:?} along axis {} is out of bounds of dimensions {:?}",
+                range, axis, dimensions
+            ),
+            Self::InvalidBoundaries { index } => {
+                write!(f, "Boundaries at index {} are empty or invalid", index)
+            }
         }
     }
 }
+#[cfg(feature = "std")]
 impl std::error::Error for SpaceError {}
 
+/// Describes the first reason why a space or position does not fit into another space.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SpaceMismatch {
+    ShapeMismatch {
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
+    BoundariesTypeMismatch {
+        index: usize,
+        expected: DimensionBoundaries,
+        actual: DimensionBoundaries,
+    },
+    ValueTypeMismatch {
+        index: usize,
+        boundaries: DimensionBoundaries,
+        value: DimensionValue,
+    },
+    ValueOutOfBounds {
+        index: usize,
+        boundaries: DimensionBoundaries,
+        value: DimensionValue,
+    },
+}
+
+impl core::fmt::Display for SpaceMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ShapeMismatch { expected, actual } => {
+                write!(f, "Expected dimensions {:?} but got {:?}", expected, actual)
+            }
+            Self::BoundariesTypeMismatch {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Boundaries {:?} at index {} do not match expected {:?}",
+                actual, index, expected
+            ),
+            Self::ValueTypeMismatch {
+                index,
+                boundaries,
+                value,
+            } => write!(
+                f,
+                "Value {:?} at index {} does not match the type of {:?}",
+                value, index, boundaries
+            ),
+            Self::ValueOutOfBounds {
+                index,
+                boundaries,
+                value,
+            } => write!(
+                f,
+                "Value {:?} at index {} is outside of {:?}",
+                value, index, boundaries
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SpaceMismatch {}
+
+/// Checks that every value has the type of its boundaries and lies inside them.
+fn check_values(
+    boundaries: &[DimensionBoundaries],
+    values: impl Iterator<Item = DimensionValue>,
+) -> Result<(), SpaceMismatch> {
+    boundaries
+        .iter()
+        .zip(values)
+        .enumerate()
+        .try_for_each(|(index, (boundaries, value))| {
+            if !boundaries.matches_value(&value) {
+                Err(SpaceMismatch::ValueTypeMismatch {
+                    index,
+                    boundaries: *boundaries,
+                    value,
+                })
+            } else if !boundaries.contains(&value) {
+                Err(SpaceMismatch::ValueOutOfBounds {
+                    index,
+                    boundaries: *boundaries,
+                    value,
+                })
+            } else {
+                Ok(())
+            }
+        })
+}
+
 /* --- --- --- FORMAT --- --- --- */
 
 /// Specific errors applicable to the Format structure.
@@ -256,56 +628,96 @@ impl std::error::Error for SpaceError {}
 pub enum FormatError {
     KeyAlreadyExistsInFormat(String),
     KeyNotFoundInFormat(String),
-    SpaceCreationError(SpaceError),
-    PositionCreationError(SpaceError),
-    GivenSpaceDoesNotFit { needed: usize, given: usize },
-    SpaceIndexError(SpaceError),
-    PositionIndexError(SpaceError),
+    SpaceCreationError {
+        key: String,
+        error: SpaceError,
+    },
+    PositionCreationError {
+        key: String,
+        error: SpaceError,
+    },
+    GivenSpaceDoesNotFit {
+        key: Option<String>,
+        needed: usize,
+        given: usize,
+    },
+    SpaceIndexError {
+        key: String,
+        error: SpaceError,
+    },
+    PositionIndexError {
+        key: String,
+        error: SpaceError,
+    },
 }
 
-impl std::fmt::Display for FormatError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::KeyAlreadyExistsInFormat(key) => {
                 write!(f, "Key \"{}\" has already been added to this format", key)
             }
             Self::KeyNotFoundInFormat(key) => write!(f, "Key \"{}\" not found in format", key),
-            Self::SpaceCreationError(space_error) => write!(
+            Self::SpaceCreationError { key, error } => write!(
+                f,
+                "Space Error \"{}\" occurred while creation of space for key \"{}\"",
+                error, key
+            ),
+            Self::PositionCreationError { key, error } => write!(
                 f,
-                "Space Error \"{}\" occurred while creation of space",
-                space_error
+                "Space Error \"{}\" occurred while creation of position for key \"{}\"",
+                error, key
             ),
-            Self::PositionCreationError(space_error) => write!(
+            Self::GivenSpaceDoesNotFit {
+                key: Some(key),
+                needed,
+                given,
+            } => write!(
                 f,
-                "Space Error \"{}\" occurred while creation of position",
-                space_error
+                "Given space ({}) does not fit needed ({}) for key \"{}\"",
+                given, needed, key
             ),
-            Self::GivenSpaceDoesNotFit { needed, given } => write!(
+            Self::GivenSpaceDoesNotFit {
+                key: None,
+                needed,
+                given,
+            } => write!(
                 f,
                 "Given space ({}) does not fit needed ({})",
                 given, needed
             ),
-            Self::SpaceIndexError(space_error) => write!(
+            Self::SpaceIndexError { key, error } => write!(
                 f,
-                "Space Error \"{}\" occurred while indexing of space",
-                space_error
+                "Space Error \"{}\" occurred while indexing of space for key \"{}\"",
+                error, key
             ),
-            Self::PositionIndexError(space_error) => write!(
+            Self::PositionIndexError { key, error } => write!(
                 f,
-                "Space Error \"{}\" occurred while indexing of position",
-                space_error
+                "Space Error \"{}\" occurred while indexing of position for key \"{}\"",
+                error, key
             ),
         }
     }
 }
 
-impl std::error::Error for FormatError {}
+#[cfg(feature = "std")]
+impl std::error::Error for FormatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::SpaceCreationError { error, .. }
+            | Self::PositionCreationError { error, .. }
+            | Self::SpaceIndexError { error, .. }
+            | Self::PositionIndexError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
 
-#[derive(Serialize, Deserialize)]
 struct SubFormat {
     offset: usize,
     shape: Vec<usize>,
     length: usize,
+    nested: Option<Format>,
 }
 
 impl SubFormat {
@@ -315,43 +727,361 @@ impl SubFormat {
             offset,
             shape,
             length,
+            nested: None,
+        }
+    }
+
+    fn nested(offset: usize, format: Format) -> Self {
+        Self {
+            offset,
+            shape: vec![format.length],
+            length: format.length,
+            nested: Some(format),
         }
     }
 }
 
 /// Structure to define irregular structures (Read the bottom of the module description).
-#[derive(Default, Serialize, Deserialize)]
+///
+/// The keys keep the order in which they have been added, also when serialized.
+#[derive(Default)]
 pub struct Format {
-    v: HashMap<String, SubFormat>,
+    v: Vec<(String, SubFormat)>,
     length: usize,
 }
 
 impl Format {
-    /* Allowing clippy::map_entry, because if the key exists a error should be returned.
-     * Map_entry does not provide such way or I didn't see that.
-     */
-    #[allow(clippy::map_entry)]
-    pub fn add(&mut self, key: String, shape: Vec<usize>) -> Result<(), FormatError> {
-        if self.v.contains_key(&key) {
+    fn get(&self, key: &str) -> Option<&SubFormat> {
+        self.v
+            .iter()
+            .find(|(sf_key, _)| sf_key == key)
+            .map(|(_, sf)| sf)
+    }
+
+    fn insert(&mut self, key: String, sub_format: SubFormat) -> Result<(), FormatError> {
+        if self.get(&key).is_some() {
             Err(FormatError::KeyAlreadyExistsInFormat(key))
         } else {
-            let sub_format = SubFormat::new(self.length, shape);
             self.length += sub_format.length;
-            self.v.insert(key, sub_format);
+            self.v.push((key, sub_format));
             Ok(())
         }
     }
 
+    pub fn add(&mut self, key: String, shape: Vec<usize>) -> Result<(), FormatError> {
+        self.insert(key, SubFormat::new(self.length, shape))
+    }
+
+    /// Adds a whole format under the given key.
+    ///
+    /// Its keys become reachable as `"<key>.<inner key>"` and the key itself addresses the
+    /// whole block as a one dimensional structure.
+    ///
+    /// ```
+    /// use gymnarium_base::space::Format;
+    ///
+    /// let mut camera = Format::default();
+    /// camera.add("0".to_string(), vec![10, 10, 3]).unwrap();
+    /// camera.add("1".to_string(), vec![10, 10, 3]).unwrap();
+    ///
+    /// let mut sensors = Format::default();
+    /// sensors.add("position".to_string(), vec![3]).unwrap();
+    /// sensors.add_format("front_cam".to_string(), camera).unwrap();
+    ///
+    /// let mut format = Format::default();
+    /// format.add("charge".to_string(), vec![1]).unwrap();
+    /// format.add_format("sensors".to_string(), sensors).unwrap();
+    ///
+    /// assert_eq!(Some(&vec![10, 10, 3]), format.shape_of("sensors.front_cam.1"));
+    /// assert_eq!(Some(&vec![603]), format.shape_of("sensors"));
+    /// assert_eq!(Some(304), format.offset_of("sensors.front_cam.1"));
+    /// ```
+    pub fn add_format(&mut self, key: String, format: Format) -> Result<(), FormatError> {
+        self.insert(key, SubFormat::nested(self.length, format))
+    }
+
+    /// Finds the sub format for the given key and its offset inside the whole structure.
+    ///
+    /// Keys added directly are preferred, afterwards the key is resolved through nested formats
+    /// at every dot.
+    fn resolve(&self, key: &str) -> Option<(usize, &SubFormat)> {
+        if let Some(sf) = self.get(key) {
+            return Some((sf.offset, sf));
+        }
+        key.match_indices('.').find_map(|(index, _)| {
+            let sf = self.get(&key[..index])?;
+            let (inner_offset, inner_sf) = sf.nested.as_ref()?.resolve(&key[index + 1..])?;
+            Some((sf.offset + inner_offset, inner_sf))
+        })
+    }
+
     pub fn contains(&self, key: &str) -> bool {
-        self.v.contains_key(key)
+        self.resolve(key).is_some()
     }
 
     pub fn shape_of(&self, key: &str) -> Option<&Vec<usize>> {
-        self.v.get(key).map(|sf| &sf.shape)
+        self.resolve(key).map(|(_, sf)| &sf.shape)
+    }
+
+    /// Returns the index of the first element of the given key inside the whole structure.
+    pub fn offset_of(&self, key: &str) -> Option<usize> {
+        self.resolve(key).map(|(offset, _)| offset)
+    }
+
+    /// Returns the format added under the given key with `add_format`.
+    pub fn sub_format(&self, key: &str) -> Option<&Format> {
+        self.resolve(key).and_then(|(_, sf)| sf.nested.as_ref())
     }
 
+    /// Returns the keys added to this format in insertion order, without the keys of nested
+    /// formats.
     pub fn keys(&self) -> Vec<&String> {
-        self.v.keys().collect()
+        self.v.iter().map(|(key, _)| key).collect()
+    }
+
+    /// Iterates over every key in insertion order together with its shape and its offset inside
+    /// the whole structure.
+    ///
+    /// Nested formats are expanded, so their keys are returned as `"<key>.<inner key>"`.
+    pub fn iter(&self) -> impl Iterator<Item = (String, &Vec<usize>, usize)> + '_ {
+        let mut entries = Vec::new();
+        self.collect_entries("", 0, false, &mut entries);
+        entries.into_iter()
+    }
+
+    fn collect_entries<'a>(
+        &'a self,
+        prefix: &str,
+        offset: usize,
+        include_nested: bool,
+        entries: &mut Vec<(String, &'a Vec<usize>, usize)>,
+    ) {
+        for (key, sf) in &self.v {
+            let full_key = format!("{}{}", prefix, key);
+            match &sf.nested {
+                Some(format) => {
+                    if include_nested {
+                        entries.push((full_key.clone(), &sf.shape, offset + sf.offset));
+                    }
+                    format.collect_entries(
+                        &format!("{}.", full_key),
+                        offset + sf.offset,
+                        include_nested,
+                        entries,
+                    )
+                }
+                None => entries.push((full_key, &sf.shape, offset + sf.offset)),
+            }
+        }
+    }
+
+    /// Binds the position to this format for access by key.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionValue, Format, Position};
+    ///
+    /// let mut format = Format::default();
+    /// format.add("charge".to_string(), vec![1]).unwrap();
+    /// format.add("sensors.position".to_string(), vec![3]).unwrap();
+    ///
+    /// let position = Position::simple((0..4).map(DimensionValue::from).collect());
+    /// let view = format.view(&position).unwrap();
+    ///
+    /// assert_eq!(Ok(DimensionValue::from(2)), view["sensors.position"].get(&[1]));
+    /// ```
+    pub fn view<'a>(
+        &'a self,
+        position: &'a Position,
+    ) -> Result<FormattedPosition<'a>, FormatError> {
+        self.check_fits(position)?;
+        let mut entries = Vec::new();
+        self.collect_entries("", 0, true, &mut entries);
+        Ok(FormattedPosition {
+            entries: entries
+                .into_iter()
+                .map(|(key, shape, offset)| {
+                    let values = position
+                        .values
+                        .slice(offset..offset + shape.iter().product::<usize>());
+                    (key, SubPosition { values, shape })
+                })
+                .collect(),
+        })
+    }
+
+    /// Binds the position mutably to this format for access by key.
+    ///
+    /// Only keys which do not point to a whole nested format can be accessed. Values are set like
+    /// with `Position::set_value`, so that positions storing values of a single type keep doing
+    /// so, see `Position::as_f32_slice`.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionValue, Format, Position};
+    ///
+    /// let mut format = Format::default();
+    /// format.add("charge".to_string(), vec![1]).unwrap();
+    /// format.add("sensors.position".to_string(), vec![3]).unwrap();
+    ///
+    /// let mut position = Position::from_i32_values(vec![0; 4], vec![4]).unwrap();
+    /// let mut view = format.view_mut(&mut position).unwrap();
+    /// let mut sensors = view.get_mut("sensors.position").unwrap();
+    /// sensors.set(&[2], DimensionValue::from(7)).unwrap();
+    /// sensors.as_i32_slice_mut().unwrap()[0] = 5;
+    ///
+    /// assert_eq!(Some(&[0, 5, 0, 7][..]), position.as_i32_slice());
+    /// ```
+    pub fn view_mut<'a>(
+        &'a self,
+        position: &'a mut Position,
+    ) -> Result<FormattedPositionMut<'a>, FormatError> {
+        self.check_fits(position)?;
+        Ok(FormattedPositionMut {
+            entries: self
+                .iter()
+                .map(|(key, shape, offset)| (key, &shape[..], offset))
+                .collect(),
+            values: position.values_mut(),
+        })
+    }
+
+    fn check_fits(&self, position: &Position) -> Result<(), FormatError> {
+        if position.values.len() == self.length {
+            Ok(())
+        } else {
+            Err(FormatError::GivenSpaceDoesNotFit {
+                key: None,
+                needed: self.length,
+                given: position.values.len(),
+            })
+        }
+    }
+
+    /// Removes the given key (which may point into a nested format) and moves all following keys
+    /// forward, so that no gap is left.
+    ///
+    /// Spaces and positions created with this format beforehand do not fit it anymore.
+    pub fn remove(&mut self, key: &str) -> Result<(), FormatError> {
+        self.remove_returning_length(key)
+            .map(|_| ())
+            .ok_or_else(|| FormatError::KeyNotFoundInFormat(key.to_string()))
+    }
+
+    fn remove_returning_length(&mut self, key: &str) -> Option<usize> {
+        let (index, removed_length) = match self.v.iter().position(|(sf_key, _)| sf_key == key) {
+            Some(index) => {
+                let (_, sf) = self.v.remove(index);
+                (index, sf.length)
+            }
+            None => key.match_indices('.').find_map(|(dot, _)| {
+                let index = self
+                    .v
+                    .iter()
+                    .position(|(sf_key, _)| sf_key == &key[..dot])?;
+                let sf = &mut self.v[index].1;
+                let removed_length = sf
+                    .nested
+                    .as_mut()?
+                    .remove_returning_length(&key[dot + 1..])?;
+                sf.length -= removed_length;
+                sf.shape = vec![sf.length];
+                Some((index + 1, removed_length))
+            })?,
+        };
+        for (_, sf) in self.v.iter_mut().skip(index) {
+            sf.offset -= removed_length;
+        }
+        self.length -= removed_length;
+        Some(removed_length)
+    }
+
+    /// Appends all keys of the other format, prefixed with `"<prefix>."` unless the prefix is
+    /// empty.
+    ///
+    /// Nothing is added if any of the resulting keys already exists.
+    pub fn merge(&mut self, other: Format, prefix: &str) -> Result<(), FormatError> {
+        let prefixed_key = |key: String| {
+            if prefix.is_empty() {
+                key
+            } else {
+                format!("{}.{}", prefix, key)
+            }
+        };
+        if let Some((key, _)) = other
+            .v
+            .iter()
+            .find(|(key, _)| self.get(&prefixed_key(key.clone())).is_some())
+        {
+            return Err(FormatError::KeyAlreadyExistsInFormat(prefixed_key(
+                key.clone(),
+            )));
+        }
+        for (key, sf) in other.v {
+            match sf.nested {
+                Some(format) => self.add_format(prefixed_key(key), format)?,
+                None => self.add(prefixed_key(key), sf.shape)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of elements a space or position needs to hold this format.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Describes the keys, shapes and offsets of this format as JSON schema, so that external
+    /// tools can introspect the layout of positions built with it.
+    ///
+    /// Every key is an array property with the custom keywords `x-shape`, `x-offset` (inside the
+    /// whole structure) and `x-length`, nested formats are object properties.
+    ///
+    /// ```
+    /// use gymnarium_base::space::Format;
+    ///
+    /// let mut format = Format::default();
+    /// format.add("position".to_string(), vec![3]).unwrap();
+    /// format.add("camera".to_string(), vec![2, 2]).unwrap();
+    ///
+    /// let schema = format.to_json_schema();
+    /// assert_eq!(4, schema["properties"]["camera"]["x-length"]);
+    /// assert_eq!(3, schema["properties"]["camera"]["x-offset"]);
+    /// ```
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        let mut schema = self.json_schema_with_offset(0);
+        schema["$schema"] = "http://json-schema.org/draft-07/schema#".into();
+        schema
+    }
+
+    fn json_schema_with_offset(&self, offset: usize) -> serde_json::Value {
+        let properties: serde_json::Map<String, serde_json::Value> = self
+            .v
+            .iter()
+            .map(|(key, sf)| {
+                let schema = match &sf.nested {
+                    Some(format) => format.json_schema_with_offset(offset + sf.offset),
+                    None => serde_json::json!({
+                        "type": "array",
+                        "minItems": sf.length,
+                        "maxItems": sf.length,
+                        "x-shape": sf.shape,
+                        "x-offset": offset + sf.offset,
+                        "x-length": sf.length,
+                    }),
+                };
+                (key.clone(), schema)
+            })
+            .collect();
+        serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": self.keys(),
+            "x-offset": offset,
+            "x-length": self.length,
+        })
     }
 
     pub fn new_space(&self) -> Space {
@@ -359,13 +1089,18 @@ impl Format {
     }
 
     pub fn get_subspace(&self, space: &Space, key: &str) -> Result<Space, FormatError> {
-        let subformat = self.v.get(key);
-        if let Some(sf) = subformat {
+        let subformat = self.resolve(key);
+        if let Some((offset, sf)) = subformat {
             let mut space_values = Vec::new();
-            for index in sf.offset..(sf.offset + sf.length) {
+            for index in offset..(offset + sf.length) {
                 space_values.push(space.boundaries[index]);
             }
-            Space::new(space_values, sf.shape.clone()).map_err(FormatError::SpaceCreationError)
+            Space::new(space_values, sf.shape.clone()).map_err(|error| {
+                FormatError::SpaceCreationError {
+                    key: key.to_string(),
+                    error,
+                }
+            })
         } else {
             Err(FormatError::KeyNotFoundInFormat(key.to_string()))
         }
@@ -377,15 +1112,16 @@ impl Format {
         key: &str,
         subspace: Space,
     ) -> Result<(), FormatError> {
-        let subformat = self.v.get(key);
-        if let Some(sf) = subformat {
+        let subformat = self.resolve(key);
+        if let Some((offset, sf)) = subformat {
             if subspace.boundaries.len() == sf.length {
                 for index in 0..sf.length {
-                    space.boundaries[sf.offset + index] = subspace.boundaries[index];
+                    space.boundaries[offset + index] = subspace.boundaries[index];
                 }
                 Ok(())
             } else {
                 Err(FormatError::GivenSpaceDoesNotFit {
+                    key: Some(key.to_string()),
                     needed: sf.length,
                     given: subspace.boundaries.len(),
                 })
@@ -401,11 +1137,15 @@ impl Format {
         key: &str,
         index: &[usize],
     ) -> Result<&'a DimensionBoundaries, FormatError> {
-        let subformat = self.v.get(key);
-        if let Some(sf) = subformat {
-            let current_index =
-                calculate_index(&sf.shape, index).map_err(FormatError::SpaceIndexError)?;
-            Ok(&space.boundaries[sf.offset + current_index])
+        let subformat = self.resolve(key);
+        if let Some((offset, sf)) = subformat {
+            let current_index = calculate_index(&sf.shape, index).map_err(|error| {
+                FormatError::SpaceIndexError {
+                    key: key.to_string(),
+                    error,
+                }
+            })?;
+            Ok(&space.boundaries[offset + current_index])
         } else {
             Err(FormatError::KeyNotFoundInFormat(key.to_string()))
         }
@@ -418,11 +1158,15 @@ impl Format {
         index: &[usize],
         boundaries: DimensionBoundaries,
     ) -> Result<(), FormatError> {
-        let subformat = self.v.get(key);
-        if let Some(sf) = subformat {
-            let current_index =
-                calculate_index(&sf.shape, index).map_err(FormatError::SpaceIndexError)?;
-            space.boundaries[sf.offset + current_index] = boundaries;
+        let subformat = self.resolve(key);
+        if let Some((offset, sf)) = subformat {
+            let current_index = calculate_index(&sf.shape, index).map_err(|error| {
+                FormatError::SpaceIndexError {
+                    key: key.to_string(),
+                    error,
+                }
+            })?;
+            space.boundaries[offset + current_index] = boundaries;
             Ok(())
         } else {
             Err(FormatError::KeyNotFoundInFormat(key.to_string()))
@@ -430,14 +1174,18 @@ impl Format {
     }
 
     pub fn get_subposition(&self, position: &Position, key: &str) -> Result<Position, FormatError> {
-        let subformat = self.v.get(key);
-        if let Some(sf) = subformat {
-            let mut position_values = Vec::new();
-            for index in sf.offset..(sf.offset + sf.length) {
-                position_values.push(position.values[index]);
-            }
-            Position::new(position_values, sf.shape.clone())
-                .map_err(FormatError::PositionCreationError)
+        let subformat = self.resolve(key);
+        if let Some((offset, sf)) = subformat {
+            let values = position
+                .values
+                .slice(offset..offset + sf.length)
+                .to_values();
+            Position::with_values(values, sf.shape.clone()).map_err(|error| {
+                FormatError::PositionCreationError {
+                    key: key.to_string(),
+                    error,
+                }
+            })
         } else {
             Err(FormatError::KeyNotFoundInFormat(key.to_string()))
         }
@@ -449,15 +1197,16 @@ impl Format {
         key: &str,
         subposition: Position,
     ) -> Result<(), FormatError> {
-        let subformat = self.v.get(key);
-        if let Some(sf) = subformat {
+        let subformat = self.resolve(key);
+        if let Some((offset, sf)) = subformat {
             if subposition.values.len() == sf.length {
-                for index in 0..sf.length {
-                    position.values[sf.offset + index] = subposition.values[index];
+                for (index, value) in subposition.values.iter().enumerate() {
+                    position.values_mut().set(offset + index, value);
                 }
                 Ok(())
             } else {
                 Err(FormatError::GivenSpaceDoesNotFit {
+                    key: Some(key.to_string()),
                     given: subposition.values.len(),
                     needed: sf.length,
                 })
@@ -467,17 +1216,37 @@ impl Format {
         }
     }
 
+    pub fn value(
+        &self,
+        position: &Position,
+        key: &str,
+        index: &[usize],
+    ) -> Result<DimensionValue, FormatError> {
+        self.inner_index(key, index)
+            .map(|index| position.values.get(index))
+    }
+
+    /// Borrows the value like `Position::get_value`.
     pub fn get_value<'a>(
         &self,
         position: &'a Position,
         key: &str,
         index: &[usize],
     ) -> Result<&'a DimensionValue, FormatError> {
-        let subformat = self.v.get(key);
-        if let Some(sf) = subformat {
-            let current_index =
-                calculate_index(&sf.shape, index).map_err(FormatError::PositionIndexError)?;
-            Ok(&position.values[sf.offset + current_index])
+        self.inner_index(key, index)
+            .map(|index| &position.tagged_values()[index])
+    }
+
+    fn inner_index(&self, key: &str, index: &[usize]) -> Result<usize, FormatError> {
+        let subformat = self.resolve(key);
+        if let Some((offset, sf)) = subformat {
+            let current_index = calculate_index(&sf.shape, index).map_err(|error| {
+                FormatError::PositionIndexError {
+                    key: key.to_string(),
+                    error,
+                }
+            })?;
+            Ok(offset + current_index)
         } else {
             Err(FormatError::KeyNotFoundInFormat(key.to_string()))
         }
@@ -490,118 +1259,813 @@ impl Format {
         index: &[usize],
         value: DimensionValue,
     ) -> Result<(), FormatError> {
-        let subformat = self.v.get(key);
-        if let Some(sf) = subformat {
-            let current_index =
-                calculate_index(&sf.shape, index).map_err(FormatError::PositionIndexError)?;
-            position.values[sf.offset + current_index] = value;
-            Ok(())
-        } else {
-            Err(FormatError::KeyNotFoundInFormat(key.to_string()))
-        }
+        let index = self.inner_index(key, index)?;
+        position.values_mut().set(index, value);
+        Ok(())
     }
 }
 
-/* --- --- --- SPACE --- --- --- */
-
-/// Defines a space in which states or positions can be placed.
-#[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
-pub struct Space {
-    boundaries: Vec<DimensionBoundaries>,
-    dimensions: Vec<usize>,
+/// Entry of a serialized `Format`, either the shape of a key or a nested format.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SerializedSubFormat {
+    Shape(Vec<usize>),
+    Format(Format),
 }
 
-impl Space {
-    pub fn new(
-        dimension_boundaries: Vec<DimensionBoundaries>,
-        dimensions: Vec<usize>,
-    ) -> Result<Self, SpaceError> {
-        if dimensions.iter().product::<usize>() == dimension_boundaries.len() {
-            Ok(Self {
-                boundaries: dimension_boundaries,
-                dimensions,
-            })
-        } else {
-            Err(SpaceError::GivenDimensionsDoNotMatch)
+impl Serialize for Format {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.v.len()))?;
+        for (key, sf) in &self.v {
+            match &sf.nested {
+                Some(format) => map.serialize_entry(key, format)?,
+                None => map.serialize_entry(key, &sf.shape)?,
+            }
         }
+        map.end()
     }
+}
 
-    pub fn all(dimension_boundaries: DimensionBoundaries, dimensions: Vec<usize>) -> Self {
-        Self {
-            boundaries: vec![dimension_boundaries; dimensions.iter().product()],
-            dimensions,
-        }
-    }
+impl<'de> Deserialize<'de> for Format {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FormatVisitor;
 
-    pub fn simple(dimension_boundaries: Vec<DimensionBoundaries>) -> Self {
-        let length = dimension_boundaries.len();
-        Self {
-            boundaries: dimension_boundaries,
-            dimensions: vec![length],
+        impl<'de> Visitor<'de> for FormatVisitor {
+            type Value = Format;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "a map of keys to shapes or nested formats")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut format = Format::default();
+                while let Some((key, entry)) = map.next_entry::<String, SerializedSubFormat>()? {
+                    match entry {
+                        SerializedSubFormat::Shape(shape) => format.add(key, shape),
+                        SerializedSubFormat::Format(nested) => format.add_format(key, nested),
+                    }
+                    .map_err(A::Error::custom)?;
+                }
+                Ok(format)
+            }
         }
+
+        deserializer.deserialize_map(FormatVisitor)
     }
+}
 
-    pub fn simple_all(dimension_boundaries: DimensionBoundaries, times: usize) -> Self {
-        Self {
-            boundaries: vec![dimension_boundaries; times],
-            dimensions: vec![times],
-        }
+/* --- --- --- FORMATTED POSITION --- --- --- */
+
+/// Read-only view on the values of a single key of a `FormattedPosition`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SubPosition<'a> {
+    values: ValuesRef<'a>,
+    shape: &'a [usize],
+}
+
+impl<'a> SubPosition<'a> {
+    pub fn shape(&self) -> &'a [usize] {
+        self.shape
     }
 
-    pub fn dimensions(&self) -> &Vec<usize> {
-        &self.dimensions
+    /// Returns the values, only copying them if the position stores values of a single type.
+    pub fn values(&self) -> Cow<'a, [DimensionValue]> {
+        self.values.to_dimension_values()
     }
 
-    pub fn get_boundary(&self, index: &[usize]) -> &DimensionBoundaries {
-        &self[index]
+    pub fn get(&self, index: &[usize]) -> Result<DimensionValue, SpaceError> {
+        Ok(self.values.get(calculate_index(self.shape, index)?))
     }
 
-    pub fn set_boundary(&mut self, index: &[usize], boundary: DimensionBoundaries) {
-        self[index] = boundary;
+    /// Returns the values as contiguous floats, like `Position::as_f32_slice`.
+    pub fn as_f32_slice(&self) -> Option<&'a [f32]> {
+        self.values.as_f32_slice()
     }
 
-    pub fn get_boundaries(&self) -> &[DimensionBoundaries] {
-        &self.boundaries
+    /// Returns the values as contiguous integers, like `Position::as_i32_slice`.
+    pub fn as_i32_slice(&self) -> Option<&'a [i32]> {
+        self.values.as_i32_slice()
     }
 
-    pub fn sample(&self) -> Position {
+    pub fn to_position(&self) -> Position {
         Position {
-            values: self
-                .boundaries
-                .iter()
-                .map(|boundaries| boundaries.sample())
-                .collect(),
-            dimensions: self.dimensions.clone(),
+            values: self.values.to_values(),
+            dimensions: self.shape.to_vec(),
+            tagged: TaggedValuesCache::default(),
         }
     }
+}
 
-    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Position {
-        Position {
-            values: self
-                .boundaries
-                .iter()
-                .map(|boundaries| boundaries.sample_with(rng))
-                .collect(),
-            dimensions: self.dimensions.clone(),
-        }
+/// Mutable view on the values of a single key of a `FormattedPositionMut`.
+#[derive(Debug, PartialEq)]
+pub struct SubPositionMut<'a> {
+    values: &'a mut Values,
+    offset: usize,
+    shape: &'a [usize],
+}
+
+impl SubPositionMut<'_> {
+    pub fn shape(&self) -> &[usize] {
+        self.shape
     }
 
-    pub fn matches(&self, other: &Space) -> bool {
-        self.dimensions == other.dimensions
-            && self
-                .boundaries
-                .iter()
-                .zip(other.boundaries.iter())
-                .any(|(a, b)| !a.matches(b))
+    /// Returns the values, only copying them if the position stores values of a single type.
+    pub fn values(&self) -> Cow<'_, [DimensionValue]> {
+        self.as_sub_position().values()
     }
 
-    pub fn contains(&self, other: &Position) -> bool {
-        self.dimensions == other.dimensions
-            && self
-                .boundaries
+    pub fn get(&self, index: &[usize]) -> Result<DimensionValue, SpaceError> {
+        self.as_sub_position().get(index)
+    }
+
+    /// Stores the value, switching the whole position to mixed storage if its type differs from
+    /// the stored one.
+    pub fn set(&mut self, index: &[usize], value: DimensionValue) -> Result<(), SpaceError> {
+        let index = calculate_index(self.shape, index)?;
+        self.values.set(self.offset + index, value);
+        Ok(())
+    }
+
+    /// Returns the values as contiguous floats, like `Position::as_f32_slice`.
+    pub fn as_f32_slice(&self) -> Option<&[f32]> {
+        self.as_sub_position().as_f32_slice()
+    }
+
+    /// Returns the values as contiguous integers, like `Position::as_i32_slice`.
+    pub fn as_i32_slice(&self) -> Option<&[i32]> {
+        self.as_sub_position().as_i32_slice()
+    }
+
+    /// Returns the values as mutable contiguous floats, if the position stores only floats.
+    pub fn as_f32_slice_mut(&mut self) -> Option<&mut [f32]> {
+        let range = self.range();
+        self.values.f32_slice_mut(range)
+    }
+
+    /// Returns the values as mutable contiguous integers, if the position stores only integers.
+    pub fn as_i32_slice_mut(&mut self) -> Option<&mut [i32]> {
+        let range = self.range();
+        self.values.i32_slice_mut(range)
+    }
+
+    pub fn to_position(&self) -> Position {
+        self.as_sub_position().to_position()
+    }
+
+    fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.shape.iter().product::<usize>()
+    }
+
+    fn as_sub_position(&self) -> SubPosition<'_> {
+        SubPosition {
+            values: self.values.slice(self.range()),
+            shape: self.shape,
+        }
+    }
+}
+
+/// A position bound to a `Format`, created with `Format::view`.
+#[derive(Debug)]
+pub struct FormattedPosition<'a> {
+    entries: Vec<(String, SubPosition<'a>)>,
+}
+
+impl<'a> FormattedPosition<'a> {
+    pub fn get(&self, key: &str) -> Option<&SubPosition<'a>> {
+        self.entries
+            .iter()
+            .find(|(entry_key, _)| entry_key == key)
+            .map(|(_, sub_position)| sub_position)
+    }
+}
+
+impl<'a> Index<&str> for FormattedPosition<'a> {
+    type Output = SubPosition<'a>;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        self.get(key)
+            .unwrap_or_else(|| panic!("Key \"{}\" not found in format", key))
+    }
+}
+
+/// A position mutably bound to a `Format`, created with `Format::view_mut`.
+#[derive(Debug)]
+pub struct FormattedPositionMut<'a> {
+    values: &'a mut Values,
+    entries: Vec<(String, &'a [usize], usize)>,
+}
+
+impl<'a> FormattedPositionMut<'a> {
+    pub fn get(&self, key: &str) -> Option<SubPosition<'_>> {
+        self.entry(key).map(|(shape, offset)| SubPosition {
+            values: self
+                .values
+                .slice(offset..offset + shape.iter().product::<usize>()),
+            shape,
+        })
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<SubPositionMut<'_>> {
+        let (shape, offset) = self.entry(key)?;
+        Some(SubPositionMut {
+            values: self.values,
+            offset,
+            shape,
+        })
+    }
+
+    fn entry(&self, key: &str) -> Option<(&'a [usize], usize)> {
+        self.entries
+            .iter()
+            .find(|(entry_key, _, _)| entry_key == key)
+            .map(|(_, shape, offset)| (*shape, *offset))
+    }
+}
+
+/* --- --- --- SPACE --- --- --- */
+
+/// Names, units and category labels of a part of a space.
+type SelectedLabels = (
+    Option<Vec<String>>,
+    Option<Vec<String>>,
+    Option<Vec<Vec<String>>>,
+);
+
+/// Defines a space in which states or positions can be placed.
+#[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Space {
+    boundaries: Storage<DimensionBoundaries>,
+    dimensions: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    names: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    units: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    categories: Option<Vec<Vec<String>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    catalog: Option<ActionCatalog>,
+}
+
+impl Space {
+    pub fn builder() -> SpaceBuilder {
+        SpaceBuilder::new()
+    }
+
+    pub fn new(
+        dimension_boundaries: Vec<DimensionBoundaries>,
+        dimensions: Vec<usize>,
+    ) -> Result<Self, SpaceError> {
+        if dimensions.iter().product::<usize>() == dimension_boundaries.len() {
+            Ok(Self {
+                boundaries: into_storage(dimension_boundaries),
+                dimensions,
+                names: None,
+                units: None,
+                categories: None,
+                catalog: None,
+            })
+        } else {
+            Err(SpaceError::GivenDimensionsDoNotMatch {
+                expected_length: dimensions.iter().product(),
+                actual_length: dimension_boundaries.len(),
+                dimensions,
+            })
+        }
+    }
+
+    pub fn all(dimension_boundaries: DimensionBoundaries, dimensions: Vec<usize>) -> Self {
+        Self {
+            boundaries: core::iter::repeat_n(dimension_boundaries, dimensions.iter().product())
+                .collect(),
+            dimensions,
+            names: None,
+            units: None,
+            categories: None,
+            catalog: None,
+        }
+    }
+
+    pub fn simple(dimension_boundaries: Vec<DimensionBoundaries>) -> Self {
+        let length = dimension_boundaries.len();
+        Self {
+            boundaries: into_storage(dimension_boundaries),
+            dimensions: vec![length],
+            names: None,
+            units: None,
+            categories: None,
+            catalog: None,
+        }
+    }
+
+    pub fn simple_all(dimension_boundaries: DimensionBoundaries, times: usize) -> Self {
+        Self {
+            boundaries: core::iter::repeat_n(dimension_boundaries, times).collect(),
+            dimensions: vec![times],
+            names: None,
+            units: None,
+            categories: None,
+            catalog: None,
+        }
+    }
+
+    /// Creates a space of `n` flags, each either `0` or `1`.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionBoundaries, Space};
+    ///
+    /// assert_eq!(
+    ///     Space::simple_all(DimensionBoundaries::Integer(0, 1), 3),
+    ///     Space::multi_binary(3)
+    /// );
+    /// ```
+    pub fn multi_binary(n: usize) -> Self {
+        Self::simple_all(DimensionBoundaries::Integer(0, 1), n)
+    }
+
+    /// Creates a space where dimension `i` takes one of `ns[i]` values `0` to `ns[i] - 1`.
+    ///
+    /// Every count has to lie between `1` and `i32::MAX`, otherwise the index of the first
+    /// dimension violating this is returned as `SpaceError::InvalidBoundaries`.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionBoundaries, Space, SpaceError};
+    ///
+    /// assert_eq!(
+    ///     Ok(Space::simple(vec![
+    ///         DimensionBoundaries::Integer(0, 4),
+    ///         DimensionBoundaries::Integer(0, 1),
+    ///     ])),
+    ///     Space::multi_discrete(&[5, 2])
+    /// );
+    /// assert_eq!(
+    ///     Err(SpaceError::InvalidBoundaries { index: 1 }),
+    ///     Space::multi_discrete(&[5, 0])
+    /// );
+    /// ```
+    pub fn multi_discrete(ns: &[usize]) -> Result<Self, SpaceError> {
+        ns.iter()
+            .enumerate()
+            .map(|(index, n)| match i32::try_from(*n) {
+                Ok(n) if n > 0 => Ok(DimensionBoundaries::Integer(0, n - 1)),
+                _ => Err(SpaceError::InvalidBoundaries { index }),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::simple)
+    }
+
+    pub fn dimensions(&self) -> &Vec<usize> {
+        &self.dimensions
+    }
+
+    /// Returns how many boundaries lie between two neighbours of each dimension inside the
+    /// row-major storage.
+    pub fn strides(&self) -> Vec<usize> {
+        calculate_strides(&self.dimensions)
+    }
+
+    pub fn get_boundary(&self, index: &[usize]) -> &DimensionBoundaries {
+        &self[index]
+    }
+
+    pub fn set_boundary(&mut self, index: &[usize], boundary: DimensionBoundaries) {
+        self[index] = boundary;
+    }
+
+    pub fn get_boundaries(&self) -> &[DimensionBoundaries] {
+        &self.boundaries
+    }
+
+    /// Attaches a name to every boundary of this space, given in row-major order.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionBoundaries, Space};
+    ///
+    /// let space = Space::simple_all(DimensionBoundaries::from(1f32), 2)
+    ///     .with_dimension_names(vec!["cart_position", "cart_velocity"])
+    ///     .unwrap()
+    ///     .with_dimension_units(vec!["m", "m/s"])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(Some("cart_velocity"), space.dimension_name(&[1]));
+    /// assert_eq!(Some("m/s"), space.dimension_unit(&[1]));
+    /// ```
+    pub fn with_dimension_names<S: Into<String>>(
+        mut self,
+        names: Vec<S>,
+    ) -> Result<Self, SpaceError> {
+        self.names = Some(
+            self.check_labels(names)?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        );
+        Ok(self)
+    }
+
+    /// Attaches a unit to every boundary of this space, given in row-major order.
+    pub fn with_dimension_units<S: Into<String>>(
+        mut self,
+        units: Vec<S>,
+    ) -> Result<Self, SpaceError> {
+        self.units = Some(
+            self.check_labels(units)?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        );
+        Ok(self)
+    }
+
+    /// Attaches the labels of their categories to every boundary of this space, given in
+    /// row-major order.
+    ///
+    /// Categorical boundaries take one label per category or none at all, other boundaries
+    /// never take labels. Otherwise the index of the first boundary violating this is returned
+    /// as `SpaceError::InvalidBoundaries`.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionBoundaries, DimensionValue, Space};
+    ///
+    /// let space = Space::simple(vec![
+    ///     DimensionBoundaries::Categorical(3),
+    ///     DimensionBoundaries::from(0f32..=1f32),
+    /// ])
+    /// .with_categories(vec![vec!["sunny", "rain", "fog"], vec![]])
+    /// .unwrap();
+    ///
+    /// assert_eq!(Some("rain"), space.category(&[0], &DimensionValue::Integer(1)));
+    /// assert_eq!(Some(DimensionValue::Integer(2)), space.category_value(&[0], "fog"));
+    /// assert_eq!(None, space.category_value(&[1], "fog"));
+    /// ```
+    pub fn with_categories<S: Into<String>>(
+        mut self,
+        categories: Vec<Vec<S>>,
+    ) -> Result<Self, SpaceError> {
+        let categories = self
+            .check_labels(categories)?
+            .into_iter()
+            .map(|labels| labels.into_iter().map(Into::into).collect::<Vec<String>>())
+            .collect::<Vec<_>>();
+        if let Some(index) =
+            self.boundaries
                 .iter()
-                .zip(other.values.iter())
-                .any(|(boundaries, value)| boundaries.contains(value))
+                .zip(categories.iter())
+                .position(|(boundaries, labels)| match boundaries {
+                    DimensionBoundaries::Categorical(count) => {
+                        !labels.is_empty() && labels.len() != *count as usize
+                    }
+                    _ => !labels.is_empty(),
+                })
+        {
+            return Err(SpaceError::InvalidBoundaries { index });
+        }
+        self.categories = Some(categories);
+        Ok(self)
+    }
+
+    fn check_labels<T>(&self, labels: Vec<T>) -> Result<Vec<T>, SpaceError> {
+        if labels.len() == self.boundaries.len() {
+            Ok(labels)
+        } else {
+            Err(SpaceError::GivenDimensionsDoNotMatch {
+                dimensions: self.dimensions.clone(),
+                expected_length: self.boundaries.len(),
+                actual_length: labels.len(),
+            })
+        }
+    }
+
+    pub fn dimension_names(&self) -> Option<&[String]> {
+        self.names.as_deref()
+    }
+
+    pub fn dimension_units(&self) -> Option<&[String]> {
+        self.units.as_deref()
+    }
+
+    pub fn dimension_name(&self, index: &[usize]) -> Option<&str> {
+        let index = calculate_index(&self.dimensions, index).ok()?;
+        self.names.as_ref()?.get(index).map(String::as_str)
+    }
+
+    pub fn dimension_unit(&self, index: &[usize]) -> Option<&str> {
+        let index = calculate_index(&self.dimensions, index).ok()?;
+        self.units.as_ref()?.get(index).map(String::as_str)
+    }
+
+    /// Returns the category labels of every boundary, empty for unlabelled ones.
+    pub fn categories(&self) -> Option<&[Vec<String>]> {
+        self.categories.as_deref()
+    }
+
+    /// Returns the label of a categorical value of the boundary at the given index.
+    pub fn category(&self, index: &[usize], value: &DimensionValue) -> Option<&str> {
+        let index = calculate_index(&self.dimensions, index).ok()?;
+        match value {
+            DimensionValue::Integer(value) if *value >= 0 => self
+                .categories
+                .as_ref()?
+                .get(index)?
+                .get(*value as usize)
+                .map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// Returns the categorical value labelled `label` of the boundary at the given index.
+    pub fn category_value(&self, index: &[usize], label: &str) -> Option<DimensionValue> {
+        let index = calculate_index(&self.dimensions, index).ok()?;
+        self.categories
+            .as_ref()?
+            .get(index)?
+            .iter()
+            .position(|known| known == label)
+            .map(|value| DimensionValue::Integer(value as i32))
+    }
+
+    /// Returns the names, units and category labels of the boundaries at the given row-major
+    /// indices.
+    ///
+    /// Labels not covering every index, e.g. after deserializing foreign data, are dropped.
+    fn select_labels(&self, indices: &[usize]) -> SelectedLabels {
+        fn select<T: Clone>(labels: &Option<Vec<T>>, indices: &[usize]) -> Option<Vec<T>> {
+            let labels = labels.as_ref()?;
+            indices.iter().map(|&i| labels.get(i).cloned()).collect()
+        }
+        (
+            select(&self.names, indices),
+            select(&self.units, indices),
+            select(&self.categories, indices),
+        )
+    }
+
+    /// Returns the row-major indices of the boundaries as a value array of this shape.
+    fn boundary_indices(&self) -> Vec<usize> {
+        (0..self.boundaries.len()).collect()
+    }
+
+    /// Joins both spaces along their first dimension.
+    ///
+    /// All other dimensions have to be equal. An empty space is returned as the other space.
+    pub fn concat(&self, other: &Space) -> Result<Self, SpaceError> {
+        if self.boundaries.is_empty() {
+            return Ok(other.clone());
+        } else if other.boundaries.is_empty() {
+            return Ok(self.clone());
+        }
+        let dimensions = concatenate_dimensions(&self.dimensions, &other.dimensions)?;
+        Ok(Self {
+            dimensions,
+            ..self.product(other)
+        })
+    }
+
+    /// Returns the part `range` along dimension `axis` of this space.
+    ///
+    /// E.g. `slice(2, 0..1)` of a `[h, w, 3]` image space returns the `[h, w, 1]` first channel.
+    pub fn slice(&self, axis: usize, range: Range<usize>) -> Result<Self, SpaceError> {
+        let (indices, dimensions) =
+            slice_values(&self.boundary_indices(), &self.dimensions, axis, range)?;
+        let (names, units, categories) = self.select_labels(&indices);
+        Ok(Self {
+            boundaries: indices.iter().map(|&i| self.boundaries[i]).collect(),
+            dimensions,
+            names,
+            units,
+            categories,
+            catalog: self
+                .catalog
+                .as_ref()
+                .map(|catalog| catalog.select(&indices)),
+        })
+    }
+
+    /// Returns this space with other dimensions but the same boundaries in the same order.
+    pub fn reshape(&self, dimensions: Vec<usize>) -> Result<Self, SpaceError> {
+        Ok(Self {
+            names: self.names.clone(),
+            units: self.units.clone(),
+            categories: self.categories.clone(),
+            catalog: self.catalog.clone(),
+            ..Self::new(self.boundaries.to_vec(), dimensions)?
+        })
+    }
+
+    /// Returns this space with rearranged dimensions, so that dimension `i` is the former
+    /// dimension `axes[i]`.
+    ///
+    /// E.g. `transpose(&[2, 0, 1])` turns a `[h, w, c]` image space into a `[c, h, w]` one.
+    pub fn transpose(&self, axes: &[usize]) -> Result<Self, SpaceError> {
+        let (indices, dimensions) =
+            transpose_values(&self.boundary_indices(), &self.dimensions, axes)?;
+        let (names, units, categories) = self.select_labels(&indices);
+        Ok(Self {
+            boundaries: indices.iter().map(|&i| self.boundaries[i]).collect(),
+            dimensions,
+            names,
+            units,
+            categories,
+            catalog: self
+                .catalog
+                .as_ref()
+                .map(|catalog| catalog.select(&indices)),
+        })
+    }
+
+    /// Returns the cartesian product of both spaces as a one dimensional space.
+    ///
+    /// In contrast to `concat` the shapes of both spaces do not matter, they are flattened.
+    /// If only one of both spaces carries names, units, category labels or an action catalog,
+    /// the other one contributes empty ones.
+    pub fn product(&self, other: &Space) -> Self {
+        let mut boundaries = self.boundaries.to_vec();
+        boundaries.extend_from_slice(&other.boundaries);
+        fn join<T: Clone + Default>(
+            own: &Option<Vec<T>>,
+            own_length: usize,
+            theirs: &Option<Vec<T>>,
+            their_length: usize,
+        ) -> Option<Vec<T>> {
+            if own.is_none() && theirs.is_none() {
+                return None;
+            }
+            // Deserialized spaces may carry fewer labels than dimensions.
+            let mut labels = own.clone().unwrap_or_default();
+            labels.resize(own_length, T::default());
+            let mut their_labels = theirs.clone().unwrap_or_default();
+            their_labels.resize(their_length, T::default());
+            labels.append(&mut their_labels);
+            Some(labels)
+        }
+        Self {
+            names: join(
+                &self.names,
+                self.boundaries.len(),
+                &other.names,
+                other.boundaries.len(),
+            ),
+            units: join(
+                &self.units,
+                self.boundaries.len(),
+                &other.units,
+                other.boundaries.len(),
+            ),
+            categories: join(
+                &self.categories,
+                self.boundaries.len(),
+                &other.categories,
+                other.boundaries.len(),
+            ),
+            catalog: ActionCatalog::join(
+                &self.catalog,
+                self.boundaries.len(),
+                &other.catalog,
+                other.boundaries.len(),
+            ),
+            ..Self::simple(boundaries)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn sample(&self) -> Position {
+        Position {
+            values: self
+                .boundaries
+                .iter()
+                .map(|boundaries| boundaries.sample())
+                .collect(),
+            dimensions: self.dimensions.clone(),
+            tagged: TaggedValuesCache::default(),
+        }
+    }
+
+    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Position {
+        Position {
+            values: self
+                .boundaries
+                .iter()
+                .map(|boundaries| boundaries.sample_with(rng))
+                .collect(),
+            dimensions: self.dimensions.clone(),
+            tagged: TaggedValuesCache::default(),
+        }
+    }
+
+    /// Samples `n` positions seeded from a random `Seed`.
+    #[cfg(feature = "std")]
+    pub fn sample_batch(&self, n: usize) -> Vec<Position> {
+        self.sample_batch_with(n, Seed::new_random())
+    }
+
+    /// Samples `n` positions, each with its own random number generator derived from `seed`.
+    ///
+    /// With the feature `rayon` the positions are sampled in parallel. The result does not
+    /// depend on the feature or the number of threads.
+    ///
+    /// ```
+    /// use gymnarium_base::Seed;
+    /// use gymnarium_base::space::{DimensionBoundaries, Space};
+    ///
+    /// let space = Space::simple_all(DimensionBoundaries::from(-1f32..=1f32), 4);
+    /// let batch = space.sample_batch_with(16, Seed::from(7));
+    ///
+    /// assert_eq!(16, batch.len());
+    /// assert_eq!(batch, space.sample_batch_with(16, Seed::from(7)));
+    /// ```
+    pub fn sample_batch_with(&self, n: usize, seed: Seed) -> Vec<Position> {
+        let mut rng = StdRng::from_seed(seed.mixed());
+        let seeds: Vec<u64> = (0..n).map(|_| rng.gen()).collect();
+        #[cfg(feature = "rayon")]
+        let seeds = seeds.into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let seeds = seeds.into_iter();
+        seeds
+            .map(|seed| self.sample_with(&mut StdRng::seed_from_u64(seed)))
+            .collect()
+    }
+
+    /// Samples into an existing position, reusing its allocations.
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    /// use gymnarium_base::space::{DimensionBoundaries, Position, Space};
+    ///
+    /// let space = Space::simple_all(DimensionBoundaries::from(4), 3);
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let mut position = Position::default();
+    /// for _ in 0..10 {
+    ///     space.sample_into(&mut position, &mut rng);
+    ///     assert!(space.contains(&position).is_ok());
+    /// }
+    /// ```
+    pub fn sample_into<R: Rng + ?Sized>(&self, position: &mut Position, rng: &mut R) {
+        let values = position.values_mut();
+        values.clear();
+        values.extend(
+            self.boundaries
+                .iter()
+                .map(|boundaries| boundaries.sample_with(rng)),
+        );
+        position.dimensions.clear();
+        position.dimensions.extend_from_slice(&self.dimensions);
+    }
+
+    /// Checks that both spaces have the same dimensions.
+    pub fn matches_shape(&self, other: &Space) -> Result<(), SpaceMismatch> {
+        if self.dimensions == other.dimensions {
+            Ok(())
+        } else {
+            Err(SpaceMismatch::ShapeMismatch {
+                expected: self.dimensions.clone(),
+                actual: other.dimensions.clone(),
+            })
+        }
+    }
+
+    /// Checks that both spaces have the same dimensions and every boundary has the same type.
+    pub fn matches_types(&self, other: &Space) -> Result<(), SpaceMismatch> {
+        self.matches_shape(other)?;
+        self.boundaries
+            .iter()
+            .zip(other.boundaries.iter())
+            .enumerate()
+            .try_for_each(|(index, (expected, actual))| {
+                if expected.matches(actual) {
+                    Ok(())
+                } else {
+                    Err(SpaceMismatch::BoundariesTypeMismatch {
+                        index,
+                        expected: *expected,
+                        actual: *actual,
+                    })
+                }
+            })
+    }
+
+    /// Checks that the position has the same dimensions as this space and every value lies
+    /// inside its boundaries.
+    ///
+    /// The first offending value is reported.
+    pub fn contains(&self, position: &Position) -> Result<(), SpaceMismatch> {
+        self.contains_sub_position(&SubPosition {
+            values: position.values.as_ref(),
+            shape: &position.dimensions,
+        })
+    }
+
+    /// Checks a borrowed position like `contains`, without copying its values.
+    pub(crate) fn contains_sub_position(
+        &self,
+        position: &SubPosition<'_>,
+    ) -> Result<(), SpaceMismatch> {
+        if self.dimensions[..] != *position.shape {
+            return Err(SpaceMismatch::ShapeMismatch {
+                expected: self.dimensions.clone(),
+                actual: position.shape.to_vec(),
+            });
+        }
+        check_values(&self.boundaries, position.values.iter())
     }
 }
 
@@ -629,13 +2093,169 @@ impl IndexMut<&[usize]> for Space {
     }
 }
 
+/// Prints the shape and the boundaries, e.g. `Space[2x2x3: FLOAT 0..=255]`.
+///
+/// A single boundary is printed if all boundaries are equal. Long boundary lists are truncated
+/// unless formatted with `{:#}`.
+impl core::fmt::Display for Space {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Space[")?;
+        write_shape(f, &self.dimensions)?;
+        write!(f, ": ")?;
+        match self.boundaries.split_first() {
+            Some((first, rest)) if rest.iter().all(|boundaries| boundaries == first) => {
+                write!(f, "{}", first)?
+            }
+            _ => write_truncated(f, self.boundaries.iter())?,
+        }
+        write!(f, "]")
+    }
+}
+
+/* --- --- --- SPACE BUILDER --- --- --- */
+
+/// Fluent builder for `Space`s.
+///
+/// ```
+/// use gymnarium_base::space::{DimensionBoundaries, Space, SpaceBuilder};
+///
+/// let space = SpaceBuilder::new()
+///     .continuous(0f32..=255f32)
+///     .shape(&[2, 2, 3])
+///     .build()
+///     .unwrap();
+/// assert_eq!(space, Space::all(DimensionBoundaries::from(255f32), vec![2, 2, 3]));
+///
+/// let controller = Space::builder().discrete(5).discrete(2).repeat(3).build().unwrap();
+/// assert_eq!(
+///     controller,
+///     Space::simple(vec![
+///         DimensionBoundaries::from(4),
+///         DimensionBoundaries::from(1),
+///         DimensionBoundaries::from(1),
+///         DimensionBoundaries::from(1),
+///     ])
+/// );
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct SpaceBuilder {
+    boundaries: Vec<DimensionBoundaries>,
+    categories: Vec<Vec<String>>,
+    shape: Option<Vec<usize>>,
+}
+
+impl SpaceBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a dimension with `n` possible integer values `0` to `n - 1`.
+    ///
+    /// `n` has to be positive, otherwise `build` returns `SpaceError::InvalidBoundaries`.
+    pub fn discrete(self, n: i32) -> Self {
+        self.boundaries(DimensionBoundaries::Integer(0, n.saturating_sub(1)))
+    }
+
+    /// Adds a dimension with the integer values inside the given range.
+    pub fn integer(self, range: RangeInclusive<i32>) -> Self {
+        let (min, max) = range.into_inner();
+        self.boundaries(DimensionBoundaries::Integer(min, max))
+    }
+
+    /// Adds a dimension with the float values inside the given range.
+    pub fn continuous(self, range: RangeInclusive<f32>) -> Self {
+        let (min, max) = range.into_inner();
+        self.boundaries(DimensionBoundaries::Float(min, max))
+    }
+
+    /// Adds a categorical dimension with one category per label.
+    pub fn categorical<S: Into<String>>(mut self, labels: Vec<S>) -> Self {
+        let labels = labels.into_iter().map(Into::into).collect::<Vec<String>>();
+        self.boundaries
+            .push(DimensionBoundaries::Categorical(labels.len() as u32));
+        self.categories.push(labels);
+        self
+    }
+
+    pub fn boundaries(mut self, boundaries: DimensionBoundaries) -> Self {
+        self.boundaries.push(boundaries);
+        self.categories.push(Vec::new());
+        self
+    }
+
+    /// Lets the last added dimension appear `times` times in total.
+    pub fn repeat(mut self, times: usize) -> Self {
+        if let (Some(last), Some(labels)) = (self.boundaries.pop(), self.categories.pop()) {
+            self.boundaries.extend(core::iter::repeat_n(last, times));
+            self.categories.extend(core::iter::repeat_n(labels, times));
+        }
+        self
+    }
+
+    /// Sets the shape of the built space.
+    ///
+    /// If only a single dimension has been added, it is used for every element of the shape.
+    pub fn shape(mut self, shape: &[usize]) -> Self {
+        self.shape = Some(shape.to_vec());
+        self
+    }
+
+    /// Validates the boundaries and the shape and creates the space.
+    pub fn build(self) -> Result<Space, SpaceError> {
+        if let Some(index) = self
+            .boundaries
+            .iter()
+            .position(|boundaries| !boundaries.is_valid())
+        {
+            return Err(SpaceError::InvalidBoundaries { index });
+        }
+        let space = match self.shape {
+            None => Space::simple(self.boundaries),
+            Some(shape) if self.boundaries.len() == 1 => Space::all(self.boundaries[0], shape),
+            Some(shape) => Space::new(self.boundaries, shape)?,
+        };
+        if self.categories.iter().all(Vec::is_empty) {
+            return Ok(space);
+        }
+        let categories = match &self.categories[..] {
+            [labels] => vec![labels.clone(); space.boundaries.len()],
+            _ => self.categories,
+        };
+        space.with_categories(categories)
+    }
+}
+
 /* --- --- --- POSITION --- --- --- */
 
+/// Floats are rounded to multiples of this value before being hashed by `Position::stable_hash`.
+pub const STABLE_HASH_FLOAT_RESOLUTION: f64 = 1e-6;
+
 /// Defines the state or position inside a space.
-#[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+///
+/// Positions created with `from_f32_values` or `from_i32_values`, like images, store their
+/// values as plain `f32` or `i32` without the tag of `DimensionValue`. All other positions store
+/// `DimensionValue`s. Borrowing a value of a typed position with `get_value`, `get_values` or
+/// indexing copies all values once until the next change, and `position[index] = value` switches
+/// it to `DimensionValue` storage. `value`, `set_value` and the typed views of `f32_values` and
+/// `i32_values` never copy.
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct Position {
-    values: Vec<DimensionValue>,
+    values: Values,
     dimensions: Vec<usize>,
+    #[serde(skip)]
+    tagged: TaggedValuesCache,
+}
+
+/// Holds the values of a typed position as `DimensionValue`s for the accessors returning
+/// references. Is reset whenever the values of the position are mutably accessed.
+#[derive(Default)]
+struct TaggedValuesCache(OnceBox<Storage<DimensionValue>>);
+
+/// Clones start without the copy, which is filled again when needed.
+impl Clone for TaggedValuesCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
 }
 
 impl Position {
@@ -643,102 +2263,691 @@ impl Position {
         dimension_values: Vec<DimensionValue>,
         dimensions: Vec<usize>,
     ) -> Result<Self, SpaceError> {
-        if dimensions.iter().product::<usize>() == dimension_values.len() {
+        Self::with_values(Values::from(dimension_values), dimensions)
+    }
+
+    fn with_values(values: Values, dimensions: Vec<usize>) -> Result<Self, SpaceError> {
+        if dimensions.iter().product::<usize>() == values.len() {
             Ok(Self {
-                values: dimension_values,
+                values,
                 dimensions,
+                tagged: TaggedValuesCache::default(),
             })
         } else {
-            Err(SpaceError::GivenDimensionsDoNotMatch)
+            Err(SpaceError::GivenDimensionsDoNotMatch {
+                expected_length: dimensions.iter().product(),
+                actual_length: values.len(),
+                dimensions,
+            })
+        }
+    }
+
+    pub fn all(dimension_value: DimensionValue, dimensions: Vec<usize>) -> Self {
+        Self {
+            values: core::iter::repeat_n(dimension_value, dimensions.iter().product()).collect(),
+            dimensions,
+            tagged: TaggedValuesCache::default(),
+        }
+    }
+
+    pub fn simple(dimension_values: Vec<DimensionValue>) -> Self {
+        let dimension_values_length = dimension_values.len();
+        Self {
+            values: Values::from(dimension_values),
+            dimensions: vec![dimension_values_length],
+            tagged: TaggedValuesCache::default(),
+        }
+    }
+
+    pub fn simple_all(dimension_value: DimensionValue, times: usize) -> Self {
+        Self {
+            values: core::iter::repeat_n(dimension_value, times).collect(),
+            dimensions: vec![times],
+            tagged: TaggedValuesCache::default(),
+        }
+    }
+
+    pub fn dimensions(&self) -> &Vec<usize> {
+        &self.dimensions
+    }
+
+    /// Creates a one-dimensional position inside `Space::multi_binary(flags.len())`.
+    pub fn from_bools(flags: &[bool]) -> Self {
+        Self::simple(
+            flags
+                .iter()
+                .map(|flag| DimensionValue::Integer(*flag as i32))
+                .collect(),
+        )
+    }
+
+    /// Creates a one-dimensional position inside a `Space::multi_discrete` space.
+    pub fn from_indices(indices: &[usize]) -> Self {
+        Self::simple(
+            indices
+                .iter()
+                .map(|index| DimensionValue::Integer(*index as i32))
+                .collect(),
+        )
+    }
+
+    /// Decodes a position of a `Space::multi_binary` space.
+    ///
+    /// Returns `None` if any value is not the integer `0` or `1`.
+    ///
+    /// ```
+    /// use gymnarium_base::space::Position;
+    ///
+    /// let flags = Position::from_bools(&[true, false, true]);
+    /// assert_eq!(Some(vec![true, false, true]), flags.to_bools());
+    /// assert_eq!(Some(vec![1, 0, 1]), flags.to_indices());
+    /// assert_eq!(None, Position::from_indices(&[2]).to_bools());
+    /// ```
+    pub fn to_bools(&self) -> Option<Vec<bool>> {
+        self.values
+            .iter()
+            .map(|value| match value {
+                DimensionValue::Integer(0) => Some(false),
+                DimensionValue::Integer(1) => Some(true),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Decodes a position of a `Space::multi_discrete` space.
+    ///
+    /// Returns `None` if any value is not a non-negative integer.
+    pub fn to_indices(&self) -> Option<Vec<usize>> {
+        self.values
+            .iter()
+            .map(|value| match value {
+                DimensionValue::Integer(value) if value >= 0 => Some(value as usize),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Overwrites this position with the other one, reusing the existing allocations.
+    pub fn copy_from(&mut self, other: &Position) {
+        self.values_mut().clone_from(&other.values);
+        self.dimensions.clear();
+        self.dimensions.extend_from_slice(&other.dimensions);
+    }
+
+    /// Returns a key implementing `Eq`, `Hash` and `Ord` for use in e.g. Q-tables.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use gymnarium_base::space::{DimensionValue, Position};
+    ///
+    /// let mut visits = HashMap::new();
+    /// let state = Position::simple(vec![DimensionValue::from(1), DimensionValue::from(2)]);
+    /// *visits.entry(state.as_hash_key()).or_insert(0) += 1;
+    /// *visits.entry(state.clone().into()).or_insert(0) += 1;
+    ///
+    /// assert_eq!(Some(&2), visits.get(&state.as_hash_key()));
+    /// ```
+    pub fn as_hash_key(&self) -> PositionKey {
+        PositionKey(self.clone())
+    }
+
+    /// Returns true if both positions have the same dimensions and every value has the same type.
+    pub fn matches(&self, other: &Position) -> bool {
+        self.dimensions == other.dimensions
+            && self
+                .values
+                .iter()
+                .zip(other.values.iter())
+                .all(|(a, b)| a.matches(&b))
+    }
+
+    /// Returns how many values lie between two neighbours of each dimension inside the
+    /// row-major storage.
+    pub fn strides(&self) -> Vec<usize> {
+        calculate_strides(&self.dimensions)
+    }
+
+    /// # Panics
+    /// If the index does not fit the dimensions.
+    pub fn value(&self, index: &[usize]) -> DimensionValue {
+        self.values.get(self.inner_index(index))
+    }
+
+    /// Borrows the value, see `Position` for the cost with typed storage.
+    ///
+    /// # Panics
+    /// If the index does not fit the dimensions.
+    pub fn get_value(&self, index: &[usize]) -> &DimensionValue {
+        &self[index]
+    }
+
+    /// Stores the value, switching to mixed storage if its type differs from the stored one.
+    ///
+    /// # Panics
+    /// If the index does not fit the dimensions.
+    pub fn set_value(&mut self, index: &[usize], value: DimensionValue) {
+        let index = self.inner_index(index);
+        self.values_mut().set(index, value);
+    }
+
+    /// Borrows all values as `DimensionValue`s, copying typed values once until the next change.
+    fn tagged_values(&self) -> &[DimensionValue] {
+        match &self.values {
+            Values::Mixed(values) => values,
+            values => self
+                .tagged
+                .0
+                .get_or_init(|| Box::new(values.iter().collect())),
+        }
+    }
+
+    fn values_mut(&mut self) -> &mut Values {
+        self.tagged.0 = OnceBox::new();
+        &mut self.values
+    }
+
+    fn inner_index(&self, index: &[usize]) -> usize {
+        calculate_index(&self.dimensions, index)
+            .unwrap_or_else(|e| panic!(
+                "Could not calculate inner index for position with dimensions {:?} and given index {:?} (cause: {})",
+                self.dimensions, index, e
+            ))
+    }
+
+    /// Returns all values in row-major order, only copying them if they share one type.
+    ///
+    /// Prefer `as_f32_slice` and `as_i32_slice` for positions storing values of a single type.
+    pub fn values(&self) -> Cow<'_, [DimensionValue]> {
+        self.values.as_ref().to_dimension_values()
+    }
+
+    /// Borrows all values in row-major order, see `Position` for the cost with typed storage.
+    pub fn get_values(&self) -> &[DimensionValue] {
+        self.tagged_values()
+    }
+
+    /// Creates a position only containing float values.
+    ///
+    /// The values stay available through `as_f32_slice` without any conversion.
+    pub fn from_f32_values(values: Vec<f32>, dimensions: Vec<usize>) -> Result<Self, SpaceError> {
+        Self::with_values(Values::F32(into_storage(values)), dimensions)
+    }
+
+    /// Creates a position only containing integer values.
+    ///
+    /// The values stay available through `as_i32_slice` without any conversion.
+    pub fn from_i32_values(values: Vec<i32>, dimensions: Vec<usize>) -> Result<Self, SpaceError> {
+        Self::with_values(Values::I32(into_storage(values)), dimensions)
+    }
+
+    /// Returns all values as contiguous floats, if this position stores only floats.
+    ///
+    /// Positions created with `from_f32_values` store their values as such, so that this only
+    /// borrows. Once a value of another type is set or a value is assigned by indexing, the
+    /// position stores its values mixed until they are replaced as a whole, e.g. with `copy_from`.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionValue, Position};
+    ///
+    /// let image = Position::from_f32_values(vec![0.0, 0.5, 1.0, 0.5], vec![2, 2]).unwrap();
+    /// assert_eq!(Some(&[0.0, 0.5, 1.0, 0.5][..]), image.as_f32_slice());
+    /// assert_eq!(None, image.as_i32_slice());
+    ///
+    /// let mixed = Position::simple(vec![DimensionValue::from(1), DimensionValue::from(1f32)]);
+    /// assert_eq!(None, mixed.as_f32_slice());
+    /// ```
+    pub fn as_f32_slice(&self) -> Option<&[f32]> {
+        self.values.as_ref().as_f32_slice()
+    }
+
+    /// Returns all values as contiguous integers, if this position stores only integers.
+    ///
+    /// See `as_f32_slice` for when values are stored by type.
+    pub fn as_i32_slice(&self) -> Option<&[i32]> {
+        self.values.as_ref().as_i32_slice()
+    }
+
+    /// Returns a view indexing the floats by the dimensions of this position, if it stores only
+    /// floats.
+    ///
+    /// ```
+    /// use gymnarium_base::space::Position;
+    ///
+    /// let mut image = Position::from_f32_values(vec![0.0; 4], vec![2, 2]).unwrap();
+    /// image.f32_values_mut().unwrap()[&[1, 0][..]] = 0.5;
+    /// assert_eq!(0.5, image.f32_values().unwrap()[&[1, 0][..]]);
+    /// assert_eq!(Some(&[0.0, 0.0, 0.5, 0.0][..]), image.as_f32_slice());
+    /// ```
+    pub fn f32_values(&self) -> Option<TypedValues<'_, f32>> {
+        Some(TypedValues {
+            values: self.as_f32_slice()?,
+            dimensions: &self.dimensions,
+        })
+    }
+
+    /// Returns a view indexing the integers by the dimensions of this position, if it stores only
+    /// integers.
+    pub fn i32_values(&self) -> Option<TypedValues<'_, i32>> {
+        Some(TypedValues {
+            values: self.as_i32_slice()?,
+            dimensions: &self.dimensions,
+        })
+    }
+
+    /// Returns a view writing floats directly into the storage, if this position stores only
+    /// floats.
+    pub fn f32_values_mut(&mut self) -> Option<TypedValuesMut<'_, f32>> {
+        let length = self.values.len();
+        self.tagged.0 = OnceBox::new();
+        Some(TypedValuesMut {
+            values: self.values.f32_slice_mut(0..length)?,
+            dimensions: &self.dimensions,
+        })
+    }
+
+    /// Returns a view writing integers directly into the storage, if this position stores only
+    /// integers.
+    pub fn i32_values_mut(&mut self) -> Option<TypedValuesMut<'_, i32>> {
+        let length = self.values.len();
+        self.tagged.0 = OnceBox::new();
+        Some(TypedValuesMut {
+            values: self.values.i32_slice_mut(0..length)?,
+            dimensions: &self.dimensions,
+        })
+    }
+
+    /// Returns a hash of the shape and the values of this position, which stays the same across
+    /// runs, platforms and versions of this crate.
+    ///
+    /// Floats are rounded to multiples of `STABLE_HASH_FLOAT_RESOLUTION` first, so results of
+    /// slightly differing floating point operations hash the same. `-0.0` equals `0.0` and every
+    /// `NaN` equals every other `NaN`, but integers never equal floats.
+    ///
+    /// ```
+    /// use gymnarium_base::position;
+    ///
+    /// assert_eq!(position![0.1f32 + 0.2f32].stable_hash(), position![0.3f32].stable_hash());
+    /// assert_ne!(position![1, 2].stable_hash(), position![2, 1].stable_hash());
+    /// assert_ne!(position![1].stable_hash(), position![1f32].stable_hash());
+    /// ```
+    pub fn stable_hash(&self) -> u64 {
+        // 64 bit FNV-1a
+        let mut hash = 0xcbf2_9ce4_8422_2325u64;
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        write(&(self.dimensions.len() as u64).to_le_bytes());
+        for dimension in &self.dimensions {
+            write(&(*dimension as u64).to_le_bytes());
+        }
+        for value in self.values.iter() {
+            match value {
+                DimensionValue::Integer(value) => {
+                    write(&[0]);
+                    write(&(value as i64).to_le_bytes());
+                }
+                DimensionValue::Float(value) if value.is_nan() => write(&[2]),
+                DimensionValue::Float(value) => {
+                    let steps = libm::round(value as f64 / STABLE_HASH_FLOAT_RESOLUTION) as i64;
+                    write(&[1]);
+                    write(&steps.to_le_bytes());
+                }
+            }
+        }
+        hash
+    }
+
+    /// Returns all values as `f64` in row-major order, e.g. to hand them to another language.
+    pub fn to_f64_values(&self) -> Vec<f64> {
+        match &self.values {
+            Values::F32(values) => values.iter().map(|value| *value as f64).collect(),
+            Values::I32(values) => values.iter().map(|value| *value as f64).collect(),
+            Values::Mixed(values) => values
+                .iter()
+                .map(|value| match value {
+                    DimensionValue::Integer(value) => *value as f64,
+                    DimensionValue::Float(value) => *value as f64,
+                })
+                .collect(),
         }
     }
 
-    pub fn all(dimension_value: DimensionValue, dimensions: Vec<usize>) -> Self {
-        Self {
-            values: vec![dimension_value; dimensions.iter().product()],
-            dimensions,
-        }
+    /// Reads values in row-major order into a position of the given space.
+    ///
+    /// Values of integer boundaries have to be whole numbers, and every value has to lie inside
+    /// its boundaries.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionBoundaries, Position, Space};
+    ///
+    /// let space = Space::simple(vec![
+    ///     DimensionBoundaries::from(0..=3),
+    ///     DimensionBoundaries::from(-1.0..=1.0),
+    /// ]);
+    /// let position = Position::from_f64_values_in(&[2.0, 0.5], &space).unwrap();
+    /// assert_eq!(vec![2.0, 0.5], position.to_f64_values());
+    /// assert!(Position::from_f64_values_in(&[2.5, 0.5], &space).is_err());
+    /// ```
+    pub fn from_f64_values_in(values: &[f64], space: &Space) -> Result<Self, SpaceMismatch> {
+        if values.len() != space.boundaries.len() {
+            return Err(SpaceMismatch::ShapeMismatch {
+                expected: space.dimensions.clone(),
+                actual: vec![values.len()],
+            });
+        }
+        let values = space
+            .boundaries
+            .iter()
+            .zip(values.iter())
+            .enumerate()
+            .map(|(index, (boundaries, value))| match boundaries {
+                // The cast saturates, so only whole numbers inside the range of i32 survive it.
+                DimensionBoundaries::Integer(_, _) | DimensionBoundaries::Categorical(_)
+                    if *value as i32 as f64 == *value =>
+                {
+                    Ok(DimensionValue::Integer(*value as i32))
+                }
+                DimensionBoundaries::Integer(_, _) | DimensionBoundaries::Categorical(_) => {
+                    Err(SpaceMismatch::ValueTypeMismatch {
+                        index,
+                        boundaries: *boundaries,
+                        value: DimensionValue::Float(*value as f32),
+                    })
+                }
+                DimensionBoundaries::Float(_, _) => Ok(DimensionValue::Float(*value as f32)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let position = Self {
+            values: Values::from(values),
+            dimensions: space.dimensions.clone(),
+            tagged: TaggedValuesCache::default(),
+        };
+        space.contains(&position)?;
+        Ok(position)
+    }
+
+    /// Joins both positions along their first dimension.
+    ///
+    /// All other dimensions have to be equal. An empty position is returned as the other position.
+    pub fn concat(&self, other: &Position) -> Result<Self, SpaceError> {
+        if self.values.is_empty() {
+            return Ok(other.clone());
+        } else if other.values.is_empty() {
+            return Ok(self.clone());
+        }
+        let dimensions = concatenate_dimensions(&self.dimensions, &other.dimensions)?;
+        let mut values = self.values.clone();
+        values.extend(other.values.iter());
+        Ok(Self {
+            values,
+            dimensions,
+            tagged: TaggedValuesCache::default(),
+        })
+    }
+
+    /// Returns the part `range` along dimension `axis` of this position.
+    pub fn slice(&self, axis: usize, range: Range<usize>) -> Result<Self, SpaceError> {
+        let (values, dimensions) = self.values.slice_along(&self.dimensions, axis, range)?;
+        Ok(Self {
+            values,
+            dimensions,
+            tagged: TaggedValuesCache::default(),
+        })
+    }
+
+    /// Returns this position with other dimensions but the same values in the same order.
+    pub fn reshape(&self, dimensions: Vec<usize>) -> Result<Self, SpaceError> {
+        Self::with_values(self.values.clone(), dimensions)
+    }
+
+    /// Returns this position with rearranged dimensions, so that dimension `i` is the former
+    /// dimension `axes[i]`.
+    ///
+    /// E.g. `transpose(&[2, 0, 1])` turns a `[h, w, c]` image into a `[c, h, w]` one.
+    pub fn transpose(&self, axes: &[usize]) -> Result<Self, SpaceError> {
+        let (values, dimensions) = self.values.transpose(&self.dimensions, axes)?;
+        Ok(Self {
+            values,
+            dimensions,
+            tagged: TaggedValuesCache::default(),
+        })
+    }
+
+    /// Returns both positions as one flattened position inside the product of their spaces.
+    pub fn product(&self, other: &Position) -> Self {
+        let mut values = self.values.clone();
+        values.extend(other.values.iter());
+        Self {
+            dimensions: vec![values.len()],
+            values,
+            tagged: TaggedValuesCache::default(),
+        }
+    }
+}
+
+impl core::fmt::Debug for Position {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Position")
+            .field("values", &self.values)
+            .field("dimensions", &self.dimensions)
+            .finish()
+    }
+}
+
+impl PartialEq for Position {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values && self.dimensions == other.dimensions
+    }
+}
+
+/// Prints the shape and the values, e.g. `Position[2x2: 1, 2, 3, 4]`.
+///
+/// Long value lists are truncated unless formatted with `{:#}`.
+impl core::fmt::Display for Position {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Position[")?;
+        write_shape(f, &self.dimensions)?;
+        write!(f, ": ")?;
+        write_truncated(f, self.values.iter())?;
+        write!(f, "]")
+    }
+}
+
+impl Index<&[usize]> for Position {
+    type Output = DimensionValue;
+
+    fn index(&self, index: &[usize]) -> &Self::Output {
+        &self.tagged_values()[self.inner_index(index)]
+    }
+}
+
+/// Switches typed positions to `DimensionValue` storage to hand out the reference.
+impl IndexMut<&[usize]> for Position {
+    fn index_mut(&mut self, index: &[usize]) -> &mut Self::Output {
+        let index = self.inner_index(index);
+        &mut self.values_mut().mixed_mut()[index]
+    }
+}
+
+/* --- --- --- TYPED VALUES --- --- --- */
+
+/// Values of a position storing only one type, indexed by the dimensions of the position.
+///
+/// Created with `Position::f32_values` and `Position::i32_values`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypedValues<'a, T> {
+    values: &'a [T],
+    dimensions: &'a [usize],
+}
+
+impl<'a, T> TypedValues<'a, T> {
+    pub fn as_slice(&self) -> &'a [T] {
+        self.values
+    }
+}
+
+impl<T> Index<&[usize]> for TypedValues<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: &[usize]) -> &Self::Output {
+        &self.values[typed_values_index(self.dimensions, index)]
+    }
+}
+
+/// Mutable values of a position storing only one type, indexed by the dimensions of the position.
+///
+/// Created with `Position::f32_values_mut` and `Position::i32_values_mut`. Writing through it
+/// keeps the typed storage; use `Position::set_value` to store a value of another type.
+#[derive(Debug, PartialEq)]
+pub struct TypedValuesMut<'a, T> {
+    values: &'a mut [T],
+    dimensions: &'a [usize],
+}
+
+impl<T> TypedValuesMut<'_, T> {
+    pub fn as_slice(&self) -> &[T] {
+        self.values
     }
 
-    pub fn simple(dimension_values: Vec<DimensionValue>) -> Self {
-        let dimension_values_length = dimension_values.len();
-        Self {
-            values: dimension_values,
-            dimensions: vec![dimension_values_length],
-        }
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.values
     }
+}
 
-    pub fn simple_all(dimension_value: DimensionValue, times: usize) -> Self {
-        Self {
-            values: vec![dimension_value; times],
-            dimensions: vec![times],
-        }
+impl<T> Index<&[usize]> for TypedValuesMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: &[usize]) -> &Self::Output {
+        &self.values[typed_values_index(self.dimensions, index)]
     }
+}
 
-    pub fn dimensions(&self) -> &Vec<usize> {
-        &self.dimensions
+impl<T> IndexMut<&[usize]> for TypedValuesMut<'_, T> {
+    fn index_mut(&mut self, index: &[usize]) -> &mut Self::Output {
+        &mut self.values[typed_values_index(self.dimensions, index)]
     }
+}
 
-    pub fn matches(&self, other: &Position) -> bool {
-        self.dimensions == other.dimensions
-            && self
-                .values
-                .iter()
-                .zip(other.values.iter())
-                .any(|(a, b)| !a.matches(b))
+fn typed_values_index(dimensions: &[usize], index: &[usize]) -> usize {
+    calculate_index(dimensions, index)
+        .unwrap_or_else(|e| panic!(
+            "Could not calculate inner index for position with dimensions {:?} and given index {:?} (cause: {})",
+            dimensions, index, e
+        ))
+}
+
+/* --- --- --- POSITION KEY --- --- --- */
+
+/// A `Position` usable as key in hashed or ordered collections.
+///
+/// Floats are compared by their bits after turning `-0.0` into `0.0` and every `NaN` into the
+/// same `NaN`, so that every float is equal to itself. Integers are ordered before floats.
+#[derive(Debug, Clone)]
+pub struct PositionKey(Position);
+
+impl PositionKey {
+    pub fn position(&self) -> &Position {
+        &self.0
     }
 
-    pub fn get_value(&self, index: &[usize]) -> &DimensionValue {
-        &self[index]
+    pub fn into_position(self) -> Position {
+        self.0
     }
 
-    pub fn set_value(&mut self, index: &[usize], value: DimensionValue) {
-        self[index] = value;
+    fn normalized_values(&self) -> impl Iterator<Item = (u8, u32)> + '_ {
+        self.0.values.iter().map(|value| match value {
+            DimensionValue::Integer(value) => (0, value as u32),
+            DimensionValue::Float(value) if value.is_nan() => (1, f32::NAN.to_bits()),
+            DimensionValue::Float(0f32) => (1, 0f32.to_bits()),
+            DimensionValue::Float(value) => (1, value.to_bits()),
+        })
     }
+}
 
-    pub fn get_values(&self) -> &[DimensionValue] {
-        &self.values
+impl From<Position> for PositionKey {
+    fn from(position: Position) -> Self {
+        Self(position)
     }
 }
 
-impl Index<&[usize]> for Position {
-    type Output = DimensionValue;
+impl PartialEq for PositionKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
 
-    fn index(&self, index: &[usize]) -> &Self::Output {
-        let index = calculate_index(self.dimensions(), index)
-            .unwrap_or_else(|e| panic!(
-                "Could not calculate inner index for position with dimensions {:?} and given index {:?} (cause: {})",
-                self.dimensions, index, e
-            ));
-        &self.values[index]
+impl Eq for PositionKey {}
+
+impl PartialOrd for PositionKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl IndexMut<&[usize]> for Position {
-    fn index_mut(&mut self, index: &[usize]) -> &mut Self::Output {
-        let index = calculate_index(self.dimensions(), index)
-            .unwrap_or_else(|e| panic!(
-                "Could not calculate inner index for position with dimensions {:?} and given index {:?} (cause: {})",
-                self.dimensions, index, e
-            ));
-        &mut self.values[index]
+impl Ord for PositionKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let compare = |(a_type, a_bits): (u8, u32), (b_type, b_bits): (u8, u32)| {
+            a_type.cmp(&b_type).then_with(|| {
+                if a_type == 0 {
+                    (a_bits as i32).cmp(&(b_bits as i32))
+                } else {
+                    f32::from_bits(a_bits).total_cmp(&f32::from_bits(b_bits))
+                }
+            })
+        };
+        self.0.dimensions.cmp(&other.0.dimensions).then_with(|| {
+            let mut others = other.normalized_values();
+            for own in self.normalized_values() {
+                match others.next() {
+                    Some(theirs) => match compare(own, theirs) {
+                        Ordering::Equal => continue,
+                        ordering => return ordering,
+                    },
+                    None => return Ordering::Greater,
+                }
+            }
+            if others.next().is_some() {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        })
+    }
+}
+
+impl Hash for PositionKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.dimensions.hash(state);
+        self.normalized_values().for_each(|value| value.hash(state));
     }
 }
 
 /* --- --- --- DIMENSION BOUNDARIES --- --- --- */
 
 /// The inclusive upper and inclusive lower bound of a dimension.
+///
+/// `Categorical` dimensions hold one of as many categories as given, as integer value from `0`.
+/// Their labels are stored once on the `Space`, see `Space::with_categories`.
 #[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum DimensionBoundaries {
     Integer(i32, i32),
     Float(f32, f32),
+    Categorical(u32),
 }
 
 impl DimensionBoundaries {
+    #[cfg(feature = "std")]
     pub fn sample(&self) -> DimensionValue {
         self.sample_with(&mut rand::thread_rng())
     }
 
+    /// Samples a value inside these boundaries.
+    ///
+    /// Categorical boundaries sample at most `i32::MAX`, the largest category an integer value
+    /// holds, and empty ones always sample the category `0`.
     pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> DimensionValue {
         match self {
             Self::Integer(min, max) => {
@@ -747,22 +2956,41 @@ impl DimensionBoundaries {
             Self::Float(min, max) => {
                 DimensionValue::Float(Uniform::new_inclusive(min, max).sample(rng))
             }
+            Self::Categorical(count) => {
+                let count = (*count).clamp(1, i32::MAX as u32 + 1);
+                DimensionValue::Integer(rng.gen_range(0, count) as i32)
+            }
         }
     }
 
     pub fn matches(&self, value: &DimensionBoundaries) -> bool {
+        matches!(
+            (self, value),
+            (Self::Integer(_, _), Self::Integer(_, _))
+                | (Self::Float(_, _), Self::Float(_, _))
+                | (Self::Categorical(_), Self::Categorical(_))
+        )
+    }
+
+    /// Returns true if at least one value lies inside these boundaries.
+    pub fn is_valid(&self) -> bool {
         match self {
-            Self::Integer(_, _) => match value {
-                Self::Integer(_, _) => true,
-                Self::Float(_, _) => false,
-            },
-            Self::Float(_, _) => match value {
-                Self::Integer(_, _) => false,
-                Self::Float(_, _) => true,
-            },
+            Self::Integer(min, max) => min <= max,
+            Self::Float(min, max) => min <= max && min.is_finite() && max.is_finite(),
+            Self::Categorical(count) => 0 < *count && *count <= i32::MAX as u32,
         }
     }
 
+    /// Returns true if the value has the same type as these boundaries.
+    pub fn matches_value(&self, value: &DimensionValue) -> bool {
+        matches!(
+            (self, value),
+            (Self::Integer(_, _), DimensionValue::Integer(_))
+                | (Self::Float(_, _), DimensionValue::Float(_))
+                | (Self::Categorical(_), DimensionValue::Integer(_))
+        )
+    }
+
     pub fn contains(&self, value: &DimensionValue) -> bool {
         match self {
             Self::Integer(min, max) => match value {
@@ -773,6 +3001,68 @@ impl DimensionBoundaries {
                 DimensionValue::Integer(_) => false,
                 DimensionValue::Float(val) => *min <= *val && *val <= *max,
             },
+            Self::Categorical(count) => match value {
+                DimensionValue::Integer(val) => 0 <= *val && (*val as u32) < *count,
+                DimensionValue::Float(_) => false,
+            },
+        }
+    }
+
+    /// Returns the smallest and the largest integer value inside integer or categorical
+    /// boundaries.
+    ///
+    /// Empty categorical boundaries return `(0, -1)` and categories above `i32::MAX` are left out.
+    pub fn integer_range(&self) -> Option<(i32, i32)> {
+        match self {
+            Self::Integer(min, max) => Some((*min, *max)),
+            Self::Float(_, _) => None,
+            Self::Categorical(count) => Some((
+                0,
+                count
+                    .checked_sub(1)
+                    .map_or(-1, |max| max.min(i32::MAX as u32) as i32),
+            )),
+        }
+    }
+
+    /// Encodes a categorical value as one float per category, `1.0` for its category and `0.0`
+    /// else.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionBoundaries, DimensionValue};
+    ///
+    /// let weather = DimensionBoundaries::Categorical(3);
+    ///
+    /// assert_eq!(Some(vec![0f32, 0f32, 1f32]), weather.one_hot(&DimensionValue::Integer(2)));
+    /// assert_eq!(Some(DimensionValue::Integer(1)), weather.from_one_hot(&[0.1, 0.7, 0.2]));
+    /// ```
+    pub fn one_hot(&self, value: &DimensionValue) -> Option<Vec<f32>> {
+        match self {
+            Self::Categorical(count) if self.contains(value) => {
+                let mut encoded = vec![0f32; *count as usize];
+                encoded[value.expect_integer() as usize] = 1f32;
+                Some(encoded)
+            }
+            _ => None,
+        }
+    }
+
+    /// Decodes a categorical value as the category with the highest score, the first one on
+    /// ties.
+    pub fn from_one_hot(&self, scores: &[f32]) -> Option<DimensionValue> {
+        match self {
+            Self::Categorical(count) if *count as usize == scores.len() => scores
+                .iter()
+                .enumerate()
+                .fold(
+                    None,
+                    |best: Option<(usize, f32)>, (index, score)| match best {
+                        Some((_, best_score)) if best_score >= *score => best,
+                        _ => Some((index, *score)),
+                    },
+                )
+                .map(|(index, _)| DimensionValue::Integer(index as i32)),
+            _ => None,
         }
     }
 
@@ -791,6 +3081,24 @@ impl DimensionBoundaries {
             panic!("{:?} is not FLOAT as expected", self);
         }
     }
+
+    pub fn expect_categorical(&self) -> u32 {
+        if let Self::Categorical(count) = self {
+            *count
+        } else {
+            panic!("{:?} is not CATEGORICAL as expected", self);
+        }
+    }
+}
+
+impl core::fmt::Display for DimensionBoundaries {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Integer(min, max) => write!(f, "INTEGER {}..={}", min, max),
+            Self::Float(min, max) => write!(f, "FLOAT {}..={}", min, max),
+            Self::Categorical(count) => write!(f, "CATEGORICAL {}", count),
+        }
+    }
 }
 
 /* i32 */
@@ -867,6 +3175,15 @@ impl DimensionValue {
     }
 }
 
+impl core::fmt::Display for DimensionValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
 /* i32 */
 
 impl From<i32> for DimensionValue {
@@ -882,3 +3199,972 @@ impl From<f32> for DimensionValue {
         Self::Float(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn space_contains_requires_every_value_in_bounds() {
+        let space = Space::simple(vec![
+            DimensionBoundaries::from(0..=3),
+            DimensionBoundaries::from(-1f32..=1f32),
+        ]);
+
+        assert_eq!(
+            Ok(()),
+            space.contains(&Position::simple(vec![
+                DimensionValue::from(3),
+                DimensionValue::from(-1f32)
+            ]))
+        );
+        assert_eq!(
+            Err(SpaceMismatch::ValueOutOfBounds {
+                index: 1,
+                boundaries: DimensionBoundaries::Float(-1f32, 1f32),
+                value: DimensionValue::Float(2f32)
+            }),
+            space.contains(&Position::simple(vec![
+                DimensionValue::from(1),
+                DimensionValue::from(2f32)
+            ]))
+        );
+        assert_eq!(
+            Err(SpaceMismatch::ValueTypeMismatch {
+                index: 0,
+                boundaries: DimensionBoundaries::Integer(0, 3),
+                value: DimensionValue::Float(1f32)
+            }),
+            space.contains(&Position::simple(vec![
+                DimensionValue::from(1f32),
+                DimensionValue::from(0f32)
+            ]))
+        );
+        assert_eq!(
+            Err(SpaceMismatch::ShapeMismatch {
+                expected: vec![2],
+                actual: vec![1]
+            }),
+            space.contains(&Position::simple(vec![DimensionValue::from(1)]))
+        );
+    }
+
+    #[test]
+    fn space_matches_types_and_shape() {
+        let space = Space::simple(vec![
+            DimensionBoundaries::from(3),
+            DimensionBoundaries::from(1f32),
+        ]);
+        let same_types = Space::simple(vec![
+            DimensionBoundaries::from(-5..=5),
+            DimensionBoundaries::from(10f32),
+        ]);
+        let other_types = Space::simple(vec![
+            DimensionBoundaries::from(3),
+            DimensionBoundaries::from(1),
+        ]);
+
+        assert_eq!(Ok(()), space.matches_types(&same_types));
+        assert_eq!(Ok(()), space.matches_shape(&other_types));
+        assert_eq!(
+            Err(SpaceMismatch::BoundariesTypeMismatch {
+                index: 1,
+                expected: DimensionBoundaries::Float(0f32, 1f32),
+                actual: DimensionBoundaries::Integer(0, 1)
+            }),
+            space.matches_types(&other_types)
+        );
+        assert!(space
+            .matches_shape(&Space::simple_all(DimensionBoundaries::from(1), 3))
+            .is_err());
+    }
+
+    #[test]
+    fn space_concat_joins_first_dimension() {
+        let a = Space::all(DimensionBoundaries::from(1), vec![2, 3]);
+        let b = Space::all(DimensionBoundaries::from(1f32), vec![1, 3]);
+
+        let joined = a.concat(&b).unwrap();
+        assert_eq!(&vec![3, 3], joined.dimensions());
+        assert_eq!(
+            DimensionBoundaries::Integer(0, 1),
+            joined.get_boundaries()[5]
+        );
+        assert_eq!(
+            DimensionBoundaries::Float(0f32, 1f32),
+            joined.get_boundaries()[6]
+        );
+        assert_eq!(Ok(a.clone()), Space::default().concat(&a));
+        assert_eq!(
+            Err(SpaceError::IncompatibleDimensions {
+                first: vec![2, 3],
+                second: vec![2, 2]
+            }),
+            a.concat(&Space::all(DimensionBoundaries::from(1), vec![2, 2]))
+        );
+    }
+
+    #[test]
+    fn space_and_position_product_flatten() {
+        let a = Space::all(DimensionBoundaries::from(1), vec![2, 2]);
+        let b = Space::simple(vec![DimensionBoundaries::from(1f32)]);
+        let product = a.product(&b);
+
+        assert_eq!(&vec![5], product.dimensions());
+
+        let position = Position::all(DimensionValue::from(1), vec![2, 2])
+            .product(&Position::simple(vec![DimensionValue::from(0.5f32)]));
+        assert_eq!(Ok(()), product.contains(&position));
+    }
+
+    #[test]
+    fn position_concat_joins_first_dimension() {
+        let a = Position::simple(vec![DimensionValue::from(1)]);
+        let b = Position::simple(vec![DimensionValue::from(2), DimensionValue::from(3)]);
+
+        assert_eq!(
+            Ok(Position::simple(vec![
+                DimensionValue::from(1),
+                DimensionValue::from(2),
+                DimensionValue::from(3)
+            ])),
+            a.concat(&b)
+        );
+        assert_eq!(
+            Err(SpaceError::IncompatibleDimensions {
+                first: vec![1],
+                second: vec![1, 1]
+            }),
+            a.concat(&Position::all(DimensionValue::from(1), vec![1, 1]))
+        );
+    }
+
+    #[test]
+    fn slice_of_two_dimensional_position_works() {
+        let position =
+            Position::new((0..6).map(DimensionValue::from).collect(), vec![2, 3]).unwrap();
+
+        let row = position.slice(0, 1..2).unwrap();
+        assert_eq!(&vec![1, 3], row.dimensions());
+        for column in 0..3 {
+            assert_eq!(position.value(&[1, column]), row.value(&[0, column]));
+        }
+
+        let columns = position.slice(1, 1..3).unwrap();
+        assert_eq!(&vec![2, 2], columns.dimensions());
+        for row in 0..2 {
+            for column in 0..2 {
+                assert_eq!(
+                    position.value(&[row, column + 1]),
+                    columns.value(&[row, column])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn slice_out_of_bounds_fails() {
+        let space = Space::all(DimensionBoundaries::from(1), vec![2, 3]);
+
+        assert_eq!(
+            Err(SpaceError::SliceOutOfBounds {
+                dimensions: vec![2, 3],
+                axis: 2,
+                range: 0..1
+            }),
+            space.slice(2, 0..1)
+        );
+        assert_eq!(
+            Err(SpaceError::SliceOutOfBounds {
+                dimensions: vec![2, 3],
+                axis: 1,
+                range: 2..4
+            }),
+            space.slice(1, 2..4)
+        );
+        assert_eq!(
+            Ok(Space::all(DimensionBoundaries::from(1), vec![2, 0])),
+            space.slice(1, 3..3)
+        );
+    }
+
+    #[test]
+    fn position_reshape_validates_element_count() {
+        let position = Position::simple((0..6).map(DimensionValue::from).collect());
+
+        let reshaped = position.reshape(vec![2, 3]).unwrap();
+        assert_eq!(&vec![2, 3], reshaped.dimensions());
+        assert_eq!(position.values(), reshaped.values());
+        assert_eq!(
+            Err(SpaceError::GivenDimensionsDoNotMatch {
+                dimensions: vec![4, 2],
+                expected_length: 8,
+                actual_length: 6
+            }),
+            position.reshape(vec![4, 2])
+        );
+    }
+
+    #[test]
+    fn position_transpose_works() {
+        let position =
+            Position::new((0..6).map(DimensionValue::from).collect(), vec![2, 3]).unwrap();
+
+        let transposed = position.transpose(&[1, 0]).unwrap();
+        assert_eq!(&vec![3, 2], transposed.dimensions());
+        for row in 0..2 {
+            for column in 0..3 {
+                assert_eq!(
+                    position.value(&[row, column]),
+                    transposed.value(&[column, row])
+                );
+            }
+        }
+        assert_eq!(Ok(position.clone()), transposed.transpose(&[1, 0]));
+        assert_eq!(
+            Err(SpaceError::InvalidAxes {
+                dimensions: vec![2, 3],
+                axes: vec![0, 0]
+            }),
+            position.transpose(&[0, 0])
+        );
+        assert_eq!(
+            Err(SpaceError::InvalidAxes {
+                dimensions: vec![2, 3],
+                axes: vec![0]
+            }),
+            position.transpose(&[0])
+        );
+    }
+
+    #[test]
+    fn strides_are_row_major() {
+        assert_eq!(
+            vec![6, 3, 1],
+            Space::all(DimensionBoundaries::from(1), vec![2, 2, 3]).strides()
+        );
+        assert_eq!(
+            vec![1],
+            Position::simple(vec![DimensionValue::from(1)]).strides()
+        );
+        assert_eq!(Vec::<usize>::new(), Position::default().strides());
+    }
+
+    #[test]
+    fn three_dimensional_index_is_row_major() {
+        let position =
+            Position::new((0..12).map(DimensionValue::from).collect(), vec![2, 2, 3]).unwrap();
+
+        assert_eq!(DimensionValue::from(0), position.value(&[0, 0, 0]));
+        assert_eq!(DimensionValue::from(2), position.value(&[0, 0, 2]));
+        assert_eq!(DimensionValue::from(3), position.value(&[0, 1, 0]));
+        assert_eq!(DimensionValue::from(8), position.value(&[1, 0, 2]));
+        assert_eq!(DimensionValue::from(11), position.value(&[1, 1, 2]));
+    }
+
+    #[test]
+    fn calculate_index_rejects_wrong_index_length() {
+        assert_eq!(Ok(5), calculate_index(&[2, 3], &[1, 2]));
+        assert_eq!(
+            Err(SpaceError::IndexOutOfBounds {
+                dimensions: vec![2, 3],
+                index: vec![1]
+            }),
+            calculate_index(&[2, 3], &[1])
+        );
+        assert_eq!(
+            Err(SpaceError::IndexOutOfBounds {
+                dimensions: vec![2, 3],
+                index: vec![1, 2, 0]
+            }),
+            calculate_index(&[2, 3], &[1, 2, 0])
+        );
+    }
+
+    #[test]
+    fn transpose_hwc_to_chw_works() {
+        let image =
+            Position::new((0..12).map(DimensionValue::from).collect(), vec![2, 2, 3]).unwrap();
+
+        let channels_first = image.transpose(&[2, 0, 1]).unwrap();
+        assert_eq!(&vec![3, 2, 2], channels_first.dimensions());
+        assert_eq!(
+            (0..12)
+                .step_by(3)
+                .map(DimensionValue::from)
+                .collect::<Vec<_>>(),
+            channels_first.slice(0, 0..1).unwrap().values().to_vec()
+        );
+    }
+
+    #[test]
+    fn space_builder_rejects_invalid_input() {
+        assert_eq!(
+            Err(SpaceError::InvalidBoundaries { index: 1 }),
+            Space::builder().discrete(2).discrete(0).build()
+        );
+        assert_eq!(
+            Err(SpaceError::InvalidBoundaries { index: 0 }),
+            Space::builder().discrete(-3).build()
+        );
+        assert_eq!(
+            Err(SpaceError::InvalidBoundaries { index: 1 }),
+            Space::builder().discrete(2).discrete(i32::MIN).build()
+        );
+        assert_eq!(
+            Err(SpaceError::InvalidBoundaries { index: 0 }),
+            Space::multi_discrete(&[i32::MAX as usize + 1])
+        );
+        assert_eq!(
+            Ok(Space::simple(vec![DimensionBoundaries::Integer(
+                0,
+                i32::MAX - 1
+            )])),
+            Space::multi_discrete(&[i32::MAX as usize])
+        );
+        assert_eq!(
+            Err(SpaceError::InvalidBoundaries { index: 0 }),
+            Space::builder().continuous(1f32..=-1f32).build()
+        );
+        assert_eq!(
+            Err(SpaceError::GivenDimensionsDoNotMatch {
+                dimensions: vec![2, 2],
+                expected_length: 4,
+                actual_length: 2
+            }),
+            Space::builder()
+                .discrete(2)
+                .discrete(3)
+                .shape(&[2, 2])
+                .build()
+        );
+        assert_eq!(
+            Ok(Space::all(DimensionBoundaries::Integer(-3, 3), vec![2, 2])),
+            Space::builder()
+                .integer(-3..=3)
+                .repeat(4)
+                .shape(&[2, 2])
+                .build()
+        );
+    }
+
+    #[test]
+    fn nested_format_resolves_paths_with_offsets() {
+        let mut inner = Format::default();
+        inner.add("a".to_string(), vec![2]).unwrap();
+        inner.add("b".to_string(), vec![1]).unwrap();
+        let mut format = Format::default();
+        format.add("x".to_string(), vec![1]).unwrap();
+        format.add_format("inner".to_string(), inner).unwrap();
+
+        let mut position = format
+            .new_space()
+            .sample_with(&mut StdRng::seed_from_u64(0));
+        format
+            .set_value(&mut position, "inner.b", &[0], DimensionValue::from(7))
+            .unwrap();
+
+        assert_eq!(DimensionValue::from(7), position.value(&[3]));
+        assert_eq!(
+            Ok(Position::simple(vec![
+                DimensionValue::from(0),
+                DimensionValue::from(0),
+                DimensionValue::from(7)
+            ])),
+            format.get_subposition(&position, "inner")
+        );
+        assert!(format.sub_format("inner").is_some());
+        assert!(!format.contains("inner.c"));
+        assert!(!format.contains("x.a"));
+    }
+
+    #[test]
+    fn format_serialization_keeps_insertion_order() {
+        let mut inner = Format::default();
+        inner.add("z".to_string(), vec![2]).unwrap();
+        inner.add("a".to_string(), vec![1]).unwrap();
+        let mut format = Format::default();
+        format.add("velocity".to_string(), vec![3]).unwrap();
+        format.add_format("inner".to_string(), inner).unwrap();
+        format.add("camera".to_string(), vec![2, 2]).unwrap();
+
+        let serialized = serde_json::to_string(&format).unwrap();
+        assert_eq!(
+            r#"{"velocity":[3],"inner":{"z":[2],"a":[1]},"camera":[2,2]}"#,
+            serialized
+        );
+
+        let deserialized: Format = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(format.keys(), deserialized.keys());
+        assert_eq!(Some(5), deserialized.offset_of("inner.a"));
+        assert_eq!(Some(6), deserialized.offset_of("camera"));
+        assert_eq!(10, deserialized.len());
+    }
+
+    #[test]
+    fn format_json_schema_describes_nested_offsets() {
+        let mut inner = Format::default();
+        inner.add("a".to_string(), vec![2]).unwrap();
+        let mut format = Format::default();
+        format.add("x".to_string(), vec![1]).unwrap();
+        format.add_format("inner".to_string(), inner).unwrap();
+
+        let schema = format.to_json_schema();
+        assert_eq!("object", schema["properties"]["inner"]["type"]);
+        assert_eq!(
+            1,
+            schema["properties"]["inner"]["properties"]["a"]["x-offset"]
+        );
+        assert_eq!(
+            serde_json::json!([2]),
+            schema["properties"]["inner"]["properties"]["a"]["x-shape"]
+        );
+        assert_eq!(serde_json::json!(["x", "inner"]), schema["required"]);
+    }
+
+    #[test]
+    fn format_iter_expands_nested_formats_in_order() {
+        let mut inner = Format::default();
+        inner.add("b".to_string(), vec![2]).unwrap();
+        inner.add("a".to_string(), vec![1]).unwrap();
+        let mut format = Format::default();
+        format.add("x".to_string(), vec![3]).unwrap();
+        format.add_format("inner".to_string(), inner).unwrap();
+        format.add("y".to_string(), vec![1]).unwrap();
+
+        assert_eq!(
+            vec![
+                ("x".to_string(), &vec![3], 0),
+                ("inner.b".to_string(), &vec![2], 3),
+                ("inner.a".to_string(), &vec![1], 5),
+                ("y".to_string(), &vec![1], 6),
+            ],
+            format.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn format_remove_compacts_offsets() {
+        let mut inner = Format::default();
+        inner.add("b".to_string(), vec![2]).unwrap();
+        inner.add("a".to_string(), vec![1]).unwrap();
+        let mut format = Format::default();
+        format.add("x".to_string(), vec![3]).unwrap();
+        format.add_format("inner".to_string(), inner).unwrap();
+        format.add("y".to_string(), vec![1]).unwrap();
+
+        format.remove("x").unwrap();
+        assert_eq!(Some(0), format.offset_of("inner.b"));
+        assert_eq!(Some(3), format.offset_of("y"));
+
+        format.remove("inner.b").unwrap();
+        assert_eq!(Some(0), format.offset_of("inner.a"));
+        assert_eq!(Some(&vec![1]), format.shape_of("inner"));
+        assert_eq!(Some(1), format.offset_of("y"));
+        assert_eq!(2, format.len());
+
+        assert_eq!(
+            Err(FormatError::KeyNotFoundInFormat("inner.b".to_string())),
+            format.remove("inner.b")
+        );
+    }
+
+    #[test]
+    fn format_merge_prefixes_keys() {
+        let mut other = Format::default();
+        other.add("position".to_string(), vec![3]).unwrap();
+        other.add("velocity".to_string(), vec![3]).unwrap();
+        let mut format = Format::default();
+        format.add("charge".to_string(), vec![1]).unwrap();
+        format.add("arm.position".to_string(), vec![2]).unwrap();
+
+        let mut conflicting = Format::default();
+        conflicting.add("position".to_string(), vec![1]).unwrap();
+        assert_eq!(
+            Err(FormatError::KeyAlreadyExistsInFormat(
+                "arm.position".to_string()
+            )),
+            format.merge(conflicting, "arm")
+        );
+
+        format.merge(other, "sensors").unwrap();
+        assert_eq!(Some(3), format.offset_of("sensors.position"));
+        assert_eq!(Some(6), format.offset_of("sensors.velocity"));
+        assert_eq!(9, format.len());
+    }
+
+    #[test]
+    fn formatted_position_views_nested_formats() {
+        let mut inner = Format::default();
+        inner.add("a".to_string(), vec![2, 2]).unwrap();
+        let mut format = Format::default();
+        format.add("x".to_string(), vec![1]).unwrap();
+        format.add_format("inner".to_string(), inner).unwrap();
+        format.add("y".to_string(), vec![2]).unwrap();
+
+        let mut position = Position::from_i32_values((0..7).collect(), vec![7]).unwrap();
+        {
+            let view = format.view(&position).unwrap();
+            assert_eq!(Ok(DimensionValue::from(3)), view["inner.a"].get(&[1, 0]));
+            assert_eq!(&[4], view["inner"].shape());
+            assert_eq!(
+                Position::simple(vec![DimensionValue::from(5), DimensionValue::from(6)]),
+                view["y"].to_position()
+            );
+            assert!(view.get("z").is_none());
+        }
+        {
+            let mut view = format.view_mut(&mut position).unwrap();
+            let mut inner = view.get_mut("inner.a").unwrap();
+            inner.set(&[0, 1], DimensionValue::from(20)).unwrap();
+            assert!(inner.set(&[2, 0], DimensionValue::from(0)).is_err());
+            view.get_mut("y").unwrap().as_i32_slice_mut().unwrap()[0] = 50;
+            assert_eq!(
+                Ok(DimensionValue::from(50)),
+                view.get("y").unwrap().get(&[0])
+            );
+            assert!(view.get("inner").is_none());
+        }
+        assert_eq!(DimensionValue::from(20), position.value(&[2]));
+        assert_eq!(DimensionValue::from(50), position.value(&[5]));
+        assert!(position.as_i32_slice().is_some());
+
+        let mut image = Position::from_f32_values(vec![0.0; 7], vec![7]).unwrap();
+        {
+            let mut view = format.view_mut(&mut image).unwrap();
+            view.get_mut("x")
+                .unwrap()
+                .set(&[0], DimensionValue::from(0.5f32))
+                .unwrap();
+            assert_eq!(None, view.get_mut("y").unwrap().as_i32_slice_mut());
+        }
+        assert_eq!(
+            Some(&[0.5, 0.0, 0.0][..]),
+            image.as_f32_slice().map(|values| &values[..3])
+        );
+        {
+            let mut view = format.view_mut(&mut image).unwrap();
+            view.get_mut("x")
+                .unwrap()
+                .set(&[0], DimensionValue::from(1))
+                .unwrap();
+        }
+        assert_eq!(None, image.as_f32_slice());
+        assert_eq!(DimensionValue::from(1), image.value(&[0]));
+
+        assert_eq!(
+            Err(FormatError::GivenSpaceDoesNotFit {
+                key: None,
+                needed: 7,
+                given: 1
+            }),
+            format
+                .view(&Position::simple(vec![DimensionValue::from(0)]))
+                .map(|_| ())
+        );
+    }
+
+    #[test]
+    fn space_dimension_names_follow_structural_operations() {
+        let space = Space::all(DimensionBoundaries::from(1f32), vec![2, 2])
+            .with_dimension_names(vec!["a", "b", "c", "d"])
+            .unwrap();
+        assert_eq!(
+            Err(SpaceError::GivenDimensionsDoNotMatch {
+                dimensions: vec![2, 2],
+                expected_length: 4,
+                actual_length: 1
+            }),
+            space.clone().with_dimension_units(vec!["m"])
+        );
+        assert_eq!(None, space.dimension_unit(&[0, 0]));
+        assert_eq!(None, space.dimension_name(&[2, 0]));
+
+        let transposed = space.transpose(&[1, 0]).unwrap();
+        assert_eq!(Some("c"), transposed.dimension_name(&[0, 1]));
+
+        let sliced = space.slice(1, 1..2).unwrap();
+        assert_eq!(
+            Some(&["b".to_string(), "d".to_string()][..]),
+            sliced.dimension_names()
+        );
+
+        let product = space.product(&Space::simple(vec![DimensionBoundaries::from(1)]));
+        assert_eq!(Some(""), product.dimension_name(&[4]));
+        assert_eq!(Some("d"), product.dimension_name(&[3]));
+
+        let deserialized: Space =
+            serde_json::from_str(&serde_json::to_string(&space).unwrap()).unwrap();
+        assert_eq!(space, deserialized);
+
+        let mut json = serde_json::to_value(&space).unwrap();
+        json["names"] = serde_json::json!(["a"]);
+        let truncated: Space = serde_json::from_value(json).unwrap();
+        assert_eq!(Some("a"), truncated.dimension_name(&[0, 0]));
+        assert_eq!(None, truncated.dimension_name(&[1, 1]));
+        assert_eq!(None, truncated.slice(1, 1..2).unwrap().dimension_names());
+
+        let named = Space::simple(vec![DimensionBoundaries::from(1)])
+            .with_dimension_names(vec!["e"])
+            .unwrap();
+        let product = truncated.product(&named);
+        assert_eq!(5, product.dimension_names().unwrap().len());
+        assert_eq!(Some("a"), product.dimension_name(&[0]));
+        assert_eq!(Some(""), product.dimension_name(&[3]));
+        assert_eq!(Some("e"), product.dimension_name(&[4]));
+    }
+
+    #[test]
+    fn display_prints_shapes_and_truncates_values() {
+        assert_eq!(
+            "Space[2x2x3: FLOAT 0..=255]",
+            Space::all(DimensionBoundaries::from(255f32), vec![2, 2, 3]).to_string()
+        );
+        assert_eq!(
+            "Space[2: INTEGER 0..=4, FLOAT -1..=1]",
+            Space::simple(vec![
+                DimensionBoundaries::from(4),
+                DimensionBoundaries::from(-1f32..=1f32)
+            ])
+            .to_string()
+        );
+
+        let position =
+            Position::new((0..12).map(DimensionValue::from).collect(), vec![3, 4]).unwrap();
+        assert_eq!(
+            "Position[3x4: 0, 1, 2, 3, 4, 5, 6, 7, ... (12 total)]",
+            position.to_string()
+        );
+        assert_eq!(
+            "Position[3x4: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]",
+            format!("{:#}", position)
+        );
+        assert_eq!("0.5", DimensionValue::from(0.5f32).to_string());
+    }
+
+    #[test]
+    fn stable_hashes_do_not_change() {
+        assert_eq!(
+            0x3a98_90f4_78e0_d6a0,
+            Position::simple(vec![DimensionValue::from(1), DimensionValue::from(-2)]).stable_hash()
+        );
+        assert_eq!(
+            0x671f_5227_b5f7_f358,
+            Position::simple(vec![DimensionValue::from(0.5f32)]).stable_hash()
+        );
+        let flat = Position::from_i32_values(vec![1, 2, 3, 4], vec![4]).unwrap();
+        let square = Position::from_i32_values(vec![1, 2, 3, 4], vec![2, 2]).unwrap();
+        assert_ne!(flat.stable_hash(), square.stable_hash());
+        assert_eq!(
+            Position::simple(vec![DimensionValue::from(-0f32)]).stable_hash(),
+            Position::simple(vec![DimensionValue::from(0f32)]).stable_hash()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn position_key_treats_special_floats_as_equal() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |key: &PositionKey| {
+            let mut hasher = DefaultHasher::new();
+            key.hash(&mut hasher);
+            hasher.finish()
+        };
+        let zero = Position::simple(vec![DimensionValue::from(0f32)]).as_hash_key();
+        let negative_zero = Position::simple(vec![DimensionValue::from(-0f32)]).as_hash_key();
+        assert_eq!(zero, negative_zero);
+        assert_eq!(hash(&zero), hash(&negative_zero));
+
+        let nan = Position::simple(vec![DimensionValue::from(f32::NAN)]).as_hash_key();
+        assert_eq!(nan, nan.clone());
+        assert!(zero < nan);
+
+        let integer = Position::simple(vec![DimensionValue::from(0)]).as_hash_key();
+        assert_ne!(integer, zero);
+        assert!(integer < zero);
+        assert!(
+            Position::simple(vec![DimensionValue::from(-1)]).as_hash_key()
+                < Position::simple(vec![DimensionValue::from(1)]).as_hash_key()
+        );
+    }
+
+    #[test]
+    fn sample_into_matches_sample_with() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let space = Space::all(DimensionBoundaries::from(-3..=3), vec![2, 3]);
+        let mut position = Position::simple_all(DimensionValue::from(0f32), 10);
+        space.sample_into(&mut position, &mut StdRng::seed_from_u64(4));
+        assert_eq!(space.sample_with(&mut StdRng::seed_from_u64(4)), position);
+
+        let mut copy = Position::default();
+        copy.copy_from(&position);
+        assert_eq!(position, copy);
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn small_positions_are_stored_inline() {
+        let space = Space::simple_all(DimensionBoundaries::from(1f32), INLINE_STORAGE_CAPACITY);
+        assert!(!space.boundaries.spilled());
+        assert!(matches!(space.sample().values, Values::Mixed(values) if !values.spilled()));
+        assert!(
+            Space::simple_all(DimensionBoundaries::from(1f32), INLINE_STORAGE_CAPACITY + 1)
+                .boundaries
+                .spilled()
+        );
+    }
+
+    #[test]
+    fn typed_slices_follow_mutations() {
+        let mut position = Position::from_i32_values(vec![1, 2, 3], vec![3]).unwrap();
+        assert_eq!(Some(&[1, 2, 3][..]), position.as_i32_slice());
+
+        position.set_value(&[1], DimensionValue::from(5));
+        assert_eq!(Some(&[1, 5, 3][..]), position.as_i32_slice());
+
+        position.set_value(&[2], DimensionValue::from(5f32));
+        assert_eq!(None, position.as_i32_slice());
+        assert_eq!(None, position.as_f32_slice());
+
+        position.copy_from(&Position::from_f32_values(vec![2f32, 2f32], vec![2]).unwrap());
+        assert_eq!(Some(&[2f32, 2f32][..]), position.as_f32_slice());
+        assert_eq!(
+            Position::simple_all(DimensionValue::from(2f32), 2),
+            position
+        );
+    }
+
+    #[test]
+    fn typed_values_are_indexed_through_typed_views() {
+        let mut position = Position::from_i32_values(vec![1, 2, 3, 4], vec![2, 2]).unwrap();
+        assert_eq!(&DimensionValue::from(3), position.get_value(&[1, 0]));
+        assert_eq!(4, position.i32_values().unwrap()[&[1, 1][..]]);
+        assert_eq!(4, position.get_values().len());
+        assert!(position.f32_values().is_none());
+
+        position.i32_values_mut().unwrap()[&[1, 0][..]] = 7;
+        assert_eq!(&DimensionValue::from(7), position.get_value(&[1, 0]));
+        assert_eq!(DimensionValue::from(7), position[&[1, 0][..]]);
+        assert_eq!(Some(&[1, 2, 7, 4][..]), position.as_i32_slice());
+
+        let mut format = Format::default();
+        format.add("all".to_string(), vec![4]).unwrap();
+        assert_eq!(
+            Ok(&DimensionValue::from(2)),
+            format.get_value(&position, "all", &[1])
+        );
+
+        position.set_value(&[0, 0], DimensionValue::from(0.5f32));
+        assert_eq!(DimensionValue::from(0.5f32), position.value(&[0, 0]));
+        assert!(position.i32_values_mut().is_none());
+    }
+
+    #[test]
+    fn indexing_typed_positions_mutably_switches_to_dimension_values() {
+        let mut position = Position::from_f32_values(vec![1.0, 2.0], vec![2]).unwrap();
+        assert_eq!(DimensionValue::from(2f32), position[&[1][..]]);
+        assert_eq!(Some(&[1f32, 2f32][..]), position.as_f32_slice());
+
+        position[&[0][..]] = DimensionValue::from(3f32);
+        assert_eq!(None, position.as_f32_slice());
+        assert_eq!(
+            &[DimensionValue::from(3f32), DimensionValue::from(2f32)][..],
+            position.get_values()
+        );
+    }
+
+    #[test]
+    fn homogeneous_values_are_stored_by_type() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<Position>();
+
+        let floats = Position::from_f32_values(vec![1.0, 2.0], vec![2]).unwrap();
+        assert!(matches!(floats.values, Values::F32(_)));
+        let tagged = Position::simple(vec![DimensionValue::from(1f32), DimensionValue::from(2f32)]);
+        assert!(matches!(tagged.values, Values::Mixed(_)));
+        assert_eq!(tagged, floats);
+
+        let json = serde_json::to_string(&floats).unwrap();
+        assert_eq!(
+            r#"{"values":[{"Float":1.0},{"Float":2.0}],"dimensions":[2]}"#,
+            json
+        );
+        let deserialized: Position = serde_json::from_str(&json).unwrap();
+        assert_eq!(floats, deserialized);
+
+        let mixed = floats
+            .concat(&Position::from_i32_values(vec![3], vec![1]).unwrap())
+            .unwrap();
+        assert!(matches!(mixed.values, Values::Mixed(_)));
+        let sliced = mixed.slice(0, 2..3).unwrap();
+        assert_eq!(None, sliced.as_i32_slice());
+        assert_eq!(&[DimensionValue::from(3)][..], sliced.get_values());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn format_errors_carry_the_key() {
+        let mut format = Format::default();
+        format.add("sensors".to_string(), vec![2, 2]).unwrap();
+        let position = Position::simple_all(DimensionValue::from(0), 4);
+
+        let error = format.value(&position, "sensors", &[2, 0]).unwrap_err();
+        assert_eq!(
+            FormatError::PositionIndexError {
+                key: "sensors".to_string(),
+                error: SpaceError::IndexOutOfBounds {
+                    dimensions: vec![2, 2],
+                    index: vec![2, 0]
+                }
+            },
+            error
+        );
+        assert!(std::error::Error::source(&error).is_some());
+        assert_eq!(
+            "Space Error \"Given index [2, 0] is out of bounds of dimensions [2, 2]\" occurred while indexing of position for key \"sensors\"",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn position_matches_requires_all_types_equal() {
+        let position = Position::simple(vec![DimensionValue::from(1), DimensionValue::from(1f32)]);
+
+        assert!(position.matches(&Position::simple(vec![
+            DimensionValue::from(7),
+            DimensionValue::from(0f32)
+        ])));
+        assert!(!position.matches(&Position::simple(vec![
+            DimensionValue::from(7),
+            DimensionValue::from(0)
+        ])));
+    }
+
+    #[test]
+    fn f64_values_are_checked_against_the_space() {
+        let space = Space::new(vec![DimensionBoundaries::from(0..=3); 4], vec![2, 2]).unwrap();
+        let position = Position::from_f64_values_in(&[0.0, 1.0, 2.0, 3.0], &space).unwrap();
+        assert_eq!(vec![2, 2], position.dimensions);
+        assert_eq!(
+            Position::from_i32_values(vec![0, 1, 2, 3], vec![2, 2]),
+            Ok(position)
+        );
+
+        assert!(matches!(
+            Position::from_f64_values_in(&[0.0, 1.0], &space),
+            Err(SpaceMismatch::ShapeMismatch { .. })
+        ));
+        assert!(matches!(
+            Position::from_f64_values_in(&[0.0, 1.0, 2.0, 4.0], &space),
+            Err(SpaceMismatch::ValueOutOfBounds { index: 3, .. })
+        ));
+        assert!(matches!(
+            Position::from_f64_values_in(&[0.0, f64::NAN, 2.0, 3.0], &space),
+            Err(SpaceMismatch::ValueTypeMismatch { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn empty_and_oversized_categorical_boundaries_do_not_panic() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let empty = DimensionBoundaries::Categorical(0);
+        assert_eq!(DimensionValue::Integer(0), empty.sample_with(&mut rng));
+        assert_eq!(Some((0, -1)), empty.integer_range());
+        assert!(!empty.contains(&DimensionValue::Integer(0)));
+        assert_eq!(
+            Some((0, i32::MAX)),
+            DimensionBoundaries::Categorical(u32::MAX).integer_range()
+        );
+        for _ in 0..50 {
+            let sample = DimensionBoundaries::Categorical(u32::MAX).sample_with(&mut rng);
+            assert!(sample.expect_integer() >= 0);
+        }
+
+        let space = Space::simple(vec![empty]);
+        assert!(space.contains(&space.sample_with(&mut rng)).is_err());
+        assert_eq!(
+            Some((0, 0)),
+            DimensionBoundaries::Categorical(1).integer_range()
+        );
+    }
+
+    #[test]
+    fn categorical_boundaries_hold_label_indices() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let space = Space::builder()
+            .categorical(vec!["sunny", "rain", "fog"])
+            .integer(0..=1)
+            .build()
+            .unwrap();
+        let weather = space.get_boundaries()[0];
+        assert_eq!(DimensionBoundaries::Categorical(3), weather);
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..50 {
+            let sample = space.sample_with(&mut rng);
+            assert!(space.contains(&sample).is_ok());
+            assert!(space.category(&[0], &sample.value(&[0])).is_some());
+        }
+        assert!(matches!(
+            space.contains(&crate::position![3, 0]),
+            Err(SpaceMismatch::ValueOutOfBounds { index: 0, .. })
+        ));
+        assert!(!weather.contains(&DimensionValue::Float(1.0)));
+        assert!(!DimensionBoundaries::Categorical(0).is_valid());
+        assert_eq!(
+            Some(DimensionValue::Integer(2)),
+            space.category_value(&[0], "fog")
+        );
+        assert_eq!(None, space.category_value(&[0], "snow"));
+        assert_eq!(None, space.category(&[1], &DimensionValue::Integer(0)));
+        assert_eq!("CATEGORICAL 3", weather.to_string());
+
+        let json = serde_json::to_string(&space).unwrap();
+        assert_eq!(space, serde_json::from_str(&json).unwrap());
+
+        let product = space.product(&Space::simple(vec![DimensionBoundaries::from(1)]));
+        assert_eq!(
+            Some("fog"),
+            product.category(&[0], &DimensionValue::Integer(2))
+        );
+        let sliced = product.slice(0, 1..3).unwrap();
+        assert_eq!(Some(&[vec![], vec![]][..]), sliced.categories());
+
+        assert_eq!(
+            Err(SpaceError::InvalidBoundaries { index: 0 }),
+            space.clone().with_categories(vec![vec!["sunny"], vec![]])
+        );
+        assert_eq!(
+            Err(SpaceError::InvalidBoundaries { index: 1 }),
+            space.with_categories(vec![vec![], vec!["no", "yes"]])
+        );
+    }
+
+    #[test]
+    fn one_hot_encodings_round_trip() {
+        let weather = DimensionBoundaries::Categorical(3);
+        for index in 0..3 {
+            let value = DimensionValue::Integer(index);
+            let encoded = weather.one_hot(&value).unwrap();
+            assert_eq!(1f32, encoded.iter().sum::<f32>());
+            assert_eq!(Some(value), weather.from_one_hot(&encoded));
+        }
+        assert_eq!(None, weather.one_hot(&DimensionValue::Integer(3)));
+        assert_eq!(None, weather.from_one_hot(&[1.0, 0.0]));
+        assert_eq!(
+            Some(DimensionValue::Integer(0)),
+            weather.from_one_hot(&[0.5, 0.5, 0.0])
+        );
+        assert_eq!(
+            None,
+            DimensionBoundaries::Integer(0, 2).one_hot(&DimensionValue::Integer(1))
+        );
+    }
+}
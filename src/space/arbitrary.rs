@@ -0,0 +1,125 @@
+//! `arbitrary::Arbitrary` implementations generating valid spaces for fuzzing.
+//!
+//! Boundaries are always valid, so sampling from generated spaces never panics because of them.
+//! Positions are generated independently of any space and may contain every float, including
+//! `NaN` and infinities.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::{DimensionBoundaries, DimensionValue, Position, Space};
+
+/// Most dimensions of a generated space or position.
+const MAX_RANK: usize = 4;
+/// Largest length of a dimension of a generated space or position.
+const MAX_LENGTH: usize = 8;
+/// Most categories of generated categorical boundaries.
+const MAX_CATEGORIES: u32 = 16;
+
+fn arbitrary_dimensions(u: &mut Unstructured<'_>) -> Result<Vec<usize>> {
+    (0..u.int_in_range(1..=MAX_RANK)?)
+        .map(|_| u.int_in_range(1..=MAX_LENGTH))
+        .collect()
+}
+
+/// Returns a finite float, replacing `NaN` and infinities by `0.0`.
+fn finite(value: f32) -> f32 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
+
+impl<'a> Arbitrary<'a> for DimensionBoundaries {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=2u8)? {
+            0 => {
+                let (a, b) = (finite(f32::arbitrary(u)?), finite(f32::arbitrary(u)?));
+                Ok(Self::Float(a.min(b), a.max(b)))
+            }
+            1 => {
+                let (a, b) = (i32::arbitrary(u)?, i32::arbitrary(u)?);
+                Ok(Self::Integer(a.min(b), a.max(b)))
+            }
+            _ => Ok(Self::Categorical(u.int_in_range(1..=MAX_CATEGORIES)?)),
+        }
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(u8::size_hint(depth), (1, Some(8)))
+    }
+}
+
+impl<'a> Arbitrary<'a> for DimensionValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.arbitrary()? {
+            Ok(Self::Integer(u.arbitrary()?))
+        } else {
+            Ok(Self::Float(u.arbitrary()?))
+        }
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(bool::size_hint(depth), (4, Some(4)))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Space {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let dimensions = arbitrary_dimensions(u)?;
+        let boundaries = (0..dimensions.iter().product::<usize>())
+            .map(|_| u.arbitrary())
+            .collect::<Result<Vec<DimensionBoundaries>>>()?;
+        Ok(Space::new(boundaries, dimensions).expect("boundaries fill the dimensions"))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Position {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let dimensions = arbitrary_dimensions(u)?;
+        let values = (0..dimensions.iter().product::<usize>())
+            .map(|_| u.arbitrary())
+            .collect::<Result<Vec<DimensionValue>>>()?;
+        Ok(Position::new(values, dimensions).expect("values fill the dimensions"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_spaces_are_valid() {
+        let bytes = (0..4096u32)
+            .map(|index| (index.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect::<Vec<_>>();
+        let mut u = Unstructured::new(&bytes);
+        let mut categorical = 0;
+        while !u.is_empty() {
+            let space = Space::arbitrary(&mut u).unwrap();
+            assert!(space.get_boundaries().iter().all(|b| b.is_valid()));
+            categorical += space
+                .get_boundaries()
+                .iter()
+                .filter(|b| matches!(b, DimensionBoundaries::Categorical(_)))
+                .count();
+            assert!((1..=MAX_RANK).contains(&space.dimensions().len()));
+            let position = Position::arbitrary(&mut u).unwrap();
+            assert_eq!(
+                position.dimensions().iter().product::<usize>(),
+                position.values().len()
+            );
+        }
+        assert!(categorical > 0);
+    }
+
+    #[test]
+    fn empty_input_still_generates_values() {
+        let mut u = Unstructured::new(&[]);
+        assert_eq!(
+            DimensionBoundaries::Float(0.0, 0.0),
+            DimensionBoundaries::arbitrary(&mut u).unwrap()
+        );
+        assert_eq!(&vec![1], Space::arbitrary(&mut u).unwrap().dimensions());
+    }
+}
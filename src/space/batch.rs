@@ -0,0 +1,411 @@
+//! Contiguous storage for many positions of the same space.
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, vec::Vec};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+use super::{
+    into_storage, DimensionValue, Position, Space, SpaceError, SpaceMismatch, SubPosition, Values,
+    ValuesRef,
+};
+
+fn check_length(length: usize, position_dimensions: &[usize]) -> Result<usize, SpaceError> {
+    let position_length = position_dimensions.iter().product::<usize>();
+    if position_length > 0 && length.is_multiple_of(position_length) {
+        Ok(length / position_length)
+    } else {
+        Err(SpaceError::GivenDimensionsDoNotMatch {
+            dimensions: position_dimensions.to_vec(),
+            expected_length: position_length,
+            actual_length: length,
+        })
+    }
+}
+
+/* --- --- --- POSITION BATCH --- --- --- */
+
+/// Stores positions with equal dimensions back to back inside one buffer.
+///
+/// Like positions, batches of typed positions store their values as plain `f32` or `i32`, which
+/// `as_f32_slice` and `as_i32_slice` borrow without any conversion.
+///
+/// ```
+/// use gymnarium_base::space::{DimensionValue, Position, PositionBatch};
+///
+/// let mut batch = PositionBatch::new(vec![2]);
+/// batch.push(&Position::from_i32_values(vec![1, 2], vec![2]).unwrap()).unwrap();
+/// batch.push(&Position::from_i32_values(vec![3, 4], vec![2]).unwrap()).unwrap();
+///
+/// assert_eq!(vec![2, 2], batch.dimensions());
+/// assert_eq!(Ok(DimensionValue::from(3)), batch.get(1).unwrap().get(&[0]));
+/// assert_eq!(Some(&[1, 2, 3, 4][..]), batch.as_i32_slice());
+/// assert_eq!(vec![1f32, 2f32, 3f32, 4f32], batch.to_f32_vec());
+/// ```
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct PositionBatch {
+    values: Values,
+    position_dimensions: Vec<usize>,
+    position_length: usize,
+    len: usize,
+}
+
+impl PositionBatch {
+    pub fn new(position_dimensions: Vec<usize>) -> Self {
+        Self::with_capacity(position_dimensions, 0)
+    }
+
+    pub fn with_capacity(position_dimensions: Vec<usize>, capacity: usize) -> Self {
+        let position_length = position_dimensions.iter().product();
+        Self {
+            values: Values::Mixed(into_storage(Vec::with_capacity(capacity * position_length))),
+            position_dimensions,
+            position_length,
+            len: 0,
+        }
+    }
+
+    /// Creates an empty batch for positions of the given space.
+    pub fn for_space(space: &Space) -> Self {
+        Self::new(space.dimensions().clone())
+    }
+
+    /// Creates a batch from positions which all need the dimensions of the first one.
+    pub fn from_positions<'a, I: IntoIterator<Item = &'a Position>>(
+        positions: I,
+    ) -> Result<Self, SpaceMismatch> {
+        let mut positions = positions.into_iter().peekable();
+        let mut batch = match positions.peek() {
+            Some(first) => Self::new(first.dimensions().clone()),
+            None => Self::default(),
+        };
+        for position in positions {
+            batch.push(position)?;
+        }
+        Ok(batch)
+    }
+
+    /// Creates a batch of floats, whose number has to be a multiple of the length of a position.
+    pub fn from_f32_values(
+        values: Vec<f32>,
+        position_dimensions: Vec<usize>,
+    ) -> Result<Self, SpaceError> {
+        let len = check_length(values.len(), &position_dimensions)?;
+        Ok(Self {
+            values: Values::F32(into_storage(values)),
+            position_length: position_dimensions.iter().product(),
+            position_dimensions,
+            len,
+        })
+    }
+
+    /// Creates a batch of integers, whose number has to be a multiple of the length of a
+    /// position.
+    pub fn from_i32_values(
+        values: Vec<i32>,
+        position_dimensions: Vec<usize>,
+    ) -> Result<Self, SpaceError> {
+        let len = check_length(values.len(), &position_dimensions)?;
+        Ok(Self {
+            values: Values::I32(into_storage(values)),
+            position_length: position_dimensions.iter().product(),
+            position_dimensions,
+            len,
+        })
+    }
+
+    /// Appends the position, which needs the dimensions of this batch.
+    pub fn push(&mut self, position: &Position) -> Result<(), SpaceMismatch> {
+        if position.dimensions() != &self.position_dimensions {
+            return Err(SpaceMismatch::ShapeMismatch {
+                expected: self.position_dimensions.clone(),
+                actual: position.dimensions().clone(),
+            });
+        }
+        self.values.extend_from(position.values.as_ref());
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn get(&self, index: usize) -> Option<SubPosition<'_>> {
+        self.view().get(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = SubPosition<'_>> {
+        self.view().iter()
+    }
+
+    /// Borrows the positions of this batch.
+    pub fn view(&self) -> PositionBatchView<'_> {
+        PositionBatchView {
+            values: self.values.as_ref(),
+            position_dimensions: &self.position_dimensions,
+            position_length: self.position_length,
+            len: self.len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.len = 0;
+    }
+
+    /// Returns the dimensions of a single position of this batch.
+    pub fn position_dimensions(&self) -> &[usize] {
+        &self.position_dimensions
+    }
+
+    /// Returns the dimensions of the whole batch, which is `[len, ...position dimensions]`.
+    pub fn dimensions(&self) -> Vec<usize> {
+        self.view().dimensions()
+    }
+
+    /// Returns all values, only copying them if they are stored by type.
+    pub fn get_values(&self) -> Cow<'_, [DimensionValue]> {
+        self.values.as_ref().to_dimension_values()
+    }
+
+    /// Returns all values as contiguous floats, if this batch stores only floats.
+    pub fn as_f32_slice(&self) -> Option<&[f32]> {
+        self.values.as_ref().as_f32_slice()
+    }
+
+    /// Returns all values as contiguous integers, if this batch stores only integers.
+    pub fn as_i32_slice(&self) -> Option<&[i32]> {
+        self.values.as_ref().as_i32_slice()
+    }
+
+    /// Returns all values in row-major order of `dimensions()`, converting integers to floats.
+    pub fn to_f32_vec(&self) -> Vec<f32> {
+        self.view().to_f32_vec()
+    }
+
+    /// Returns the whole batch as one position with the dimensions `dimensions()`.
+    pub fn to_position(&self) -> Position {
+        Position::with_values(self.values.clone(), self.dimensions())
+            .expect("Batch values always match the batch dimensions")
+    }
+}
+
+/* --- --- --- POSITION BATCH VIEW --- --- --- */
+
+/// Borrowed positions with equal dimensions back to back, e.g. of a `PositionBatch` or of a
+/// buffer shared with other processes.
+///
+/// ```
+/// use gymnarium_base::space::{DimensionValue, PositionBatchView};
+///
+/// let frames = [0.0, 0.5, 1.0, 0.5, 0.25, 0.75];
+/// let dimensions = [3];
+/// let view = PositionBatchView::from_f32_slice(&frames, &dimensions).unwrap();
+///
+/// assert_eq!(2, view.len());
+/// assert_eq!(Some(&frames[3..]), view.get(1).unwrap().as_f32_slice());
+/// assert_eq!(Ok(DimensionValue::from(0.25f32)), view.get(1).unwrap().get(&[1]));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PositionBatchView<'a> {
+    values: ValuesRef<'a>,
+    position_dimensions: &'a [usize],
+    position_length: usize,
+    len: usize,
+}
+
+impl<'a> PositionBatchView<'a> {
+    /// Borrows floats, whose number has to be a multiple of the length of a position.
+    pub fn from_f32_slice(
+        values: &'a [f32],
+        position_dimensions: &'a [usize],
+    ) -> Result<Self, SpaceError> {
+        Ok(Self {
+            len: check_length(values.len(), position_dimensions)?,
+            values: ValuesRef::F32(values),
+            position_dimensions,
+            position_length: position_dimensions.iter().product(),
+        })
+    }
+
+    /// Borrows integers, whose number has to be a multiple of the length of a position.
+    pub fn from_i32_slice(
+        values: &'a [i32],
+        position_dimensions: &'a [usize],
+    ) -> Result<Self, SpaceError> {
+        Ok(Self {
+            len: check_length(values.len(), position_dimensions)?,
+            values: ValuesRef::I32(values),
+            position_dimensions,
+            position_length: position_dimensions.iter().product(),
+        })
+    }
+
+    /// Borrows floats of whole positions, leaving out the values after the last whole one.
+    #[cfg(feature = "shm")]
+    pub(crate) fn whole_f32_positions(values: &'a [f32], position_dimensions: &'a [usize]) -> Self {
+        Self::whole_positions(ValuesRef::F32(values), position_dimensions)
+    }
+
+    /// Borrows integers of whole positions, leaving out the values after the last whole one.
+    #[cfg(feature = "shm")]
+    pub(crate) fn whole_i32_positions(values: &'a [i32], position_dimensions: &'a [usize]) -> Self {
+        Self::whole_positions(ValuesRef::I32(values), position_dimensions)
+    }
+
+    #[cfg(feature = "shm")]
+    fn whole_positions(values: ValuesRef<'a>, position_dimensions: &'a [usize]) -> Self {
+        let position_length = position_dimensions.iter().product::<usize>();
+        Self {
+            len: values.len().checked_div(position_length).unwrap_or(0),
+            values,
+            position_dimensions,
+            position_length,
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<SubPosition<'a>> {
+        if index < self.len {
+            let start = index * self.position_length;
+            Some(SubPosition {
+                values: self.values.slice(start..start + self.position_length),
+                shape: self.position_dimensions,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = SubPosition<'a>> {
+        let view = *self;
+        (0..self.len).filter_map(move |index| view.get(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the dimensions of a single position of this view.
+    pub fn position_dimensions(&self) -> &'a [usize] {
+        self.position_dimensions
+    }
+
+    /// Returns the dimensions of the whole view, which is `[len, ...position dimensions]`.
+    pub fn dimensions(&self) -> Vec<usize> {
+        let mut dimensions = Vec::with_capacity(self.position_dimensions.len() + 1);
+        dimensions.push(self.len);
+        dimensions.extend_from_slice(self.position_dimensions);
+        dimensions
+    }
+
+    /// Returns all values as contiguous floats, if this view holds only floats.
+    pub fn as_f32_slice(&self) -> Option<&'a [f32]> {
+        self.values.as_f32_slice()
+    }
+
+    /// Returns all values as contiguous integers, if this view holds only integers.
+    pub fn as_i32_slice(&self) -> Option<&'a [i32]> {
+        self.values.as_i32_slice()
+    }
+
+    /// Returns all values in row-major order of `dimensions()`, converting integers to floats.
+    pub fn to_f32_vec(&self) -> Vec<f32> {
+        match self.values {
+            ValuesRef::F32(values) => values.to_vec(),
+            ValuesRef::I32(values) => values.iter().map(|value| *value as f32).collect(),
+            ValuesRef::Mixed(values) => values
+                .iter()
+                .map(|value| match value {
+                    DimensionValue::Integer(value) => *value as f32,
+                    DimensionValue::Float(value) => *value,
+                })
+                .collect(),
+        }
+    }
+
+    /// Copies the viewed positions into an owned batch.
+    pub fn to_batch(&self) -> PositionBatch {
+        PositionBatch {
+            values: self.values.to_values(),
+            position_dimensions: self.position_dimensions.to_vec(),
+            position_length: self.position_length,
+            len: self.len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::DimensionBoundaries;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn push_rejects_other_dimensions_and_iterates_in_order() {
+        let space = Space::all(DimensionBoundaries::from(3), vec![2, 2]);
+        let mut rng = StdRng::seed_from_u64(0);
+        let positions: Vec<Position> = (0..3).map(|_| space.sample_with(&mut rng)).collect();
+        let mut batch = PositionBatch::from_positions(&positions).unwrap();
+
+        assert_eq!(
+            Err(SpaceMismatch::ShapeMismatch {
+                expected: vec![2, 2],
+                actual: vec![4]
+            }),
+            batch.push(&Position::simple_all(DimensionValue::from(0), 4))
+        );
+        assert_eq!(3, batch.len());
+        assert!(batch.get(3).is_none());
+        assert_eq!(
+            positions,
+            batch.iter().map(|p| p.to_position()).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![3, 2, 2], batch.to_position().dimensions().clone());
+
+        batch.clear();
+        assert!(batch.is_empty());
+        assert_eq!(vec![0, 2, 2], batch.dimensions());
+    }
+
+    #[test]
+    fn homogeneous_batches_are_stored_by_type() {
+        let positions: Vec<Position> = (0..4)
+            .map(|index| Position::from_f32_values(vec![index as f32; 2], vec![2]).unwrap())
+            .collect();
+        let batch = PositionBatch::from_positions(&positions).unwrap();
+        let floats = batch.as_f32_slice().unwrap();
+        assert_eq!(8, floats.len());
+        assert_eq!(Some(&floats[2..4]), batch.get(1).unwrap().as_f32_slice());
+        assert_eq!(
+            batch,
+            PositionBatch::from_f32_values(floats.to_vec(), vec![2]).unwrap()
+        );
+        assert_eq!(
+            batch,
+            PositionBatchView::from_f32_slice(floats, &[2])
+                .unwrap()
+                .to_batch()
+        );
+
+        let mut mixed = batch.clone();
+        mixed.push(&crate::position![1, 0.5f32]).unwrap();
+        assert_eq!(None, mixed.as_f32_slice());
+        assert_eq!(positions[3], mixed.get(3).unwrap().to_position());
+
+        assert!(PositionBatch::from_i32_values(vec![1, 2, 3], vec![2]).is_err());
+        assert!(PositionBatchView::from_i32_slice(&[], &[0]).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Human-readable labels for the integer values of action spaces.
+
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{DimensionValue, Position, Space, SpaceError};
+
+/* --- --- --- ACTION CATALOG --- --- --- */
+
+/// Labels integer values of every dimension of a space, e.g. `0` as `"NOOP"` and `1` as `"FIRE"`.
+///
+/// Dimensions are counted in row-major order like the boundaries of the space. Attached to a
+/// space with `Space::with_action_catalog`, it follows slicing, reshaping and the other
+/// structural operations of the space.
+///
+/// ```
+/// use gymnarium_base::position;
+/// use gymnarium_base::space::{ActionCatalog, DimensionBoundaries, Space};
+///
+/// let action_space = Space::simple(vec![DimensionBoundaries::from(0..=2)])
+///     .with_action_catalog(ActionCatalog::discrete(vec!["NOOP", "FIRE", "LEFT"]))
+///     .unwrap();
+/// let catalog = action_space.action_catalog().unwrap();
+///
+/// assert_eq!(Some("FIRE"), catalog.label(0, 1));
+/// assert_eq!(Some(2), catalog.value_of(0, "LEFT"));
+/// assert_eq!("LEFT", catalog.describe(&position![2]));
+/// ```
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ActionCatalog {
+    labels: Vec<BTreeMap<i32, String>>,
+}
+
+impl ActionCatalog {
+    /// Creates a catalog for `dimensions` dimensions without any labels.
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            labels: (0..dimensions).map(|_| BTreeMap::new()).collect(),
+        }
+    }
+
+    /// Creates a catalog of a single dimension labelling the values `0`, `1` and so on.
+    pub fn discrete<S: Into<String>>(labels: Vec<S>) -> Self {
+        Self {
+            labels: vec![(0..)
+                .zip(labels)
+                .map(|(value, label)| (value, label.into()))
+                .collect()],
+        }
+    }
+
+    /// Labels `value` of `dimension`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the catalog has no dimension `dimension`.
+    pub fn with_label<S: Into<String>>(mut self, dimension: usize, value: i32, label: S) -> Self {
+        self.labels[dimension].insert(value, label.into());
+        self
+    }
+
+    /// Returns the number of dimensions.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn label(&self, dimension: usize, value: i32) -> Option<&str> {
+        self.labels
+            .get(dimension)
+            .and_then(|labels| labels.get(&value))
+            .map(String::as_str)
+    }
+
+    /// Returns the value labelled `label` inside `dimension`, e.g. to parse typed actions.
+    pub fn value_of(&self, dimension: usize, label: &str) -> Option<i32> {
+        self.labels.get(dimension).and_then(|labels| {
+            labels
+                .iter()
+                .find(|(_, known)| known.as_str() == label)
+                .map(|(value, _)| *value)
+        })
+    }
+
+    /// Returns all labelled values of `dimension` in ascending order.
+    pub fn labels(&self, dimension: usize) -> impl Iterator<Item = (i32, &str)> + '_ {
+        self.labels
+            .get(dimension)
+            .into_iter()
+            .flat_map(|labels| labels.iter().map(|(value, label)| (*value, label.as_str())))
+    }
+
+    /// Describes an action by the labels of its values separated by `", "`, using the values
+    /// themselves where no label exists.
+    pub fn describe(&self, action: &Position) -> String {
+        action
+            .values()
+            .iter()
+            .enumerate()
+            .map(|(dimension, value)| match value {
+                DimensionValue::Integer(integer) => match self.label(dimension, *integer) {
+                    Some(label) => label.to_string(),
+                    None => value.to_string(),
+                },
+                DimensionValue::Float(_) => value.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Returns the catalog of the dimensions at the given row-major indices.
+    pub(super) fn select(&self, indices: &[usize]) -> Self {
+        Self {
+            labels: indices.iter().map(|&i| self.labels[i].clone()).collect(),
+        }
+    }
+
+    /// Joins both catalogs, using empty dimensions for a missing or short one.
+    pub(super) fn join(
+        own: &Option<Self>,
+        own_length: usize,
+        theirs: &Option<Self>,
+        their_length: usize,
+    ) -> Option<Self> {
+        if own.is_none() && theirs.is_none() {
+            return None;
+        }
+        let mut labels = own.clone().unwrap_or_default().labels;
+        labels.resize_with(own_length, BTreeMap::new);
+        let mut their_labels = theirs.clone().unwrap_or_default().labels;
+        their_labels.resize_with(their_length, BTreeMap::new);
+        labels.append(&mut their_labels);
+        Some(Self { labels })
+    }
+}
+
+/* --- --- --- SPACE --- --- --- */
+
+impl Space {
+    /// Attaches labels for the values of every boundary of this space.
+    pub fn with_action_catalog(mut self, catalog: ActionCatalog) -> Result<Self, SpaceError> {
+        if catalog.len() == self.boundaries.len() {
+            self.catalog = Some(catalog);
+            Ok(self)
+        } else {
+            Err(SpaceError::GivenDimensionsDoNotMatch {
+                dimensions: self.dimensions.clone(),
+                expected_length: self.boundaries.len(),
+                actual_length: catalog.len(),
+            })
+        }
+    }
+
+    pub fn action_catalog(&self) -> Option<&ActionCatalog> {
+        self.catalog.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position;
+    use crate::space::DimensionBoundaries;
+
+    fn gamepad() -> Space {
+        Space::simple(vec![
+            DimensionBoundaries::from(0..=2),
+            DimensionBoundaries::from(0..=1),
+        ])
+        .with_action_catalog(
+            ActionCatalog::new(2)
+                .with_label(0, 1, "LEFT")
+                .with_label(0, 2, "RIGHT")
+                .with_label(1, 1, "FIRE"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn catalogs_describe_actions() {
+        let catalog = gamepad().action_catalog().unwrap().clone();
+        assert_eq!("LEFT, FIRE", catalog.describe(&position![1, 1]));
+        assert_eq!("0, 0", catalog.describe(&position![0, 0]));
+        assert_eq!(
+            vec![(1, "LEFT"), (2, "RIGHT")],
+            catalog.labels(0).collect::<Vec<_>>()
+        );
+        assert_eq!(None, catalog.value_of(1, "LEFT"));
+        assert!(Space::simple(vec![DimensionBoundaries::from(0..=1)])
+            .with_action_catalog(catalog)
+            .is_err());
+    }
+
+    #[test]
+    fn catalogs_follow_structural_operations() {
+        let space = gamepad();
+        let buttons = space.slice(0, 1..2).unwrap();
+        assert_eq!(Some("FIRE"), buttons.action_catalog().unwrap().label(0, 1));
+
+        let product = Space::simple(vec![DimensionBoundaries::from(0..=1)]).product(&space);
+        let catalog = product.action_catalog().unwrap();
+        assert_eq!(3, catalog.len());
+        assert_eq!(None, catalog.label(0, 1));
+        assert_eq!(Some("RIGHT"), catalog.label(1, 2));
+
+        let json = serde_json::to_string(&space).unwrap();
+        assert_eq!(space, serde_json::from_str(&json).unwrap());
+    }
+}
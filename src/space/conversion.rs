@@ -0,0 +1,161 @@
+//! Conversions between own types and positions, usually derived with the `derive` feature.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::convert::TryInto;
+
+use super::{DimensionBoundaries, DimensionValue, Position, Space, SpaceMismatch};
+
+/// Types which can be turned into positions of a fixed space.
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use gymnarium_base::space::{DimensionBoundaries, FromPosition, Space, ToPosition};
+///
+/// #[derive(Debug, PartialEq, ToPosition, FromPosition)]
+/// struct CartPoleObservation {
+///     #[position(boundaries = -4.8f32..=4.8f32)]
+///     cart_position: f32,
+///     cart_velocity: f32,
+///     #[position(boundaries = 0..=1)]
+///     flags: [i32; 2],
+/// }
+///
+/// let observation = CartPoleObservation { cart_position: 1.0, cart_velocity: -0.5, flags: [0, 1] };
+/// let position = observation.to_position();
+///
+/// assert_eq!(&DimensionBoundaries::from(1), CartPoleObservation::space().get_boundary(&[3]));
+/// assert!(CartPoleObservation::space().contains(&position).is_ok());
+/// assert_eq!(Ok(observation), CartPoleObservation::from_position(&position));
+/// # }
+/// ```
+pub trait ToPosition {
+    fn space() -> Space;
+
+    fn to_position(&self) -> Position;
+}
+
+/// Types which can be read back from positions.
+///
+/// Only the shape and the value types are checked, not the boundaries.
+pub trait FromPosition: Sized {
+    fn from_position(position: &Position) -> Result<Self, SpaceMismatch>;
+}
+
+/// Field types supported by the `ToPosition` and `FromPosition` derive macros.
+pub trait PositionField: Sized {
+    /// How many values this field occupies inside the position.
+    const LENGTH: usize;
+
+    /// Returns the boundaries of all values, using `given` instead of the default boundaries.
+    fn boundaries(given: Option<DimensionBoundaries>) -> Vec<DimensionBoundaries>;
+
+    fn write_values(&self, values: &mut Vec<DimensionValue>);
+
+    /// Reads the field from the first `LENGTH` values or returns the index of the first value with
+    /// the wrong type.
+    fn read_values(values: &[DimensionValue]) -> Result<Self, usize>;
+
+    /// Describes the wrong type of value `index` of this field starting at `offset`.
+    fn type_mismatch(values: &[DimensionValue], offset: usize, index: usize) -> SpaceMismatch {
+        SpaceMismatch::ValueTypeMismatch {
+            index: offset + index,
+            boundaries: Self::boundaries(None)[index],
+            value: values[offset + index],
+        }
+    }
+}
+
+impl PositionField for f32 {
+    const LENGTH: usize = 1;
+
+    fn boundaries(given: Option<DimensionBoundaries>) -> Vec<DimensionBoundaries> {
+        vec![given.unwrap_or(DimensionBoundaries::Float(f32::MIN, f32::MAX))]
+    }
+
+    fn write_values(&self, values: &mut Vec<DimensionValue>) {
+        values.push(DimensionValue::Float(*self));
+    }
+
+    fn read_values(values: &[DimensionValue]) -> Result<Self, usize> {
+        match values.first() {
+            Some(DimensionValue::Float(value)) => Ok(*value),
+            _ => Err(0),
+        }
+    }
+}
+
+impl PositionField for i32 {
+    const LENGTH: usize = 1;
+
+    fn boundaries(given: Option<DimensionBoundaries>) -> Vec<DimensionBoundaries> {
+        vec![given.unwrap_or(DimensionBoundaries::Integer(i32::MIN, i32::MAX))]
+    }
+
+    fn write_values(&self, values: &mut Vec<DimensionValue>) {
+        values.push(DimensionValue::Integer(*self));
+    }
+
+    fn read_values(values: &[DimensionValue]) -> Result<Self, usize> {
+        match values.first() {
+            Some(DimensionValue::Integer(value)) => Ok(*value),
+            _ => Err(0),
+        }
+    }
+}
+
+impl<T: PositionField, const N: usize> PositionField for [T; N] {
+    const LENGTH: usize = N * T::LENGTH;
+
+    fn boundaries(given: Option<DimensionBoundaries>) -> Vec<DimensionBoundaries> {
+        (0..N).flat_map(|_| T::boundaries(given)).collect()
+    }
+
+    fn write_values(&self, values: &mut Vec<DimensionValue>) {
+        self.iter().for_each(|element| element.write_values(values));
+    }
+
+    fn read_values(values: &[DimensionValue]) -> Result<Self, usize> {
+        let elements = (0..N)
+            .map(|element| {
+                let offset = element * T::LENGTH;
+                T::read_values(&values[offset..]).map_err(|index| offset + index)
+            })
+            .collect::<Result<Vec<T>, usize>>()?;
+        Ok(elements
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("Exactly N elements have been read")))
+    }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+    use crate::space::{FromPosition, ToPosition};
+
+    #[derive(Debug, PartialEq, ToPosition, FromPosition)]
+    struct Grid([[i32; 2]; 2], f32);
+
+    #[test]
+    fn derived_conversions_report_type_mismatches() {
+        let grid = Grid([[1, 2], [3, 4]], 0.5);
+        let mut position = grid.to_position();
+        assert_eq!(&vec![5], position.dimensions());
+        assert_eq!(Ok(grid), Grid::from_position(&position));
+
+        position.set_value(&[2], DimensionValue::from(3f32));
+        assert_eq!(
+            Err(SpaceMismatch::ValueTypeMismatch {
+                index: 2,
+                boundaries: DimensionBoundaries::Integer(i32::MIN, i32::MAX),
+                value: DimensionValue::from(3f32)
+            }),
+            Grid::from_position(&position)
+        );
+        assert!(matches!(
+            Grid::from_position(&Position::from_indices(&[1])),
+            Err(SpaceMismatch::ShapeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+//! Enumerates the positions of spaces with only integer boundaries.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+
+use super::{DimensionValue, Space};
+
+/// Why the positions of a space cannot be enumerated.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum DiscreteError {
+    NotDiscrete {
+        index: usize,
+    },
+    /// The values of a dimension, or all positions together, cannot be counted with `usize`.
+    TooManyPositions,
+}
+
+/// The minimum and the count of values of every dimension of a discrete space.
+///
+/// Positions are numbered like numbers with the last dimension as least significant digit.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct DiscreteRanges {
+    minimums: Vec<i32>,
+    cardinalities: Vec<usize>,
+}
+
+impl DiscreteRanges {
+    pub(crate) fn of(space: &Space) -> Result<Self, DiscreteError> {
+        let (minimums, cardinalities) = space
+            .get_boundaries()
+            .iter()
+            .enumerate()
+            .map(|(index, boundaries)| match boundaries.integer_range() {
+                Some((min, max)) => usize::try_from(i64::from(max) - i64::from(min) + 1)
+                    .map(|cardinality| (min, cardinality))
+                    .map_err(|_| DiscreteError::TooManyPositions),
+                None => Err(DiscreteError::NotDiscrete { index }),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+        Ok(Self {
+            minimums,
+            cardinalities,
+        })
+    }
+
+    pub(crate) fn cardinalities(&self) -> &[usize] {
+        &self.cardinalities
+    }
+
+    /// Returns the count of all positions, or `None` if it does not fit into `usize`.
+    pub(crate) fn count(&self) -> Option<usize> {
+        self.cardinalities
+            .iter()
+            .try_fold(1usize, |count, cardinality| count.checked_mul(*cardinality))
+    }
+
+    /// Returns the value at `offset` from the minimum of the dimension.
+    pub(crate) fn value(&self, dimension: usize, offset: usize) -> DimensionValue {
+        DimensionValue::Integer((i64::from(self.minimums[dimension]) + offset as i64) as i32)
+    }
+
+    /// Returns the offsets of the values from the minimums, or `None` if a value is outside.
+    pub(crate) fn offsets(&self, values: &[DimensionValue]) -> Option<Vec<usize>> {
+        if values.len() != self.minimums.len() {
+            return None;
+        }
+        values
+            .iter()
+            .zip(self.minimums.iter().zip(self.cardinalities.iter()))
+            .map(|(value, (min, cardinality))| match value {
+                DimensionValue::Integer(value) => {
+                    usize::try_from(i64::from(*value) - i64::from(*min))
+                        .ok()
+                        .filter(|offset| offset < cardinality)
+                }
+                DimensionValue::Float(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the values of the position with the given number, or `None` if out of range.
+    pub(crate) fn decode(&self, mut index: usize) -> Option<Vec<DimensionValue>> {
+        if index >= self.count()? {
+            return None;
+        }
+        let mut values = vec![DimensionValue::Integer(0); self.cardinalities.len()];
+        for (dimension, cardinality) in self.cardinalities.iter().enumerate().rev() {
+            values[dimension] = self.value(dimension, index % cardinality);
+            index /= cardinality;
+        }
+        Some(values)
+    }
+
+    /// Returns the number of the position with the given values, or `None` if outside.
+    pub(crate) fn encode(&self, values: &[DimensionValue]) -> Option<usize> {
+        self.offsets(values)?
+            .iter()
+            .zip(self.cardinalities.iter())
+            .try_fold(0usize, |index, (offset, cardinality)| {
+                index.checked_mul(*cardinality)?.checked_add(*offset)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::DimensionBoundaries;
+
+    #[test]
+    fn positions_are_numbered_without_overflow() {
+        let ranges = DiscreteRanges::of(&Space::simple(vec![
+            DimensionBoundaries::from(-2..=0),
+            DimensionBoundaries::from(i32::MIN..=i32::MAX),
+        ]))
+        .unwrap();
+        #[cfg(target_pointer_width = "64")]
+        {
+            let last = vec![
+                DimensionValue::Integer(0),
+                DimensionValue::Integer(i32::MAX),
+            ];
+            assert_eq!(Some(3 << 32), ranges.count());
+            assert_eq!(Some((3 << 32) - 1), ranges.encode(&last));
+            assert_eq!(Some(last), ranges.decode((3 << 32) - 1));
+        }
+        assert_eq!(
+            None,
+            ranges.encode(&[DimensionValue::Integer(1), DimensionValue::Integer(0)])
+        );
+        assert_eq!(
+            Err(DiscreteError::NotDiscrete { index: 1 }),
+            DiscreteRanges::of(&Space::simple(vec![
+                DimensionBoundaries::from(0..=1),
+                DimensionBoundaries::from(0f32..=1f32),
+            ]))
+        );
+    }
+}
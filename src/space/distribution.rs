@@ -0,0 +1,318 @@
+//! Probability distributions over the positions of discrete spaces.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use rand::Rng;
+
+use super::discrete::{DiscreteError, DiscreteRanges};
+use super::{Position, Space};
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::float::Float;
+
+/* --- --- --- DISTRIBUTION ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DistributionError {
+    NotDiscrete {
+        index: usize,
+    },
+    /// The positions of the space cannot be counted with `usize`.
+    TooManyPositions,
+    WeightCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidWeights,
+}
+
+impl From<DiscreteError> for DistributionError {
+    fn from(error: DiscreteError) -> Self {
+        match error {
+            DiscreteError::NotDiscrete { index } => Self::NotDiscrete { index },
+            DiscreteError::TooManyPositions => Self::TooManyPositions,
+        }
+    }
+}
+
+impl core::fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotDiscrete { index } => {
+                write!(f, "Boundaries at index {} are not discrete", index)
+            }
+            Self::TooManyPositions => write!(f, "Space has too many positions to be counted"),
+            Self::WeightCountMismatch { expected, actual } => {
+                write!(f, "Expected {} weights but got {}", expected, actual)
+            }
+            Self::InvalidWeights => write!(
+                f,
+                "Weights have to be finite, non-negative and must not all be zero"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DistributionError {}
+
+/* --- --- --- DISCRETE DISTRIBUTION --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+enum Probabilities {
+    /// One probability per position, the last dimension varying fastest.
+    Joint(Vec<f64>),
+    /// One probability per value of each dimension, independent of the other dimensions.
+    PerDimension(Vec<Vec<f64>>),
+}
+
+/// A probability distribution over all positions of a space with only integer boundaries.
+///
+/// ```
+/// use gymnarium_base::rand::{rngs::StdRng, SeedableRng};
+/// use gymnarium_base::space::{DiscreteDistribution, DimensionValue, Position, Space};
+///
+/// let space = Space::multi_discrete(&[3]).unwrap();
+/// let policy = DiscreteDistribution::from_logits(space, &[0.0, 0.0, 0.0f64.ln()]).unwrap();
+///
+/// let action = policy.sample_with(&mut StdRng::seed_from_u64(0));
+/// assert_ne!(DimensionValue::from(2), action.value(&[0]));
+/// assert!((policy.log_prob(&Position::from_indices(&[0])) - 0.5f64.ln()).abs() < 1e-12);
+/// assert!((policy.entropy() - 2f64.ln()).abs() < 1e-12);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct DiscreteDistribution {
+    space: Space,
+    ranges: DiscreteRanges,
+    probabilities: Probabilities,
+}
+
+impl DiscreteDistribution {
+    /// Creates a distribution with one weight per position of the space.
+    ///
+    /// The positions are ordered like numbers with the last dimension as least significant digit.
+    pub fn from_weights(space: Space, weights: &[f64]) -> Result<Self, DistributionError> {
+        let ranges = DiscreteRanges::of(&space)?;
+        let expected = ranges.count().ok_or(DistributionError::TooManyPositions)?;
+        if weights.len() != expected {
+            return Err(DistributionError::WeightCountMismatch {
+                expected,
+                actual: weights.len(),
+            });
+        }
+        Ok(Self {
+            space,
+            ranges,
+            probabilities: Probabilities::Joint(normalize(weights)?),
+        })
+    }
+
+    /// Creates a distribution with independent weights for the values of each dimension.
+    pub fn from_dimension_weights(
+        space: Space,
+        weights: &[Vec<f64>],
+    ) -> Result<Self, DistributionError> {
+        let ranges = DiscreteRanges::of(&space)?;
+        let cardinalities = ranges.cardinalities();
+        if weights.len() != cardinalities.len() {
+            return Err(DistributionError::WeightCountMismatch {
+                expected: cardinalities.len(),
+                actual: weights.len(),
+            });
+        }
+        let probabilities = weights
+            .iter()
+            .zip(cardinalities.iter())
+            .map(|(weights, cardinality)| {
+                if weights.len() == *cardinality {
+                    normalize(weights)
+                } else {
+                    Err(DistributionError::WeightCountMismatch {
+                        expected: *cardinality,
+                        actual: weights.len(),
+                    })
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            space,
+            ranges,
+            probabilities: Probabilities::PerDimension(probabilities),
+        })
+    }
+
+    /// Like `from_weights`, but applies softmax to the given logits first.
+    pub fn from_logits(space: Space, logits: &[f64]) -> Result<Self, DistributionError> {
+        Self::from_weights(space, &softmax(logits))
+    }
+
+    /// Like `from_dimension_weights`, but applies softmax to the logits of each dimension first.
+    pub fn from_dimension_logits(
+        space: Space,
+        logits: &[Vec<f64>],
+    ) -> Result<Self, DistributionError> {
+        let weights: Vec<Vec<f64>> = logits.iter().map(|logits| softmax(logits)).collect();
+        Self::from_dimension_weights(space, &weights)
+    }
+
+    pub fn space(&self) -> &Space {
+        &self.space
+    }
+
+    #[cfg(feature = "std")]
+    pub fn sample(&self) -> Position {
+        self.sample_with(&mut rand::thread_rng())
+    }
+
+    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Position {
+        let values = match &self.probabilities {
+            Probabilities::Joint(probabilities) => self
+                .ranges
+                .decode(sample_index(probabilities, rng))
+                .expect("There is one probability per position"),
+            Probabilities::PerDimension(probabilities) => probabilities
+                .iter()
+                .enumerate()
+                .map(|(dimension, probabilities)| {
+                    self.ranges
+                        .value(dimension, sample_index(probabilities, rng))
+                })
+                .collect(),
+        };
+        Position::new(values, self.space.dimensions().clone())
+            .expect("Sampled values always match the space dimensions")
+    }
+
+    /// Returns the natural logarithm of the probability of the position.
+    ///
+    /// Positions outside of the space have a log-probability of negative infinity.
+    pub fn log_prob(&self, position: &Position) -> f64 {
+        if self.space.contains(position).is_err() {
+            return f64::NEG_INFINITY;
+        }
+        let offsets = match self.ranges.offsets(&position.values()) {
+            Some(offsets) => offsets,
+            None => return f64::NEG_INFINITY,
+        };
+        match &self.probabilities {
+            Probabilities::Joint(probabilities) => match self.ranges.encode(&position.values()) {
+                Some(index) => probabilities[index].ln(),
+                None => f64::NEG_INFINITY,
+            },
+            Probabilities::PerDimension(probabilities) => offsets
+                .into_iter()
+                .zip(probabilities.iter())
+                .map(|(offset, probabilities)| probabilities[offset].ln())
+                .sum(),
+        }
+    }
+
+    /// Returns the entropy in nats.
+    pub fn entropy(&self) -> f64 {
+        match &self.probabilities {
+            Probabilities::Joint(probabilities) => entropy(probabilities),
+            Probabilities::PerDimension(probabilities) => {
+                probabilities.iter().map(|p| entropy(p)).sum()
+            }
+        }
+    }
+}
+
+fn normalize(weights: &[f64]) -> Result<Vec<f64>, DistributionError> {
+    let sum: f64 = weights.iter().sum();
+    if weights
+        .iter()
+        .any(|weight| !weight.is_finite() || *weight < 0f64)
+        || sum <= 0f64
+    {
+        Err(DistributionError::InvalidWeights)
+    } else {
+        Ok(weights.iter().map(|weight| weight / sum).collect())
+    }
+}
+
+fn softmax(logits: &[f64]) -> Vec<f64> {
+    let max = logits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    logits.iter().map(|logit| (logit - max).exp()).collect()
+}
+
+fn sample_index<R: Rng + ?Sized>(probabilities: &[f64], rng: &mut R) -> usize {
+    let mut remaining = rng.gen::<f64>();
+    for (index, probability) in probabilities.iter().enumerate() {
+        if remaining < *probability {
+            return index;
+        }
+        remaining -= probability;
+    }
+    probabilities
+        .iter()
+        .rposition(|probability| *probability > 0f64)
+        .unwrap_or(0)
+}
+
+fn entropy(probabilities: &[f64]) -> f64 {
+    -probabilities
+        .iter()
+        .filter(|probability| **probability > 0f64)
+        .map(|probability| probability * probability.ln())
+        .sum::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::DimensionBoundaries;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn joint_and_per_dimension_distributions_agree() {
+        let space = Space::multi_discrete(&[2, 3]).unwrap();
+        let per_dimension = DiscreteDistribution::from_dimension_weights(
+            space.clone(),
+            &[vec![1.0, 3.0], vec![1.0, 1.0, 2.0]],
+        )
+        .unwrap();
+        let joint =
+            DiscreteDistribution::from_weights(space, &[1.0, 1.0, 2.0, 3.0, 3.0, 6.0]).unwrap();
+
+        let position = Position::from_indices(&[1, 2]);
+        assert!((per_dimension.log_prob(&position) - (0.75f64 * 0.5).ln()).abs() < 1e-12);
+        assert!((joint.log_prob(&position) - per_dimension.log_prob(&position)).abs() < 1e-12);
+        assert!((joint.entropy() - per_dimension.entropy()).abs() < 1e-12);
+        assert_eq!(
+            f64::NEG_INFINITY,
+            joint.log_prob(&Position::from_indices(&[2, 0]))
+        );
+
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let sample = joint.sample_with(&mut rng);
+            assert!(joint.log_prob(&sample).is_finite());
+        }
+    }
+
+    #[test]
+    fn creation_validates_space_and_weights() {
+        assert_eq!(
+            Err(DistributionError::NotDiscrete { index: 0 }),
+            DiscreteDistribution::from_weights(
+                Space::simple(vec![DimensionBoundaries::from(1f32)]),
+                &[1.0]
+            )
+        );
+        assert_eq!(
+            Err(DistributionError::WeightCountMismatch {
+                expected: 4,
+                actual: 2
+            }),
+            DiscreteDistribution::from_weights(Space::multi_binary(2), &[1.0, 1.0])
+        );
+        assert_eq!(
+            Err(DistributionError::InvalidWeights),
+            DiscreteDistribution::from_weights(Space::multi_binary(1), &[0.0, 0.0])
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! Graph structured observations like `gymnasium.spaces.Graph`.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use rand::Rng;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Position, Space, SpaceMismatch};
+
+/* --- --- --- GRAPH MISMATCH --- --- --- */
+
+/// Describes why a `GraphPosition` is not contained inside a `GraphSpace`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum GraphMismatch {
+    TooManyNodes {
+        max: usize,
+        actual: usize,
+    },
+    TooManyEdges {
+        max: usize,
+        actual: usize,
+    },
+    EdgeCountMismatch {
+        edge_links: usize,
+        edges: usize,
+    },
+    InvalidEdgeLink {
+        edge: usize,
+        link: (usize, usize),
+        nodes: usize,
+    },
+    Node {
+        node: usize,
+        mismatch: SpaceMismatch,
+    },
+    Edge {
+        edge: usize,
+        mismatch: SpaceMismatch,
+    },
+}
+
+impl core::fmt::Display for GraphMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooManyNodes { max, actual } => {
+                write!(
+                    f,
+                    "Graph has {} nodes but at most {} are allowed",
+                    actual, max
+                )
+            }
+            Self::TooManyEdges { max, actual } => {
+                write!(
+                    f,
+                    "Graph has {} edges but at most {} are allowed",
+                    actual, max
+                )
+            }
+            Self::EdgeCountMismatch { edge_links, edges } => write!(
+                f,
+                "Graph has {} edge links but {} edge features",
+                edge_links, edges
+            ),
+            Self::InvalidEdgeLink { edge, link, nodes } => write!(
+                f,
+                "Edge {} links {:?} but the graph only has {} nodes",
+                edge, link, nodes
+            ),
+            Self::Node { node, mismatch } => write!(f, "Node {}: {}", node, mismatch),
+            Self::Edge { edge, mismatch } => write!(f, "Edge {}: {}", edge, mismatch),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GraphMismatch {}
+
+/* --- --- --- GRAPH SPACE --- --- --- */
+
+/// Graphs with up to `max_nodes` nodes and `max_edges` directed edges.
+///
+/// Every node has features inside `node_space`, every edge has features inside `edge_space`, if
+/// there is one.
+///
+/// ```
+/// use gymnarium_base::rand::{rngs::StdRng, SeedableRng};
+/// use gymnarium_base::space::{DimensionBoundaries, GraphSpace, Space};
+///
+/// let space = GraphSpace::new(
+///     Space::simple_all(DimensionBoundaries::from(1f32), 2),
+///     Some(Space::simple(vec![DimensionBoundaries::from(10)])),
+///     5,
+///     8,
+/// );
+/// let graph = space.sample_with(&mut StdRng::seed_from_u64(0));
+///
+/// assert!(graph.nodes.len() <= 5);
+/// assert_eq!(graph.edges.len(), graph.edge_links.len());
+/// assert!(space.contains(&graph).is_ok());
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct GraphSpace {
+    pub node_space: Space,
+    pub edge_space: Option<Space>,
+    pub max_nodes: usize,
+    pub max_edges: usize,
+}
+
+impl GraphSpace {
+    pub fn new(
+        node_space: Space,
+        edge_space: Option<Space>,
+        max_nodes: usize,
+        max_edges: usize,
+    ) -> Self {
+        Self {
+            node_space,
+            edge_space,
+            max_nodes,
+            max_edges,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn sample(&self) -> GraphPosition {
+        self.sample_with(&mut rand::thread_rng())
+    }
+
+    /// Samples a graph with a uniformly chosen number of nodes and edges.
+    ///
+    /// Edges are only sampled if the graph has at least one node.
+    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> GraphPosition {
+        let node_count = rng.gen_range(0, self.max_nodes + 1);
+        let edge_count = if node_count == 0 {
+            0
+        } else {
+            rng.gen_range(0, self.max_edges + 1)
+        };
+        let nodes = (0..node_count)
+            .map(|_| self.node_space.sample_with(rng))
+            .collect();
+        let edge_links = (0..edge_count)
+            .map(|_| (rng.gen_range(0, node_count), rng.gen_range(0, node_count)))
+            .collect();
+        let edges = match &self.edge_space {
+            Some(edge_space) => (0..edge_count)
+                .map(|_| edge_space.sample_with(rng))
+                .collect(),
+            None => Vec::new(),
+        };
+        GraphPosition {
+            nodes,
+            edges,
+            edge_links,
+        }
+    }
+
+    /// Checks the node and edge counts, the edge links and all features.
+    pub fn contains(&self, graph: &GraphPosition) -> Result<(), GraphMismatch> {
+        if graph.nodes.len() > self.max_nodes {
+            return Err(GraphMismatch::TooManyNodes {
+                max: self.max_nodes,
+                actual: graph.nodes.len(),
+            });
+        }
+        if graph.edge_links.len() > self.max_edges {
+            return Err(GraphMismatch::TooManyEdges {
+                max: self.max_edges,
+                actual: graph.edge_links.len(),
+            });
+        }
+        let expected_edges = if self.edge_space.is_some() {
+            graph.edge_links.len()
+        } else {
+            0
+        };
+        if graph.edges.len() != expected_edges {
+            return Err(GraphMismatch::EdgeCountMismatch {
+                edge_links: graph.edge_links.len(),
+                edges: graph.edges.len(),
+            });
+        }
+        if let Some((edge, link)) = graph
+            .edge_links
+            .iter()
+            .enumerate()
+            .find(|(_, (from, to))| *from >= graph.nodes.len() || *to >= graph.nodes.len())
+        {
+            return Err(GraphMismatch::InvalidEdgeLink {
+                edge,
+                link: *link,
+                nodes: graph.nodes.len(),
+            });
+        }
+        for (node, position) in graph.nodes.iter().enumerate() {
+            self.node_space
+                .contains(position)
+                .map_err(|mismatch| GraphMismatch::Node { node, mismatch })?;
+        }
+        if let Some(edge_space) = &self.edge_space {
+            for (edge, position) in graph.edges.iter().enumerate() {
+                edge_space
+                    .contains(position)
+                    .map_err(|mismatch| GraphMismatch::Edge { edge, mismatch })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* --- --- --- GRAPH POSITION --- --- --- */
+
+/// A graph inside a `GraphSpace`.
+///
+/// `edge_links[i]` is the `(from, to)` node index pair of edge `i`, whose features are
+/// `edges[i]`.
+#[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct GraphPosition {
+    pub nodes: Vec<Position>,
+    pub edges: Vec<Position>,
+    pub edge_links: Vec<(usize, usize)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::{DimensionBoundaries, DimensionValue};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[test]
+    fn contains_checks_links_and_features() {
+        let space = GraphSpace::new(
+            Space::simple(vec![DimensionBoundaries::from(1)]),
+            None,
+            2,
+            1,
+        );
+        let node = Position::simple(vec![DimensionValue::from(1)]);
+        let mut graph = GraphPosition {
+            nodes: vec![node.clone(), node],
+            edges: Vec::new(),
+            edge_links: vec![(0, 2)],
+        };
+        assert_eq!(
+            Err(GraphMismatch::InvalidEdgeLink {
+                edge: 0,
+                link: (0, 2),
+                nodes: 2
+            }),
+            space.contains(&graph)
+        );
+
+        graph.edge_links = vec![(1, 0)];
+        assert_eq!(Ok(()), space.contains(&graph));
+
+        graph.nodes[1] = Position::simple(vec![DimensionValue::from(2)]);
+        assert!(matches!(
+            space.contains(&graph),
+            Err(GraphMismatch::Node { node: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,492 @@
+//! Translation between spaces and the JSON shapes of gym's `spaces`.
+//!
+//! Spaces are written as `Discrete`, `MultiDiscrete` or `Box` and read from those as well as from
+//! `Dict` and `Tuple`:
+//!
+//! ```text
+//! {"type": "Discrete", "n": 3, "start": 0, "dtype": "int64"}
+//! {"type": "MultiDiscrete", "nvec": [[2, 3], [4, 5]], "start": [[0, 0], [0, 0]], "dtype": "int64"}
+//! {"type": "Box", "low": [-1.0, 0.0], "high": [1.0, 5.0], "shape": [2], "dtype": "float32"}
+//! {"type": "Dict", "spaces": {"position": {...}, "velocity": {...}}}
+//! {"type": "Tuple", "spaces": [{...}, {...}]}
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use serde_json::{Map, Value};
+
+use super::{DimensionBoundaries, Space, SpaceError};
+
+/* --- --- --- GYM JSON ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum GymJsonError {
+    MissingField { space_type: String, field: String },
+    InvalidValue { field: String, message: String },
+    UnsupportedType(String),
+    Space(SpaceError),
+}
+
+impl core::fmt::Display for GymJsonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingField { space_type, field } => {
+                write!(f, "Space {} is missing the field \"{}\"", space_type, field)
+            }
+            Self::InvalidValue { field, message } => {
+                write!(f, "Field \"{}\" is invalid ({})", field, message)
+            }
+            Self::UnsupportedType(space_type) => {
+                write!(f, "Space type \"{}\" is not supported", space_type)
+            }
+            Self::Space(error) => write!(f, "Space Error \"{}\" occurred", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GymJsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Space(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<SpaceError> for GymJsonError {
+    fn from(error: SpaceError) -> Self {
+        Self::Space(error)
+    }
+}
+
+fn invalid(field: &str, message: &str) -> GymJsonError {
+    GymJsonError::InvalidValue {
+        field: field.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/* --- --- --- NESTED ARRAYS --- --- --- */
+
+/// Nests row-major values into arrays of the given dimensions, like numpy's `tolist`.
+fn nest(values: &[Value], dimensions: &[usize]) -> Value {
+    match dimensions.split_first() {
+        Some((_, [])) | None => Value::Array(values.to_vec()),
+        Some((_, inner)) => {
+            let length = inner.iter().product::<usize>().max(1);
+            Value::Array(
+                values
+                    .chunks(length)
+                    .map(|chunk| nest(chunk, inner))
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// Flattens nested arrays into row-major values and their dimensions.
+fn flatten<'a>(
+    value: &'a Value,
+    field: &str,
+) -> Result<(Vec<&'a Value>, Vec<usize>), GymJsonError> {
+    match value {
+        Value::Array(items) => {
+            let mut values = Vec::new();
+            let mut inner_dimensions = None;
+            for item in items {
+                let (item_values, item_dimensions) = flatten(item, field)?;
+                match &inner_dimensions {
+                    Some(dimensions) if dimensions != &item_dimensions => {
+                        return Err(invalid(field, "Nested arrays have different lengths"))
+                    }
+                    Some(_) => {}
+                    None => inner_dimensions = Some(item_dimensions),
+                }
+                values.extend(item_values);
+            }
+            let mut dimensions = vec![items.len()];
+            dimensions.extend(inner_dimensions.unwrap_or_default());
+            Ok((values, dimensions))
+        }
+        value => Ok((vec![value], Vec::new())),
+    }
+}
+
+/// Reads a number, also accepting `"inf"` and `"-inf"` as numpy's infinite bounds.
+fn number(value: &Value, field: &str) -> Result<f64, GymJsonError> {
+    match value {
+        Value::Number(number) => number
+            .as_f64()
+            .ok_or_else(|| invalid(field, "Number is out of range")),
+        Value::String(text) => text
+            .parse::<f64>()
+            .map_err(|_| invalid(field, "Text is no number")),
+        _ => Err(invalid(field, "Value is no number")),
+    }
+}
+
+fn integer(value: &Value, field: &str) -> Result<i32, GymJsonError> {
+    let number = number(value, field)?;
+    if number as i32 as f64 == number {
+        Ok(number as i32)
+    } else {
+        Err(invalid(field, "Value is no 32 bit integer"))
+    }
+}
+
+/// Repeats a scalar bound for every element or checks that the bounds have the shape of the space.
+fn broadcast<'a>(
+    values: Vec<&'a Value>,
+    value_dimensions: Vec<usize>,
+    dimensions: &[usize],
+    field: &str,
+) -> Result<Vec<&'a Value>, GymJsonError> {
+    if value_dimensions.is_empty() {
+        Ok(vec![values[0]; dimensions.iter().product::<usize>()])
+    } else if value_dimensions == dimensions {
+        Ok(values)
+    } else {
+        Err(invalid(field, "Shape differs from the shape of the space"))
+    }
+}
+
+/* --- --- --- SPACE --- --- --- */
+
+impl Space {
+    /// Translates this space into the JSON shape of gym's `spaces`.
+    ///
+    /// Spaces of only integers become `Discrete` with a single dimension and `MultiDiscrete`
+    /// otherwise, spaces of only floats become a `float32` `Box`. Spaces mixing both become a
+    /// `float32` `Box` as well, which reads back as floats only. Categorical boundaries count as
+    /// integers and lose their labels.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionBoundaries, Space};
+    ///
+    /// let space = Space::simple(vec![DimensionBoundaries::from(2..=4)]);
+    /// assert_eq!(
+    ///     serde_json::json!({"type": "Discrete", "n": 3, "start": 2, "dtype": "int64"}),
+    ///     space.to_gym_json()
+    /// );
+    /// assert_eq!(Ok(space.clone()), Space::from_gym_json(&space.to_gym_json()));
+    /// ```
+    pub fn to_gym_json(&self) -> Value {
+        let integers = self
+            .boundaries
+            .iter()
+            .map(DimensionBoundaries::integer_range)
+            .collect::<Option<Vec<_>>>();
+        let mut json = Map::new();
+        match integers {
+            Some(integers) if self.dimensions.len() == 1 && integers.len() == 1 => {
+                let (min, max) = integers[0];
+                json.insert("type".to_string(), Value::from("Discrete"));
+                json.insert("n".to_string(), Value::from(max as i64 - min as i64 + 1));
+                json.insert("start".to_string(), Value::from(min));
+                json.insert("dtype".to_string(), Value::from("int64"));
+            }
+            Some(integers) => {
+                let (nvec, start) = integers
+                    .iter()
+                    .map(|(min, max)| {
+                        (
+                            Value::from(*max as i64 - *min as i64 + 1),
+                            Value::from(*min),
+                        )
+                    })
+                    .unzip::<_, _, Vec<_>, Vec<_>>();
+                json.insert("type".to_string(), Value::from("MultiDiscrete"));
+                json.insert("nvec".to_string(), nest(&nvec, &self.dimensions));
+                json.insert("start".to_string(), nest(&start, &self.dimensions));
+                json.insert("dtype".to_string(), Value::from("int64"));
+            }
+            None => {
+                let (low, high) = self
+                    .boundaries
+                    .iter()
+                    .map(|boundaries| match boundaries {
+                        DimensionBoundaries::Float(min, max) => {
+                            (Value::from(*min), Value::from(*max))
+                        }
+                        _ => {
+                            let (min, max) = boundaries.integer_range().expect("not float");
+                            (Value::from(min as f32), Value::from(max as f32))
+                        }
+                    })
+                    .unzip::<_, _, Vec<_>, Vec<_>>();
+                json.insert("type".to_string(), Value::from("Box"));
+                json.insert("low".to_string(), nest(&low, &self.dimensions));
+                json.insert("high".to_string(), nest(&high, &self.dimensions));
+                json.insert("shape".to_string(), Value::from(self.dimensions.clone()));
+                json.insert("dtype".to_string(), Value::from("float32"));
+            }
+        }
+        Value::Object(json)
+    }
+
+    /// Reads a space from the JSON shape of gym's `spaces`.
+    ///
+    /// `Box` spaces of an integer dtype get integer boundaries. The sub-spaces of `Dict` and
+    /// `Tuple` are flattened and concatenated into a single dimension, `Dict` in the order of
+    /// its keys like gym sorts them. Their dimensions are named after the keys or indices, e.g.
+    /// `position_0` or `position.x` for nested dictionaries.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionBoundaries, Space};
+    ///
+    /// let json = serde_json::json!({"type": "Dict", "spaces": {
+    ///     "velocity": {"type": "Box", "low": -1.0, "high": 1.0, "shape": [2], "dtype": "float32"},
+    ///     "gear": {"type": "Discrete", "n": 4},
+    /// }});
+    /// let space = Space::from_gym_json(&json).unwrap();
+    ///
+    /// assert_eq!(&vec![3], space.dimensions());
+    /// assert_eq!(&DimensionBoundaries::from(0..=3), space.get_boundary(&[0]));
+    /// assert_eq!(
+    ///     Some(&["gear".to_string(), "velocity_0".to_string(), "velocity_1".to_string()][..]),
+    ///     space.dimension_names()
+    /// );
+    /// ```
+    pub fn from_gym_json(json: &Value) -> Result<Self, GymJsonError> {
+        let space_type =
+            json.get("type")
+                .and_then(Value::as_str)
+                .ok_or_else(|| GymJsonError::MissingField {
+                    space_type: "of unknown type".to_string(),
+                    field: "type".to_string(),
+                })?;
+        let field = |name: &str| {
+            json.get(name).ok_or_else(|| GymJsonError::MissingField {
+                space_type: space_type.to_string(),
+                field: name.to_string(),
+            })
+        };
+        let checked =
+            |boundaries: Vec<DimensionBoundaries>| match boundaries.iter().position(|boundaries| {
+                match boundaries {
+                    DimensionBoundaries::Integer(min, max) => min > max,
+                    DimensionBoundaries::Float(min, max) => {
+                        min > max || min.is_nan() || max.is_nan()
+                    }
+                    DimensionBoundaries::Categorical(count) => *count == 0,
+                }
+            }) {
+                Some(index) => Err(GymJsonError::Space(SpaceError::InvalidBoundaries { index })),
+                None => Ok(boundaries),
+            };
+
+        match space_type {
+            "Discrete" => {
+                let n = integer(field("n")?, "n")?;
+                let start = match json.get("start") {
+                    Some(start) => integer(start, "start")?,
+                    None => 0,
+                };
+                let max = start
+                    .checked_add(n - 1)
+                    .ok_or_else(|| invalid("n", "Space exceeds 32 bit integers"))?;
+                Ok(Space::simple(checked(vec![DimensionBoundaries::Integer(
+                    start, max,
+                )])?))
+            }
+            "MultiDiscrete" => {
+                let (nvec, dimensions) = flatten(field("nvec")?, "nvec")?;
+                let start = match json.get("start") {
+                    Some(start) => {
+                        let (start, start_dimensions) = flatten(start, "start")?;
+                        if start_dimensions != dimensions {
+                            return Err(invalid("start", "Shape differs from nvec"));
+                        }
+                        start
+                            .into_iter()
+                            .map(|start| integer(start, "start"))
+                            .collect::<Result<Vec<_>, _>>()?
+                    }
+                    None => vec![0; nvec.len()],
+                };
+                let boundaries = nvec
+                    .into_iter()
+                    .zip(start)
+                    .map(|(n, start)| {
+                        let n = integer(n, "nvec")?;
+                        start
+                            .checked_add(n - 1)
+                            .map(|max| DimensionBoundaries::Integer(start, max))
+                            .ok_or_else(|| invalid("nvec", "Space exceeds 32 bit integers"))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Space::new(checked(boundaries)?, dimensions)?)
+            }
+            "Box" => {
+                let integral = match json.get("dtype").and_then(Value::as_str) {
+                    Some(dtype) => dtype.starts_with("int") || dtype.starts_with("uint"),
+                    None => false,
+                };
+                let (low, low_dimensions) = flatten(field("low")?, "low")?;
+                let (high, high_dimensions) = flatten(field("high")?, "high")?;
+                let dimensions = match json.get("shape") {
+                    Some(shape) => serde_json::from_value::<Vec<usize>>(shape.clone())
+                        .map_err(|e| invalid("shape", &e.to_string()))?,
+                    None if !low_dimensions.is_empty() => low_dimensions.clone(),
+                    None => high_dimensions.clone(),
+                };
+                let low = broadcast(low, low_dimensions, &dimensions, "low")?;
+                let high = broadcast(high, high_dimensions, &dimensions, "high")?;
+                let boundaries = low
+                    .into_iter()
+                    .zip(high)
+                    .map(|(low, high)| {
+                        if integral {
+                            Ok(DimensionBoundaries::Integer(
+                                integer(low, "low")?,
+                                integer(high, "high")?,
+                            ))
+                        } else {
+                            Ok(DimensionBoundaries::Float(
+                                number(low, "low")? as f32,
+                                number(high, "high")? as f32,
+                            ))
+                        }
+                    })
+                    .collect::<Result<Vec<_>, GymJsonError>>()?;
+                Ok(Space::new(checked(boundaries)?, dimensions)?)
+            }
+            "Dict" => {
+                let spaces = field("spaces")?
+                    .as_object()
+                    .ok_or_else(|| invalid("spaces", "Value is no object"))?;
+                Self::concatenated(spaces.iter().map(|(key, space)| (key.clone(), space)))
+            }
+            "Tuple" => {
+                let spaces = field("spaces")?
+                    .as_array()
+                    .ok_or_else(|| invalid("spaces", "Value is no array"))?;
+                Self::concatenated(
+                    spaces
+                        .iter()
+                        .enumerate()
+                        .map(|(index, space)| (index.to_string(), space)),
+                )
+            }
+            other => Err(GymJsonError::UnsupportedType(other.to_string())),
+        }
+    }
+
+    fn concatenated<'a, I: Iterator<Item = (String, &'a Value)>>(
+        spaces: I,
+    ) -> Result<Self, GymJsonError> {
+        let mut boundaries = Vec::new();
+        let mut names = Vec::new();
+        for (key, json) in spaces {
+            let space = Self::from_gym_json(json)?;
+            match space.dimension_names() {
+                Some(inner_names) => {
+                    names.extend(inner_names.iter().map(|name| format!("{}.{}", key, name)))
+                }
+                None if space.boundaries.len() == 1 => names.push(key),
+                None => names
+                    .extend((0..space.boundaries.len()).map(|index| format!("{}_{}", key, index))),
+            }
+            boundaries.extend(space.boundaries.iter().copied());
+        }
+        Ok(Space::simple(boundaries).with_dimension_names(names)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn spaces_round_trip_through_gym_json() {
+        let discrete = Space::new(
+            vec![
+                DimensionBoundaries::from(0..=1),
+                DimensionBoundaries::from(0..=2),
+                DimensionBoundaries::from(1..=3),
+                DimensionBoundaries::from(-1..=1),
+            ],
+            vec![2, 2],
+        )
+        .unwrap();
+        assert_eq!(json!([[2, 3], [3, 3]]), discrete.to_gym_json()["nvec"]);
+        assert_eq!(
+            Ok(discrete.clone()),
+            Space::from_gym_json(&discrete.to_gym_json())
+        );
+
+        let continuous = Space::all(DimensionBoundaries::from(-0.5f32..=2.5f32), vec![3, 1]);
+        assert_eq!(
+            json!([[-0.5], [-0.5], [-0.5]]),
+            continuous.to_gym_json()["low"]
+        );
+        assert_eq!(
+            Ok(continuous.clone()),
+            Space::from_gym_json(&continuous.to_gym_json())
+        );
+    }
+
+    #[test]
+    fn boxes_accept_scalar_and_infinite_bounds() {
+        let space = Space::from_gym_json(&json!({
+            "type": "Box", "low": "-inf", "high": [1, 2], "shape": [2], "dtype": "float64"
+        }))
+        .unwrap();
+        assert_eq!(
+            &DimensionBoundaries::Float(f32::NEG_INFINITY, 2.0),
+            space.get_boundary(&[1])
+        );
+        let space = Space::from_gym_json(&json!({
+            "type": "Box", "low": 0, "high": 255, "shape": [2, 2], "dtype": "uint8"
+        }))
+        .unwrap();
+        assert_eq!(&vec![2, 2], space.dimensions());
+        assert_eq!(
+            &DimensionBoundaries::from(0..=255),
+            space.get_boundary(&[1, 1])
+        );
+    }
+
+    #[test]
+    fn invalid_json_is_rejected() {
+        assert_eq!(
+            Err(GymJsonError::UnsupportedType("Graph".to_string())),
+            Space::from_gym_json(&json!({"type": "Graph"}))
+        );
+        assert!(matches!(
+            Space::from_gym_json(&json!({"type": "Discrete"})),
+            Err(GymJsonError::MissingField { .. })
+        ));
+        assert!(matches!(
+            Space::from_gym_json(&json!({"type": "Box", "low": [1.0], "high": [0.0]})),
+            Err(GymJsonError::Space(SpaceError::InvalidBoundaries {
+                index: 0
+            }))
+        ));
+        assert!(matches!(
+            Space::from_gym_json(&json!({"type": "MultiDiscrete", "nvec": [[2], [2, 3]]})),
+            Err(GymJsonError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn tuples_name_dimensions_by_index() {
+        let space = Space::from_gym_json(&json!({"type": "Tuple", "spaces": [
+            {"type": "Discrete", "n": 2},
+            {"type": "Dict", "spaces": {"x": {"type": "Discrete", "n": 3}}},
+        ]}))
+        .unwrap();
+        assert_eq!(
+            Some(&["0".to_string(), "1.x".to_string()][..]),
+            space.dimension_names()
+        );
+    }
+}
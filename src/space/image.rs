@@ -0,0 +1,142 @@
+//! Conversions between `[height, width, 3]` positions and RGB pixel buffers.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "image")]
+use core::convert::TryInto;
+
+use super::{DimensionBoundaries, DimensionValue, Position, SpaceError, SpaceMismatch};
+
+/// Boundaries every channel of a pixel has to lie within.
+const CHANNEL_BOUNDARIES: DimensionBoundaries = DimensionBoundaries::Integer(0, 255);
+
+impl Position {
+    /// Returns the pixels of a `[height, width, 3]` position of integers in `0..=255` as an RGB
+    /// buffer with one byte per channel, row by row.
+    ///
+    /// ```
+    /// use gymnarium_base::space::{DimensionValue, Position};
+    ///
+    /// let image = Position::from_i32_values(
+    ///     vec![255, 0, 0, 0, 255, 0, 0, 0, 255, 10, 20, 30],
+    ///     vec![2, 2, 3],
+    /// )
+    /// .unwrap();
+    /// let buffer = image.to_rgb_image_buffer().unwrap();
+    ///
+    /// assert_eq!(&[0, 0, 255], &buffer[6..9]);
+    /// assert_eq!(Ok(image), Position::from_rgb_image_buffer(2, 2, &buffer));
+    /// ```
+    pub fn to_rgb_image_buffer(&self) -> Result<Vec<u8>, SpaceMismatch> {
+        match self.dimensions.as_slice() {
+            [_, _, 3] => {}
+            dimensions => {
+                return Err(SpaceMismatch::ShapeMismatch {
+                    expected: vec![
+                        dimensions.first().copied().unwrap_or(0),
+                        dimensions.get(1).copied().unwrap_or(0),
+                        3,
+                    ],
+                    actual: dimensions.to_vec(),
+                })
+            }
+        }
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| match value {
+                DimensionValue::Integer(channel @ 0..=255) => Ok(channel as u8),
+                DimensionValue::Integer(_) => Err(SpaceMismatch::ValueOutOfBounds {
+                    index,
+                    boundaries: CHANNEL_BOUNDARIES,
+                    value,
+                }),
+                DimensionValue::Float(_) => Err(SpaceMismatch::ValueTypeMismatch {
+                    index,
+                    boundaries: CHANNEL_BOUNDARIES,
+                    value,
+                }),
+            })
+            .collect()
+    }
+
+    /// Creates a `[height, width, 3]` position of integers from an RGB buffer with one byte per
+    /// channel, row by row.
+    pub fn from_rgb_image_buffer(
+        width: usize,
+        height: usize,
+        buffer: &[u8],
+    ) -> Result<Self, SpaceError> {
+        Self::from_i32_values(
+            buffer.iter().map(|channel| *channel as i32).collect(),
+            vec![height, width, 3],
+        )
+    }
+
+    /// Converts a `[height, width, 3]` position of integers in `0..=255` into an `image::RgbImage`.
+    #[cfg(feature = "image")]
+    pub fn to_rgb_image(&self) -> Result<::image::RgbImage, SpaceMismatch> {
+        let buffer = self.to_rgb_image_buffer()?;
+        let too_large = || SpaceMismatch::ShapeMismatch {
+            expected: vec![u32::MAX as usize, u32::MAX as usize, 3],
+            actual: self.dimensions.clone(),
+        };
+        let height = self.dimensions[0].try_into().map_err(|_| too_large())?;
+        let width = self.dimensions[1].try_into().map_err(|_| too_large())?;
+        Ok(::image::RgbImage::from_raw(width, height, buffer)
+            .expect("buffer has exactly width * height * 3 bytes"))
+    }
+
+    /// Creates a `[height, width, 3]` position of integers from an `image::RgbImage`.
+    #[cfg(feature = "image")]
+    pub fn from_rgb_image(image: &::image::RgbImage) -> Self {
+        Self::from_rgb_image_buffer(image.width() as usize, image.height() as usize, image)
+            .expect("image has exactly width * height * 3 bytes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_pixel_positions_are_rejected() {
+        let flat = Position::from_i32_values(vec![0, 0, 0], vec![3]).unwrap();
+        assert_eq!(
+            Err(SpaceMismatch::ShapeMismatch {
+                expected: vec![3, 0, 3],
+                actual: vec![3],
+            }),
+            flat.to_rgb_image_buffer()
+        );
+        let bright = Position::from_i32_values(vec![0, 256, 0], vec![1, 1, 3]).unwrap();
+        assert!(matches!(
+            bright.to_rgb_image_buffer(),
+            Err(SpaceMismatch::ValueOutOfBounds { index: 1, .. })
+        ));
+        let float = Position::from_f32_values(vec![0.0; 3], vec![1, 1, 3]).unwrap();
+        assert!(matches!(
+            float.to_rgb_image_buffer(),
+            Err(SpaceMismatch::ValueTypeMismatch { index: 0, .. })
+        ));
+        assert!(Position::from_rgb_image_buffer(2, 1, &[0; 5]).is_err());
+    }
+
+    #[test]
+    fn buffers_are_stored_row_by_row() {
+        let buffer = (0..18).collect::<Vec<u8>>();
+        let image = Position::from_rgb_image_buffer(3, 2, &buffer).unwrap();
+        assert_eq!(&vec![2, 3, 3], image.dimensions());
+        assert_eq!(DimensionValue::Integer(14), image.value(&[1, 1, 2]));
+        assert_eq!(Ok(buffer), image.to_rgb_image_buffer());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn images_round_trip() {
+        let image = ::image::RgbImage::from_fn(3, 2, |x, y| ::image::Rgb([x as u8, y as u8, 7]));
+        let position = Position::from_rgb_image(&image);
+        assert_eq!(DimensionValue::Integer(2), position.value(&[0, 2, 0]));
+        assert_eq!(Ok(image), position.to_rgb_image());
+    }
+}
@@ -0,0 +1,19 @@
+//! Exchange of positions with JavaScript, e.g. for browser front-ends of environments.
+
+use js_sys::Float64Array;
+
+use super::{Position, Space, SpaceMismatch};
+
+impl Position {
+    /// Copies all values in row-major order into a new `Float64Array`.
+    pub fn to_js_array(&self) -> Float64Array {
+        Float64Array::from(&self.to_f64_values()[..])
+    }
+
+    /// Reads a position of the given space from an array filled in row-major order.
+    ///
+    /// See `from_f64_values_in` for the checks done.
+    pub fn from_js_array(array: &Float64Array, space: &Space) -> Result<Self, SpaceMismatch> {
+        Self::from_f64_values_in(&array.to_vec(), space)
+    }
+}
@@ -0,0 +1,148 @@
+//! Variable length sequences of positions like `gymnasium.spaces.Sequence`.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use rand::Rng;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Position, Space, SpaceMismatch};
+
+/// Probability to stop after each element when sampling a sequence without maximum length.
+const UNBOUNDED_STOP_PROBABILITY: f64 = 0.25;
+
+/* --- --- --- SEQUENCE MISMATCH --- --- --- */
+
+/// Describes why a sequence is not contained inside a `SequenceSpace`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SequenceMismatch {
+    TooLong {
+        max: usize,
+        actual: usize,
+    },
+    Element {
+        index: usize,
+        mismatch: SpaceMismatch,
+    },
+}
+
+impl core::fmt::Display for SequenceMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLong { max, actual } => write!(
+                f,
+                "Sequence has {} elements but at most {} are allowed",
+                actual, max
+            ),
+            Self::Element { index, mismatch } => write!(f, "Element {}: {}", index, mismatch),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SequenceMismatch {}
+
+/* --- --- --- SEQUENCE SPACE --- --- --- */
+
+/// Sequences of any length up to `max_len` with every element inside `inner`.
+///
+/// ```
+/// use gymnarium_base::rand::{rngs::StdRng, SeedableRng};
+/// use gymnarium_base::space::{DimensionBoundaries, SequenceSpace, Space};
+///
+/// let enemies = SequenceSpace::new(Space::simple_all(DimensionBoundaries::from(10f32), 2), Some(4));
+/// let sequence = enemies.sample_with(&mut StdRng::seed_from_u64(0));
+///
+/// assert!(sequence.len() <= 4);
+/// assert!(enemies.contains(&sequence).is_ok());
+/// ```
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SequenceSpace {
+    pub inner: Space,
+    pub max_len: Option<usize>,
+}
+
+impl SequenceSpace {
+    pub fn new(inner: Space, max_len: Option<usize>) -> Self {
+        Self { inner, max_len }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn sample(&self) -> Vec<Position> {
+        self.sample_with(&mut rand::thread_rng())
+    }
+
+    /// Samples a sequence with a uniformly chosen length up to `max_len`.
+    ///
+    /// Without `max_len` the length is geometrically distributed with a mean of 3.
+    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Position> {
+        let length = match self.max_len {
+            Some(max_len) => rng.gen_range(0, max_len + 1),
+            None => {
+                let mut length = 0;
+                while !rng.gen_bool(UNBOUNDED_STOP_PROBABILITY) {
+                    length += 1;
+                }
+                length
+            }
+        };
+        (0..length).map(|_| self.inner.sample_with(rng)).collect()
+    }
+
+    /// Checks the length and every element, reporting the first offending element.
+    pub fn contains(&self, sequence: &[Position]) -> Result<(), SequenceMismatch> {
+        if let Some(max_len) = self.max_len {
+            if sequence.len() > max_len {
+                return Err(SequenceMismatch::TooLong {
+                    max: max_len,
+                    actual: sequence.len(),
+                });
+            }
+        }
+        sequence
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, position)| {
+                self.inner
+                    .contains(position)
+                    .map_err(|mismatch| SequenceMismatch::Element { index, mismatch })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::{DimensionBoundaries, DimensionValue};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn contains_checks_length_and_elements() {
+        let space = SequenceSpace::new(Space::simple(vec![DimensionBoundaries::from(1)]), Some(2));
+        let element = Position::simple(vec![DimensionValue::from(1)]);
+
+        assert_eq!(Ok(()), space.contains(&[]));
+        assert_eq!(
+            Err(SequenceMismatch::TooLong { max: 2, actual: 3 }),
+            space.contains(&vec![element.clone(); 3])
+        );
+        assert!(matches!(
+            space.contains(&[element, Position::simple(vec![DimensionValue::from(5)])]),
+            Err(SequenceMismatch::Element { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn unbounded_sampling_produces_varying_lengths() {
+        let space = SequenceSpace::new(Space::simple(vec![DimensionBoundaries::from(1)]), None);
+        let mut rng = StdRng::seed_from_u64(3);
+        let lengths: Vec<usize> = (0..50).map(|_| space.sample_with(&mut rng).len()).collect();
+
+        assert!(lengths.contains(&0));
+        assert!(lengths.iter().any(|length| *length > 1));
+    }
+}
@@ -0,0 +1,202 @@
+//! Spaces and positions with a dimension count known at compile time.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+use core::convert::TryFrom;
+use core::ops::{Index, IndexMut};
+
+use rand::Rng;
+
+use super::SpaceMismatch;
+use super::{check_values, DimensionBoundaries, DimensionValue, Position, Space, SpaceError};
+
+/* --- --- --- TYPED SPACE --- --- --- */
+
+/// A one-dimensional space with exactly `N` boundaries stored inline.
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use gymnarium_base::rand::{rngs::StdRng, SeedableRng};
+/// use gymnarium_base::space::{DimensionBoundaries, Space, TypedSpace};
+///
+/// let space = TypedSpace::new([
+///     DimensionBoundaries::from(-2.4f32..=2.4f32),
+///     DimensionBoundaries::from(1),
+/// ]);
+/// let position = space.sample_with(&mut StdRng::seed_from_u64(0));
+/// assert!(space.contains(&position).is_ok());
+///
+/// let dynamic = Space::from(space);
+/// assert_eq!(Ok(space), TypedSpace::<2>::try_from(dynamic));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypedSpace<const N: usize> {
+    boundaries: [DimensionBoundaries; N],
+}
+
+impl<const N: usize> TypedSpace<N> {
+    pub fn new(boundaries: [DimensionBoundaries; N]) -> Self {
+        Self { boundaries }
+    }
+
+    pub fn all(boundaries: DimensionBoundaries) -> Self {
+        Self {
+            boundaries: [boundaries; N],
+        }
+    }
+
+    pub fn boundaries(&self) -> &[DimensionBoundaries; N] {
+        &self.boundaries
+    }
+
+    #[cfg(feature = "std")]
+    pub fn sample(&self) -> TypedPosition<N> {
+        self.sample_with(&mut rand::thread_rng())
+    }
+
+    pub fn sample_with<R: Rng + ?Sized>(&self, rng: &mut R) -> TypedPosition<N> {
+        TypedPosition {
+            values: self
+                .boundaries
+                .map(|boundaries| boundaries.sample_with(rng)),
+        }
+    }
+
+    /// Checks that every value lies inside its boundaries, reporting the first offending value.
+    pub fn contains(&self, position: &TypedPosition<N>) -> Result<(), SpaceMismatch> {
+        check_values(&self.boundaries, position.values.iter().copied())
+    }
+}
+
+impl<const N: usize> Index<usize> for TypedSpace<N> {
+    type Output = DimensionBoundaries;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.boundaries[index]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for TypedSpace<N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.boundaries[index]
+    }
+}
+
+impl<const N: usize> From<TypedSpace<N>> for Space {
+    fn from(space: TypedSpace<N>) -> Self {
+        Space::simple(space.boundaries.to_vec())
+    }
+}
+
+impl<const N: usize> TryFrom<Space> for TypedSpace<N> {
+    type Error = SpaceError;
+
+    /// Only succeeds for one-dimensional spaces with exactly `N` boundaries.
+    fn try_from(space: Space) -> Result<Self, Self::Error> {
+        if space.dimensions() != &[N] {
+            return Err(SpaceError::IncompatibleDimensions {
+                first: vec![N],
+                second: space.dimensions().clone(),
+            });
+        }
+        let mut boundaries = [DimensionBoundaries::Integer(0, 0); N];
+        boundaries.copy_from_slice(space.get_boundaries());
+        Ok(Self { boundaries })
+    }
+}
+
+/* --- --- --- TYPED POSITION --- --- --- */
+
+/// A one-dimensional position with exactly `N` values stored inline.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TypedPosition<const N: usize> {
+    values: [DimensionValue; N],
+}
+
+impl<const N: usize> TypedPosition<N> {
+    pub fn new(values: [DimensionValue; N]) -> Self {
+        Self { values }
+    }
+
+    pub fn values(&self) -> &[DimensionValue; N] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> [DimensionValue; N] {
+        self.values
+    }
+}
+
+impl<const N: usize> Index<usize> for TypedPosition<N> {
+    type Output = DimensionValue;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.values[index]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for TypedPosition<N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.values[index]
+    }
+}
+
+impl<const N: usize> From<TypedPosition<N>> for Position {
+    fn from(position: TypedPosition<N>) -> Self {
+        Position::simple(position.values.to_vec())
+    }
+}
+
+impl<const N: usize> TryFrom<Position> for TypedPosition<N> {
+    type Error = SpaceError;
+
+    /// Only succeeds for one-dimensional positions with exactly `N` values.
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        if position.dimensions() != &[N] {
+            return Err(SpaceError::IncompatibleDimensions {
+                first: vec![N],
+                second: position.dimensions().clone(),
+            });
+        }
+        let mut values = [DimensionValue::Integer(0); N];
+        values.copy_from_slice(&position.values());
+        Ok(Self { values })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_check_the_dimensions() {
+        let position = TypedPosition::new([DimensionValue::from(1), DimensionValue::from(2f32)]);
+        let dynamic = Position::from(position);
+        assert_eq!(Ok(position), TypedPosition::<2>::try_from(dynamic.clone()));
+        assert_eq!(
+            Err(SpaceError::IncompatibleDimensions {
+                first: vec![3],
+                second: vec![2]
+            }),
+            TypedPosition::<3>::try_from(dynamic)
+        );
+
+        let space = TypedSpace::<4>::all(DimensionBoundaries::from(1));
+        assert!(
+            TypedSpace::<4>::try_from(Space::from(space).reshape(vec![2, 2]).unwrap()).is_err()
+        );
+        assert_eq!(
+            Err(SpaceMismatch::ValueOutOfBounds {
+                index: 2,
+                boundaries: DimensionBoundaries::Integer(0, 1),
+                value: DimensionValue::Integer(2)
+            }),
+            space.contains(&TypedPosition::new([
+                DimensionValue::from(0),
+                DimensionValue::from(1),
+                DimensionValue::from(2),
+                DimensionValue::from(0)
+            ]))
+        );
+    }
+}
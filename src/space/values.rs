@@ -0,0 +1,354 @@
+//! Stores the values of positions contiguously by type if all of them share one.
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, vec::Vec};
+use core::iter::FromIterator;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{into_storage, slice_values, transpose_values, DimensionValue, SpaceError, Storage};
+
+/* --- --- --- VALUES --- --- --- */
+
+/// The values of a position in row-major order.
+///
+/// Values created from plain `f32` or `i32` are stored as such, so that e.g. images need neither
+/// the tag of `DimensionValue` nor a branch per value. Values created from `DimensionValue`s are
+/// stored `Mixed`, and storing a value of another type switches typed values to `Mixed` as well.
+pub(crate) enum Values {
+    F32(Storage<f32>),
+    I32(Storage<i32>),
+    Mixed(Storage<DimensionValue>),
+}
+
+impl Values {
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Self::F32(values) => values.len(),
+            Self::I32(values) => values.len(),
+            Self::Mixed(values) => values.len(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// # Panics
+    /// If the index is out of bounds.
+    pub(crate) fn get(&self, index: usize) -> DimensionValue {
+        self.as_ref().get(index)
+    }
+
+    /// Stores the value, switching to `Mixed` if its type differs from the stored one.
+    ///
+    /// # Panics
+    /// If the index is out of bounds.
+    pub(crate) fn set(&mut self, index: usize, value: DimensionValue) {
+        match (&mut *self, value) {
+            (Self::F32(values), DimensionValue::Float(value)) => values[index] = value,
+            (Self::I32(values), DimensionValue::Integer(value)) => values[index] = value,
+            (Self::Mixed(values), value) => values[index] = value,
+            (_, value) => self.mixed_mut()[index] = value,
+        }
+    }
+
+    /// Appends the value, switching to `Mixed` if its type differs from the stored one.
+    pub(crate) fn push(&mut self, value: DimensionValue) {
+        match (&mut *self, value) {
+            (Self::F32(values), DimensionValue::Float(value)) => values.push(value),
+            (Self::I32(values), DimensionValue::Integer(value)) => values.push(value),
+            (Self::Mixed(values), value) => values.push(value),
+            (_, value) => self.mixed_mut().push(value),
+        }
+    }
+
+    /// Appends all given values, copying them as a whole if they have the stored type.
+    pub(crate) fn extend_from(&mut self, values: ValuesRef<'_>) {
+        match (&mut *self, values) {
+            (Self::F32(own), ValuesRef::F32(values)) => own.extend_from_slice(values),
+            (Self::I32(own), ValuesRef::I32(values)) => own.extend_from_slice(values),
+            (Self::Mixed(own), ValuesRef::Mixed(values)) => own.extend_from_slice(values),
+            (own, values) if own.is_empty() => *own = values.to_values(),
+            (own, values) => own.extend(values.iter()),
+        }
+    }
+
+    /// Removes all values but keeps the type and the allocation of the storage.
+    pub(crate) fn clear(&mut self) {
+        match self {
+            Self::F32(values) => values.clear(),
+            Self::I32(values) => values.clear(),
+            Self::Mixed(values) => values.clear(),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl ExactSizeIterator<Item = DimensionValue> + '_ {
+        (0..self.len()).map(move |index| self.get(index))
+    }
+
+    pub(crate) fn as_ref(&self) -> ValuesRef<'_> {
+        match self {
+            Self::F32(values) => ValuesRef::F32(values),
+            Self::I32(values) => ValuesRef::I32(values),
+            Self::Mixed(values) => ValuesRef::Mixed(values),
+        }
+    }
+
+    pub(crate) fn slice(&self, range: Range<usize>) -> ValuesRef<'_> {
+        self.as_ref().slice(range)
+    }
+
+    /// Returns the floats inside the range mutably, if only floats are stored.
+    pub(crate) fn f32_slice_mut(&mut self, range: Range<usize>) -> Option<&mut [f32]> {
+        match self {
+            Self::F32(values) => Some(&mut values[range]),
+            _ if range.is_empty() => Some(&mut []),
+            _ => None,
+        }
+    }
+
+    /// Returns the integers inside the range mutably, if only integers are stored.
+    pub(crate) fn i32_slice_mut(&mut self, range: Range<usize>) -> Option<&mut [i32]> {
+        match self {
+            Self::I32(values) => Some(&mut values[range]),
+            _ if range.is_empty() => Some(&mut []),
+            _ => None,
+        }
+    }
+
+    /// Switches to `Mixed` storage and returns it.
+    pub(crate) fn mixed_mut(&mut self) -> &mut Storage<DimensionValue> {
+        match self {
+            Self::F32(values) => {
+                *self = Self::Mixed(values.iter().copied().map(DimensionValue::Float).collect())
+            }
+            Self::I32(values) => {
+                *self = Self::Mixed(
+                    values
+                        .iter()
+                        .copied()
+                        .map(DimensionValue::Integer)
+                        .collect(),
+                )
+            }
+            Self::Mixed(_) => {}
+        }
+        match self {
+            Self::Mixed(values) => values,
+            _ => unreachable!("Values have just been switched to mixed storage"),
+        }
+    }
+
+    /// Copies the part `range` of dimension `axis` out of the n-dimensional values.
+    pub(crate) fn slice_along(
+        &self,
+        dimensions: &[usize],
+        axis: usize,
+        range: Range<usize>,
+    ) -> Result<(Self, Vec<usize>), SpaceError> {
+        Ok(match self {
+            Self::F32(values) => {
+                let (values, dimensions) = slice_values(values, dimensions, axis, range)?;
+                (Self::F32(into_storage(values)), dimensions)
+            }
+            Self::I32(values) => {
+                let (values, dimensions) = slice_values(values, dimensions, axis, range)?;
+                (Self::I32(into_storage(values)), dimensions)
+            }
+            Self::Mixed(values) => {
+                let (values, dimensions) = slice_values(values, dimensions, axis, range)?;
+                (Self::Mixed(into_storage(values)), dimensions)
+            }
+        })
+    }
+
+    /// Rearranges the n-dimensional values, so that dimension `i` is the former `axes[i]`.
+    pub(crate) fn transpose(
+        &self,
+        dimensions: &[usize],
+        axes: &[usize],
+    ) -> Result<(Self, Vec<usize>), SpaceError> {
+        Ok(match self {
+            Self::F32(values) => {
+                let (values, dimensions) = transpose_values(values, dimensions, axes)?;
+                (Self::F32(into_storage(values)), dimensions)
+            }
+            Self::I32(values) => {
+                let (values, dimensions) = transpose_values(values, dimensions, axes)?;
+                (Self::I32(into_storage(values)), dimensions)
+            }
+            Self::Mixed(values) => {
+                let (values, dimensions) = transpose_values(values, dimensions, axes)?;
+                (Self::Mixed(into_storage(values)), dimensions)
+            }
+        })
+    }
+}
+
+impl Default for Values {
+    fn default() -> Self {
+        Self::Mixed(Storage::new())
+    }
+}
+
+impl Clone for Values {
+    fn clone(&self) -> Self {
+        match self {
+            Self::F32(values) => Self::F32(values.clone()),
+            Self::I32(values) => Self::I32(values.clone()),
+            Self::Mixed(values) => Self::Mixed(values.clone()),
+        }
+    }
+
+    /// Reuses the allocation if both store the same type.
+    fn clone_from(&mut self, source: &Self) {
+        match (self, source) {
+            (Self::F32(own), Self::F32(source)) => own.clone_from(source),
+            (Self::I32(own), Self::I32(source)) => own.clone_from(source),
+            (Self::Mixed(own), Self::Mixed(source)) => own.clone_from(source),
+            (own, source) => *own = source.clone(),
+        }
+    }
+}
+
+impl From<Vec<DimensionValue>> for Values {
+    fn from(values: Vec<DimensionValue>) -> Self {
+        Self::Mixed(into_storage(values))
+    }
+}
+
+impl FromIterator<DimensionValue> for Values {
+    fn from_iter<I: IntoIterator<Item = DimensionValue>>(values: I) -> Self {
+        let mut collected = Self::default();
+        collected.extend(values);
+        collected
+    }
+}
+
+impl Extend<DimensionValue> for Values {
+    fn extend<I: IntoIterator<Item = DimensionValue>>(&mut self, values: I) {
+        values.into_iter().for_each(|value| self.push(value));
+    }
+}
+
+impl PartialEq for Values {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl core::fmt::Debug for Values {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+/// Serializes like a sequence of `DimensionValue`, whatever the storage.
+impl Serialize for Values {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Values {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<DimensionValue>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/* --- --- --- VALUES REF --- --- --- */
+
+/// Borrowed part of `Values`.
+#[derive(Clone, Copy)]
+pub(crate) enum ValuesRef<'a> {
+    F32(&'a [f32]),
+    I32(&'a [i32]),
+    Mixed(&'a [DimensionValue]),
+}
+
+impl<'a> ValuesRef<'a> {
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Self::F32(values) => values.len(),
+            Self::I32(values) => values.len(),
+            Self::Mixed(values) => values.len(),
+        }
+    }
+
+    /// # Panics
+    /// If the index is out of bounds.
+    pub(crate) fn get(&self, index: usize) -> DimensionValue {
+        match self {
+            Self::F32(values) => DimensionValue::Float(values[index]),
+            Self::I32(values) => DimensionValue::Integer(values[index]),
+            Self::Mixed(values) => values[index],
+        }
+    }
+
+    pub(crate) fn iter(self) -> impl ExactSizeIterator<Item = DimensionValue> + 'a {
+        (0..self.len()).map(move |index| self.get(index))
+    }
+
+    pub(crate) fn slice(self, range: Range<usize>) -> Self {
+        match self {
+            Self::F32(values) => Self::F32(&values[range]),
+            Self::I32(values) => Self::I32(&values[range]),
+            Self::Mixed(values) => Self::Mixed(&values[range]),
+        }
+    }
+
+    /// Returns every value as `DimensionValue`, only copying typed storage.
+    pub(crate) fn to_dimension_values(self) -> Cow<'a, [DimensionValue]> {
+        match self {
+            Self::Mixed(values) => Cow::Borrowed(values),
+            typed => Cow::Owned(typed.iter().collect()),
+        }
+    }
+
+    /// Returns the floats, if every value is one.
+    pub(crate) fn as_f32_slice(self) -> Option<&'a [f32]> {
+        match self {
+            Self::F32(values) => Some(values),
+            _ if self.len() == 0 => Some(&[]),
+            _ => None,
+        }
+    }
+
+    /// Returns the integers, if every value is one.
+    pub(crate) fn as_i32_slice(self) -> Option<&'a [i32]> {
+        match self {
+            Self::I32(values) => Some(values),
+            _ if self.len() == 0 => Some(&[]),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_values(self) -> Values {
+        match self {
+            Self::F32(values) => Values::F32(into_storage(values.to_vec())),
+            Self::I32(values) => Values::I32(into_storage(values.to_vec())),
+            Self::Mixed(values) => Values::Mixed(into_storage(values.to_vec())),
+        }
+    }
+}
+
+impl PartialEq for ValuesRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::F32(own), Self::F32(other)) => own == other,
+            (Self::I32(own), Self::I32(other)) => own == other,
+            (Self::Mixed(own), Self::Mixed(other)) => own == other,
+            _ => self.len() == other.len() && self.iter().eq(other.iter()),
+        }
+    }
+}
+
+impl core::fmt::Debug for ValuesRef<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
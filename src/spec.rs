@@ -0,0 +1,240 @@
+//! Metadata describing an environment for tooling, returned by `Environment::spec` and stored in
+//! a `registry::Registry`.
+
+use serde::{Deserialize, Serialize};
+
+/// Separates the name from the version in versioned identifiers like `"CartPole-v2"`.
+pub const VERSION_SEPARATOR: &str = "-v";
+
+/// Splits a versioned identifier like `"CartPole-v2"` into its name and version.
+///
+/// Identifiers without a version suffix return `None`.
+///
+/// ```
+/// use gymnarium_base::spec::split_versioned_id;
+///
+/// assert_eq!(Some(("CartPole", 2)), split_versioned_id("CartPole-v2"));
+/// assert_eq!(Some(("Mountain-Car", 0)), split_versioned_id("Mountain-Car-v0"));
+/// assert_eq!(None, split_versioned_id("CartPole"));
+/// assert_eq!(None, split_versioned_id("CartPole-vX"));
+/// ```
+pub fn split_versioned_id(versioned_id: &str) -> Option<(&str, u32)> {
+    let (name, version) = versioned_id.rsplit_once(VERSION_SEPARATOR)?;
+    if name.is_empty() || !version.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    Some((name, version.parse().ok()?))
+}
+
+/* --- --- --- ENVIRONMENT SPEC --- --- --- */
+
+/// Describes an environment beyond its spaces, e.g. for listings, benchmarks and reproducibility
+/// checks.
+///
+/// ```
+/// use gymnarium_base::spec::EnvironmentSpec;
+///
+/// let spec = EnvironmentSpec::new("CartPole")
+///     .with_version(1)
+///     .with_reward_threshold(475.0)
+///     .with_max_episode_steps(500)
+///     .with_author("Barto, Sutton and Anderson")
+///     .with_tag("classic-control");
+///
+/// assert_eq!(Some(500), spec.max_episode_steps);
+/// assert!(!spec.nondeterministic);
+/// ```
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct EnvironmentSpec {
+    pub id: String,
+    pub version: u32,
+    /// Whether equal seeds may still lead to different episodes.
+    pub nondeterministic: bool,
+    /// Average return at which the environment counts as solved.
+    pub reward_threshold: Option<f64>,
+    pub max_episode_steps: Option<u128>,
+    pub authors: Vec<String>,
+    pub tags: Vec<String>,
+    /// Explains why this version should not be used anymore, e.g. naming its successor.
+    pub deprecation: Option<String>,
+}
+
+impl EnvironmentSpec {
+    /// Creates a deterministic spec of version `0` without further metadata.
+    pub fn new<S: Into<String>>(id: S) -> Self {
+        Self {
+            id: id.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn nondeterministic(mut self) -> Self {
+        self.nondeterministic = true;
+        self
+    }
+
+    pub fn with_reward_threshold(mut self, reward_threshold: f64) -> Self {
+        self.reward_threshold = Some(reward_threshold);
+        self
+    }
+
+    pub fn with_max_episode_steps(mut self, max_episode_steps: u128) -> Self {
+        self.max_episode_steps = Some(max_episode_steps);
+        self
+    }
+
+    pub fn with_author<S: Into<String>>(mut self, author: S) -> Self {
+        self.authors.push(author.into());
+        self
+    }
+
+    pub fn with_tag<S: Into<String>>(mut self, tag: S) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    pub fn deprecated<S: Into<String>>(mut self, note: S) -> Self {
+        self.deprecation = Some(note.into());
+        self
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation.is_some()
+    }
+
+    /// Returns the identifier pinning this version, like `"CartPole-v2"`.
+    pub fn versioned_id(&self) -> String {
+        format!("{}{}{}", self.id, VERSION_SEPARATOR, self.version)
+    }
+
+    /// Returns whether the given average return reaches the reward threshold.
+    ///
+    /// Environments without threshold are never solved.
+    pub fn is_solved_by(&self, average_return: f64) -> bool {
+        self.reward_threshold
+            .is_some_and(|threshold| average_return >= threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curriculum::ConfigurableEnvironment;
+    use crate::registry::{RegisteredEnvironment, Registry};
+    use crate::space::{DimensionBoundaries, Format, Position, Space};
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::wrappers::*;
+    use crate::{Environment, EnvironmentState, Info, Parameters};
+
+    fn spec_of<E: std::error::Error, Env: Environment<E, f64, Info, i32>>(
+        environment: &Env,
+    ) -> EnvironmentSpec {
+        environment.spec()
+    }
+
+    impl ConfigurableEnvironment<std::fmt::Error>
+        for RegisteredEnvironment<std::fmt::Error, f64, Info, i32>
+    {
+        fn set_parameters(&mut self, _parameters: Parameters) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn the_default_spec_is_forwarded_by_wrappers() {
+        let spec =
+            Environment::<std::fmt::Error, f64, Info, i32>::spec(&CountingEnvironment::default());
+        assert!(spec.id.ends_with("CountingEnvironment"));
+        assert_eq!(Some(10), spec.max_episode_steps);
+
+        let wrapped = NoisyObservation::new(CountingEnvironment::default(), None);
+        assert_eq!(
+            spec,
+            Environment::<std::fmt::Error, f64, Info, i32>::spec(&wrapped)
+        );
+    }
+
+    #[test]
+    fn custom_specs_are_forwarded_by_every_wrapper() {
+        let spec = EnvironmentSpec::new("Counting")
+            .with_version(3)
+            .with_reward_threshold(5.0);
+        let mut registry = Registry::<std::fmt::Error, f64, Info, i32>::default();
+        registry
+            .register_with_spec(spec.clone(), || Ok(CountingEnvironment::default()))
+            .unwrap();
+        let make = || registry.make("Counting-v3").unwrap();
+        let image = |channels: usize| {
+            let space = Space::all(DimensionBoundaries::from(0..=5), vec![1, 1, channels]);
+            TransformObservation::new(make(), space, move |state: &EnvironmentState| {
+                Position::new(vec![*state.get_value(&[0]); channels], vec![1, 1, channels]).unwrap()
+            })
+        };
+
+        assert_eq!(spec, spec_of(&make()));
+        assert_eq!(spec, spec_of(&ActionRepeat::new(make(), 2)));
+        assert_eq!(spec, spec_of(&CsvLogger::new(make(), Vec::new())));
+        assert_eq!(
+            spec,
+            spec_of(
+                &DomainRandomizer::new(make(), Format::default(), Space::simple(Vec::new()), None)
+                    .unwrap()
+            )
+        );
+        assert_eq!(spec, spec_of(&RecordEpisodeStatistics::new(make())));
+        assert_eq!(spec, spec_of(&GrayscaleObservation::new(image(3)).unwrap()));
+        assert_eq!(
+            spec,
+            spec_of(&ResizeObservation::new(image(1), 2, 2, Interpolation::Nearest).unwrap())
+        );
+        assert_eq!(spec, spec_of(&NoisyObservation::new(make(), None)));
+        let observation = PartialObservation::new(&make().observation_space());
+        assert_eq!(
+            spec,
+            spec_of(&PartiallyObservableWrapper::new(make(), observation))
+        );
+        let shaper = |_: &EnvironmentState, _: &_, _: &EnvironmentState, reward: f64, _| reward;
+        assert_eq!(spec, spec_of(&ShapedReward::new(make(), shaper)));
+        assert_eq!(spec, spec_of(&StickyActions::new(make(), 0.5)));
+        let space = make().action_space();
+        assert_eq!(
+            spec,
+            spec_of(&TransformAction::new(make(), space, Position::clone))
+        );
+        assert_eq!(spec, spec_of(&ValidatedEnvironment::new(make())));
+    }
+
+    #[test]
+    fn versioned_ids_split_into_id_and_version() {
+        let spec = EnvironmentSpec::new("Corridor")
+            .with_version(3)
+            .deprecated("use Corridor-v4 with fixed rewards");
+        assert_eq!("Corridor-v3", spec.versioned_id());
+        assert_eq!(
+            Some((spec.id.as_str(), spec.version)),
+            split_versioned_id(&spec.versioned_id())
+        );
+        assert!(spec.is_deprecated());
+        assert_eq!(None, split_versioned_id("-v1"));
+        assert_eq!(None, split_versioned_id("Corridor-v"));
+    }
+
+    #[test]
+    fn specs_survive_serialization() {
+        let spec = EnvironmentSpec::new("Corridor")
+            .with_version(2)
+            .nondeterministic()
+            .with_reward_threshold(0.5)
+            .with_max_episode_steps(1_000)
+            .with_tag("grid");
+        let json = serde_json::to_string(&spec).unwrap();
+        assert_eq!(spec, serde_json::from_str(&json).unwrap());
+        assert!(spec.is_solved_by(0.5));
+        assert!(!EnvironmentSpec::new("Corridor").is_solved_by(f64::MAX));
+    }
+}
@@ -0,0 +1,386 @@
+//! Helpers for testing environments, spaces and the code working with them.
+//!
+//! `golden_trajectory` locks in the determinism of an environment: it replays the episode of a
+//! seed and compares the `Position::stable_hash` of every state against hashes stored earlier.
+//!
+//! `arbitrary_space` and `arbitrary_position` generate valid spaces and positions inside them from
+//! a random number generator, e.g. one seeded by a property testing framework, and `check_laws`
+//! checks the round trips every space and position have to fulfil:
+//!
+//! ```
+//! use gymnarium_base::testing::{check_laws, for_all_spaces, SpaceLimits};
+//!
+//! for_all_spaces(100, 7, &SpaceLimits::default(), |space, position| {
+//!     check_laws(space, position)
+//! });
+//! ```
+//!
+//! Golden trajectories are recorded once and compared in later test runs:
+//!
+//! ```
+//! use gymnarium_base::testing::{golden_trajectory, trajectory_hashes};
+//! use gymnarium_base::Seed;
+//! # use gymnarium_base::{AgentAction, Environment, EnvironmentState, ResetOptions};
+//! # use gymnarium_base::space::{DimensionBoundaries, Space};
+//! # struct Walk(i32);
+//! # impl Environment<std::fmt::Error, f64, (), ()> for Walk {
+//! #     fn action_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(-1..=1)]) }
+//! #     fn observation_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(-3..=3)]) }
+//! #     fn suggested_episode_steps_count(&self) -> Option<u128> { None }
+//! #     fn reset(&mut self, _: Option<Seed>, _: Option<ResetOptions>) -> Result<EnvironmentState, std::fmt::Error> { self.0 = 0; Ok(self.state()) }
+//! #     fn state(&self) -> EnvironmentState { gymnarium_base::position![self.0] }
+//! #     fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, f64, bool, ()), std::fmt::Error> {
+//! #         self.0 += action.value(&[0]).expect_integer();
+//! #         Ok((self.state(), 0.0, self.0.abs() == 3, ()))
+//! #     }
+//! #     fn load(&mut self, _: ()) -> Result<(), std::fmt::Error> { Ok(()) }
+//! #     fn store(&self) {}
+//! #     fn close(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+//! # }
+//! // Recorded once and stored inside the test afterwards.
+//! let golden = trajectory_hashes(&mut Walk(0), Seed::from("golden"), 20).unwrap();
+//!
+//! golden_trajectory(&mut Walk(0), Seed::from("golden"), &golden);
+//! ```
+
+use std::fmt::Debug;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::space::{DimensionBoundaries, Position, Space};
+use crate::{Environment, Reward, Seed};
+
+/* --- --- --- GOLDEN TRAJECTORY --- --- --- */
+
+/// Resets the environment with `seed` and steps it with actions drawn uniformly from the action
+/// space with a generator seeded by `seed` as well, until the episode is done or `max_steps` were
+/// taken.
+///
+/// Returns the `stable_hash` of the initial state and of the state after every step.
+pub fn trajectory_hashes<Env, E, R, I, D>(
+    environment: &mut Env,
+    seed: Seed,
+    max_steps: usize,
+) -> Result<Vec<u64>, E>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    let mut rng = StdRng::from_seed(seed.mixed());
+    let mut hashes = vec![environment.reset(Some(seed), None)?.stable_hash()];
+    let action_space = environment.action_space();
+    for _ in 0..max_steps {
+        let (state, _, done, _) = environment.step(&action_space.sample_with(&mut rng))?;
+        hashes.push(state.stable_hash());
+        if done {
+            break;
+        }
+    }
+    Ok(hashes)
+}
+
+/// Asserts that the trajectory of `seed` reproduces the `expected` hashes of
+/// `trajectory_hashes`, taking at most `expected.len() - 1` steps.
+///
+/// # Panics
+///
+/// Panics naming the first step whose state differs, if the episode ends at another step or if
+/// the environment returns an error.
+pub fn golden_trajectory<Env, E, R, I, D>(environment: &mut Env, seed: Seed, expected: &[u64])
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    let actual =
+        match trajectory_hashes(environment, seed.clone(), expected.len().saturating_sub(1)) {
+            Ok(actual) => actual,
+            Err(error) => panic!(
+                "Environment Error \"{}\" occurred in the trajectory of seed {:?}",
+                error, seed
+            ),
+        };
+    if let Some(step) = actual
+        .iter()
+        .zip(expected)
+        .position(|(actual, expected)| actual != expected)
+    {
+        panic!(
+            "State {} of the trajectory of seed {:?} has hash {:#018x} instead of {:#018x}",
+            step, seed, actual[step], expected[step]
+        );
+    }
+    assert_eq!(
+        expected.len(),
+        actual.len(),
+        "The trajectory of seed {:?} ended after {} steps instead of {}",
+        seed,
+        actual.len() - 1,
+        expected.len().saturating_sub(1)
+    );
+}
+
+/* --- --- --- ARBITRARY SPACES --- --- --- */
+
+/// Limits the size and the boundaries of generated spaces.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SpaceLimits {
+    /// Most dimensions a space has, at least `1`.
+    pub max_rank: usize,
+    /// Largest length of a dimension, at least `1`.
+    pub max_length: usize,
+    /// Boundaries lie within `-max_magnitude..=max_magnitude`.
+    pub max_magnitude: i32,
+}
+
+impl Default for SpaceLimits {
+    fn default() -> Self {
+        Self {
+            max_rank: 3,
+            max_length: 4,
+            max_magnitude: 1_000,
+        }
+    }
+}
+
+/// Generates a space with integer and float boundaries mixed within the given limits.
+///
+/// Every dimension is at least one element long and every boundary is valid.
+pub fn arbitrary_space<R: Rng + ?Sized>(rng: &mut R, limits: &SpaceLimits) -> Space {
+    let magnitude = limits.max_magnitude.max(0);
+    let dimensions = (0..rng.gen_range(1, limits.max_rank.max(1) + 1))
+        .map(|_| rng.gen_range(1, limits.max_length.max(1) + 1))
+        .collect::<Vec<_>>();
+    let boundaries = (0..dimensions.iter().product::<usize>())
+        .map(|_| {
+            let (a, b) = (
+                rng.gen_range(-magnitude, magnitude + 1),
+                rng.gen_range(-magnitude, magnitude + 1),
+            );
+            if rng.gen() {
+                DimensionBoundaries::Integer(a.min(b), a.max(b))
+            } else {
+                let fraction = rng.gen::<f32>();
+                DimensionBoundaries::Float(a.min(b) as f32, a.max(b) as f32 + fraction)
+            }
+        })
+        .collect();
+    Space::new(boundaries, dimensions).expect("boundaries fill the dimensions")
+}
+
+/// Generates a position inside the space.
+pub fn arbitrary_position<R: Rng + ?Sized>(space: &Space, rng: &mut R) -> Position {
+    space.sample_with(rng)
+}
+
+/// Checks `check` with `cases` generated spaces and a position inside each of them.
+///
+/// # Panics
+///
+/// Panics with the first violation, naming the case and the seed to reproduce it.
+pub fn for_all_spaces<F>(cases: usize, seed: u64, limits: &SpaceLimits, mut check: F)
+where
+    F: FnMut(&Space, &Position) -> Result<(), LawViolation>,
+{
+    let mut rng = StdRng::seed_from_u64(seed);
+    for case in 0..cases {
+        let space = arbitrary_space(&mut rng, limits);
+        let position = arbitrary_position(&space, &mut rng);
+        if let Err(violation) = check(&space, &position) {
+            panic!(
+                "Case {} of seed {} failed: {}\nSpace: {:#}\nPosition: {:#}",
+                case, seed, violation, space, position
+            );
+        }
+    }
+}
+
+/* --- --- --- LAWS --- --- --- */
+
+/// A round trip which did not return what it started with.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LawViolation {
+    pub law: &'static str,
+    pub message: String,
+}
+
+impl std::fmt::Display for LawViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Law \"{}\" is violated ({})", self.law, self.message)
+    }
+}
+
+impl std::error::Error for LawViolation {}
+
+fn violation<M: Into<String>>(law: &'static str, message: M) -> LawViolation {
+    LawViolation {
+        law,
+        message: message.into(),
+    }
+}
+
+/// Flattening the position into one dimension and reshaping it back, as well as turning it into
+/// `f64` values and reading them back inside the space, returns the position.
+pub fn check_flatten_law(space: &Space, position: &Position) -> Result<(), LawViolation> {
+    const LAW: &str = "flatten";
+    let length = position.values().len();
+    let unflattened = position
+        .reshape(vec![length])
+        .and_then(|flat| flat.reshape(position.dimensions().clone()))
+        .map_err(|error| violation(LAW, error.to_string()))?;
+    if &unflattened != position {
+        return Err(violation(
+            LAW,
+            format!("Reshaping returned {:#}", unflattened),
+        ));
+    }
+    let read = Position::from_f64_values_in(&position.to_f64_values(), space)
+        .map_err(|error| violation(LAW, error.to_string()))?;
+    if &read != position {
+        return Err(violation(
+            LAW,
+            format!("Reading f64 values returned {:#}", read),
+        ));
+    }
+    Ok(())
+}
+
+/// Serializing the space and the position as JSON and deserializing them returns both.
+pub fn check_serde_law(space: &Space, position: &Position) -> Result<(), LawViolation> {
+    const LAW: &str = "serde";
+    let read_space = serde_json::to_string(space)
+        .and_then(|json| serde_json::from_str::<Space>(&json))
+        .map_err(|error| violation(LAW, error.to_string()))?;
+    if &read_space != space {
+        return Err(violation(LAW, format!("Space became {:#}", read_space)));
+    }
+    let read_position = serde_json::to_string(position)
+        .and_then(|json| serde_json::from_str::<Position>(&json))
+        .map_err(|error| violation(LAW, error.to_string()))?;
+    if &read_position != position {
+        return Err(violation(
+            LAW,
+            format!("Position became {:#}", read_position),
+        ));
+    }
+    Ok(())
+}
+
+/// The space contains the position, and indexing both at every index returns the value stored
+/// at its row-major offset within the boundaries stored there.
+pub fn check_index_law(space: &Space, position: &Position) -> Result<(), LawViolation> {
+    const LAW: &str = "index";
+    space
+        .contains(position)
+        .map_err(|mismatch| violation(LAW, mismatch.to_string()))?;
+    let strides = position.strides();
+    let mut index = vec![0; position.dimensions().len()];
+    for offset in 0..position.values().len() {
+        for (axis, stride) in strides.iter().enumerate() {
+            index[axis] = offset / stride % position.dimensions()[axis];
+        }
+        let value = position.value(&index);
+        if value != position.values()[offset] {
+            return Err(violation(
+                LAW,
+                format!(
+                    "Index {:?} does not return the value at offset {}",
+                    index, offset
+                ),
+            ));
+        }
+        if space.get_boundary(&index) != &space.get_boundaries()[offset] {
+            return Err(violation(
+                LAW,
+                format!(
+                    "Index {:?} does not return the boundaries at offset {}",
+                    index, offset
+                ),
+            ));
+        }
+        if !space.get_boundary(&index).contains(&value) {
+            return Err(violation(
+                LAW,
+                format!("Value at {:?} is out of bounds", index),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the index, flatten and serde laws.
+pub fn check_laws(space: &Space, position: &Position) -> Result<(), LawViolation> {
+    check_index_law(space, position)?;
+    check_flatten_law(space, position)?;
+    check_serde_law(space, position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn trajectories_are_reproduced() {
+        let seed = Seed::from("counting");
+        let hashes =
+            trajectory_hashes(&mut CountingEnvironment::default(), seed.clone(), 50).unwrap();
+        assert_eq!(
+            hashes,
+            trajectory_hashes(&mut CountingEnvironment::default(), seed.clone(), 50).unwrap()
+        );
+        golden_trajectory(&mut CountingEnvironment::default(), seed, &hashes);
+    }
+
+    #[test]
+    #[should_panic(expected = "State 1 of the trajectory")]
+    fn diverging_states_are_reported() {
+        let seed = Seed::from("counting");
+        let mut hashes =
+            trajectory_hashes(&mut CountingEnvironment::default(), seed.clone(), 3).unwrap();
+        hashes[1] ^= 1;
+        golden_trajectory(&mut CountingEnvironment::default(), seed, &hashes);
+    }
+
+    #[test]
+    fn arbitrary_spaces_respect_the_limits() {
+        let limits = SpaceLimits {
+            max_rank: 2,
+            max_length: 3,
+            max_magnitude: 5,
+        };
+        for_all_spaces(200, 1, &limits, |space, position| {
+            assert!(space.dimensions().len() <= 2);
+            assert!(space
+                .dimensions()
+                .iter()
+                .all(|length| (1..=3).contains(length)));
+            assert!(space.get_boundaries().iter().all(|boundaries| {
+                boundaries.is_valid()
+                    && match boundaries {
+                        DimensionBoundaries::Integer(min, max) => *min >= -5 && *max <= 5,
+                        DimensionBoundaries::Float(min, max) => *min >= -5.0 && *max <= 6.0,
+                        DimensionBoundaries::Categorical(_) => false,
+                    }
+            }));
+            check_laws(space, position)
+        });
+    }
+
+    #[test]
+    fn violations_name_the_law() {
+        let space = Space::simple(vec![DimensionBoundaries::from(0..=1)]);
+        let outside = crate::position![2];
+        let violation = check_laws(&space, &outside).unwrap_err();
+        assert_eq!("index", violation.law);
+        assert!(check_flatten_law(&space, &crate::position![1]).is_ok());
+    }
+}
@@ -0,0 +1,313 @@
+//! Pacing steps of an environment to wall-clock time.
+//!
+//! Environments simulating a fixed time span per step implement `TimedEnvironment`. A
+//! `RealTimeRunner` then waits between the steps so the simulation runs in real time or at a
+//! multiple of it, which human players and rendering front-ends rely on.
+
+use std::fmt::Debug;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::runner::{EpisodeResult, RunnerError};
+use crate::{Agent, AgentAction, Environment, EnvironmentState, Reward, Seed};
+
+/* --- --- --- TIMED ENVIRONMENT --- --- --- */
+
+/// Environments advancing the simulated time by a fixed span with every step.
+pub trait TimedEnvironment {
+    /// Returns the simulated time passing within one step.
+    fn dt(&self) -> Duration;
+}
+
+/* --- --- --- PACING CONTROL --- --- --- */
+
+#[derive(Debug)]
+struct Pacing {
+    speed: f64,
+    paused: bool,
+}
+
+/// Shared handle to pause, resume and speed up a `RealTimeRunner`, e.g. from an input thread.
+#[derive(Debug, Clone)]
+pub struct PacingControl {
+    shared: Arc<(Mutex<Pacing>, Condvar)>,
+}
+
+impl PacingControl {
+    fn new(speed: f64) -> Self {
+        Self {
+            shared: Arc::new((
+                Mutex::new(Pacing {
+                    speed,
+                    paused: false,
+                }),
+                Condvar::new(),
+            )),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Pacing> {
+        self.shared
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Blocks every following step until `resume` is called.
+    pub fn pause(&self) {
+        self.lock().paused = true;
+    }
+
+    pub fn resume(&self) {
+        self.lock().paused = false;
+        self.shared.1.notify_all();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.lock().paused
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.lock().speed
+    }
+
+    /// Sets the multiple of real time the steps are paced to, e.g. `2.0` for double speed.
+    ///
+    /// Panics if the speed is not a positive number.
+    pub fn set_speed(&self, speed: f64) {
+        assert!(
+            speed.is_finite() && speed > 0f64,
+            "Speed must be a positive number, but was {}",
+            speed
+        );
+        self.lock().speed = speed;
+    }
+
+    /// Waits until not paused and returns the speed and whether a pause happened.
+    fn wait_while_paused(&self) -> (f64, bool) {
+        let mut pacing = self.lock();
+        let mut was_paused = false;
+        while pacing.paused {
+            was_paused = true;
+            pacing = self
+                .shared
+                .1
+                .wait(pacing)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        (pacing.speed, was_paused)
+    }
+}
+
+/* --- --- --- REAL TIME RUNNER --- --- --- */
+
+/// Paces the steps of a `TimedEnvironment` to wall-clock time.
+///
+/// A step waits until `dt / speed` passed since the previous one. If stepping takes longer than
+/// that, the following steps are not hurried to catch up.
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// # use gymnarium_base::{AgentAction, Environment, EnvironmentState, ResetOptions, Seed};
+/// # use gymnarium_base::space::{DimensionBoundaries, Space};
+/// use gymnarium_base::timing::{RealTimeRunner, TimedEnvironment};
+/// # struct Clock;
+/// # impl Environment<std::fmt::Error, f64, (), ()> for Clock {
+/// #     fn action_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(0..=0)]) }
+/// #     fn observation_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(0..=0)]) }
+/// #     fn suggested_episode_steps_count(&self) -> Option<u128> { None }
+/// #     fn reset(&mut self, _: Option<Seed>, _: Option<ResetOptions>) -> Result<EnvironmentState, std::fmt::Error> { Ok(self.state()) }
+/// #     fn state(&self) -> EnvironmentState { gymnarium_base::position![0] }
+/// #     fn step(&mut self, _: &AgentAction) -> Result<(EnvironmentState, f64, bool, ()), std::fmt::Error> { Ok((self.state(), 0f64, false, ())) }
+/// #     fn load(&mut self, _: ()) -> Result<(), std::fmt::Error> { Ok(()) }
+/// #     fn store(&self) {}
+/// #     fn close(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+/// # }
+///
+/// impl TimedEnvironment for Clock {
+///     fn dt(&self) -> Duration {
+///         Duration::from_millis(20)
+///     }
+/// }
+///
+/// let mut runner = RealTimeRunner::new().with_speed(2f64);
+/// let start = Instant::now();
+/// for _ in 0..3 {
+///     runner.step(&mut Clock, &gymnarium_base::position![0]).unwrap();
+/// }
+/// assert!(start.elapsed() >= Duration::from_millis(20));
+/// ```
+#[derive(Debug)]
+pub struct RealTimeRunner {
+    control: PacingControl,
+    next_step: Option<Instant>,
+    max_steps: Option<u128>,
+}
+
+impl RealTimeRunner {
+    pub fn new() -> Self {
+        Self {
+            control: PacingControl::new(1f64),
+            next_step: None,
+            max_steps: None,
+        }
+    }
+
+    /// Sets the multiple of real time, see `PacingControl::set_speed`.
+    pub fn with_speed(self, speed: f64) -> Self {
+        self.control.set_speed(speed);
+        self
+    }
+
+    /// Ends every episode run with `run_episode` after the given number of steps at the latest.
+    pub fn with_max_steps(mut self, max_steps: u128) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Returns a handle to pause, resume and change the speed of this runner.
+    pub fn control(&self) -> PacingControl {
+        self.control.clone()
+    }
+
+    /// Lets the next step happen immediately, e.g. after a reset.
+    pub fn restart(&mut self) {
+        self.next_step = None;
+    }
+
+    fn wait(&mut self, dt: Duration) {
+        let (speed, was_paused) = self.control.wait_while_paused();
+        if was_paused {
+            self.next_step = None;
+        }
+        let now = Instant::now();
+        let start = match self.next_step {
+            Some(deadline) if deadline > now => {
+                std::thread::sleep(deadline - now);
+                deadline
+            }
+            _ => now,
+        };
+        self.next_step = Some(start + dt.div_f64(speed));
+    }
+
+    /// Performs a step after waiting for its time to come.
+    pub fn step<Env, E, R, I, D>(
+        &mut self,
+        environment: &mut Env,
+        action: &AgentAction,
+    ) -> Result<(EnvironmentState, R, bool, I), E>
+    where
+        Env: Environment<E, R, I, D> + TimedEnvironment,
+        E: std::error::Error,
+        R: Reward,
+        I: Debug,
+        D: Serialize + DeserializeOwned,
+    {
+        self.wait(environment.dt());
+        environment.step(action)
+    }
+
+    /// Plays a single episode in real time.
+    pub fn run_episode<Env, A, EE, AE, R, I, ED, AD>(
+        &mut self,
+        environment: &mut Env,
+        agent: &mut A,
+        seed: Option<Seed>,
+    ) -> Result<EpisodeResult, RunnerError<EE, AE>>
+    where
+        Env: Environment<EE, R, I, ED> + TimedEnvironment,
+        A: Agent<AE, R, AD>,
+        EE: std::error::Error,
+        AE: std::error::Error,
+        R: Reward,
+        I: Debug,
+        ED: Serialize + DeserializeOwned,
+        AD: Serialize + DeserializeOwned,
+    {
+        let mut state = environment
+            .reset(seed, None)
+            .map_err(RunnerError::Environment)?;
+        agent.reset().map_err(RunnerError::Agent)?;
+        self.restart();
+
+        let mut result = EpisodeResult::default();
+        while !result.done
+            && self
+                .max_steps
+                .is_none_or(|max_steps| result.steps < max_steps)
+        {
+            let action = agent.choose_action(&state).map_err(RunnerError::Agent)?;
+            let (new_state, reward, done, _) = self
+                .step(environment, &action)
+                .map_err(RunnerError::Environment)?;
+            result.steps += 1;
+            result.total_reward += reward.value();
+            result.done = done;
+            agent
+                .process_reward(&state, &action, &new_state, reward, done)
+                .map_err(RunnerError::Agent)?;
+            state = new_state;
+        }
+        Ok(result)
+    }
+}
+
+impl Default for RealTimeRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::test_agent::ConstantAgent;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    impl TimedEnvironment for CountingEnvironment {
+        fn dt(&self) -> Duration {
+            Duration::from_millis(40)
+        }
+    }
+
+    #[test]
+    fn paces_episode_with_speed() {
+        let mut runner = RealTimeRunner::new().with_speed(4f64);
+        let start = Instant::now();
+        let result = runner
+            .run_episode(
+                &mut CountingEnvironment::default(),
+                &mut ConstantAgent::new(1),
+                None,
+            )
+            .unwrap();
+        assert_eq!(5, result.steps);
+        // The first step happens immediately, the other four after 10 milliseconds each.
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn paused_runner_waits_for_resume() {
+        let mut runner = RealTimeRunner::new();
+        let control = runner.control();
+        control.pause();
+        assert!(control.is_paused());
+
+        let start = Instant::now();
+        let resumer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(30));
+            control.resume();
+        });
+        let mut environment = CountingEnvironment::default();
+        runner
+            .step(&mut environment, &AgentAction::simple(vec![1.into()]))
+            .unwrap();
+        resumer.join().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert_eq!(1, environment.count);
+    }
+}
@@ -0,0 +1,196 @@
+//! Lets environments be written against own observation and action types.
+//!
+//! An environment implementing `TypedEnvironment` only converts between its own types and
+//! positions inside `encode_observation` and `decode_action`. Wrapping it into a
+//! `TypedEnvironmentAdapter` turns it into a regular `Environment`.
+
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- TYPED ENVIRONMENT --- --- --- */
+
+/// Like `Environment`, but observing `Observation`s and acting on `Action`s.
+///
+/// ```
+/// use gymnarium_base::{discrete_action, Environment, ResetOptions, Seed};
+/// use gymnarium_base::mappers::{DiscreteAction, MapperError};
+/// use gymnarium_base::space::{DimensionBoundaries, Position, Space};
+/// use gymnarium_base::typed_environment::{TypedEnvironment, TypedEnvironmentAdapter};
+///
+/// discrete_action! {
+///     enum Move { Back, Forward }
+/// }
+///
+/// #[derive(Default)]
+/// struct Corridor {
+///     position: i32,
+/// }
+///
+/// impl TypedEnvironment<MapperError, f64, (), i32> for Corridor {
+///     type Observation = i32;
+///     type Action = Move;
+///
+///     fn action_space() -> Space { Move::action_space() }
+///     fn observation_space() -> Space { Space::simple(vec![DimensionBoundaries::from(-9..=9)]) }
+///     fn suggested_episode_steps_count() -> Option<u128> { None }
+///
+///     fn encode_observation(observation: &i32) -> Position { gymnarium_base::position![*observation] }
+///     fn decode_action(action: &Position) -> Result<Move, MapperError> { Move::from_action(action) }
+///
+///     fn reset(&mut self, _seed: Option<Seed>, _options: Option<ResetOptions>) -> Result<i32, MapperError> {
+///         self.position = 0;
+///         Ok(0)
+///     }
+///     fn observation(&self) -> i32 { self.position }
+///     fn step(&mut self, action: Move) -> Result<(i32, f64, bool, ()), MapperError> {
+///         self.position += if action == Move::Forward { 1 } else { -1 };
+///         Ok((self.position, self.position as f64, self.position.abs() >= 9, ()))
+///     }
+///     fn load(&mut self, data: i32) -> Result<(), MapperError> { self.position = data; Ok(()) }
+///     fn store(&self) -> i32 { self.position }
+///     fn close(&mut self) -> Result<(), MapperError> { Ok(()) }
+/// }
+///
+/// let mut environment = TypedEnvironmentAdapter::new(Corridor::default());
+/// environment.reset(None, None).unwrap();
+/// let (state, reward, _, _) = environment.step(&Move::Forward.to_action()).unwrap();
+/// assert_eq!(gymnarium_base::position![1], state);
+/// assert_eq!(1.0, reward);
+/// ```
+pub trait TypedEnvironment<E, R, I, D>
+where
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    type Observation;
+    type Action;
+
+    /// Returns the available boundaries for the encoded actions for this environment.
+    fn action_space() -> ActionSpace;
+
+    /// Returns the boundaries for the encoded observations for this environment.
+    fn observation_space() -> ObservationSpace;
+
+    /// Returns the suggested episode step count if the environment provides one.
+    fn suggested_episode_steps_count() -> Option<u128>;
+
+    /// Converts an observation into a position inside the observation space.
+    fn encode_observation(observation: &Self::Observation) -> EnvironmentState;
+
+    /// Converts a position inside the action space into an action.
+    fn decode_action(action: &AgentAction) -> Result<Self::Action, E>;
+
+    /// Resets the environment and returns the initial observation.
+    ///
+    /// A given seed resets a possible internal random number generator.
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<Self::Observation, E>;
+
+    /// Returns the current observation of the environment.
+    fn observation(&self) -> Self::Observation;
+
+    /// Performs a step within this environment with the given action.
+    fn step(&mut self, action: Self::Action) -> Result<(Self::Observation, R, bool, I), E>;
+
+    /// Overrides the environments state with the provided data structure containing a previous state.
+    fn load(&mut self, data: D) -> Result<(), E>;
+
+    /// Returns a serializable structure containing everything to reconstruct the environment at
+    /// the given state.
+    fn store(&self) -> D;
+
+    /// Cleans up resources of this environment.
+    fn close(&mut self) -> Result<(), E>;
+}
+
+/* --- --- --- TYPED ENVIRONMENT ADAPTER --- --- --- */
+
+/// Implements `Environment` for a `TypedEnvironment` by encoding and decoding at the boundary.
+pub struct TypedEnvironmentAdapter<Env> {
+    environment: Env,
+}
+
+impl<Env> TypedEnvironmentAdapter<Env> {
+    pub fn new(environment: Env) -> Self {
+        Self { environment }
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, D> for TypedEnvironmentAdapter<Env>
+where
+    Env: TypedEnvironment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        Env::action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        Env::observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        Env::suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        std::any::type_name::<Env>().to_string()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.environment
+            .reset(seed, options)
+            .map(|observation| Env::encode_observation(&observation))
+    }
+
+    fn state(&self) -> EnvironmentState {
+        Env::encode_observation(&self.environment.observation())
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let action = Env::decode_action(action)?;
+        let (observation, reward, done, info) = self.environment.step(action)?;
+        Ok((Env::encode_observation(&observation), reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
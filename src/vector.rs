@@ -0,0 +1,104 @@
+//! Steps several environments at once to collect experience faster.
+//!
+//! `SubprocVecEnvironment` runs every environment in its own worker process, driven through the
+//! line based JSON protocol of `interop::python`. With the feature `rayon`,
+//! `ThreadedVecEnvironment` steps environments of the same process on a thread pool instead.
+//! Results are always returned in the order of the environments, and their observations can be
+//! gathered into one `PositionBatch` with `VecStep::observation_batch`.
+
+mod subproc;
+#[cfg(feature = "rayon")]
+mod threaded;
+
+#[cfg(feature = "shm")]
+pub use subproc::SharedVecStep;
+pub use subproc::{SubprocVecEnvironment, WorkerError, DEFAULT_MAX_RESTARTS, RESTARTED_INFO_KEY};
+#[cfg(feature = "rayon")]
+pub use threaded::ThreadedVecEnvironment;
+
+use crate::space::{PositionBatch, SpaceMismatch};
+use crate::EnvironmentState;
+
+/// Key under which auto-resetting environments insert the last observation of a finished episode
+/// into its info, as the values of `Position::to_f64_values`.
+pub const FINAL_OBSERVATION_INFO_KEY: &str = "final_observation";
+
+fn final_observation(observation: &EnvironmentState) -> (String, serde_json::Value) {
+    (
+        FINAL_OBSERVATION_INFO_KEY.to_string(),
+        observation.to_f64_values().into(),
+    )
+}
+
+/* --- --- --- VEC ENVIRONMENT ERROR --- --- --- */
+
+/// Error of the environment at `index`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct VecEnvironmentError<E> {
+    pub index: usize,
+    pub error: E,
+}
+
+impl<E> VecEnvironmentError<E> {
+    pub fn new(index: usize, error: E) -> Self {
+        Self { index, error }
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for VecEnvironmentError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Environment {} Error \"{}\" occurred",
+            self.index, self.error
+        )
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for VecEnvironmentError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/* --- --- --- VEC STEP --- --- --- */
+
+/// Results of stepping every environment once, in the order of the environments.
+#[derive(Debug, PartialEq, Clone)]
+pub struct VecStep<R, I> {
+    pub observations: Vec<EnvironmentState>,
+    pub rewards: Vec<R>,
+    pub dones: Vec<bool>,
+    pub infos: Vec<I>,
+}
+
+impl<R, I> VecStep<R, I> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            observations: Vec::with_capacity(capacity),
+            rewards: Vec::with_capacity(capacity),
+            dones: Vec::with_capacity(capacity),
+            infos: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, observation: EnvironmentState, reward: R, done: bool, info: I) {
+        self.observations.push(observation);
+        self.rewards.push(reward);
+        self.dones.push(done);
+        self.infos.push(info);
+    }
+
+    pub fn len(&self) -> usize {
+        self.observations.len()
+    }
+
+    /// Stores the observations back to back, as plain floats or integers if they share a type.
+    pub fn observation_batch(&self) -> Result<PositionBatch, SpaceMismatch> {
+        PositionBatch::from_positions(&self.observations)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.observations.is_empty()
+    }
+}
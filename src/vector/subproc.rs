@@ -0,0 +1,804 @@
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use super::{final_observation, VecEnvironmentError, VecStep};
+use crate::interop::python::{Request, Response, ResponseBody, SeedValue};
+#[cfg(feature = "shm")]
+use crate::interop::shm::SharedObservations;
+use crate::progress::ProgressReporter;
+use crate::space::Position;
+#[cfg(feature = "shm")]
+use crate::space::PositionBatchView;
+use crate::{ActionSpace, AgentAction, EnvironmentState, Info, ObservationSpace};
+
+/// How often a worker is restarted by default before its crash is reported as error.
+pub const DEFAULT_MAX_RESTARTS: u32 = 3;
+
+/// Key inserted as `true` into the info of the step which restarted a crashed worker.
+pub const RESTARTED_INFO_KEY: &str = "worker_restarted";
+
+/* --- --- --- WORKER ERROR --- --- --- */
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum WorkerError {
+    /// The worker could not be started or not be communicated with.
+    Io(String),
+    /// The worker exited or closed its output and may not be restarted anymore.
+    Crashed,
+    /// The environment of the worker answered with an error.
+    Environment(String),
+    /// The answer of the worker could not be understood.
+    Protocol(String),
+}
+
+impl std::fmt::Display for WorkerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Input/Output Error \"{}\" occurred", error),
+            Self::Crashed => write!(f, "Worker crashed"),
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::Protocol(error) => write!(f, "Protocol Error \"{}\" occurred", error),
+        }
+    }
+}
+
+impl std::error::Error for WorkerError {}
+
+impl From<std::io::Error> for WorkerError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::BrokenPipe | ErrorKind::UnexpectedEof => Self::Crashed,
+            _ => Self::Io(error.to_string()),
+        }
+    }
+}
+
+/* --- --- --- WORKER --- --- --- */
+
+/// A worker process serving an environment over its standard input and output.
+struct Worker {
+    child: Child,
+    input: ChildStdin,
+    output: BufReader<ChildStdout>,
+}
+
+impl Worker {
+    fn spawn(mut command: Command) -> Result<Self, WorkerError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let input = child.stdin.take().expect("standard input is piped");
+        let output = child.stdout.take().expect("standard output is piped");
+        Ok(Self {
+            child,
+            input,
+            output: BufReader::new(output),
+        })
+    }
+
+    fn send(&mut self, request: &Request) -> Result<(), WorkerError> {
+        let mut line = serde_json::to_vec(request)
+            .map_err(|error| WorkerError::Protocol(error.to_string()))?;
+        line.push(b'\n');
+        self.input.write_all(&line)?;
+        Ok(self.input.flush()?)
+    }
+
+    fn receive(&mut self) -> Result<ResponseBody, WorkerError> {
+        let mut line = String::new();
+        if self.output.read_line(&mut line)? == 0 {
+            return Err(WorkerError::Crashed);
+        }
+        match serde_json::from_str(&line) {
+            Ok(Response::Ok(body)) => Ok(body),
+            Ok(Response::Error { message }) => Err(WorkerError::Environment(message)),
+            Err(error) => Err(WorkerError::Protocol(error.to_string())),
+        }
+    }
+
+    fn call(&mut self, request: &Request) -> Result<ResponseBody, WorkerError> {
+        self.send(request)?;
+        self.receive()
+    }
+
+    /// Kills the process unless it exited already, so that it cannot write anything anymore.
+    fn kill(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+fn unexpected(body: ResponseBody) -> WorkerError {
+    WorkerError::Protocol(format!("Unexpected answer {:?}", body))
+}
+
+#[cfg(feature = "shm")]
+fn share_request(region: &SharedObservations, slot: usize) -> Request {
+    Request::Share {
+        path: region.path().to_string_lossy().into_owned(),
+        slot,
+        slots: region.slots(),
+        shape: region.position_dimensions().to_vec(),
+        dtype: region.dtype(),
+    }
+}
+
+fn to_info(info: serde_json::Value) -> Info {
+    match info {
+        serde_json::Value::Object(map) => map.into_iter().collect(),
+        serde_json::Value::Null => Info::new(),
+        info => Info::from([("info".to_string(), info)]),
+    }
+}
+
+/* --- --- --- SHARED VEC STEP --- --- --- */
+
+/// Results of stepping every worker once like `VecStep`, whose observations are borrowed from
+/// the memory shared with the workers.
+#[cfg(feature = "shm")]
+#[derive(Debug, PartialEq, Clone)]
+pub struct SharedVecStep<'a> {
+    pub observations: PositionBatchView<'a>,
+    pub rewards: Vec<f64>,
+    pub dones: Vec<bool>,
+    pub infos: Vec<Info>,
+}
+
+/* --- --- --- SUBPROC VEC ENVIRONMENT --- --- --- */
+
+/// Runs every environment in its own worker process, so that environments which are not `Send`
+/// or need much computation run truly in parallel.
+///
+/// Every worker is a command serving a single environment with `interop::python::serve_stdio`.
+/// Requests are sent to all workers before any answer is awaited. A worker which exits or closes
+/// its output is restarted and reset without seed, up to `DEFAULT_MAX_RESTARTS` times unless
+/// configured otherwise. The step which restarted it reports the reset observation, a reward of
+/// `0`, `done` and `RESTARTED_INFO_KEY` in the info.
+///
+/// With `with_autoreset`, workers reporting `done` are reset without seed right away. Their step
+/// then reports the first observation of the new episode, while the last one of the finished
+/// episode is inserted into the info under `FINAL_OBSERVATION_INFO_KEY`. Restarted workers are
+/// reset already and get no final observation.
+///
+/// With the feature `shm` and `with_shared_observations`, workers write their observations into
+/// memory shared with this process instead of serializing them, see `interop::shm`. `step_shared`
+/// and `reset_shared` then borrow them from there, while `step` and `reset` still copy them.
+///
+/// ```no_run
+/// use std::process::Command;
+/// use gymnarium_base::vector::SubprocVecEnvironment;
+///
+/// let mut environments = SubprocVecEnvironment::spawn(8, |index| {
+///     let mut command = Command::new("./target/release/cartpole-worker");
+///     command.arg(index.to_string());
+///     command
+/// })
+/// .unwrap()
+/// .with_max_restarts(10);
+///
+/// let observations = environments.reset(Some(42)).unwrap();
+/// let actions = observations.iter().map(|_| environments.action_space().sample()).collect::<Vec<_>>();
+/// let step = environments.step(&actions).unwrap();
+/// assert_eq!(8, step.len());
+/// environments.close().unwrap();
+/// ```
+pub struct SubprocVecEnvironment {
+    command: Box<dyn Fn(usize) -> Command>,
+    workers: Vec<Worker>,
+    restarts: Vec<u32>,
+    max_restarts: u32,
+    autoreset: bool,
+    progress: Option<ProgressReporter>,
+    action_space: ActionSpace,
+    observation_space: ObservationSpace,
+    #[cfg(feature = "shm")]
+    shared: Option<SharedObservations>,
+}
+
+impl SubprocVecEnvironment {
+    /// Starts `count` workers with the commands built for their index and fetches their spaces,
+    /// which have to be equal.
+    ///
+    /// # Panics
+    /// If `count` is zero.
+    pub fn spawn<F>(count: usize, command: F) -> Result<Self, VecEnvironmentError<WorkerError>>
+    where
+        F: Fn(usize) -> Command + 'static,
+    {
+        assert!(count > 0, "At least one worker has to be spawned");
+        let mut workers = Vec::with_capacity(count);
+        let mut spaces = Vec::with_capacity(count);
+        for index in 0..count {
+            let failed = |error| VecEnvironmentError::new(index, error);
+            let mut worker = Worker::spawn(command(index)).map_err(failed)?;
+            let space = match worker.call(&Request::Spaces).map_err(failed)? {
+                ResponseBody::Spaces {
+                    action_space,
+                    observation_space,
+                    ..
+                } => action_space
+                    .to_space()
+                    .and_then(|action_space| Ok((action_space, observation_space.to_space()?))),
+                body => return Err(failed(unexpected(body))),
+            };
+            let space = space.map_err(|error| failed(WorkerError::Protocol(error.to_string())))?;
+            if index > 0 && space != spaces[0] {
+                return Err(failed(WorkerError::Protocol(
+                    "Spaces differ from the ones of the first worker".to_string(),
+                )));
+            }
+            workers.push(worker);
+            spaces.push(space);
+        }
+        let (action_space, observation_space) = spaces.swap_remove(0);
+        Ok(Self {
+            command: Box::new(command),
+            workers,
+            restarts: vec![0; count],
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            autoreset: false,
+            progress: None,
+            action_space,
+            observation_space,
+            #[cfg(feature = "shm")]
+            shared: None,
+        })
+    }
+
+    pub fn with_max_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Resets environments reporting `done` within the same step.
+    pub fn with_autoreset(mut self) -> Self {
+        self.autoreset = true;
+        self
+    }
+
+    /// Records the steps of the workers and their finished episodes in the given reporter.
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Lets the workers write their observations into memory shared with this process, with one
+    /// slot per worker sized from the observation space.
+    ///
+    /// The observation space has to hold only floats or only integers. If the shared region
+    /// cannot be created, the error is reported for the first worker. Workers which keep
+    /// answering their observations are supported, these are written into the shared memory by
+    /// this process.
+    #[cfg(feature = "shm")]
+    pub fn with_shared_observations(mut self) -> Result<Self, VecEnvironmentError<WorkerError>> {
+        let region = SharedObservations::create(&self.observation_space, self.len())
+            .map_err(|error| VecEnvironmentError::new(0, WorkerError::Io(error.to_string())))?;
+        let requests = (0..self.len())
+            .map(|index| (index, share_request(&region, index)))
+            .collect();
+        for (index, answer) in self.exchange(requests) {
+            match answer {
+                Ok(ResponseBody::Shared { slot }) if slot == index => {}
+                Ok(body) => return Err(VecEnvironmentError::new(index, unexpected(body))),
+                Err(error) => return Err(VecEnvironmentError::new(index, error)),
+            }
+        }
+        self.shared = Some(region);
+        Ok(self)
+    }
+
+    /// Borrows the last observation of every worker straight from the shared memory, if the
+    /// observations are shared.
+    #[cfg(feature = "shm")]
+    pub fn shared_observations(&self) -> Option<PositionBatchView<'_>> {
+        // SAFETY: Workers only write while handling a request. Every request is answered, or its
+        // worker killed, before the methods taking `&mut self` return, so that no worker writes
+        // while `self` is borrowed.
+        self.shared.as_ref().map(|region| unsafe { region.view() })
+    }
+
+    pub fn progress(&self) -> Option<&ProgressReporter> {
+        self.progress.as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.workers.is_empty()
+    }
+
+    pub fn action_space(&self) -> &ActionSpace {
+        &self.action_space
+    }
+
+    pub fn observation_space(&self) -> &ObservationSpace {
+        &self.observation_space
+    }
+
+    /// Returns how often each worker has been restarted.
+    pub fn restarts(&self) -> &[u32] {
+        &self.restarts
+    }
+
+    /// Resets every environment, the one at index `i` with the seed `seed + i`.
+    pub fn reset(
+        &mut self,
+        seed: Option<u64>,
+    ) -> Result<Vec<EnvironmentState>, VecEnvironmentError<WorkerError>> {
+        let mut observations = self.reset_workers(seed)?;
+        self.copy_shared(&mut observations);
+        Ok(observations)
+    }
+
+    /// Resets every environment like `reset`, but borrows the observations from the shared
+    /// memory instead of copying them.
+    ///
+    /// # Panics
+    /// If the observations are not shared, see `with_shared_observations`.
+    #[cfg(feature = "shm")]
+    pub fn reset_shared(
+        &mut self,
+        seed: Option<u64>,
+    ) -> Result<PositionBatchView<'_>, VecEnvironmentError<WorkerError>> {
+        assert!(self.shared.is_some(), "Observations have to be shared");
+        self.reset_workers(seed)?;
+        Ok(self.shared_observations().expect("Observations are shared"))
+    }
+
+    /// Steps every environment with the action at its index.
+    ///
+    /// # Panics
+    /// If not exactly one action per environment is given.
+    pub fn step(
+        &mut self,
+        actions: &[AgentAction],
+    ) -> Result<VecStep<f64, Info>, VecEnvironmentError<WorkerError>> {
+        let mut step = self.step_workers(actions)?;
+        self.copy_shared(&mut step.observations);
+        Ok(step)
+    }
+
+    /// Steps every environment like `step`, but borrows the observations from the shared memory
+    /// instead of copying them. With `with_autoreset`, only the final observations of finished
+    /// episodes are copied into the infos.
+    ///
+    /// # Panics
+    /// If not exactly one action per environment is given or the observations are not shared,
+    /// see `with_shared_observations`.
+    #[cfg(feature = "shm")]
+    pub fn step_shared(
+        &mut self,
+        actions: &[AgentAction],
+    ) -> Result<SharedVecStep<'_>, VecEnvironmentError<WorkerError>> {
+        assert!(self.shared.is_some(), "Observations have to be shared");
+        let step = self.step_workers(actions)?;
+        Ok(SharedVecStep {
+            observations: self.shared_observations().expect("Observations are shared"),
+            rewards: step.rewards,
+            dones: step.dones,
+            infos: step.infos,
+        })
+    }
+
+    /// Closes every environment and waits for the workers to exit.
+    pub fn close(&mut self) -> Result<(), VecEnvironmentError<WorkerError>> {
+        let requests = (0..self.len())
+            .map(|index| (index, Request::Close))
+            .collect();
+        let answers = self.exchange(requests);
+        for worker in &mut self.workers {
+            let _ = worker.child.wait();
+        }
+        answers
+            .into_iter()
+            .try_for_each(|(index, answer)| match answer {
+                Ok(_) | Err(WorkerError::Crashed) => Ok(()),
+                Err(error) => Err(VecEnvironmentError::new(index, error)),
+            })
+    }
+
+    /// Resets every worker, leaving shared observations in the shared memory.
+    fn reset_workers(
+        &mut self,
+        seed: Option<u64>,
+    ) -> Result<Vec<EnvironmentState>, VecEnvironmentError<WorkerError>> {
+        let requests = (0..self.len())
+            .map(|index| {
+                let seed = seed.map(|seed| SeedValue::Number(seed.wrapping_add(index as u64)));
+                (
+                    index,
+                    Request::Reset {
+                        seed,
+                        options: None,
+                    },
+                )
+            })
+            .collect();
+        self.exchange(requests)
+            .into_iter()
+            .map(|(index, answer)| self.reset_answer(index, answer))
+            .collect()
+    }
+
+    /// Steps every worker, leaving shared observations in the shared memory apart from the final
+    /// observations of finished episodes.
+    fn step_workers(
+        &mut self,
+        actions: &[AgentAction],
+    ) -> Result<VecStep<f64, Info>, VecEnvironmentError<WorkerError>> {
+        assert_eq!(
+            self.len(),
+            actions.len(),
+            "Exactly one action per environment has to be given"
+        );
+        let requests = actions
+            .iter()
+            .map(|action| Request::Step {
+                action: action.to_f64_values(),
+            })
+            .enumerate()
+            .collect();
+        let mut step = VecStep::with_capacity(self.len());
+        let mut finished = Vec::new();
+        for (index, answer) in self.exchange(requests) {
+            match answer {
+                Ok(ResponseBody::Step {
+                    observation,
+                    reward,
+                    done,
+                    info,
+                }) => {
+                    if done && self.autoreset {
+                        finished.push((
+                            index,
+                            Request::Reset {
+                                seed: None,
+                                options: None,
+                            },
+                        ));
+                    }
+                    let observation = self.observation(index, &observation)?;
+                    step.push(observation, reward, done, to_info(info));
+                }
+                Ok(body) => return Err(VecEnvironmentError::new(index, unexpected(body))),
+                Err(WorkerError::Crashed) => {
+                    let observation = self.restart(index)?;
+                    let info = Info::from([(RESTARTED_INFO_KEY.to_string(), true.into())]);
+                    step.push(observation, 0f64, true, info);
+                }
+                Err(error) => return Err(VecEnvironmentError::new(index, error)),
+            }
+        }
+        #[cfg(feature = "shm")]
+        if let Some(view) = self.shared_observations() {
+            for (index, _) in &finished {
+                if let Some(observation) = view.get(*index) {
+                    step.observations[*index] = observation.to_position();
+                }
+            }
+        }
+        for (index, answer) in self.exchange(finished) {
+            let observation = self.reset_answer(index, answer)?;
+            let last = std::mem::replace(&mut step.observations[index], observation);
+            step.infos[index].extend(Some(final_observation(&last)));
+        }
+        if let Some(progress) = self.progress.as_mut() {
+            progress.record_vec_step(&step.dones);
+        }
+        Ok(step)
+    }
+
+    /// Replaces the placeholders of shared observations with copies of them.
+    fn copy_shared(&self, observations: &mut [EnvironmentState]) {
+        #[cfg(feature = "shm")]
+        if let Some(view) = self.shared_observations() {
+            for (observation, shared) in observations.iter_mut().zip(view.iter()) {
+                *observation = shared.to_position();
+            }
+        }
+        #[cfg(not(feature = "shm"))]
+        let _ = observations;
+    }
+
+    /// Sends every request to the worker at its index before awaiting any answer, so the workers
+    /// compute concurrently.
+    fn exchange(
+        &mut self,
+        requests: Vec<(usize, Request)>,
+    ) -> Vec<(usize, Result<ResponseBody, WorkerError>)> {
+        let sent = requests
+            .into_iter()
+            .map(|(index, request)| (index, self.workers[index].send(&request)))
+            .collect::<Vec<_>>();
+        sent.into_iter()
+            .map(|(index, sent)| (index, sent.and_then(|_| self.workers[index].receive())))
+            .collect()
+    }
+
+    fn reset_answer(
+        &mut self,
+        index: usize,
+        answer: Result<ResponseBody, WorkerError>,
+    ) -> Result<EnvironmentState, VecEnvironmentError<WorkerError>> {
+        match answer {
+            Ok(ResponseBody::Reset { observation }) => self.observation(index, &observation),
+            Ok(body) => Err(VecEnvironmentError::new(index, unexpected(body))),
+            Err(WorkerError::Crashed) => self.restart(index),
+            Err(error) => Err(VecEnvironmentError::new(index, error)),
+        }
+    }
+
+    /// Checks the observation answered by the worker.
+    ///
+    /// Shared observations stay in the shared memory, where answered ones are written to, and an
+    /// empty placeholder is returned for them instead.
+    fn observation(
+        &mut self,
+        index: usize,
+        values: &[f64],
+    ) -> Result<EnvironmentState, VecEnvironmentError<WorkerError>> {
+        let failed = |error: String| VecEnvironmentError::new(index, WorkerError::Protocol(error));
+        #[cfg(feature = "shm")]
+        if self.shared.is_some() {
+            if values.is_empty() {
+                let view = self.shared_observations().expect("Observations are shared");
+                if let Some(observation) = view.get(index) {
+                    self.observation_space
+                        .contains_sub_position(&observation)
+                        .map_err(|error| failed(error.to_string()))?;
+                }
+            } else if let Some(region) = &mut self.shared {
+                let observation = Position::from_f64_values_in(values, &self.observation_space)
+                    .map_err(|error| failed(error.to_string()))?;
+                region
+                    .write(index, &observation)
+                    .map_err(|error| failed(error.to_string()))?;
+            }
+            return Ok(Position::default());
+        }
+        Position::from_f64_values_in(values, &self.observation_space)
+            .map_err(|error| failed(error.to_string()))
+    }
+
+    fn restart(
+        &mut self,
+        index: usize,
+    ) -> Result<EnvironmentState, VecEnvironmentError<WorkerError>> {
+        let failed = |error| VecEnvironmentError::new(index, error);
+        if self.restarts[index] >= self.max_restarts {
+            self.workers[index].kill();
+            return Err(failed(WorkerError::Crashed));
+        }
+        self.restarts[index] += 1;
+        self.workers[index] = Worker::spawn((self.command)(index)).map_err(failed)?;
+        #[cfg(feature = "shm")]
+        if let Some(region) = &self.shared {
+            match self.workers[index]
+                .call(&share_request(region, index))
+                .map_err(failed)?
+            {
+                ResponseBody::Shared { .. } => {}
+                body => return Err(failed(unexpected(body))),
+            }
+        }
+        let reset = Request::Reset {
+            seed: None,
+            options: None,
+        };
+        match self.workers[index].call(&reset).map_err(failed)? {
+            ResponseBody::Reset { observation } => self.observation(index, &observation),
+            body => Err(failed(unexpected(body))),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::vector::FINAL_OBSERVATION_INFO_KEY;
+
+    /// Counts steps from `0`, finishing at `3` and exiting when reaching `$CRASH_AT`.
+    const WORKER: &str = r#"
+count=0
+while read -r line; do
+    case "$line" in
+        *'"spaces"'*)
+            schema='{"shape":[1],"low":[0.0],"high":[9.0],"dtype":"int32"}'
+            echo "{\"status\":\"ok\",\"action_space\":$schema,\"observation_space\":$schema,\"suggested_episode_steps_count\":null}" ;;
+        *'"reset"'*)
+            count=0
+            echo '{"status":"ok","observation":[0.0]}' ;;
+        *'"step"'*)
+            count=$((count + 1))
+            [ "$count" = "$CRASH_AT" ] && exit 1
+            done=false
+            [ "$count" -ge 3 ] && done=true
+            echo "{\"status\":\"ok\",\"observation\":[$count.0],\"reward\":1.0,\"done\":$done,\"info\":{\"count\":$count}}" ;;
+        *'"close"'*)
+            echo '{"status":"ok"}'
+            exit 0 ;;
+    esac
+done
+"#;
+
+    fn workers(count: usize, crashing: usize) -> SubprocVecEnvironment {
+        SubprocVecEnvironment::spawn(count, move |index| {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(WORKER);
+            if index == crashing {
+                command.env("CRASH_AT", "2");
+            }
+            command
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn workers_step_in_the_order_of_the_environments() {
+        let mut environments = workers(3, usize::MAX);
+        assert_eq!(3, environments.len());
+        assert_eq!(
+            vec![crate::position![0]; 3],
+            environments.reset(Some(7)).unwrap()
+        );
+        let actions = vec![crate::position![1]; 3];
+        environments.step(&actions).unwrap();
+        environments.step(&actions).unwrap();
+        let step = environments.step(&actions).unwrap();
+        assert_eq!(vec![crate::position![3]; 3], step.observations);
+        assert_eq!(
+            Position::from_i32_values(vec![3, 3, 3], vec![3, 1]),
+            Ok(step.observation_batch().unwrap().to_position())
+        );
+        assert_eq!(vec![1.0; 3], step.rewards);
+        assert_eq!(vec![true; 3], step.dones);
+        assert_eq!(Some(&3.into()), step.infos[2].get("count"));
+        assert_eq!(Ok(()), environments.close());
+    }
+
+    #[test]
+    fn finished_workers_are_reset_with_autoreset() {
+        let mut environments = workers(2, usize::MAX).with_autoreset();
+        environments.reset(None).unwrap();
+        let actions = vec![crate::position![1]; 2];
+        environments.step(&actions).unwrap();
+        environments.step(&actions).unwrap();
+        let step = environments.step(&actions).unwrap();
+        assert_eq!(vec![true; 2], step.dones);
+        assert_eq!(vec![crate::position![0]; 2], step.observations);
+        assert_eq!(
+            Some(&serde_json::json!([3.0])),
+            step.infos[1].get(FINAL_OBSERVATION_INFO_KEY)
+        );
+        assert_eq!(
+            vec![crate::position![1]; 2],
+            environments.step(&actions).unwrap().observations
+        );
+    }
+
+    #[test]
+    fn steps_and_finished_episodes_are_recorded_as_progress() {
+        let mut environments = workers(2, usize::MAX)
+            .with_autoreset()
+            .with_progress(ProgressReporter::new());
+        environments.reset(None).unwrap();
+        let actions = vec![crate::position![1]; 2];
+        for _ in 0..4 {
+            environments.step(&actions).unwrap();
+        }
+        let progress = environments.progress().unwrap().progress();
+        assert_eq!(8, progress.steps);
+        assert_eq!(2, progress.episodes);
+    }
+
+    /// Counts steps like `WORKER` without crashing, but writes its count into the shared slot once
+    /// asked to.
+    #[cfg(feature = "shm")]
+    const SHARING_WORKER: &str = r#"
+count=0
+path=
+observe() {
+    if [ -n "$path" ]; then
+        printf "$(printf '\\%03o' "$count")\\000\\000\\000" \
+            | dd of="$path" bs=4 seek="$slot" conv=notrunc 2>/dev/null
+        observation='[]'
+    else
+        observation="[$count.0]"
+    fi
+}
+while read -r line; do
+    case "$line" in
+        *'"spaces"'*)
+            schema='{"shape":[1],"low":[0.0],"high":[9.0],"dtype":"int32"}'
+            echo "{\"status\":\"ok\",\"action_space\":$schema,\"observation_space\":$schema,\"suggested_episode_steps_count\":null}" ;;
+        *'"share"'*)
+            path=$(echo "$line" | sed 's/.*"path":"\([^"]*\)".*/\1/')
+            slot=$(echo "$line" | sed 's/.*"slot":\([0-9]*\).*/\1/')
+            echo "{\"status\":\"ok\",\"slot\":$slot}" ;;
+        *'"reset"'*)
+            count=0
+            observe
+            echo "{\"status\":\"ok\",\"observation\":$observation}" ;;
+        *'"step"'*)
+            count=$((count + 1))
+            observe
+            done=false
+            [ "$count" -ge 3 ] && done=true
+            echo "{\"status\":\"ok\",\"observation\":$observation,\"reward\":1.0,\"done\":$done,\"info\":{}}" ;;
+    esac
+done
+"#;
+
+    #[cfg(feature = "shm")]
+    #[test]
+    fn shared_observations_are_read_from_memory() {
+        let mut environments = SubprocVecEnvironment::spawn(2, |_| {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(SHARING_WORKER);
+            command
+        })
+        .unwrap()
+        .with_shared_observations()
+        .unwrap()
+        .with_autoreset();
+        assert_eq!(
+            vec![crate::position![0]; 2],
+            environments.reset(None).unwrap()
+        );
+        let actions = vec![crate::position![1]; 2];
+        let step = environments.step(&actions);
+        assert_eq!(vec![crate::position![1]; 2], step.unwrap().observations);
+        let step = environments.step_shared(&actions).unwrap();
+        assert_eq!(Some(&[2, 2][..]), step.observations.as_i32_slice());
+        assert_eq!(vec![2, 1], step.observations.dimensions());
+        assert_eq!(vec![1.0; 2], step.rewards);
+
+        let step = environments.step_shared(&actions).unwrap();
+        assert_eq!(Some(&[0, 0][..]), step.observations.as_i32_slice());
+        assert_eq!(
+            Some(&serde_json::json!([3.0])),
+            step.infos[0].get(FINAL_OBSERVATION_INFO_KEY)
+        );
+        assert_eq!(
+            Some(&[0, 0][..]),
+            environments.reset_shared(None).unwrap().as_i32_slice()
+        );
+    }
+
+    #[test]
+    fn crashed_workers_are_restarted_until_the_limit() {
+        let mut environments = workers(2, 1).with_max_restarts(1);
+        environments.reset(None).unwrap();
+        let actions = vec![crate::position![1]; 2];
+        assert_eq!(
+            vec![false, false],
+            environments.step(&actions).unwrap().dones
+        );
+
+        let step = environments.step(&actions).unwrap();
+        assert_eq!(
+            vec![crate::position![2], crate::position![0]],
+            step.observations
+        );
+        assert_eq!(vec![false, true], step.dones);
+        assert_eq!(Some(&true.into()), step.infos[1].get(RESTARTED_INFO_KEY));
+        assert_eq!(&[0, 1], environments.restarts());
+
+        environments.step(&actions).unwrap();
+        assert_eq!(
+            Err(VecEnvironmentError::new(1, WorkerError::Crashed)),
+            environments.step(&actions)
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use rayon::prelude::*;
+use rayon::ThreadPool;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{final_observation, VecEnvironmentError, VecStep};
+use crate::progress::ProgressReporter;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{Reward, Seed};
+
+type TypeParameters<E, R, I, D> = fn() -> (E, R, I, D);
+
+/* --- --- --- THREADED VEC ENVIRONMENT --- --- --- */
+
+/// Steps every environment on a thread pool, so that slow steps of some environments overlap
+/// with the steps of the others.
+///
+/// The environments are distributed with work stealing, by default on the global pool of rayon.
+/// However long single steps take, results are returned in the order of the environments. The
+/// first error by index is returned, after every environment has finished.
+///
+/// With `with_autoreset`, environments reporting `done` are reset without seed within the same
+/// step, on the thread which stepped them. Their step then reports the first observation of the
+/// new episode, while the last one of the finished episode is inserted into the info under
+/// `FINAL_OBSERVATION_INFO_KEY`.
+///
+/// ```
+/// use gymnarium_base::vector::ThreadedVecEnvironment;
+/// use gymnarium_base::Environment;
+///
+/// fn run<Env: Environment<std::fmt::Error, f64, (), ()> + Send>(environments: Vec<Env>) {
+///     let mut environments = ThreadedVecEnvironment::new(environments);
+///     let observations = environments.reset(Some(42)).unwrap();
+///     let actions = observations
+///         .iter()
+///         .map(|_| environments.action_space().sample())
+///         .collect::<Vec<_>>();
+///     let step = environments.step(&actions).unwrap();
+///     assert_eq!(observations.len(), step.len());
+///     environments.close().unwrap();
+/// }
+/// ```
+pub struct ThreadedVecEnvironment<Env, E, R, I, D> {
+    environments: Vec<Env>,
+    pool: Option<ThreadPool>,
+    autoreset: Option<fn(&mut I, &EnvironmentState)>,
+    progress: Option<ProgressReporter>,
+    phantom: PhantomData<TypeParameters<E, R, I, D>>,
+}
+
+impl<Env, E, R, I, D> ThreadedVecEnvironment<Env, E, R, I, D>
+where
+    Env: Environment<E, R, I, D> + Send,
+    E: std::error::Error + Send,
+    R: Reward + Send,
+    I: Debug + Send,
+    D: Serialize + DeserializeOwned,
+{
+    /// # Panics
+    /// If no environment is given.
+    pub fn new(environments: Vec<Env>) -> Self {
+        assert!(
+            !environments.is_empty(),
+            "At least one environment has to be given"
+        );
+        Self {
+            environments,
+            pool: None,
+            autoreset: None,
+            progress: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Runs the environments on the given pool instead of the global one.
+    pub fn with_thread_pool(mut self, pool: ThreadPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Records the steps of the environments and their finished episodes in the given reporter.
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    pub fn progress(&self) -> Option<&ProgressReporter> {
+        self.progress.as_ref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.environments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.environments.is_empty()
+    }
+
+    /// Returns the action space of the first environment.
+    pub fn action_space(&self) -> ActionSpace {
+        self.environments[0].action_space()
+    }
+
+    /// Returns the observation space of the first environment.
+    pub fn observation_space(&self) -> ObservationSpace {
+        self.environments[0].observation_space()
+    }
+
+    pub fn environments(&self) -> &[Env] {
+        &self.environments
+    }
+
+    pub fn environments_mut(&mut self) -> &mut [Env] {
+        &mut self.environments
+    }
+
+    pub fn into_inner(self) -> Vec<Env> {
+        self.environments
+    }
+
+    /// Resets every environment, the one at index `i` with the seed `seed + i`.
+    pub fn reset(
+        &mut self,
+        seed: Option<u64>,
+    ) -> Result<Vec<EnvironmentState>, VecEnvironmentError<E>> {
+        collect(self.run(|index, environment| {
+            environment.reset(
+                seed.map(|seed| Seed::from(seed.wrapping_add(index as u64))),
+                None,
+            )
+        }))
+    }
+
+    /// Steps every environment with the action at its index.
+    ///
+    /// # Panics
+    /// If not exactly one action per environment is given.
+    pub fn step(
+        &mut self,
+        actions: &[AgentAction],
+    ) -> Result<VecStep<R, I>, VecEnvironmentError<E>> {
+        assert_eq!(
+            self.len(),
+            actions.len(),
+            "Exactly one action per environment has to be given"
+        );
+        let autoreset = self.autoreset;
+        let results = collect(self.run(|index, environment| {
+            let (observation, reward, done, mut info) = environment.step(&actions[index])?;
+            match autoreset {
+                Some(record) if done => {
+                    record(&mut info, &observation);
+                    Ok((environment.reset(None, None)?, reward, done, info))
+                }
+                _ => Ok((observation, reward, done, info)),
+            }
+        }))?;
+        let mut step = VecStep::with_capacity(results.len());
+        for (observation, reward, done, info) in results {
+            step.push(observation, reward, done, info);
+        }
+        if let Some(progress) = self.progress.as_mut() {
+            progress.record_vec_step(&step.dones);
+        }
+        Ok(step)
+    }
+
+    pub fn close(&mut self) -> Result<(), VecEnvironmentError<E>> {
+        collect(self.run(|_, environment| environment.close())).map(|_| ())
+    }
+
+    /// Runs the task for every environment on the pool, returning the results by index.
+    fn run<T, F>(&mut self, task: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(usize, &mut Env) -> T + Sync,
+    {
+        let environments = &mut self.environments;
+        let mut run = || {
+            environments
+                .par_iter_mut()
+                .enumerate()
+                .map(|(index, environment)| task(index, environment))
+                .collect()
+        };
+        match &self.pool {
+            Some(pool) => pool.install(run),
+            None => run(),
+        }
+    }
+}
+
+impl<Env, E, R, I, D> ThreadedVecEnvironment<Env, E, R, I, D>
+where
+    I: Extend<(String, serde_json::Value)>,
+{
+    /// Resets environments reporting `done` within the same step.
+    pub fn with_autoreset(mut self) -> Self {
+        self.autoreset =
+            Some(|info, observation| info.extend(Some(final_observation(observation))));
+        self
+    }
+}
+
+fn collect<T, E>(results: Vec<Result<T, E>>) -> Result<Vec<T>, VecEnvironmentError<E>> {
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| result.map_err(|error| VecEnvironmentError::new(index, error)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::{DimensionBoundaries, DimensionValue, Space};
+    use crate::vector::FINAL_OBSERVATION_INFO_KEY;
+    use crate::{Info, ResetOptions};
+    use std::time::Duration;
+
+    /// Sleeps for the given milliseconds on every step, finishes at a count of 2 and fails once
+    /// its count reaches `fail_at`.
+    struct SlowEnvironment {
+        delay: u64,
+        fail_at: i32,
+        count: i32,
+    }
+
+    impl Environment<std::fmt::Error, f64, Info, ()> for SlowEnvironment {
+        fn action_space(&self) -> ActionSpace {
+            Space::simple(vec![DimensionBoundaries::from(0..=1)])
+        }
+
+        fn observation_space(&self) -> ObservationSpace {
+            Space::simple(vec![DimensionBoundaries::from(0..=9)])
+        }
+
+        fn suggested_episode_steps_count(&self) -> Option<u128> {
+            None
+        }
+
+        fn reset(
+            &mut self,
+            _seed: Option<Seed>,
+            _options: Option<ResetOptions>,
+        ) -> Result<EnvironmentState, std::fmt::Error> {
+            self.count = 0;
+            Ok(self.state())
+        }
+
+        fn state(&self) -> EnvironmentState {
+            EnvironmentState::simple(vec![DimensionValue::from(self.count)])
+        }
+
+        fn step(
+            &mut self,
+            action: &AgentAction,
+        ) -> Result<(EnvironmentState, f64, bool, Info), std::fmt::Error> {
+            std::thread::sleep(Duration::from_millis(self.delay));
+            self.count += action.value(&[0]).expect_integer();
+            if self.count >= self.fail_at {
+                return Err(std::fmt::Error);
+            }
+            Ok((
+                self.state(),
+                self.delay as f64,
+                self.count >= 2,
+                Info::new(),
+            ))
+        }
+
+        fn load(&mut self, _data: ()) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+
+        fn store(&self) {}
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+
+    fn environments(fail_at: &[i32]) -> Vec<SlowEnvironment> {
+        fail_at
+            .iter()
+            .enumerate()
+            .map(|(index, fail_at)| SlowEnvironment {
+                delay: 20 - 5 * index as u64,
+                fail_at: *fail_at,
+                count: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn results_keep_the_order_of_the_environments() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let mut environments =
+            ThreadedVecEnvironment::new(environments(&[9, 9, 9, 9])).with_thread_pool(pool);
+        environments.reset(Some(1)).unwrap();
+        let actions = vec![
+            crate::position![1],
+            crate::position![0],
+            crate::position![1],
+            crate::position![0],
+        ];
+        let step = environments.step(&actions).unwrap();
+        assert_eq!(
+            vec![
+                crate::position![1],
+                crate::position![0],
+                crate::position![1],
+                crate::position![0],
+            ],
+            step.observations
+        );
+        assert_eq!(vec![20.0, 15.0, 10.0, 5.0], step.rewards);
+        assert_eq!(Ok(()), environments.close());
+    }
+
+    #[test]
+    fn finished_environments_are_reset_with_autoreset() {
+        let mut environments = ThreadedVecEnvironment::new(environments(&[9, 9])).with_autoreset();
+        environments.reset(None).unwrap();
+        let actions = vec![crate::position![1], crate::position![0]];
+        environments.step(&actions).unwrap();
+        let step = environments.step(&actions).unwrap();
+        assert_eq!(vec![true, false], step.dones);
+        assert_eq!(vec![crate::position![0]; 2], step.observations);
+        assert_eq!(
+            Some(&serde_json::json!([2.0])),
+            step.infos[0].get(FINAL_OBSERVATION_INFO_KEY)
+        );
+        assert_eq!(None, step.infos[1].get(FINAL_OBSERVATION_INFO_KEY));
+    }
+
+    #[test]
+    fn steps_and_finished_episodes_are_recorded_as_progress() {
+        let mut environments = ThreadedVecEnvironment::new(environments(&[9, 9]))
+            .with_autoreset()
+            .with_progress(ProgressReporter::new());
+        environments.reset(None).unwrap();
+        let actions = vec![crate::position![1], crate::position![0]];
+        for _ in 0..3 {
+            environments.step(&actions).unwrap();
+        }
+        let progress = environments.progress().unwrap().progress();
+        assert_eq!(6, progress.steps);
+        assert_eq!(1, progress.episodes);
+    }
+
+    #[test]
+    fn the_first_error_by_index_is_returned() {
+        let mut environments = ThreadedVecEnvironment::new(environments(&[9, 1, 1]));
+        environments.reset(None).unwrap();
+        let error = environments
+            .step(&vec![crate::position![1]; 3])
+            .unwrap_err();
+        assert_eq!(VecEnvironmentError::new(1, std::fmt::Error), error);
+        assert_eq!(1, environments.environments()[0].count);
+    }
+}
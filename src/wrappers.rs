@@ -0,0 +1,163 @@
+//! Contains wrappers around environments which add behaviour without touching the environment
+//! itself.
+//!
+//! Every wrapper implements `Environment` again, so wrappers can be stacked.
+//!
+//! Wrappers drawing random numbers own a generator seeded by their own seed or by entropy. A seed
+//! passed to `reset` reseeds it with a seed derived from both seeds, so seeded resets repeat their
+//! episodes while differently seeded wrappers still draw differently. Resets without a seed
+//! continue the current sequence.
+
+mod action_repeat;
+mod chained;
+mod csv_logger;
+mod domain_randomizer;
+mod episode_statistics;
+mod image;
+mod noisy_observation;
+mod partially_observable;
+mod shaped_reward;
+mod sticky_actions;
+mod transform;
+mod validated;
+
+pub use action_repeat::{ActionRepeat, ObservationPooling, RewardReducer};
+pub use chained::{ChainData, ChainError, ChainedEnvironment};
+pub use csv_logger::CsvLogger;
+pub use domain_randomizer::{DomainRandomizer, DOMAIN_INFO_PREFIX};
+pub use episode_statistics::{EpisodeStatistics, RecordEpisodeStatistics};
+pub use image::{GrayscaleObservation, ImageError, Interpolation, ResizeObservation};
+pub use noisy_observation::{NoisyObservation, ObservationNoise, GAUSSIAN_WIDENING};
+pub use partially_observable::{
+    ObservationFunction, PartialObservation, PartiallyObservableWrapper,
+};
+pub use shaped_reward::ShapedReward;
+pub use sticky_actions::StickyActions;
+pub use transform::{TransformAction, TransformObservation};
+pub use validated::{ValidatedEnvironment, ValidationError};
+
+use std::ops::{Deref, DerefMut};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::Seed;
+
+/// Random number generator of a wrapper, following the seeding described in the module
+/// documentation.
+#[derive(Clone)]
+pub(crate) struct WrapperRng {
+    seed: Option<Seed>,
+    rng: StdRng,
+}
+
+impl WrapperRng {
+    pub(crate) fn new(seed: Option<Seed>) -> Self {
+        let rng = match &seed {
+            Some(seed) => StdRng::from_seed(seed.mixed()),
+            None => StdRng::from_entropy(),
+        };
+        Self { seed, rng }
+    }
+
+    /// Reseeds the generator for a reset with the given seed, if there is one.
+    pub(crate) fn reset(&mut self, seed: Option<&Seed>) {
+        if let Some(reset_seed) = seed {
+            let mut seed_value = self
+                .seed
+                .as_ref()
+                .map_or_else(Vec::new, |seed| seed.seed_value.clone());
+            seed_value.extend_from_slice(b"/reset/");
+            seed_value.extend_from_slice(&reset_seed.seed_value);
+            self.rng = StdRng::from_seed(Seed::from(seed_value).mixed());
+        }
+    }
+}
+
+impl Deref for WrapperRng {
+    type Target = StdRng;
+
+    fn deref(&self) -> &StdRng {
+        &self.rng
+    }
+}
+
+impl DerefMut for WrapperRng {
+    fn deref_mut(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_environment {
+    use crate::space::{DimensionBoundaries, DimensionValue, Space};
+    use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+    use crate::{Info, ResetOptions, Seed};
+
+    /// Environment counting the actions given, finishing at a count of 5.
+    ///
+    /// The state is not checked, so a count outside of `0..=5` can be forced with actions.
+    #[derive(Default)]
+    pub struct CountingEnvironment {
+        pub count: i32,
+    }
+
+    impl Environment<std::fmt::Error, f64, Info, i32> for CountingEnvironment {
+        fn action_space(&self) -> ActionSpace {
+            Space::simple(vec![DimensionBoundaries::from(-1..=1)])
+        }
+
+        fn observation_space(&self) -> ObservationSpace {
+            Space::simple(vec![DimensionBoundaries::from(0..=5)])
+        }
+
+        fn suggested_episode_steps_count(&self) -> Option<u128> {
+            Some(10)
+        }
+
+        fn reset(
+            &mut self,
+            _seed: Option<Seed>,
+            options: Option<ResetOptions>,
+        ) -> Result<EnvironmentState, std::fmt::Error> {
+            self.count = match options.and_then(|options| options.initial_state) {
+                Some(distribution) => distribution
+                    .initial_state(&mut rand::thread_rng())
+                    .value(&[0])
+                    .expect_integer(),
+                None => 0,
+            };
+            Ok(self.state())
+        }
+
+        fn state(&self) -> EnvironmentState {
+            EnvironmentState::simple(vec![DimensionValue::from(self.count)])
+        }
+
+        fn step(
+            &mut self,
+            action: &AgentAction,
+        ) -> Result<(EnvironmentState, f64, bool, Info), std::fmt::Error> {
+            self.count += action.value(&[0]).expect_integer();
+            Ok((
+                self.state(),
+                self.count as f64,
+                self.count >= 5,
+                Info::new(),
+            ))
+        }
+
+        fn load(&mut self, data: i32) -> Result<(), std::fmt::Error> {
+            self.count = data;
+            Ok(())
+        }
+
+        fn store(&self) -> i32 {
+            self.count
+        }
+
+        fn close(&mut self) -> Result<(), std::fmt::Error> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::space::DimensionValue;
+use crate::spec::EnvironmentSpec;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- REDUCERS --- --- --- */
+
+/// How the rewards of the repeated steps are combined.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RewardReducer {
+    Sum,
+    Max,
+    Last,
+}
+
+impl RewardReducer {
+    fn reduce(&self, accumulated: Option<f64>, reward: f64) -> f64 {
+        match (self, accumulated) {
+            (_, None) | (Self::Last, _) => reward,
+            (Self::Sum, Some(accumulated)) => accumulated + reward,
+            (Self::Max, Some(accumulated)) => accumulated.max(reward),
+        }
+    }
+}
+
+/// Which observation is returned after the repeated steps.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ObservationPooling {
+    /// The observation of the last step.
+    Last,
+    /// The element-wise maximum of the observations of the last two steps.
+    Max,
+}
+
+/* --- --- --- ACTION REPEAT --- --- --- */
+
+/// Applies every action for a fixed number of steps of the wrapped environment, also known as
+/// frame skipping.
+///
+/// Repeating stops early if the episode ends. The rewards are combined into an `f64` with the
+/// configured `RewardReducer` and the info of the last step is returned. Therefore the reward
+/// type has to be constructible from `f64`.
+pub struct ActionRepeat<Env> {
+    environment: Env,
+    repeats: usize,
+    reducer: RewardReducer,
+    pooling: ObservationPooling,
+}
+
+impl<Env> ActionRepeat<Env> {
+    /// Creates the wrapper summing the rewards and returning the last observation.
+    ///
+    /// # Panics
+    /// If `repeats` is zero.
+    pub fn new(environment: Env, repeats: usize) -> Self {
+        assert!(repeats > 0, "Actions have to be repeated at least once");
+        Self {
+            environment,
+            repeats,
+            reducer: RewardReducer::Sum,
+            pooling: ObservationPooling::Last,
+        }
+    }
+
+    pub fn with_reducer(mut self, reducer: RewardReducer) -> Self {
+        self.reducer = reducer;
+        self
+    }
+
+    pub fn with_pooling(mut self, pooling: ObservationPooling) -> Self {
+        self.pooling = pooling;
+        self
+    }
+
+    pub fn repeats(&self) -> usize {
+        self.repeats
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+fn max_pool(previous: &EnvironmentState, last: &EnvironmentState) -> EnvironmentState {
+    if !previous.matches(last) {
+        return last.clone();
+    }
+    if let (Some(previous), Some(last_values)) = (previous.as_f32_slice(), last.as_f32_slice()) {
+        let values = previous.iter().zip(last_values).map(|(a, b)| a.max(*b));
+        return EnvironmentState::from_f32_values(values.collect(), last.dimensions().clone())
+            .expect("values of matching positions fit their dimensions");
+    }
+    let values = previous
+        .values()
+        .iter()
+        .zip(last.values().iter())
+        .map(|(previous, last)| match (previous, last) {
+            (DimensionValue::Integer(a), DimensionValue::Integer(b)) => {
+                DimensionValue::Integer(*a.max(b))
+            }
+            (DimensionValue::Float(a), DimensionValue::Float(b)) => {
+                DimensionValue::Float(a.max(*b))
+            }
+            (_, last) => *last,
+        })
+        .collect();
+    EnvironmentState::new(values, last.dimensions().clone())
+        .expect("values of matching positions fit their dimensions")
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, D> for ActionRepeat<Env>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward + From<f64>,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.environment.reset(seed, options)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let mut previous = None;
+        let mut reward = None;
+        let mut repeat = 0;
+        loop {
+            let (state, step_reward, done, info) = self.environment.step(action)?;
+            reward = Some(self.reducer.reduce(reward, step_reward.value()));
+            repeat += 1;
+            if done || repeat == self.repeats {
+                let state = match (self.pooling, previous) {
+                    (ObservationPooling::Max, Some(previous)) => max_pool(&previous, &state),
+                    _ => state,
+                };
+                return Ok((state, R::from(reward.unwrap_or_default()), done, info));
+            }
+            previous = Some(state);
+        }
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn repeats_action_and_reduces_rewards() {
+        let forward = AgentAction::simple(vec![1.into()]);
+        let mut summing = ActionRepeat::new(CountingEnvironment::default(), 2);
+        summing.reset(None, None).unwrap();
+        let (state, reward, done, _) = summing.step(&forward).unwrap();
+        assert_eq!(EnvironmentState::simple(vec![2.into()]), state);
+        assert_eq!(3f64, reward);
+        assert!(!done);
+
+        let mut last =
+            ActionRepeat::new(CountingEnvironment::default(), 4).with_reducer(RewardReducer::Last);
+        last.reset(None, None).unwrap();
+        assert_eq!(4f64, last.step(&forward).unwrap().1);
+        let (_, reward, done, _) = last.step(&forward).unwrap();
+        assert_eq!((5f64, true), (reward, done));
+    }
+
+    #[test]
+    fn max_pooling_uses_the_last_two_observations() {
+        let backward = AgentAction::simple(vec![(-1).into()]);
+        let mut environment = ActionRepeat::new(CountingEnvironment { count: 3 }, 3)
+            .with_reducer(RewardReducer::Max)
+            .with_pooling(ObservationPooling::Max);
+        let (state, reward, _, _) = environment.step(&backward).unwrap();
+        assert_eq!(EnvironmentState::simple(vec![1.into()]), state);
+        assert_eq!(2f64, reward);
+    }
+}
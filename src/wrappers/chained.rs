@@ -0,0 +1,402 @@
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::space::{DimensionBoundaries, DimensionValue, Space, SpaceMismatch};
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- CHAIN ERROR --- --- --- */
+
+/// Errors while creating a `ChainedEnvironment`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ChainError {
+    /// No environments were given.
+    Empty,
+    /// The action space of the environment at `index` differs from the first one.
+    ActionSpaceMismatch {
+        index: usize,
+        mismatch: SpaceMismatch,
+    },
+}
+
+impl std::fmt::Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "At least one environment has to be chained"),
+            Self::ActionSpaceMismatch { index, mismatch } => write!(
+                f,
+                "Action space of environment {} differs from the first one ({})",
+                index, mismatch
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChainError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Empty => None,
+            Self::ActionSpaceMismatch { mismatch, .. } => Some(mismatch),
+        }
+    }
+}
+
+/* --- --- --- CHAINED ENVIRONMENT --- --- --- */
+
+/// Stored data of a `ChainedEnvironment`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ChainData<D> {
+    pub phase: usize,
+    pub environments: Vec<D>,
+}
+
+/// Runs several environments one after another within a single episode.
+///
+/// The next environment is reset as soon as the current one is done and the episode ends with the
+/// last environment. All environments have to share the action space. A seed passed to `reset`
+/// seeds the first environment, the later ones are reset with seeds derived from it per phase.
+///
+/// Observations are flattened and prefixed with the index of the current environment. Shorter
+/// observations are padded with zeros, so the observation space is one-dimensional with the
+/// length of the longest observation plus one. Integer values become floats where the merged
+/// boundaries are floats.
+pub struct ChainedEnvironment<Env> {
+    environments: Vec<Env>,
+    phase: usize,
+    seed: Option<Seed>,
+    action_space: ActionSpace,
+    observation_space: ObservationSpace,
+}
+
+impl<Env> ChainedEnvironment<Env> {
+    pub fn new<E, R, I, D>(environments: Vec<Env>) -> Result<Self, ChainError>
+    where
+        Env: Environment<E, R, I, D>,
+        E: std::error::Error,
+        R: Reward,
+        I: Debug,
+        D: Serialize + DeserializeOwned,
+    {
+        let action_space = environments
+            .first()
+            .ok_or(ChainError::Empty)?
+            .action_space();
+        for (index, environment) in environments.iter().enumerate().skip(1) {
+            action_space
+                .matches_types(&environment.action_space())
+                .map_err(|mismatch| ChainError::ActionSpaceMismatch { index, mismatch })?;
+        }
+        let observation_space = merge_observation_spaces(
+            &environments
+                .iter()
+                .map(|environment| environment.observation_space())
+                .collect::<Vec<_>>(),
+        );
+        Ok(Self {
+            environments,
+            phase: 0,
+            seed: None,
+            action_space,
+            observation_space,
+        })
+    }
+
+    /// Returns the index of the currently running environment.
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+
+    pub fn environments(&self) -> &[Env] {
+        &self.environments
+    }
+
+    pub fn environments_mut(&mut self) -> &mut [Env] {
+        &mut self.environments
+    }
+
+    pub fn into_environments(self) -> Vec<Env> {
+        self.environments
+    }
+
+    fn observe(&self, state: &EnvironmentState) -> EnvironmentState {
+        let state_values = state.values();
+        let boundaries = self.observation_space.get_boundaries();
+        let mut values = Vec::with_capacity(boundaries.len());
+        values.push(DimensionValue::Integer(self.phase as i32));
+        values.extend(
+            boundaries[1..]
+                .iter()
+                .enumerate()
+                .map(
+                    |(index, boundaries)| match (boundaries, state_values.get(index)) {
+                        (
+                            DimensionBoundaries::Float(_, _),
+                            Some(DimensionValue::Integer(value)),
+                        ) => DimensionValue::Float(*value as f32),
+                        (_, Some(value)) => *value,
+                        (DimensionBoundaries::Float(_, _), None) => DimensionValue::Float(0f32),
+                        (_, None) => DimensionValue::Integer(0),
+                    },
+                ),
+        );
+        EnvironmentState::simple(values)
+    }
+}
+
+/// Derives the seed of the environment at the given phase from the seed of the whole episode.
+fn phase_seed(seed: &Seed, phase: usize) -> Seed {
+    let mut seed_value = seed.seed_value.clone();
+    seed_value.extend_from_slice(format!("/phase/{}", phase).as_bytes());
+    Seed::from(seed_value)
+}
+
+/// Joins the flattened spaces element-wise, falling back to float boundaries on type conflicts.
+fn merge_observation_spaces(spaces: &[Space]) -> Space {
+    let length = spaces
+        .iter()
+        .map(|space| space.get_boundaries().len())
+        .max()
+        .unwrap_or(0);
+    let mut merged = vec![DimensionBoundaries::Integer(0, spaces.len() as i32 - 1)];
+    for index in 0..length {
+        let padding = DimensionBoundaries::Integer(0, 0);
+        merged.push(
+            spaces
+                .iter()
+                .map(|space| *space.get_boundaries().get(index).unwrap_or(&padding))
+                .reduce(
+                    |first, second| match (first.integer_range(), second.integer_range()) {
+                        _ if first == second => first,
+                        (Some((a, b)), Some((c, d))) => {
+                            DimensionBoundaries::Integer(a.min(c), b.max(d))
+                        }
+                        _ => {
+                            let (a, b) = float_range(first);
+                            let (c, d) = float_range(second);
+                            DimensionBoundaries::Float(a.min(c), b.max(d))
+                        }
+                    },
+                )
+                .unwrap_or(padding),
+        );
+    }
+    Space::simple(merged)
+}
+
+fn float_range(boundaries: DimensionBoundaries) -> (f32, f32) {
+    match boundaries.integer_range() {
+        Some((low, high)) => (low as f32, high as f32),
+        None => boundaries.expect_float(),
+    }
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, ChainData<D>> for ChainedEnvironment<Env>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.action_space.clone()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.observation_space.clone()
+    }
+
+    /// Returns the sum of the suggested counts, if every environment suggests one.
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environments
+            .iter()
+            .map(|environment| environment.suggested_episode_steps_count())
+            .sum()
+    }
+
+    fn environment_id() -> String {
+        format!("Chained<{}>", Env::environment_id())
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.phase = 0;
+        self.seed = seed.clone();
+        let state = self.environments[0].reset(seed, options)?;
+        Ok(self.observe(&state))
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.observe(&self.environments[self.phase].state())
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let (mut state, reward, done, info) = self.environments[self.phase].step(action)?;
+        if done && self.phase + 1 < self.environments.len() {
+            self.phase += 1;
+            let seed = self.seed.as_ref().map(|seed| phase_seed(seed, self.phase));
+            state = self.environments[self.phase].reset(seed, None)?;
+            return Ok((self.observe(&state), reward, false, info));
+        }
+        Ok((self.observe(&state), reward, done, info))
+    }
+
+    fn load(&mut self, data: ChainData<D>) -> Result<(), E> {
+        for (environment, data) in self.environments.iter_mut().zip(data.environments) {
+            environment.load(data)?;
+        }
+        self.phase = data.phase.min(self.environments.len() - 1);
+        Ok(())
+    }
+
+    fn store(&self) -> ChainData<D> {
+        ChainData {
+            phase: self.phase,
+            environments: self
+                .environments
+                .iter()
+                .map(|environment| environment.store())
+                .collect(),
+        }
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environments
+            .iter_mut()
+            .try_for_each(|environment| environment.close())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::wrappers::{NoisyObservation, ObservationNoise, TransformObservation};
+
+    #[test]
+    fn episode_continues_with_next_environment() {
+        let mut environment = ChainedEnvironment::new(vec![
+            CountingEnvironment::default(),
+            CountingEnvironment::default(),
+        ])
+        .unwrap();
+        assert_eq!(
+            Space::simple(vec![
+                DimensionBoundaries::from(0..=1),
+                DimensionBoundaries::from(0..=5)
+            ]),
+            environment.observation_space()
+        );
+        assert_eq!(Some(20), environment.suggested_episode_steps_count());
+
+        environment.reset(None, None).unwrap();
+        let forward = AgentAction::simple(vec![1.into()]);
+        for _ in 0..4 {
+            environment.step(&forward).unwrap();
+        }
+        let (state, _, done, _) = environment.step(&forward).unwrap();
+        assert_eq!(crate::position![1, 0], state);
+        assert!(!done);
+        assert_eq!(1, environment.phase());
+
+        for _ in 0..4 {
+            environment.step(&forward).unwrap();
+        }
+        let (state, _, done, _) = environment.step(&forward).unwrap();
+        assert_eq!(crate::position![1, 5], state);
+        assert!(done);
+        assert_eq!(
+            ChainData {
+                phase: 1,
+                environments: vec![5, 5]
+            },
+            environment.store()
+        );
+    }
+
+    #[test]
+    fn merged_observation_space_pads_and_joins_boundaries() {
+        let merged = merge_observation_spaces(&[
+            Space::simple(vec![DimensionBoundaries::from(1..=3)]),
+            Space::simple(vec![
+                DimensionBoundaries::from(-1f32..=2f32),
+                DimensionBoundaries::from(4..=6),
+            ]),
+        ]);
+        assert_eq!(
+            Space::simple(vec![
+                DimensionBoundaries::from(0..=1),
+                DimensionBoundaries::from(-1f32..=3f32),
+                DimensionBoundaries::from(0..=6),
+            ]),
+            merged
+        );
+        assert!(matches!(
+            ChainedEnvironment::<CountingEnvironment>::new(vec![]),
+            Err(ChainError::Empty)
+        ));
+    }
+
+    #[test]
+    fn observations_of_every_phase_are_inside_the_merged_space() {
+        type Transformed =
+            TransformObservation<CountingEnvironment, fn(&EnvironmentState) -> EnvironmentState>;
+        let integer: Transformed = TransformObservation::new(
+            CountingEnvironment::default(),
+            Space::simple(vec![DimensionBoundaries::from(0..=5)]),
+            |state| state.clone(),
+        );
+        let float: Transformed = TransformObservation::new(
+            CountingEnvironment::default(),
+            Space::simple(vec![DimensionBoundaries::from(0f32..=0.5f32)]),
+            |state| crate::position![state.get_value(&[0]).expect_integer() as f32 / 10.0],
+        );
+        let mut environment = ChainedEnvironment::new(vec![integer, float]).unwrap();
+        let space = environment.observation_space();
+        assert_eq!(
+            &[
+                DimensionBoundaries::from(0..=1),
+                DimensionBoundaries::from(0f32..=5f32)
+            ][..],
+            space.get_boundaries()
+        );
+
+        let state = environment.reset(None, None).unwrap();
+        assert_eq!(crate::position![0, 0f32], state);
+        assert!(space.contains(&state).is_ok());
+        let forward = AgentAction::simple(vec![1.into()]);
+        for phase in [0, 0, 0, 0, 1, 1] {
+            let (state, _, _, _) = environment.step(&forward).unwrap();
+            assert_eq!(phase, environment.phase());
+            assert!(space.contains(&state).is_ok());
+        }
+    }
+
+    #[test]
+    fn later_phases_are_reset_with_seeds_derived_from_the_episode_seed() {
+        let observations = || {
+            let noisy = || {
+                NoisyObservation::new(CountingEnvironment::default(), None).with_noise(
+                    0,
+                    ObservationNoise::Gaussian {
+                        standard_deviation: 2.0,
+                    },
+                )
+            };
+            let mut environment = ChainedEnvironment::new(vec![noisy(), noisy()]).unwrap();
+            let mut observations = vec![environment.reset(Some(Seed::from(11)), None).unwrap()];
+            let forward = AgentAction::simple(vec![1.into()]);
+            for _ in 0..10 {
+                observations.push(environment.step(&forward).unwrap().0);
+            }
+            assert_eq!(1, environment.phase());
+            observations
+        };
+        assert_eq!(observations(), observations());
+    }
+}
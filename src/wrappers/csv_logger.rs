@@ -0,0 +1,280 @@
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::metrics::csv_field;
+use crate::space::Space;
+use crate::spec::EnvironmentSpec;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- CSV LOGGER --- --- --- */
+
+/// Appends one CSV row per step with the columns `episode`, `step`, every dimension of the
+/// observation and of the action, `reward` and `done`.
+///
+/// The observation is the state the action was chosen in. Dimension columns are named after the
+/// dimension names of the spaces where available and `observation_<index>` or `action_<index>`
+/// otherwise. Episodes are counted from `0` and advance with every reset after the first one.
+///
+/// Writing errors do not interrupt the environment, the first one is returned by `flush`.
+///
+/// ```
+/// # use gymnarium_base::{AgentAction, Environment, EnvironmentState, ResetOptions, Seed};
+/// # use gymnarium_base::space::{DimensionBoundaries, Space};
+/// # use gymnarium_base::wrappers::CsvLogger;
+/// # struct Cart(i32);
+/// # impl Environment<std::fmt::Error, f64, (), ()> for Cart {
+/// #     fn action_space(&self) -> Space { Space::simple(vec![DimensionBoundaries::from(-1..=1)]) }
+/// #     fn observation_space(&self) -> Space {
+/// #         Space::simple(vec![DimensionBoundaries::from(-9..=9)])
+/// #             .with_dimension_names(vec!["cart_position"])
+/// #             .unwrap()
+/// #     }
+/// #     fn suggested_episode_steps_count(&self) -> Option<u128> { None }
+/// #     fn reset(&mut self, _: Option<Seed>, _: Option<ResetOptions>) -> Result<EnvironmentState, std::fmt::Error> { self.0 = 0; Ok(self.state()) }
+/// #     fn state(&self) -> EnvironmentState { gymnarium_base::position![self.0] }
+/// #     fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, f64, bool, ()), std::fmt::Error> {
+/// #         self.0 += action.value(&[0]).expect_integer();
+/// #         Ok((self.state(), 0.5, self.0 == 2, ()))
+/// #     }
+/// #     fn load(&mut self, _: ()) -> Result<(), std::fmt::Error> { Ok(()) }
+/// #     fn store(&self) {}
+/// #     fn close(&mut self) -> Result<(), std::fmt::Error> { Ok(()) }
+/// # }
+/// let mut environment = CsvLogger::new(Cart(0), Vec::new());
+/// environment.reset(None, None).unwrap();
+/// environment.step(&gymnarium_base::position![1]).unwrap();
+/// environment.step(&gymnarium_base::position![1]).unwrap();
+///
+/// assert_eq!(
+///     "episode,step,cart_position,action_0,reward,done\n0,0,0,1,0.5,false\n0,1,1,1,0.5,true\n",
+///     String::from_utf8(environment.into_writer()).unwrap()
+/// );
+/// ```
+pub struct CsvLogger<Env, W: Write> {
+    environment: Env,
+    writer: W,
+    header_written: bool,
+    error: Option<std::io::Error>,
+    episode: Option<u64>,
+    step: u64,
+    observation: Option<EnvironmentState>,
+}
+
+impl<Env, W: Write> CsvLogger<Env, W> {
+    pub fn new(environment: Env, writer: W) -> Self {
+        Self {
+            environment,
+            writer,
+            header_written: false,
+            error: None,
+            episode: None,
+            step: 0,
+            observation: None,
+        }
+    }
+
+    /// Writes buffered rows and returns the first error since the last flush.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        let result = self.writer.flush();
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => result,
+        }
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+
+    fn write_row(&mut self, row: &[String]) {
+        let result = writeln!(self.writer, "{}", row.join(","));
+        if let (None, Err(error)) = (&self.error, result) {
+            self.error = Some(error);
+        }
+    }
+}
+
+impl<Env> CsvLogger<Env, BufWriter<File>> {
+    /// Logs into the file at `path`, replacing an existing one.
+    pub fn create<P: AsRef<Path>>(environment: Env, path: P) -> std::io::Result<Self> {
+        Ok(Self::new(environment, BufWriter::new(File::create(path)?)))
+    }
+}
+
+fn dimension_columns(prefix: &str, space: &Space) -> Vec<String> {
+    match space.dimension_names() {
+        Some(names) => names.iter().map(|name| csv_field(name)).collect(),
+        None => (0..space.get_boundaries().len())
+            .map(|index| format!("{}_{}", prefix, index))
+            .collect(),
+    }
+}
+
+impl<Env, E, R, I, D, W> Environment<E, R, I, D> for CsvLogger<Env, W>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+    W: Write,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        let state = self.environment.reset(seed, options)?;
+        self.episode = Some(self.episode.map_or(0, |episode| episode + 1));
+        self.step = 0;
+        self.observation = Some(state.clone());
+        Ok(state)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let observation = match self.observation.take() {
+            Some(observation) => observation,
+            None => self.environment.state(),
+        };
+        let (state, reward, done, info) = self.environment.step(action)?;
+
+        if !self.header_written {
+            let mut header = vec!["episode".to_string(), "step".to_string()];
+            header.extend(dimension_columns(
+                "observation",
+                &self.environment.observation_space(),
+            ));
+            header.extend(dimension_columns(
+                "action",
+                &self.environment.action_space(),
+            ));
+            header.push("reward".to_string());
+            header.push("done".to_string());
+            self.write_row(&header);
+            self.header_written = true;
+        }
+        let mut row = vec![self.episode.unwrap_or(0).to_string(), self.step.to_string()];
+        row.extend(observation.values().iter().map(ToString::to_string));
+        row.extend(action.values().iter().map(ToString::to_string));
+        row.push(reward.value().to_string());
+        row.push(done.to_string());
+        self.write_row(&row);
+
+        self.step += 1;
+        self.observation = Some(state.clone());
+        Ok((state, reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.observation = None;
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        if let Err(error) = self.writer.flush() {
+            if self.error.is_none() {
+                self.error = Some(error);
+            }
+        }
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn episodes_advance_with_every_reset() {
+        let mut environment = CsvLogger::new(CountingEnvironment::default(), Vec::new());
+        let forward = AgentAction::simple(vec![1.into()]);
+        environment.reset(None, None).unwrap();
+        environment.step(&forward).unwrap();
+        environment.reset(None, None).unwrap();
+        environment.step(&forward).unwrap();
+        environment.step(&forward).unwrap();
+        assert_eq!(Ok(()), environment.flush().map_err(|e| e.kind()));
+
+        let log = String::from_utf8(environment.into_writer()).unwrap();
+        let rows = log.lines().collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "episode,step,observation_0,action_0,reward,done",
+                "0,0,0,1,1,false",
+                "1,0,0,1,1,false",
+                "1,1,1,1,2,false",
+            ],
+            rows
+        );
+    }
+
+    #[test]
+    fn writing_errors_are_returned_by_flush() {
+        struct Failing;
+        impl Write for Failing {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut environment = CsvLogger::new(CountingEnvironment::default(), Failing);
+        environment.reset(None, None).unwrap();
+        assert!(environment
+            .step(&AgentAction::simple(vec![1.into()]))
+            .is_ok());
+        assert_eq!(
+            Some(std::io::ErrorKind::BrokenPipe),
+            environment.flush().err().map(|e| e.kind())
+        );
+        assert!(environment.flush().is_ok());
+    }
+}
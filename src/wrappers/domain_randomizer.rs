@@ -0,0 +1,313 @@
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::curriculum::ConfigurableEnvironment;
+use crate::space::{DimensionValue, Format, FormatError, Position, Space};
+use crate::spec::EnvironmentSpec;
+use crate::wrappers::WrapperRng;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{Parameters, ResetOptions, Reward, Seed};
+
+/// Prefix of the keys under which the sampled parameters are inserted into the info.
+pub const DOMAIN_INFO_PREFIX: &str = "domain.";
+
+fn to_json(value: &DimensionValue, categories: &[String]) -> serde_json::Value {
+    match value {
+        DimensionValue::Integer(value) => match categories.get(*value as usize) {
+            Some(label) if *value >= 0 => label.as_str().into(),
+            _ => (*value).into(),
+        },
+        DimensionValue::Float(value) => (*value).into(),
+    }
+}
+
+/* --- --- --- DOMAIN RANDOMIZER --- --- --- */
+
+/// Samples new parameters of the wrapped environment before every reset, e.g. to train policies
+/// which transfer from simulation to reality.
+///
+/// The parameter space is given as a space together with a format naming its values. Every key
+/// of the format becomes a parameter, a number for keys of a single value and an array in
+/// row-major order otherwise. Labelled categorical values become their labels, see
+/// `Space::with_categories`. The parameters are passed
+/// to `set_parameters` and inserted into the info of every step, prefixed with
+/// `DOMAIN_INFO_PREFIX`.
+///
+/// The random number generator is seeded as described in the module documentation.
+///
+/// ```
+/// use gymnarium_base::curriculum::ConfigurableEnvironment;
+/// use gymnarium_base::space::{DimensionBoundaries, Format, Space};
+/// use gymnarium_base::wrappers::DomainRandomizer;
+/// use gymnarium_base::{Environment, Info, Seed};
+///
+/// fn randomized<Env>(environment: Env) -> DomainRandomizer<Env>
+/// where
+///     Env: Environment<std::fmt::Error, f64, Info, ()> + ConfigurableEnvironment<std::fmt::Error>,
+/// {
+///     let mut format = Format::default();
+///     format.add("friction".to_string(), vec![1]).unwrap();
+///     format.add("surface".to_string(), vec![1]).unwrap();
+///     let space = Space::simple(vec![
+///         DimensionBoundaries::from(0.5f32..=1.5f32),
+///         DimensionBoundaries::Categorical(3),
+///     ])
+///     .with_categories(vec![vec![], vec!["ice", "grass", "asphalt"]])
+///     .unwrap();
+///     DomainRandomizer::new(environment, format, space, Some(Seed::from(42))).unwrap()
+/// }
+/// ```
+pub struct DomainRandomizer<Env> {
+    environment: Env,
+    format: Format,
+    space: Space,
+    rng: WrapperRng,
+    parameters: Option<Parameters>,
+}
+
+impl<Env> DomainRandomizer<Env> {
+    /// Creates the wrapper with a generator seeded by the given seed or by entropy.
+    ///
+    /// The format has to describe exactly the values of the space.
+    pub fn new(
+        environment: Env,
+        format: Format,
+        space: Space,
+        seed: Option<Seed>,
+    ) -> Result<Self, FormatError> {
+        if format.len() != space.get_boundaries().len() {
+            return Err(FormatError::GivenSpaceDoesNotFit {
+                key: None,
+                needed: format.len(),
+                given: space.get_boundaries().len(),
+            });
+        }
+        Ok(Self {
+            environment,
+            format,
+            space,
+            rng: WrapperRng::new(seed),
+            parameters: None,
+        })
+    }
+
+    /// Resets the random number generator with the given seed or by entropy.
+    pub fn reseed(&mut self, seed: Option<Seed>) {
+        self.rng = WrapperRng::new(seed);
+    }
+
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    pub fn space(&self) -> &Space {
+        &self.space
+    }
+
+    /// Returns the parameters sampled at the last reset.
+    pub fn parameters(&self) -> Option<&Parameters> {
+        self.parameters.as_ref()
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+
+    /// Names the values of a position of the parameter space by the keys of the format.
+    pub fn to_parameters(&self, position: &Position) -> Parameters {
+        let values = position.values();
+        let categories = self.space.categories();
+        self.format
+            .iter()
+            .map(|(key, shape, offset)| {
+                let length = shape.iter().product::<usize>();
+                let mut elements = (offset..offset + length)
+                    .map(|index| {
+                        let labels = categories.and_then(|categories| categories.get(index));
+                        to_json(&values[index], labels.map_or(&[], Vec::as_slice))
+                    })
+                    .collect::<Vec<_>>();
+                let value = if length == 1 {
+                    elements.remove(0)
+                } else {
+                    serde_json::Value::Array(elements)
+                };
+                (key, value)
+            })
+            .collect()
+    }
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, D> for DomainRandomizer<Env>
+where
+    Env: Environment<E, R, I, D> + ConfigurableEnvironment<E>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug + Extend<(String, serde_json::Value)>,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.rng.reset(seed.as_ref());
+        let sample = self.space.sample_with(&mut *self.rng);
+        let parameters = self.to_parameters(&sample);
+        self.environment.set_parameters(parameters.clone())?;
+        self.parameters = Some(parameters);
+        self.environment.reset(seed, options)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let (state, reward, done, mut info) = self.environment.step(action)?;
+        if let Some(parameters) = &self.parameters {
+            info.extend(
+                parameters
+                    .iter()
+                    .map(|(key, value)| (format!("{}{}", DOMAIN_INFO_PREFIX, key), value.clone())),
+            );
+        }
+        Ok((state, reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::DimensionBoundaries;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    impl ConfigurableEnvironment<std::fmt::Error> for CountingEnvironment {
+        /// Fails for unknown parameters, so that every sampled key is checked.
+        fn set_parameters(&mut self, parameters: Parameters) -> Result<(), std::fmt::Error> {
+            let known = ["mass", "weather", "offsets"];
+            if parameters.keys().all(|key| known.contains(&key.as_str())) {
+                Ok(())
+            } else {
+                Err(std::fmt::Error)
+            }
+        }
+    }
+
+    fn randomizer(seed: u64) -> DomainRandomizer<CountingEnvironment> {
+        let mut format = Format::default();
+        format.add("mass".to_string(), vec![1]).unwrap();
+        format.add("weather".to_string(), vec![1]).unwrap();
+        format.add("offsets".to_string(), vec![2]).unwrap();
+        let space = Space::builder()
+            .continuous(1f32..=2f32)
+            .categorical(vec!["sunny", "rain"])
+            .integer(-3..=3)
+            .repeat(2)
+            .build()
+            .unwrap();
+        DomainRandomizer::new(
+            CountingEnvironment::default(),
+            format,
+            space,
+            Some(Seed::from(seed)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parameters_are_sampled_on_reset_and_recorded_in_info() {
+        let mut environment = randomizer(9);
+        environment.reset(None, None).unwrap();
+        let parameters = environment.parameters().unwrap().clone();
+        let mass = parameters["mass"].as_f64().unwrap();
+        assert!((1.0..=2.0).contains(&mass));
+        assert!(["sunny", "rain"].contains(&parameters["weather"].as_str().unwrap()));
+        assert_eq!(2, parameters["offsets"].as_array().unwrap().len());
+
+        let (_, _, _, info) = environment.step(&crate::position![1]).unwrap();
+        assert_eq!(Some(&parameters["mass"]), info.get("domain.mass"));
+        assert_eq!(Some(&parameters["offsets"]), info.get("domain.offsets"));
+
+        environment.reset(None, None).unwrap();
+        assert_ne!(Some(&parameters), environment.parameters());
+        let mut again = randomizer(9);
+        again.reset(None, None).unwrap();
+        assert_eq!(Some(&parameters), again.parameters());
+    }
+
+    #[test]
+    fn seeded_resets_repeat_their_parameters() {
+        let mut environment = randomizer(9);
+        environment.reset(Some(Seed::from(4)), None).unwrap();
+        let parameters = environment.parameters().unwrap().clone();
+        environment.reset(None, None).unwrap();
+        environment.reset(Some(Seed::from(4)), None).unwrap();
+        assert_eq!(Some(&parameters), environment.parameters());
+
+        let mut other = randomizer(10);
+        other.reset(Some(Seed::from(4)), None).unwrap();
+        assert_ne!(Some(&parameters), other.parameters());
+    }
+
+    #[test]
+    fn formats_have_to_fit_the_space() {
+        let mut format = Format::default();
+        format.add("mass".to_string(), vec![2]).unwrap();
+        assert!(matches!(
+            DomainRandomizer::new(
+                CountingEnvironment::default(),
+                format,
+                Space::simple(vec![DimensionBoundaries::from(0..=1)]),
+                None,
+            ),
+            Err(FormatError::GivenSpaceDoesNotFit {
+                needed: 2,
+                given: 1,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt::Debug;
+use std::time::Instant;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::metrics::MetricsSink;
+use crate::spec::EnvironmentSpec;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- EPISODE STATISTICS --- --- --- */
+
+/// Summary of a finished episode.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EpisodeStatistics {
+    /// Sum of all rewards.
+    pub reward: f64,
+    /// Number of steps.
+    pub length: u64,
+    /// Elapsed seconds since the reset.
+    pub time: f64,
+}
+
+/* --- --- --- RECORD EPISODE STATISTICS --- --- --- */
+
+/// Tracks return, length and duration of every episode and inserts them as `episode.r`,
+/// `episode.l` and `episode.t` into the info of the step finishing an episode.
+///
+/// With a metrics sink, they are emitted as `episode/return`, `episode/length` and `episode/time`
+/// with the index of the episode as step.
+///
+/// ```
+/// # use gymnarium_base::{Environment, Info};
+/// # use gymnarium_base::wrappers::RecordEpisodeStatistics;
+/// fn last_episode_return<Env>(environment: &mut RecordEpisodeStatistics<Env>) -> Option<f64>
+/// where
+///     Env: Environment<std::fmt::Error, f64, Info, ()>,
+/// {
+///     environment.last_episode().map(|statistics| statistics.reward)
+/// }
+/// ```
+pub struct RecordEpisodeStatistics<Env> {
+    environment: Env,
+    reward: f64,
+    length: u64,
+    started: Instant,
+    last_episode: Option<EpisodeStatistics>,
+    episodes: u64,
+    sink: Option<Box<dyn MetricsSink>>,
+}
+
+impl<Env> RecordEpisodeStatistics<Env> {
+    pub fn new(environment: Env) -> Self {
+        Self {
+            environment,
+            reward: 0f64,
+            length: 0,
+            started: Instant::now(),
+            last_episode: None,
+            episodes: 0,
+            sink: None,
+        }
+    }
+
+    /// Emits the statistics of every finished episode into the sink.
+    pub fn with_metrics_sink<S: MetricsSink + 'static>(mut self, sink: S) -> Self {
+        self.sink = Some(Box::new(sink));
+        self
+    }
+
+    pub fn metrics_sink_mut(&mut self) -> Option<&mut (dyn MetricsSink + 'static)> {
+        self.sink.as_deref_mut()
+    }
+
+    /// Returns the number of finished episodes.
+    pub fn episodes(&self) -> u64 {
+        self.episodes
+    }
+
+    /// Returns the statistics of the last finished episode.
+    pub fn last_episode(&self) -> Option<EpisodeStatistics> {
+        self.last_episode
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, D> for RecordEpisodeStatistics<Env>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug + Extend<(String, serde_json::Value)>,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        let state = self.environment.reset(seed, options)?;
+        self.reward = 0f64;
+        self.length = 0;
+        self.started = Instant::now();
+        Ok(state)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let (state, reward, done, mut info) = self.environment.step(action)?;
+        self.reward += reward.value();
+        self.length += 1;
+        if done {
+            let statistics = EpisodeStatistics {
+                reward: self.reward,
+                length: self.length,
+                time: self.started.elapsed().as_secs_f64(),
+            };
+            info.extend(vec![
+                (
+                    "episode.r".to_string(),
+                    serde_json::Value::from(statistics.reward),
+                ),
+                (
+                    "episode.l".to_string(),
+                    serde_json::Value::from(statistics.length),
+                ),
+                (
+                    "episode.t".to_string(),
+                    serde_json::Value::from(statistics.time),
+                ),
+            ]);
+            if let Some(sink) = self.sink.as_mut() {
+                sink.scalar("episode/return", statistics.reward, self.episodes);
+                sink.scalar("episode/length", statistics.length as f64, self.episodes);
+                sink.scalar("episode/time", statistics.time, self.episodes);
+            }
+            self.episodes += 1;
+            self.last_episode = Some(statistics);
+        }
+        Ok((state, reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn finished_episodes_insert_statistics_into_info() {
+        let mut environment = RecordEpisodeStatistics::new(CountingEnvironment::default());
+        environment.reset(None, None).unwrap();
+        let forward = AgentAction::simple(vec![1.into()]);
+
+        for _ in 0..4 {
+            let (_, _, done, info) = environment.step(&forward).unwrap();
+            assert!(!done);
+            assert!(info.is_empty());
+        }
+        let (_, _, done, info) = environment.step(&forward).unwrap();
+        assert!(done);
+        assert_eq!(Some(&serde_json::Value::from(15f64)), info.get("episode.r"));
+        assert_eq!(Some(&serde_json::Value::from(5u64)), info.get("episode.l"));
+        assert!(info.contains_key("episode.t"));
+        assert_eq!(Some(5), environment.last_episode().map(|s| s.length));
+
+        environment.reset(None, None).unwrap();
+        environment.step(&forward).unwrap();
+        assert_eq!(Some(15f64), environment.last_episode().map(|s| s.reward));
+    }
+
+    #[test]
+    fn finished_episodes_are_emitted_into_sink() {
+        let mut environment = RecordEpisodeStatistics::new(CountingEnvironment::default())
+            .with_metrics_sink(crate::metrics::CsvSink::new(std::io::sink()));
+        environment.reset(None, None).unwrap();
+        let forward = AgentAction::simple(vec![1.into()]);
+        while !environment.step(&forward).unwrap().2 {}
+        assert_eq!(1, environment.episodes());
+        assert_eq!(Ok(()), environment.metrics_sink_mut().unwrap().flush());
+    }
+}
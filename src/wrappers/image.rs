@@ -0,0 +1,496 @@
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::space::{DimensionBoundaries, DimensionValue, Position, Space};
+use crate::spec::EnvironmentSpec;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- IMAGE ERROR --- --- --- */
+
+/// Errors while creating image observation wrappers.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ImageError {
+    /// The observation space is not shaped like `(height, width)` or `(height, width, channels)`,
+    /// or does not have the channels required.
+    NotAnImage { dimensions: Vec<usize> },
+    /// The requested image size is zero in at least one dimension.
+    EmptySize { height: usize, width: usize },
+}
+
+impl std::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnImage { dimensions } => write!(
+                f,
+                "Observation space with dimensions {:?} is not a fitting image",
+                dimensions
+            ),
+            Self::EmptySize { height, width } => {
+                write!(f, "Image size {}x{} is empty", height, width)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+/// Interpolation used to resize images.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Interpolation {
+    Nearest,
+    Bilinear,
+}
+
+/* --- --- --- LINEAR TRANSFORM --- --- --- */
+
+/// Maps every output value to a weighted sum of input values.
+///
+/// Applying the same weights to the boundaries keeps every transformed position inside the
+/// transformed space.
+struct LinearTransform {
+    weights: Vec<Vec<(usize, f64)>>,
+    dimensions: Vec<usize>,
+    space: Space,
+}
+
+impl LinearTransform {
+    fn new(weights: Vec<Vec<(usize, f64)>>, dimensions: Vec<usize>, input: &Space) -> Self {
+        let boundaries = input.get_boundaries();
+        let space = Space::new(
+            weights
+                .iter()
+                .map(|element| {
+                    let low = element.iter().map(|(index, weight)| {
+                        match boundaries[*index].integer_range() {
+                            Some((low, _)) => (Some(low), weight * low as f64),
+                            None => (None, weight * boundaries[*index].expect_float().0 as f64),
+                        }
+                    });
+                    let high = element.iter().map(|(index, weight)| {
+                        match boundaries[*index].integer_range() {
+                            Some((_, high)) => weight * high as f64,
+                            None => weight * boundaries[*index].expect_float().1 as f64,
+                        }
+                    });
+                    let integer = low.clone().all(|(integer, _)| integer.is_some());
+                    let low = low.map(|(_, value)| value).sum::<f64>();
+                    let high = high.sum::<f64>();
+                    if integer {
+                        DimensionBoundaries::Integer(low.round() as i32, high.round() as i32)
+                    } else {
+                        DimensionBoundaries::Float(low as f32, high as f32)
+                    }
+                })
+                .collect(),
+            dimensions.clone(),
+        )
+        .expect("one weight list per element of the dimensions");
+        Self {
+            weights,
+            dimensions,
+            space,
+        }
+    }
+
+    fn apply(&self, position: &Position) -> Position {
+        let values = position.values();
+        Position::new(
+            self.weights
+                .iter()
+                .map(|element| {
+                    let integer = element
+                        .iter()
+                        .all(|(index, _)| matches!(values[*index], DimensionValue::Integer(_)));
+                    let value = element
+                        .iter()
+                        .map(|(index, weight)| {
+                            weight
+                                * match values[*index] {
+                                    DimensionValue::Integer(value) => value as f64,
+                                    DimensionValue::Float(value) => value as f64,
+                                }
+                        })
+                        .sum::<f64>();
+                    if integer {
+                        DimensionValue::Integer(value.round() as i32)
+                    } else {
+                        DimensionValue::Float(value as f32)
+                    }
+                })
+                .collect(),
+            self.dimensions.clone(),
+        )
+        .expect("one weight list per element of the dimensions")
+    }
+}
+
+/// Returns height, width and channels of an image shaped space.
+fn image_shape(dimensions: &[usize]) -> Result<(usize, usize, usize), ImageError> {
+    match dimensions {
+        [height, width] => Ok((*height, *width, 1)),
+        [height, width, channels] => Ok((*height, *width, *channels)),
+        _ => Err(ImageError::NotAnImage {
+            dimensions: dimensions.to_vec(),
+        }),
+    }
+}
+
+fn grayscale_transform(space: &Space) -> Result<LinearTransform, ImageError> {
+    let (height, width, channels) = image_shape(space.dimensions())?;
+    if space.dimensions().len() != 3 || channels != 3 {
+        return Err(ImageError::NotAnImage {
+            dimensions: space.dimensions().clone(),
+        });
+    }
+    let weights = (0..height * width)
+        .map(|pixel| {
+            vec![
+                (pixel * 3, 0.299),
+                (pixel * 3 + 1, 0.587),
+                (pixel * 3 + 2, 0.114),
+            ]
+        })
+        .collect();
+    Ok(LinearTransform::new(weights, vec![height, width], space))
+}
+
+/// Returns the neighbouring source indices and their weights for one output coordinate.
+fn sample_points(
+    output: usize,
+    input_size: usize,
+    output_size: usize,
+    interpolation: Interpolation,
+) -> Vec<(usize, f64)> {
+    let scale = input_size as f64 / output_size as f64;
+    let last = (input_size - 1) as f64;
+    match interpolation {
+        Interpolation::Nearest => {
+            vec![(
+                (((output as f64 + 0.5) * scale).floor()).min(last) as usize,
+                1.0,
+            )]
+        }
+        Interpolation::Bilinear => {
+            let source = ((output as f64 + 0.5) * scale - 0.5).clamp(0.0, last);
+            let lower = source.floor();
+            let fraction = source - lower;
+            if fraction == 0.0 {
+                vec![(lower as usize, 1.0)]
+            } else {
+                vec![
+                    (lower as usize, 1.0 - fraction),
+                    (lower as usize + 1, fraction),
+                ]
+            }
+        }
+    }
+}
+
+fn resize_transform(
+    space: &Space,
+    height: usize,
+    width: usize,
+    interpolation: Interpolation,
+) -> Result<LinearTransform, ImageError> {
+    let (input_height, input_width, channels) = image_shape(space.dimensions())?;
+    if height == 0 || width == 0 {
+        return Err(ImageError::EmptySize { height, width });
+    }
+    if input_height == 0 || input_width == 0 {
+        return Err(ImageError::NotAnImage {
+            dimensions: space.dimensions().clone(),
+        });
+    }
+    let mut weights = Vec::with_capacity(height * width * channels);
+    for y in 0..height {
+        let rows = sample_points(y, input_height, height, interpolation);
+        for x in 0..width {
+            let columns = sample_points(x, input_width, width, interpolation);
+            for channel in 0..channels {
+                weights.push(
+                    rows.iter()
+                        .flat_map(|(row, row_weight)| {
+                            columns.iter().map(move |(column, column_weight)| {
+                                (
+                                    (row * input_width + column) * channels + channel,
+                                    row_weight * column_weight,
+                                )
+                            })
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+    let mut dimensions = space.dimensions().clone();
+    dimensions[0] = height;
+    dimensions[1] = width;
+    Ok(LinearTransform::new(weights, dimensions, space))
+}
+
+/* --- --- --- GRAYSCALE OBSERVATION --- --- --- */
+
+/// Converts `(height, width, 3)` RGB observations into `(height, width)` luminance observations.
+///
+/// Integer observations stay integers by rounding.
+pub struct GrayscaleObservation<Env> {
+    environment: Env,
+    transform: LinearTransform,
+}
+
+impl<Env> GrayscaleObservation<Env> {
+    pub fn new<E, R, I, D>(environment: Env) -> Result<Self, ImageError>
+    where
+        Env: Environment<E, R, I, D>,
+        E: std::error::Error,
+        R: Reward,
+        I: Debug,
+        D: Serialize + DeserializeOwned,
+    {
+        let transform = grayscale_transform(&environment.observation_space())?;
+        Ok(Self {
+            environment,
+            transform,
+        })
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, D> for GrayscaleObservation<Env>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.transform.space.clone()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.environment
+            .reset(seed, options)
+            .map(|state| self.transform.apply(&state))
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.transform.apply(&self.environment.state())
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let (state, reward, done, info) = self.environment.step(action)?;
+        Ok((self.transform.apply(&state), reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+/* --- --- --- RESIZE OBSERVATION --- --- --- */
+
+/// Resizes `(height, width)` or `(height, width, channels)` observations to a new height and
+/// width.
+///
+/// Integer observations stay integers by rounding.
+pub struct ResizeObservation<Env> {
+    environment: Env,
+    transform: LinearTransform,
+}
+
+impl<Env> ResizeObservation<Env> {
+    pub fn new<E, R, I, D>(
+        environment: Env,
+        height: usize,
+        width: usize,
+        interpolation: Interpolation,
+    ) -> Result<Self, ImageError>
+    where
+        Env: Environment<E, R, I, D>,
+        E: std::error::Error,
+        R: Reward,
+        I: Debug,
+        D: Serialize + DeserializeOwned,
+    {
+        let transform = resize_transform(
+            &environment.observation_space(),
+            height,
+            width,
+            interpolation,
+        )?;
+        Ok(Self {
+            environment,
+            transform,
+        })
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, D> for ResizeObservation<Env>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.transform.space.clone()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.environment
+            .reset(seed, options)
+            .map(|state| self.transform.apply(&state))
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.transform.apply(&self.environment.state())
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let (state, reward, done, info) = self.environment.step(action)?;
+        Ok((self.transform.apply(&state), reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grayscale_weights_channels_and_boundaries() {
+        let space = Space::all(DimensionBoundaries::from(0..=255), vec![1, 2, 3]);
+        let transform = grayscale_transform(&space).unwrap();
+        assert_eq!(
+            Space::all(DimensionBoundaries::from(0..=255), vec![1, 2]),
+            transform.space
+        );
+
+        let image = Position::from_i32_values(vec![255, 0, 0, 10, 10, 10], vec![1, 2, 3]).unwrap();
+        assert_eq!(
+            Position::from_i32_values(vec![76, 10], vec![1, 2]).unwrap(),
+            transform.apply(&image)
+        );
+        assert_eq!(
+            Err(ImageError::NotAnImage {
+                dimensions: vec![2, 2]
+            }),
+            grayscale_transform(&Space::all(DimensionBoundaries::from(0..=1), vec![2, 2]))
+                .map(|transform| transform.dimensions)
+        );
+    }
+
+    #[test]
+    fn resize_interpolates_nearest_and_bilinear() {
+        let space = Space::all(DimensionBoundaries::from(0f32..=4f32), vec![2, 2]);
+        let image = Position::from_f32_values(vec![0.0, 1.0, 2.0, 3.0], vec![2, 2]).unwrap();
+
+        let nearest = resize_transform(&space, 4, 4, Interpolation::Nearest).unwrap();
+        assert_eq!(vec![4, 4], nearest.dimensions);
+        assert_eq!(
+            [0.0, 0.0, 1.0, 1.0].map(DimensionValue::from),
+            nearest.apply(&image).get_values()[..4]
+        );
+
+        let bilinear = resize_transform(&space, 1, 4, Interpolation::Bilinear).unwrap();
+        let resized = bilinear.apply(&image);
+        assert_eq!(
+            Position::from_f32_values(vec![1.0, 1.25, 1.75, 2.0], vec![1, 4]),
+            Ok(resized.clone())
+        );
+        assert!(bilinear.space.contains(&resized).is_ok());
+
+        let shrunk = resize_transform(&space, 1, 1, Interpolation::Bilinear).unwrap();
+        assert_eq!(
+            Position::from_f32_values(vec![1.5], vec![1, 1]),
+            Ok(shrunk.apply(&image))
+        );
+    }
+}
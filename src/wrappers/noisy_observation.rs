@@ -0,0 +1,339 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::noise::standard_normal;
+use crate::space::{DimensionBoundaries, DimensionValue};
+use crate::spec::EnvironmentSpec;
+use crate::wrappers::WrapperRng;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/// Standard deviations by which float boundaries are widened for Gaussian noise.
+pub const GAUSSIAN_WIDENING: f64 = 3.0;
+
+/// Turns the row-major `index` into one index per dimension.
+fn unravel(mut index: usize, dimensions: &[usize]) -> Vec<usize> {
+    let mut indices = vec![0; dimensions.len()];
+    for (axis, length) in dimensions.iter().enumerate().rev() {
+        indices[axis] = index % length;
+        index /= length;
+    }
+    indices
+}
+
+/* --- --- --- OBSERVATION NOISE --- --- --- */
+
+/// Zero-mean noise added to a single dimension of observations.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ObservationNoise {
+    Gaussian {
+        standard_deviation: f64,
+    },
+    /// Noise drawn uniformly from `-half_width..half_width`.
+    Uniform {
+        half_width: f64,
+    },
+}
+
+impl ObservationNoise {
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        match self {
+            Self::Gaussian { standard_deviation } => standard_deviation * standard_normal(rng),
+            Self::Uniform { half_width } if *half_width > 0.0 => {
+                rng.gen_range(-half_width, half_width)
+            }
+            Self::Uniform { .. } => 0.0,
+        }
+    }
+
+    /// Returns how far float boundaries are widened for this noise.
+    pub fn widening(&self) -> f64 {
+        match self {
+            Self::Gaussian { standard_deviation } => GAUSSIAN_WIDENING * standard_deviation.abs(),
+            Self::Uniform { half_width } => half_width.abs(),
+        }
+    }
+}
+
+/* --- --- --- NOISY OBSERVATION --- --- --- */
+
+/// Adds noise to chosen dimensions of every state returned by the wrapped environment, e.g. for
+/// robustness experiments.
+///
+/// Dimensions are addressed by their row-major index. Integer values get the noise rounded
+/// added, unless rounding is turned off, which leaves them unperturbed. Categorical dimensions
+/// are never perturbed. Every value is clamped into its boundaries, so the observation space
+/// stays the one of the wrapped environment. With `with_widened_bounds` the float boundaries
+/// grow by the `widening` of their noise instead, so that noisy values are rarely clamped.
+///
+/// The random number generator is seeded as described in the module documentation.
+/// `state` returns the observation emitted last, or the unperturbed state before the first one.
+///
+/// ```
+/// use gymnarium_base::Environment;
+/// use gymnarium_base::Seed;
+/// use gymnarium_base::wrappers::{NoisyObservation, ObservationNoise};
+///
+/// fn shaky<Env: Environment<std::fmt::Error, f64, (), ()>>(
+///     environment: Env,
+/// ) -> impl Environment<std::fmt::Error, f64, (), ()> {
+///     NoisyObservation::new(environment, Some(Seed::from(3)))
+///         .with_noise(0, ObservationNoise::Gaussian { standard_deviation: 0.05 })
+///         .with_noise(1, ObservationNoise::Uniform { half_width: 0.1 })
+///         .with_widened_bounds()
+/// }
+/// ```
+pub struct NoisyObservation<Env> {
+    environment: Env,
+    noise: BTreeMap<usize, ObservationNoise>,
+    round_integers: bool,
+    widen_bounds: bool,
+    rng: WrapperRng,
+    observation: Option<EnvironmentState>,
+}
+
+impl<Env> NoisyObservation<Env> {
+    /// Creates the wrapper without noise and with a generator seeded by the given seed or by
+    /// entropy.
+    pub fn new(environment: Env, seed: Option<Seed>) -> Self {
+        Self {
+            environment,
+            noise: BTreeMap::new(),
+            round_integers: true,
+            widen_bounds: false,
+            rng: WrapperRng::new(seed),
+            observation: None,
+        }
+    }
+
+    pub fn with_noise(mut self, index: usize, noise: ObservationNoise) -> Self {
+        self.noise.insert(index, noise);
+        self
+    }
+
+    /// Adds the same noise to the first `length` dimensions.
+    pub fn with_noise_all(mut self, length: usize, noise: ObservationNoise) -> Self {
+        self.noise.extend((0..length).map(|index| (index, noise)));
+        self
+    }
+
+    /// Leaves integer values unperturbed instead of adding rounded noise.
+    pub fn without_integer_rounding(mut self) -> Self {
+        self.round_integers = false;
+        self
+    }
+
+    pub fn with_widened_bounds(mut self) -> Self {
+        self.widen_bounds = true;
+        self
+    }
+
+    /// Resets the random number generator with the given seed or by entropy.
+    pub fn reseed(&mut self, seed: Option<Seed>) {
+        self.rng = WrapperRng::new(seed);
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+
+    fn widen(&self, mut space: ObservationSpace) -> ObservationSpace {
+        if self.widen_bounds {
+            let length = space.get_boundaries().len();
+            for (index, noise) in self.noise.range(..length) {
+                let dimension = unravel(*index, space.dimensions());
+                if let DimensionBoundaries::Float(min, max) = space.get_boundary(&dimension) {
+                    let widening = noise.widening() as f32;
+                    let widened = DimensionBoundaries::Float(min - widening, max + widening);
+                    space.set_boundary(&dimension, widened);
+                }
+            }
+        }
+        space
+    }
+
+    fn perturb(&mut self, state: EnvironmentState, space: &ObservationSpace) -> EnvironmentState {
+        let mut values = state.values().to_vec();
+        for (index, noise) in &self.noise {
+            let (value, boundaries) = match (values.get(*index), space.get_boundaries().get(*index))
+            {
+                (Some(value), Some(boundaries)) => (*value, boundaries),
+                _ => continue,
+            };
+            values[*index] = match (value, boundaries) {
+                (DimensionValue::Integer(value), DimensionBoundaries::Integer(min, max))
+                    if self.round_integers =>
+                {
+                    let noise = noise.sample(&mut self.rng);
+                    DimensionValue::Integer(
+                        ((value as f64 + noise).round() as i32).clamp(*min, *max),
+                    )
+                }
+                (DimensionValue::Float(value), DimensionBoundaries::Float(min, max)) => {
+                    let noise = noise.sample(&mut self.rng);
+                    DimensionValue::Float((value + noise as f32).clamp(*min, *max))
+                }
+                (value, _) => value,
+            };
+        }
+        let observation = EnvironmentState::new(values, state.dimensions().clone())
+            .expect("values of the state fit its own dimensions");
+        self.observation = Some(observation.clone());
+        observation
+    }
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, D> for NoisyObservation<Env>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.widen(self.environment.observation_space())
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.rng.reset(seed.as_ref());
+        let state = self.environment.reset(seed, options)?;
+        let space = self.observation_space();
+        Ok(self.perturb(state, &space))
+    }
+
+    fn state(&self) -> EnvironmentState {
+        match &self.observation {
+            Some(observation) => observation.clone(),
+            None => self.environment.state(),
+        }
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let (state, reward, done, info) = self.environment.step(action)?;
+        let space = self.observation_space();
+        Ok((self.perturb(state, &space), reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.observation = None;
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::Space;
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::wrappers::TransformObservation;
+    use crate::Info;
+
+    fn observations(environment: &mut NoisyObservation<CountingEnvironment>) -> Vec<i32> {
+        let mut observations = vec![environment.reset(None, None).unwrap()];
+        for _ in 0..5 {
+            observations.push(environment.step(&crate::position![1]).unwrap().0);
+        }
+        observations
+            .iter()
+            .map(|observation| {
+                assert!(environment
+                    .observation_space()
+                    .contains(observation)
+                    .is_ok());
+                observation.value(&[0]).expect_integer()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn noise_is_reproducible_and_rounded_into_the_space() {
+        let noisy = |seed| {
+            NoisyObservation::new(CountingEnvironment::default(), Some(Seed::from(seed)))
+                .with_noise(
+                    0,
+                    ObservationNoise::Gaussian {
+                        standard_deviation: 2.0,
+                    },
+                )
+        };
+        let first = observations(&mut noisy(5));
+        assert_eq!(first, observations(&mut noisy(5)));
+        assert_ne!(vec![0, 1, 2, 3, 4, 5], first);
+
+        let mut unrounded = noisy(5).without_integer_rounding();
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], observations(&mut unrounded));
+        assert_eq!(crate::position![5], unrounded.state());
+    }
+
+    #[test]
+    fn widened_bounds_only_grow_float_dimensions() {
+        let environment = TransformObservation::new(
+            CountingEnvironment::default(),
+            Space::simple(vec![
+                DimensionBoundaries::from(-1f32..=1f32),
+                DimensionBoundaries::from(0..=3),
+            ]),
+            |_| crate::position![0f32, 1],
+        );
+        let noisy = NoisyObservation::new(environment, Some(Seed::from(1)))
+            .with_noise(0, ObservationNoise::Uniform { half_width: 0.5 })
+            .with_noise(
+                1,
+                ObservationNoise::Gaussian {
+                    standard_deviation: 0.1,
+                },
+            )
+            .with_widened_bounds();
+        let space = Environment::<std::fmt::Error, f64, Info, i32>::observation_space(&noisy);
+        assert_eq!(
+            &[
+                DimensionBoundaries::Float(-1.5, 1.5),
+                DimensionBoundaries::Integer(0, 3),
+            ][..],
+            space.get_boundaries()
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use rand::rngs::StdRng;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::noise::standard_normal;
+use crate::space::{DimensionBoundaries, DimensionValue};
+use crate::spec::EnvironmentSpec;
+use crate::wrappers::WrapperRng;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- OBSERVATION FUNCTION --- --- --- */
+
+/// Maps the full internal state of an environment to the observation an agent receives.
+///
+/// Separates the state from the observation of partially observable environments. The function
+/// may be random, drawing from the generator of the `PartiallyObservableWrapper`.
+pub trait ObservationFunction {
+    /// Returns the space of all observations, given the space of all internal states.
+    fn observation_space(&self, state_space: &ObservationSpace) -> ObservationSpace;
+
+    /// Returns the observation of the given internal state.
+    fn observe(&self, state: &EnvironmentState, rng: &mut StdRng) -> EnvironmentState;
+}
+
+/* --- --- --- PARTIAL OBSERVATION --- --- --- */
+
+/// Hides and perturbs chosen dimensions of the state, addressed by their row-major index.
+///
+/// Masked dimensions always show the lowest value of their boundaries. Noisy dimensions get
+/// Gaussian noise added, integer values rounded, and are clamped into their boundaries, so the
+/// observation space equals the state space. Categorical dimensions are never perturbed.
+///
+/// ```
+/// use gymnarium_base::space::{DimensionBoundaries, Space};
+/// use gymnarium_base::wrappers::{ObservationFunction, PartialObservation};
+/// use rand::SeedableRng;
+///
+/// let space = Space::simple(vec![
+///     DimensionBoundaries::from(-9..=9),
+///     DimensionBoundaries::from(-1f32..=1f32),
+/// ]);
+/// let function = PartialObservation::new(&space).with_mask(0).with_noise(1, 0.1);
+/// let observation = function.observe(
+///     &gymnarium_base::position![7, 0.5f32],
+///     &mut rand::rngs::StdRng::seed_from_u64(1),
+/// );
+///
+/// assert_eq!(-9, observation.value(&[0]).expect_integer());
+/// assert!(space.contains(&observation).is_ok());
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct PartialObservation {
+    boundaries: Vec<DimensionBoundaries>,
+    masked: Vec<usize>,
+    noise: BTreeMap<usize, f64>,
+}
+
+impl PartialObservation {
+    /// Creates a function observing every dimension of states of `state_space` unchanged.
+    pub fn new(state_space: &ObservationSpace) -> Self {
+        Self {
+            boundaries: state_space.get_boundaries().to_vec(),
+            masked: Vec::new(),
+            noise: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_mask(mut self, index: usize) -> Self {
+        self.masked.push(index);
+        self
+    }
+
+    /// Adds zero-mean Gaussian noise with the given standard deviation to dimension `index`.
+    pub fn with_noise(mut self, index: usize, standard_deviation: f64) -> Self {
+        self.noise.insert(index, standard_deviation);
+        self
+    }
+}
+
+impl ObservationFunction for PartialObservation {
+    fn observation_space(&self, state_space: &ObservationSpace) -> ObservationSpace {
+        state_space.clone()
+    }
+
+    fn observe(&self, state: &EnvironmentState, rng: &mut StdRng) -> EnvironmentState {
+        let mut values = state.values().to_vec();
+        for (index, standard_deviation) in &self.noise {
+            let noise = standard_deviation * standard_normal(rng);
+            values[*index] = match (&values[*index], &self.boundaries[*index]) {
+                (DimensionValue::Integer(value), DimensionBoundaries::Integer(min, max)) => {
+                    DimensionValue::Integer(
+                        ((*value as f64 + noise).round() as i32).clamp(*min, *max),
+                    )
+                }
+                (DimensionValue::Float(value), DimensionBoundaries::Float(min, max)) => {
+                    DimensionValue::Float((*value + noise as f32).clamp(*min, *max))
+                }
+                (value, _) => *value,
+            };
+        }
+        for index in &self.masked {
+            values[*index] = match &self.boundaries[*index] {
+                DimensionBoundaries::Float(min, _) => DimensionValue::Float(*min),
+                boundaries => DimensionValue::Integer(
+                    boundaries
+                        .integer_range()
+                        .expect("Boundaries are not float")
+                        .0,
+                ),
+            };
+        }
+        EnvironmentState::new(values, state.dimensions().clone())
+            .expect("values of the state fit its own dimensions")
+    }
+}
+
+/* --- --- --- PARTIALLY OBSERVABLE WRAPPER --- --- --- */
+
+/// Emits observations of the states of the wrapped environment through an `ObservationFunction`.
+///
+/// The wrapper owns the random number generator of the function, seeded as described in the module
+/// documentation. Seeds passed to `reset` are passed on to the wrapped environment unchanged. `state`
+/// returns the observation emitted last, so asking for it draws no new randomness. Before the
+/// first observation, and after `load`, it observes with a copy of the generator instead.
+///
+/// ```
+/// use gymnarium_base::Environment;
+/// use gymnarium_base::wrappers::{PartialObservation, PartiallyObservableWrapper};
+///
+/// fn hide_velocity<Env: Environment<std::fmt::Error, f64, (), ()>>(
+///     environment: Env,
+/// ) -> impl Environment<std::fmt::Error, f64, (), ()> {
+///     let function = PartialObservation::new(&environment.observation_space()).with_mask(1);
+///     PartiallyObservableWrapper::new(environment, function)
+/// }
+/// ```
+pub struct PartiallyObservableWrapper<Env, F> {
+    environment: Env,
+    function: F,
+    rng: WrapperRng,
+    observation: Option<EnvironmentState>,
+}
+
+impl<Env, F: ObservationFunction> PartiallyObservableWrapper<Env, F> {
+    /// Creates the wrapper with a generator seeded by entropy.
+    pub fn new(environment: Env, function: F) -> Self {
+        Self {
+            environment,
+            function,
+            rng: WrapperRng::new(None),
+            observation: None,
+        }
+    }
+
+    /// Creates the wrapper with a generator seeded by the given seed.
+    pub fn with_seed(environment: Env, function: F, seed: Seed) -> Self {
+        let mut wrapper = Self::new(environment, function);
+        wrapper.rng = WrapperRng::new(Some(seed));
+        wrapper
+    }
+
+    pub fn function(&self) -> &F {
+        &self.function
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+
+    fn observe(&mut self, state: &EnvironmentState) -> EnvironmentState {
+        let observation = self.function.observe(state, &mut self.rng);
+        self.observation = Some(observation.clone());
+        observation
+    }
+}
+
+impl<Env, F, E, R, I, D> Environment<E, R, I, D> for PartiallyObservableWrapper<Env, F>
+where
+    Env: Environment<E, R, I, D>,
+    F: ObservationFunction,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.function
+            .observation_space(&self.environment.observation_space())
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.rng.reset(seed.as_ref());
+        let state = self.environment.reset(seed, options)?;
+        Ok(self.observe(&state))
+    }
+
+    fn state(&self) -> EnvironmentState {
+        match &self.observation {
+            Some(observation) => observation.clone(),
+            None => self
+                .function
+                .observe(&self.environment.state(), &mut self.rng.clone()),
+        }
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let (state, reward, done, info) = self.environment.step(action)?;
+        Ok((self.observe(&state), reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.observation = None;
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::space::Space;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    /// Observes only whether the count is even.
+    struct Parity;
+
+    impl ObservationFunction for Parity {
+        fn observation_space(&self, _state_space: &ObservationSpace) -> ObservationSpace {
+            Space::builder()
+                .categorical(vec!["even", "odd"])
+                .build()
+                .unwrap()
+        }
+
+        fn observe(&self, state: &EnvironmentState, _rng: &mut StdRng) -> EnvironmentState {
+            crate::position![state.value(&[0]).expect_integer().rem_euclid(2)]
+        }
+    }
+
+    #[test]
+    fn observation_functions_replace_the_state() {
+        let mut environment =
+            PartiallyObservableWrapper::new(CountingEnvironment::default(), Parity);
+        assert_eq!(
+            DimensionBoundaries::Categorical(2),
+            environment.observation_space().get_boundaries()[0]
+        );
+        assert_eq!(crate::position![0], environment.reset(None, None).unwrap());
+        let (state, _, _, _) = environment.step(&crate::position![1]).unwrap();
+        assert_eq!(crate::position![1], state);
+        assert_eq!(state, environment.state());
+        assert_eq!(1, environment.inner().count);
+    }
+
+    #[test]
+    fn noisy_observations_are_reproducible_and_inside_the_space() {
+        let observations = |seed: u64| {
+            let inner = CountingEnvironment::default();
+            let function = PartialObservation::new(&inner.observation_space()).with_noise(0, 2.0);
+            let mut environment = PartiallyObservableWrapper::new(inner, function);
+            let mut observations = vec![environment.reset(Some(Seed::from(seed)), None).unwrap()];
+            for _ in 0..4 {
+                let (state, _, _, _) = environment.step(&crate::position![1]).unwrap();
+                assert!(environment.observation_space().contains(&state).is_ok());
+                assert_eq!(state, environment.state());
+                observations.push(state);
+            }
+            observations
+        };
+        assert_eq!(observations(3), observations(3));
+        assert_ne!(
+            (0..5)
+                .map(|count| crate::position![count])
+                .collect::<Vec<_>>(),
+            observations(3)
+        );
+
+        let masked =
+            PartialObservation::new(&Space::simple(vec![DimensionBoundaries::from(2..=5)]))
+                .with_mask(0);
+        assert_eq!(
+            crate::position![2],
+            masked.observe(&crate::position![4], &mut StdRng::seed_from_u64(0))
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::shaping::RewardShaper;
+use crate::spec::EnvironmentSpec;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- SHAPED REWARD --- --- --- */
+
+/// Replaces the rewards of the wrapped environment with the output of a `RewardShaper`.
+///
+/// The shaped reward is converted back into the reward type with `From<f64>`.
+pub struct ShapedReward<Env, S> {
+    environment: Env,
+    shaper: S,
+    step: u64,
+}
+
+impl<Env, S: RewardShaper> ShapedReward<Env, S> {
+    pub fn new(environment: Env, shaper: S) -> Self {
+        Self {
+            environment,
+            shaper,
+            step: 0,
+        }
+    }
+
+    pub fn shaper(&self) -> &S {
+        &self.shaper
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, S, E, R, I, D> Environment<E, R, I, D> for ShapedReward<Env, S>
+where
+    Env: Environment<E, R, I, D>,
+    S: RewardShaper,
+    E: std::error::Error,
+    R: Reward + From<f64>,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.step = 0;
+        self.environment.reset(seed, options)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let old_state = self.environment.state();
+        let (state, reward, done, info) = self.environment.step(action)?;
+        let reward = self
+            .shaper
+            .shape(&old_state, action, &state, reward.value(), self.step);
+        self.step += 1;
+        Ok((state, R::from(reward), done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shaping::PotentialBasedShaping;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn shapers_see_states_and_step_counter() {
+        let penalty =
+            |_: &EnvironmentState, _: &AgentAction, _: &EnvironmentState, r: f64, s: u64| {
+                r - s as f64
+            };
+        let mut environment = ShapedReward::new(CountingEnvironment::default(), penalty);
+        environment.reset(None, None).unwrap();
+        let forward = AgentAction::simple(vec![1.into()]);
+        assert_eq!(1f64, environment.step(&forward).unwrap().1);
+        assert_eq!(1f64, environment.step(&forward).unwrap().1);
+
+        let potential = PotentialBasedShaping::new(1.0, |state: &EnvironmentState| {
+            -(state.value(&[0]).expect_integer() as f64)
+        });
+        let mut environment = ShapedReward::new(CountingEnvironment { count: 2 }, potential);
+        assert_eq!(2f64, environment.step(&forward).unwrap().1);
+    }
+}
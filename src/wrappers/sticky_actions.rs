@@ -0,0 +1,174 @@
+use std::fmt::Debug;
+
+use rand::Rng;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::spec::EnvironmentSpec;
+use crate::wrappers::WrapperRng;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- STICKY ACTIONS --- --- --- */
+
+/// Repeats the previous action instead of the given one with a fixed probability.
+///
+/// The random number generator is seeded as described in the module documentation. Seeds passed to
+/// `reset` are passed on to the wrapped environment unchanged.
+pub struct StickyActions<Env> {
+    environment: Env,
+    probability: f64,
+    rng: WrapperRng,
+    previous_action: Option<AgentAction>,
+}
+
+impl<Env> StickyActions<Env> {
+    /// Creates the wrapper with a generator seeded by entropy.
+    ///
+    /// # Panics
+    /// If `probability` lies outside of `0..=1`.
+    pub fn new(environment: Env, probability: f64) -> Self {
+        assert!(
+            (0f64..=1f64).contains(&probability),
+            "Probability has to lie within 0..=1"
+        );
+        Self {
+            environment,
+            probability,
+            rng: WrapperRng::new(None),
+            previous_action: None,
+        }
+    }
+
+    /// Creates the wrapper with a generator seeded by the given seed.
+    ///
+    /// # Panics
+    /// If `probability` lies outside of `0..=1`.
+    pub fn with_seed(environment: Env, probability: f64, seed: Seed) -> Self {
+        let mut wrapper = Self::new(environment, probability);
+        wrapper.rng = WrapperRng::new(Some(seed));
+        wrapper
+    }
+
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, E, R, I, D> Environment<E, R, I, D> for StickyActions<Env>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.rng.reset(seed.as_ref());
+        self.previous_action = None;
+        self.environment.reset(seed, options)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let action = match self.previous_action.take() {
+            Some(previous) if self.rng.gen_bool(self.probability) => previous,
+            _ => action.clone(),
+        };
+        let result = self.environment.step(&action);
+        self.previous_action = Some(action);
+        result
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    fn counts(environment: &mut StickyActions<CountingEnvironment>, seed: u64) -> Vec<i32> {
+        environment.reset(Some(Seed::from(seed)), None).unwrap();
+        [1, -1, 1, -1, 1, -1]
+            .iter()
+            .map(|value| {
+                let action = AgentAction::simple(vec![(*value).into()]);
+                environment.step(&action).unwrap();
+                environment.inner().count
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sticky_actions_are_reproducible_with_seed() {
+        let mut environment = StickyActions::new(CountingEnvironment::default(), 0.5);
+        let first = counts(&mut environment, 7);
+        assert_eq!(first, counts(&mut environment, 7));
+
+        let mut never = StickyActions::new(CountingEnvironment::default(), 0.0);
+        assert_eq!(vec![1, 0, 1, 0, 1, 0], counts(&mut never, 7));
+        let mut always = StickyActions::new(CountingEnvironment::default(), 1.0);
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], counts(&mut always, 7));
+    }
+
+    #[test]
+    fn seeded_resets_keep_the_seed_of_the_wrapper() {
+        let seeded = |seed: u64| {
+            StickyActions::with_seed(CountingEnvironment::default(), 0.5, Seed::from(seed))
+        };
+        let first = counts(&mut seeded(1), 7);
+        assert_eq!(first, counts(&mut seeded(1), 7));
+        assert_ne!(first, counts(&mut seeded(2), 7));
+    }
+}
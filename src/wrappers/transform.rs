@@ -0,0 +1,256 @@
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::spec::EnvironmentSpec;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- TRANSFORM OBSERVATION --- --- --- */
+
+/// Applies a function to every state returned by the wrapped environment.
+///
+/// The given observation space has to contain all transformed states.
+///
+/// ```
+/// # use gymnarium_base::Environment;
+/// # use gymnarium_base::space::{DimensionBoundaries, Space};
+/// # use gymnarium_base::wrappers::TransformObservation;
+/// fn scaled<Env: Environment<std::fmt::Error, f64, (), ()>>(
+///     environment: Env,
+/// ) -> impl Environment<std::fmt::Error, f64, (), ()> {
+///     TransformObservation::new(
+///         environment,
+///         Space::simple(vec![DimensionBoundaries::from(0f32..=1f32)]),
+///         |state| {
+///             let value = state.value(&[0]).expect_integer() as f32 / 255f32;
+///             gymnarium_base::position![value]
+///         },
+///     )
+/// }
+/// ```
+pub struct TransformObservation<Env, F> {
+    environment: Env,
+    observation_space: ObservationSpace,
+    transform: F,
+}
+
+impl<Env, F> TransformObservation<Env, F>
+where
+    F: Fn(&EnvironmentState) -> EnvironmentState,
+{
+    pub fn new(environment: Env, observation_space: ObservationSpace, transform: F) -> Self {
+        Self {
+            environment,
+            observation_space,
+            transform,
+        }
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, F, E, R, I, D> Environment<E, R, I, D> for TransformObservation<Env, F>
+where
+    Env: Environment<E, R, I, D>,
+    F: Fn(&EnvironmentState) -> EnvironmentState,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.observation_space.clone()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.environment
+            .reset(seed, options)
+            .map(|state| (self.transform)(&state))
+    }
+
+    fn state(&self) -> EnvironmentState {
+        (self.transform)(&self.environment.state())
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        let (state, reward, done, info) = self.environment.step(action)?;
+        Ok(((self.transform)(&state), reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+/* --- --- --- TRANSFORM ACTION --- --- --- */
+
+/// Applies a function to every action before passing it to the wrapped environment.
+///
+/// The given action space describes the actions accepted by this wrapper, the function has to
+/// map them into the action space of the wrapped environment.
+pub struct TransformAction<Env, F> {
+    environment: Env,
+    action_space: ActionSpace,
+    transform: F,
+}
+
+impl<Env, F> TransformAction<Env, F>
+where
+    F: Fn(&AgentAction) -> AgentAction,
+{
+    pub fn new(environment: Env, action_space: ActionSpace, transform: F) -> Self {
+        Self {
+            environment,
+            action_space,
+            transform,
+        }
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, F, E, R, I, D> Environment<E, R, I, D> for TransformAction<Env, F>
+where
+    Env: Environment<E, R, I, D>,
+    F: Fn(&AgentAction) -> AgentAction,
+    E: std::error::Error,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.action_space.clone()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, E> {
+        self.environment.reset(seed, options)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(&mut self, action: &AgentAction) -> Result<(EnvironmentState, R, bool, I), E> {
+        self.environment.step(&(self.transform)(action))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), E> {
+        self.environment.load(data)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), E> {
+        self.environment.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::{DimensionBoundaries, Space};
+    use crate::wrappers::test_environment::CountingEnvironment;
+    use crate::wrappers::ValidatedEnvironment;
+
+    #[test]
+    fn transforms_compose_with_validation() {
+        let doubled = TransformObservation::new(
+            CountingEnvironment::default(),
+            Space::simple(vec![DimensionBoundaries::from(0..=10)]),
+            |state| EnvironmentState::simple(vec![(state.value(&[0]).expect_integer() * 2).into()]),
+        );
+        let flipped = TransformAction::new(
+            doubled,
+            Space::simple(vec![DimensionBoundaries::from(0..=1)]),
+            |action| {
+                let forward = action.value(&[0]).expect_integer() == 1;
+                AgentAction::simple(vec![if forward { 1 } else { -1 }.into()])
+            },
+        );
+        let mut environment = ValidatedEnvironment::new(flipped);
+        environment
+            .reset(
+                None,
+                Some(ResetOptions::new().with_initial_state(crate::position![2])),
+            )
+            .unwrap();
+
+        let (state, _, _, _) = environment.step(&crate::position![1]).unwrap();
+        assert_eq!(crate::position![6], state);
+        let (state, _, _, _) = environment.step(&crate::position![0]).unwrap();
+        assert_eq!(crate::position![4], state);
+        assert!(environment.step(&crate::position![-1]).is_err());
+    }
+}
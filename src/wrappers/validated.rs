@@ -0,0 +1,222 @@
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::space::SpaceMismatch;
+use crate::spec::EnvironmentSpec;
+use crate::{ActionSpace, AgentAction, Environment, EnvironmentState, ObservationSpace};
+use crate::{ResetOptions, Reward, Seed};
+
+/* --- --- --- VALIDATION ERROR --- --- --- */
+
+/// Errors of a `ValidatedEnvironment`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValidationError<E> {
+    /// The wrapped environment returned an error itself.
+    Environment(E),
+    /// The agent action given to `step` lies outside of the action space.
+    InvalidAction(SpaceMismatch),
+    /// The wrapped environment returned a state outside of its observation space.
+    InvalidState(SpaceMismatch),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ValidationError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment(error) => write!(f, "Environment Error \"{}\" occurred", error),
+            Self::InvalidAction(mismatch) => write!(
+                f,
+                "Given action does not fit the action space ({})",
+                mismatch
+            ),
+            Self::InvalidState(mismatch) => write!(
+                f,
+                "Environment returned a state not fitting the observation space ({})",
+                mismatch
+            ),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ValidationError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Environment(error) => Some(error),
+            Self::InvalidAction(mismatch) | Self::InvalidState(mismatch) => Some(mismatch),
+        }
+    }
+}
+
+/* --- --- --- VALIDATED ENVIRONMENT --- --- --- */
+
+/// Checks every action against the action space and every returned state against the
+/// observation space of the wrapped environment.
+pub struct ValidatedEnvironment<Env> {
+    environment: Env,
+}
+
+impl<Env> ValidatedEnvironment<Env> {
+    pub fn new(environment: Env) -> Self {
+        Self { environment }
+    }
+
+    pub fn inner(&self) -> &Env {
+        &self.environment
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Env {
+        &mut self.environment
+    }
+
+    pub fn into_inner(self) -> Env {
+        self.environment
+    }
+}
+
+impl<Env, E, R, I, D> Environment<ValidationError<E>, R, I, D> for ValidatedEnvironment<Env>
+where
+    Env: Environment<E, R, I, D>,
+    E: std::error::Error + 'static,
+    R: Reward,
+    I: Debug,
+    D: Serialize + DeserializeOwned,
+{
+    fn action_space(&self) -> ActionSpace {
+        self.environment.action_space()
+    }
+
+    fn observation_space(&self) -> ObservationSpace {
+        self.environment.observation_space()
+    }
+
+    fn suggested_episode_steps_count(&self) -> Option<u128> {
+        self.environment.suggested_episode_steps_count()
+    }
+
+    fn environment_id() -> String {
+        Env::environment_id()
+    }
+
+    fn spec(&self) -> EnvironmentSpec {
+        self.environment.spec()
+    }
+
+    fn reset(
+        &mut self,
+        seed: Option<Seed>,
+        options: Option<ResetOptions>,
+    ) -> Result<EnvironmentState, ValidationError<E>> {
+        let state = self
+            .environment
+            .reset(seed, options)
+            .map_err(ValidationError::Environment)?;
+        self.environment
+            .observation_space()
+            .contains(&state)
+            .map_err(ValidationError::InvalidState)?;
+        Ok(state)
+    }
+
+    fn state(&self) -> EnvironmentState {
+        self.environment.state()
+    }
+
+    fn step(
+        &mut self,
+        action: &AgentAction,
+    ) -> Result<(EnvironmentState, R, bool, I), ValidationError<E>> {
+        self.environment
+            .action_space()
+            .contains(action)
+            .map_err(ValidationError::InvalidAction)?;
+        let (state, reward, done, info) = self
+            .environment
+            .step(action)
+            .map_err(ValidationError::Environment)?;
+        self.environment
+            .observation_space()
+            .contains(&state)
+            .map_err(ValidationError::InvalidState)?;
+        Ok((state, reward, done, info))
+    }
+
+    fn load(&mut self, data: D) -> Result<(), ValidationError<E>> {
+        self.environment
+            .load(data)
+            .map_err(ValidationError::Environment)
+    }
+
+    fn store(&self) -> D {
+        self.environment.store()
+    }
+
+    fn close(&mut self) -> Result<(), ValidationError<E>> {
+        self.environment
+            .close()
+            .map_err(ValidationError::Environment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::{DimensionBoundaries, DimensionValue};
+    use crate::wrappers::test_environment::CountingEnvironment;
+
+    #[test]
+    fn rejects_action_outside_of_action_space() {
+        let mut environment = ValidatedEnvironment::new(CountingEnvironment::default());
+        environment.reset(None, None).unwrap();
+
+        let result = environment.step(&AgentAction::simple(vec![DimensionValue::from(2)]));
+        assert_eq!(
+            Some(ValidationError::InvalidAction(
+                SpaceMismatch::ValueOutOfBounds {
+                    index: 0,
+                    boundaries: DimensionBoundaries::Integer(-1, 1),
+                    value: DimensionValue::Integer(2)
+                }
+            )),
+            result.err()
+        );
+        assert_eq!(0, environment.inner().count);
+    }
+
+    #[test]
+    fn rejects_state_outside_of_observation_space() {
+        let mut environment = ValidatedEnvironment::new(CountingEnvironment::default());
+        environment.reset(None, None).unwrap();
+
+        let result = environment.step(&AgentAction::simple(vec![DimensionValue::from(-1)]));
+        assert_eq!(
+            Some(ValidationError::InvalidState(
+                SpaceMismatch::ValueOutOfBounds {
+                    index: 0,
+                    boundaries: DimensionBoundaries::Integer(0, 5),
+                    value: DimensionValue::Integer(-1)
+                }
+            )),
+            result.err()
+        );
+    }
+
+    #[test]
+    fn reset_options_reach_the_wrapped_environment() {
+        let mut environment = ValidatedEnvironment::new(CountingEnvironment::default());
+        let start = EnvironmentState::simple(vec![DimensionValue::from(3)]);
+        let state = environment
+            .reset(
+                None,
+                Some(ResetOptions::new().with_initial_state(start.clone())),
+            )
+            .unwrap();
+        assert_eq!(start, state);
+
+        let outside = EnvironmentState::simple(vec![DimensionValue::from(7)]);
+        assert!(matches!(
+            environment.reset(None, Some(ResetOptions::new().with_initial_state(outside))),
+            Err(ValidationError::InvalidState(_))
+        ));
+    }
+}